@@ -0,0 +1,194 @@
+// Compares interned atoms with owned strings for tag names: the allocations per name and the cost of matching a name.
+// cargo bench --bench atoms
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    hint::black_box,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+use fun_with_html::{Atom, Token, Tokenizer, atom, parse_document};
+
+// counts the allocations of the whole program
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const ROUNDS: usize = 200;
+
+// the time and the allocations of running f ROUNDS times
+fn measure<T>(mut f: impl FnMut() -> T) -> (Duration, usize) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        black_box(f());
+    }
+    let elapsed = start.elapsed();
+    (elapsed, ALLOCATIONS.load(Ordering::Relaxed) - allocations)
+}
+
+fn report(name: &str, (elapsed, allocations): (Duration, usize)) {
+    println!(
+        "{name:<24} {:>10.1?}/round {:>8} allocations/round",
+        elapsed / ROUNDS as u32,
+        allocations / ROUNDS
+    );
+}
+
+fn document() -> String {
+    let row = r#"<tr><td class="name"><a href="/item">item</a></td><td><span title="x">1</span><br><img src="a.png" alt=""></td></tr>"#;
+    format!(
+        "<!DOCTYPE html><html><head><title>bench</title><meta charset=utf-8></head><body><div><table><tbody>{}</tbody></table><p>end</div></body></html>",
+        row.repeat(500)
+    )
+}
+
+fn tag_names(input: &str) -> Vec<String> {
+    Tokenizer::new(input)
+        .filter_map(|token| match token {
+            Token::StartTag(tag) | Token::EndTag(tag) => Some(tag.name.to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+// the in body start tag arms that come before the "any other start tag" arm
+fn is_special_string(name: &str) -> bool {
+    matches!(
+        name,
+        "address"
+            | "article"
+            | "aside"
+            | "blockquote"
+            | "center"
+            | "details"
+            | "dialog"
+            | "dir"
+            | "div"
+            | "dl"
+            | "fieldset"
+            | "figcaption"
+            | "figure"
+            | "footer"
+            | "header"
+            | "hgroup"
+            | "main"
+            | "menu"
+            | "nav"
+            | "ol"
+            | "p"
+            | "search"
+            | "section"
+            | "summary"
+            | "ul"
+            | "table"
+            | "tbody"
+            | "td"
+            | "tr"
+            | "img"
+            | "br"
+            | "a"
+    )
+}
+
+fn is_special_atom(name: &Atom) -> bool {
+    matches!(
+        *name,
+        atom!("address")
+            | atom!("article")
+            | atom!("aside")
+            | atom!("blockquote")
+            | atom!("center")
+            | atom!("details")
+            | atom!("dialog")
+            | atom!("dir")
+            | atom!("div")
+            | atom!("dl")
+            | atom!("fieldset")
+            | atom!("figcaption")
+            | atom!("figure")
+            | atom!("footer")
+            | atom!("header")
+            | atom!("hgroup")
+            | atom!("main")
+            | atom!("menu")
+            | atom!("nav")
+            | atom!("ol")
+            | atom!("p")
+            | atom!("search")
+            | atom!("section")
+            | atom!("summary")
+            | atom!("ul")
+            | atom!("table")
+            | atom!("tbody")
+            | atom!("td")
+            | atom!("tr")
+            | atom!("img")
+            | atom!("br")
+            | atom!("a")
+    )
+}
+
+fn main() {
+    let input = document();
+    let names = tag_names(&input);
+    println!("{} tag names\n", names.len());
+
+    // the names as the tokenizer builds them: an owned string per tag before, an atom now
+    report(
+        "names as strings",
+        measure(|| {
+            names
+                .iter()
+                .map(|name| name.to_string())
+                .collect::<Vec<_>>()
+        }),
+    );
+    report(
+        "names as atoms",
+        measure(|| {
+            names
+                .iter()
+                .map(|name| Atom::from(name.as_str()))
+                .collect::<Vec<_>>()
+        }),
+    );
+
+    let strings: Vec<String> = names.iter().map(|name| name.to_string()).collect();
+    let atoms: Vec<Atom> = names.iter().map(|name| Atom::from(name.as_str())).collect();
+    report(
+        "match strings",
+        measure(|| {
+            strings
+                .iter()
+                .filter(|name| is_special_string(name))
+                .count()
+        }),
+    );
+    report(
+        "match atoms",
+        measure(|| atoms.iter().filter(|name| is_special_atom(name)).count()),
+    );
+
+    report("tokenize", measure(|| Tokenizer::new(&input).count()));
+    report("parse document", measure(|| parse_document(&input)));
+}
@@ -0,0 +1,41 @@
+// Parses documents with more and more text: the time grows linearly with the length of the text, quadratic growth would take sixteen times as long for four times the text.
+// cargo bench --bench text
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use fun_with_html::parse_document;
+
+const ROUNDS: u32 = 5;
+
+// the fastest of ROUNDS runs
+fn measure(input: &str) -> Duration {
+    (0..ROUNDS)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(parse_document(input));
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+fn main() {
+    // the text in the body is one run of characters, the whitespace in the head is inserted one character at a time
+    for (name, prefix, text) in [
+        ("body text", "<p>", "text "),
+        ("head whitespace", "<head>", " "),
+    ] {
+        let mut first = None;
+        for mebibytes in [1, 2, 4, 8] {
+            let input = format!("{prefix}{}", text.repeat((mebibytes << 20) / text.len()));
+            let elapsed = measure(&input);
+            let first = *first.get_or_insert(elapsed);
+            println!(
+                "{name:<16} {mebibytes} MiB {elapsed:>10.1?} {:>6.1}x",
+                elapsed.as_secs_f64() / first.as_secs_f64()
+            );
+        }
+    }
+}
@@ -0,0 +1,74 @@
+// Generates src/html/atom/static_atoms.rs from the names in src/html/atom/static_atoms.txt, one name per line.
+// cargo run --example static_atoms > src/html/atom/static_atoms.rs
+use std::fmt::Write;
+
+const NAMES: &str = include_str!("../src/html/atom/static_atoms.txt");
+
+// the constant of a name: "foreignObject" is FOREIGN_OBJECT, "accept-charset" is ACCEPT_CHARSET, "xlink:href" is XLINK_HREF
+fn constant_name(name: &str) -> String {
+    if name.is_empty() {
+        return "EMPTY".to_string();
+    }
+    let mut constant = String::new();
+    let mut previous: Option<char> = None;
+    for char in name.chars() {
+        match char {
+            '-' | ':' => constant.push('_'),
+            _ if char.is_ascii_uppercase()
+                && previous.is_some_and(|previous| {
+                    previous.is_ascii_lowercase() || previous.is_ascii_digit()
+                }) =>
+            {
+                constant.push('_');
+                constant.push(char);
+            }
+            _ => constant.push(char.to_ascii_uppercase()),
+        }
+        previous = Some(char);
+    }
+    constant
+}
+
+fn main() {
+    // the empty name is the default atom
+    let mut names: Vec<&str> = NAMES.lines().filter(|name| !name.is_empty()).collect();
+    names.push("");
+    names.sort();
+    names.dedup();
+
+    let mut output = String::new();
+    output.push_str("// The names interned at compile time: the HTML elements and attributes, and the SVG and MathML names of the tree construction.\n");
+    output.push_str("// Sorted by name, the index of a name is the id of its atom.\n");
+    output.push_str("// Generated by examples/static_atoms.rs from static_atoms.txt, add a name there and run: cargo run --example static_atoms > src/html/atom/static_atoms.rs\n");
+    output.push_str("use super::Atom;\n\n");
+    writeln!(
+        output,
+        "pub(crate) static STATIC_ATOMS: [&str; {}] = [",
+        names.len()
+    )
+    .unwrap();
+    for name in &names {
+        writeln!(output, "    {name:?},").unwrap();
+    }
+    output.push_str("];\n\n");
+    for (id, name) in names.iter().enumerate() {
+        writeln!(
+            output,
+            "pub const {}: Atom = Atom::new_static({id});",
+            constant_name(name)
+        )
+        .unwrap();
+    }
+    output.push_str("\n// atom!(\"div\") is the constant of a name interned at compile time, a name without an atom does not compile\n");
+    output.push_str("#[macro_export]\nmacro_rules! atom {\n");
+    for name in &names {
+        writeln!(
+            output,
+            "    ({name:?}) => {{\n        $crate::atom::static_atoms::{}\n    }};",
+            constant_name(name)
+        )
+        .unwrap();
+    }
+    output.push_str("}\n");
+    print!("{output}");
+}
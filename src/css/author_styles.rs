@@ -0,0 +1,304 @@
+use crate::atom;
+use crate::css::cssom::{CssRule, Stylesheet, parse_a_css_declaration_block};
+use crate::css::loader::{ResourceLoader, resolve_a_url};
+use crate::css::media::MediaQueryList;
+use crate::css::parser::Declaration;
+use crate::html::tree_builder::tree::{Element, Namespace, Node, Tree};
+
+// https://drafts.csswg.org/css-cascade-5/#cascade-origin-author
+// the styles of a document
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AuthorStyles {
+    // the style sheets of the <style> and <link> elements in tree order, with their imported style sheets
+    pub stylesheets: Vec<Stylesheet>,
+    // the declarations of the style attributes in tree order, by element
+    pub style_attributes: Vec<(usize, Vec<Declaration>)>,
+}
+
+// deeper @import rules are not loaded, this also stops a cycle that goes through different urls
+const MAX_IMPORT_DEPTH: usize = 16;
+
+impl Tree<Node> {
+    // https://drafts.csswg.org/cssom/#document-or-shadow-root-css-style-sheets
+    // The style sheets and style attributes of the document, the resources of <link> elements and @import rules are read with the loader.
+    pub fn author_styles(&self, loader: &impl ResourceLoader) -> AuthorStyles {
+        let mut author_styles = AuthorStyles::default();
+        let Some(root) = self.get_root() else {
+            return author_styles;
+        };
+        // todo the <base> element, for now the urls are relative to the document
+        for (index, _) in self.descendants(root) {
+            let Some(element) = self.get_element(index) else {
+                continue;
+            };
+            let stylesheet = if element.is_html(&[atom!("style")])
+                || element.is(Namespace::Svg, atom!("style"))
+            {
+                self.style_element_stylesheet(index, element)
+            } else if element.is_html(&[atom!("link")]) {
+                link_element_stylesheet(index, element, loader)
+            } else {
+                None
+            };
+            if let Some(mut stylesheet) = stylesheet {
+                let mut importing: Vec<String> = stylesheet.location.iter().cloned().collect();
+                load_imports(&mut stylesheet, loader, &mut importing);
+                author_styles.stylesheets.push(stylesheet);
+            }
+        }
+        author_styles.style_attributes = self.style_attributes();
+        author_styles
+    }
+
+    // https://html.spec.whatwg.org/multipage/dom.html#the-style-attribute
+    // When the style attribute is set, the declarations are the result of parsing the value as a CSS declaration block.
+    // the elements with a style attribute that has declarations, in tree order
+    pub fn style_attributes(&self) -> Vec<(usize, Vec<Declaration>)> {
+        let Some(root) = self.get_root() else {
+            return Vec::new();
+        };
+        self.descendants(root)
+            .filter_map(|(index, _)| {
+                let style = self.get_element(index)?.get_attribute("style")?;
+                let declarations = parse_a_css_declaration_block(style);
+                (!declarations.is_empty()).then_some((index, declarations))
+            })
+            .collect()
+    }
+
+    // https://html.spec.whatwg.org/multipage/semantics.html#update-a-style-block
+    fn style_element_stylesheet(&self, index: usize, element: &Element) -> Option<Stylesheet> {
+        // 4. If element's type attribute is present and its value is neither the empty string nor an ASCII case-insensitive match for "text/css", then return.
+        if !is_css_type(element) {
+            return None;
+        }
+        // 6. Create a CSS style sheet with the following properties:
+        // type: text/css
+        // owner node: element
+        // media: The media attribute of element.
+        // location, parent CSS style sheet, owner CSS rule: null
+        // The child text content of the element is the source of the style sheet.
+        let text: String = self
+            .get_node(index)
+            .children
+            .iter()
+            .filter_map(|child| match &self.get_node(*child).data {
+                Node::Text(text) => Some(text.data.as_str()),
+                _ => None,
+            })
+            .collect();
+        Some(Stylesheet {
+            owner_node: Some(index),
+            media: media_attribute(element),
+            ..Stylesheet::parse(&text)
+        })
+    }
+}
+
+// https://html.spec.whatwg.org/multipage/links.html#link-type-stylesheet
+fn link_element_stylesheet(
+    index: usize,
+    element: &Element,
+    loader: &impl ResourceLoader,
+) -> Option<Stylesheet> {
+    // The stylesheet keyword may be used with link elements. This keyword creates an external resource link that contributes to the styling processing model.
+    let rel = element.get_attribute("rel")?;
+    let has_keyword = |keyword: &str| {
+        rel.split_ascii_whitespace()
+            .any(|item| item.eq_ignore_ascii_case(keyword))
+    };
+    if !has_keyword("stylesheet") {
+        return None;
+    }
+    // https://html.spec.whatwg.org/multipage/links.html#the-link-is-an-alternative-stylesheet
+    // an alternative style sheet isn't applied unless it is selected
+    // https://html.spec.whatwg.org/multipage/semantics.html#attr-link-disabled
+    // The disabled attribute is a boolean attribute that is used with the link element only when rel contains stylesheet. When the disabled attribute is present, the style sheet is not loaded.
+    if has_keyword("alternate") || element.get_attribute("disabled").is_some() {
+        return None;
+    }
+    // https://html.spec.whatwg.org/multipage/semantics.html#concept-link-obtain
+    // 1. If the href attribute's value is the empty string, then return.
+    let href = element
+        .get_attribute("href")
+        .filter(|href| !href.is_empty())?;
+    // The type attribute is a hint, a style sheet of a type other than text/css is not loaded.
+    if !is_css_type(element) {
+        return None;
+    }
+    // 2. Let url be the result of encoding-parsing a URL given the href attribute's value, relative to the element's node document.
+    let url = resolve_a_url(href, None);
+    // https://html.spec.whatwg.org/multipage/links.html#link-type-stylesheet:process-the-linked-resource
+    // If success is false or the resource can't be decoded, return.
+    let text = loader.load(&url)?;
+    // Create a CSS style sheet with the following properties:
+    // location: The resulting URL string determined during the fetch and process the linked resource algorithm.
+    // owner node: element
+    // media: The media attribute of element.
+    Some(Stylesheet {
+        location: Some(url),
+        owner_node: Some(index),
+        media: media_attribute(element),
+        ..Stylesheet::parse(&text)
+    })
+}
+
+// the type attribute is missing, empty or text/css
+fn is_css_type(element: &Element) -> bool {
+    element
+        .get_attribute("type")
+        .is_none_or(|value| value.is_empty() || value.eq_ignore_ascii_case("text/css"))
+}
+
+// https://html.spec.whatwg.org/multipage/semantics.html#attr-style-media
+// The default, if the media attribute is omitted, is "all", meaning that by default styles apply to all media.
+fn media_attribute(element: &Element) -> MediaQueryList {
+    element
+        .get_attribute("media")
+        .map(MediaQueryList::parse)
+        .unwrap_or_default()
+}
+
+// https://drafts.csswg.org/css-cascade-5/#fetch-an-import
+// The imported style sheets are loaded relative to the location of the style sheet, a url that is already being imported is skipped.
+// importing has the locations of the style sheet and the style sheets that import it
+fn load_imports(
+    stylesheet: &mut Stylesheet,
+    loader: &impl ResourceLoader,
+    importing: &mut Vec<String>,
+) {
+    if importing.len() >= MAX_IMPORT_DEPTH {
+        return;
+    }
+    let location = stylesheet.location.clone();
+    for rule in &mut stylesheet.rules {
+        let CssRule::Import(rule) = rule else {
+            continue;
+        };
+        // todo skip the import if its supports condition is false
+        // 6. Let url be the result of parsing rule's URL given parentStylesheet's location.
+        let url = resolve_a_url(&rule.href, location.as_deref());
+        if importing.contains(&url) {
+            continue;
+        }
+        let Some(text) = loader.load(&url) else {
+            continue;
+        };
+        // 8. Let importedStylesheet be the result of parsing a stylesheet given ... with its location set to url, and its media set to rule's media.
+        let mut imported = Stylesheet {
+            location: Some(url.clone()),
+            media: rule.media.clone(),
+            ..Stylesheet::parse(&text)
+        };
+        importing.push(url);
+        load_imports(&mut imported, loader, importing);
+        importing.pop();
+        rule.stylesheet = Some(imported);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use crate::css::cssom::CssRule;
+    use crate::css::loader::FileSystemLoader;
+    use crate::html::tokenizer::Tokenizer;
+    use crate::html::tree_builder::{ParserOptions, TreeBuilder};
+
+    fn parse(contents: &str) -> TreeBuilder {
+        let mut tree_builder = TreeBuilder::new(ParserOptions::default());
+        tree_builder.build(Tokenizer::new(contents));
+        tree_builder
+    }
+
+    fn resources(resources: &[(&str, &str)]) -> HashMap<String, String> {
+        resources
+            .iter()
+            .map(|(url, text)| (url.to_string(), text.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn author_styles() {
+        let tree_builder = parse(
+            r#"<link rel="Preload StyleSheet" href="css/a.css" media="print"><style media="screen and (color)">a { b: c }</style>
+<p id=p style="color: red; color: blue">x</p><link rel=stylesheet href=missing.css><link rel="alternate stylesheet" href="css/a.css">
+<style type=text/plain>e {}</style><svg><style>f {}</style></svg><template><style>t {}</style></template><style>g {}</style>"#,
+        );
+        let tree = tree_builder.get_tree();
+        let loader = resources(&[
+            ("css/a.css", "@import 'b.css' screen; h {}"),
+            ("css/b.css", "i {}"),
+        ]);
+        let author_styles = tree.author_styles(&loader);
+
+        let serialized: Vec<String> = author_styles
+            .stylesheets
+            .iter()
+            .map(|stylesheet| stylesheet.serialize())
+            .collect();
+        assert_eq!(
+            serialized,
+            [
+                "@import url(\"b.css\") screen;\nh { }",
+                "a { b: c; }",
+                "f { }",
+                "g { }"
+            ]
+        );
+        let link = &author_styles.stylesheets[0];
+        assert_eq!(link.location.as_deref(), Some("css/a.css"));
+        assert_eq!(link.media.serialize(), "print");
+        assert_eq!(
+            author_styles.stylesheets[1].media.serialize(),
+            "screen and (color)"
+        );
+        assert_eq!(author_styles.stylesheets[1].location, None);
+        let CssRule::Import(import) = &link.rules[0] else {
+            panic!("expected an @import rule");
+        };
+        let imported = import.stylesheet.as_ref().unwrap();
+        assert_eq!(imported.location.as_deref(), Some("css/b.css"));
+        assert_eq!(imported.media.serialize(), "screen");
+        assert_eq!(imported.owner_node, None);
+        assert_eq!(imported.serialize(), "i { }");
+
+        let p = tree.get_element_by_id("p").unwrap();
+        assert_eq!(author_styles.style_attributes.len(), 1);
+        assert_eq!(author_styles.style_attributes[0].0, p);
+        assert_eq!(author_styles.style_attributes[0].1[0].name, "color");
+        assert_eq!(author_styles.style_attributes[0].1.len(), 1);
+    }
+
+    #[test]
+    fn import_cycle() {
+        let tree_builder = parse(r#"<link rel=stylesheet href=a.css>"#);
+        let loader = resources(&[
+            ("a.css", "@import 'b.css'; a {}"),
+            ("b.css", "@import '/a.css'; @import 'b.css'; b {}"),
+        ]);
+        let author_styles = tree_builder.get_tree().author_styles(&loader);
+        let CssRule::Import(import) = &author_styles.stylesheets[0].rules[0] else {
+            panic!("expected an @import rule");
+        };
+        let imported = import.stylesheet.as_ref().unwrap();
+        assert!(imported.rules.iter().all(
+            |rule| matches!(rule, CssRule::Import(import) if import.stylesheet.is_none())
+                || matches!(rule, CssRule::Style(_))
+        ));
+    }
+
+    #[test]
+    fn file_system_loader() {
+        let tree_builder =
+            parse(r#"<link rel=stylesheet href="/tokenizer/../tokenizer/test1.jsonc">"#);
+        let author_styles = tree_builder
+            .get_tree()
+            .author_styles(&FileSystemLoader::new("css-tests"));
+        assert_eq!(
+            author_styles.stylesheets[0].location.as_deref(),
+            Some("tokenizer/test1.jsonc")
+        );
+    }
+}
@@ -0,0 +1,780 @@
+use crate::css::media::MediaQueryList;
+use crate::css::parser::{
+    AtRule, BlockContent, ComponentValue, Declaration, QualifiedRule, Rule,
+    parse_a_blocks_contents, parse_a_declaration, parse_a_list_of_component_values,
+    parse_a_stylesheets_contents, serialize_component_values,
+};
+use crate::css::selector::{SelectorList, parse_a_relative_selector_list, parse_a_selector};
+use crate::css::serializing::{serialize_a_url, serialize_an_identifier};
+use crate::css::tokenizer::Token;
+
+// https://drafts.csswg.org/cssom/#css-style-sheets
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Stylesheet {
+    // https://drafts.csswg.org/cssom/#concept-css-style-sheet-location
+    // the resolved url the style sheet was loaded from, none for an embedded style sheet
+    pub location: Option<String>,
+    // https://drafts.csswg.org/cssom/#concept-css-style-sheet-owner-node
+    // the <style> or <link> element, none for an imported style sheet
+    pub owner_node: Option<usize>,
+    // https://drafts.csswg.org/cssom/#concept-css-style-sheet-media
+    pub media: MediaQueryList,
+    pub origin: Origin,
+    // https://drafts.csswg.org/cssom/#concept-css-style-sheet-css-rules
+    pub rules: Vec<CssRule>,
+}
+
+// https://drafts.csswg.org/css-cascade-5/#cascading-origins
+// Each style rule has a cascade origin, which determines where it enters the cascade.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Origin {
+    UserAgent,
+    User,
+    #[default]
+    Author,
+}
+
+// https://drafts.csswg.org/cssom/#css-rules
+#[derive(Debug, Clone, PartialEq)]
+pub enum CssRule {
+    Style(StyleRule),
+    Import(ImportRule),
+    Media(MediaRule),
+    Supports(SupportsRule),
+    FontFace(FontFaceRule),
+    LayerBlock(LayerBlockRule),
+    LayerStatement(LayerStatementRule),
+    NestedDeclarations(NestedDeclarationsRule),
+}
+
+// https://drafts.csswg.org/cssom/#the-cssstylerule-interface
+#[derive(Debug, Clone, PartialEq)]
+pub struct StyleRule {
+    // a nested style rule has a relative selector that starts with the nesting selector
+    pub selectors: SelectorList,
+    pub declarations: Vec<Declaration>,
+    // https://drafts.csswg.org/css-nesting-1/#cssom-style
+    pub rules: Vec<CssRule>,
+}
+
+// https://drafts.csswg.org/cssom/#the-cssimportrule-interface
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportRule {
+    pub href: String,
+    // none without layer, "" for layer without a name
+    pub layer_name: Option<String>,
+    pub supports_text: Option<String>,
+    pub media: MediaQueryList,
+    // https://drafts.csswg.org/cssom/#dom-cssimportrule-stylesheet
+    // none until it is loaded, or if it can't be loaded
+    pub stylesheet: Option<Stylesheet>,
+}
+
+// https://drafts.csswg.org/cssom/#the-cssmediarule-interface
+#[derive(Debug, Clone, PartialEq)]
+pub struct MediaRule {
+    pub media: MediaQueryList,
+    pub rules: Vec<CssRule>,
+}
+
+// https://drafts.csswg.org/css-conditional-3/#the-csssupportsrule-interface
+#[derive(Debug, Clone, PartialEq)]
+pub struct SupportsRule {
+    // the condition is evaluated when the rules are cascaded
+    pub condition_text: String,
+    pub rules: Vec<CssRule>,
+}
+
+// https://drafts.csswg.org/css-fonts-4/#cssfontfacerule
+#[derive(Debug, Clone, PartialEq)]
+pub struct FontFaceRule {
+    pub declarations: Vec<Declaration>,
+}
+
+// https://drafts.csswg.org/css-cascade-5/#csslayerblockrule
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayerBlockRule {
+    // the dotted layer name, "" for an anonymous layer
+    pub name: String,
+    pub rules: Vec<CssRule>,
+}
+
+// https://drafts.csswg.org/css-cascade-5/#csslayerstatementrule
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayerStatementRule {
+    pub name_list: Vec<String>,
+}
+
+// https://drafts.csswg.org/css-nesting-1/#cssnesteddeclarations
+#[derive(Debug, Clone, PartialEq)]
+pub struct NestedDeclarationsRule {
+    pub declarations: Vec<Declaration>,
+}
+
+// where a rule is, it decides which rules are valid
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Context {
+    TopLevel,
+    // in a conditional group rule or a layer at the top level
+    Group,
+    // in a style rule, or in a group rule in a style rule
+    Nested,
+}
+
+impl Stylesheet {
+    // https://drafts.csswg.org/cssom/#parse-a-css-stylesheet
+    pub fn parse(input: &str) -> Stylesheet {
+        // 1. Let rules be the result of parsing a stylesheet's contents from input.
+        let rules = parse_a_stylesheets_contents(input);
+        let mut css_rules = Vec::new();
+        // https://drafts.csswg.org/css-cascade-5/#at-import
+        // Any @import rules must precede all other valid at-rules and style rules in a style sheet (ignoring @charset and @layer statement rules) and must not have any other valid at-rules or style rules between it and previous @import rules, or else the @import rule is invalid.
+        let mut imports_allowed = true;
+        for rule in rules {
+            let Some(css_rule) = css_rule(rule, Context::TopLevel, imports_allowed) else {
+                continue;
+            };
+            if !matches!(css_rule, CssRule::Import(_) | CssRule::LayerStatement(_)) {
+                imports_allowed = false;
+            }
+            css_rules.push(css_rule);
+        }
+        Stylesheet {
+            rules: css_rules,
+            ..Stylesheet::default()
+        }
+    }
+
+    pub fn serialize(&self) -> String {
+        self.rules
+            .iter()
+            .map(CssRule::serialize)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+// https://drafts.csswg.org/cssom/#parse-a-css-declaration-block
+// used for the style attribute
+pub fn parse_a_css_declaration_block(input: &str) -> Vec<Declaration> {
+    // 1. Let declarations be the return value of parsing a block's contents from string.
+    let declarations = parse_a_blocks_contents(input)
+        .into_iter()
+        .flat_map(|content| match content {
+            BlockContent::Declarations(declarations) => declarations,
+            // rules aren't allowed in a declaration block
+            BlockContent::Rule(_) => Vec::new(),
+        })
+        .collect();
+    // 2. Let parsed declarations be a new empty list.
+    // 3. For each item declaration in declarations, follow these substeps:
+    // 4. Return parsed declarations.
+    css_declarations(declarations)
+}
+
+// https://drafts.csswg.org/cssom/#concept-declarations-specified-order
+// The property names are ASCII lowercase, except for custom properties. A later declaration of the same property replaces an earlier one, unless only the earlier one is important.
+fn css_declarations(declarations: Vec<Declaration>) -> Vec<Declaration> {
+    let mut parsed_declarations: Vec<Declaration> = Vec::new();
+    for mut declaration in declarations {
+        if !declaration.name.starts_with("--") {
+            declaration.name = declaration.name.to_ascii_lowercase();
+            // todo check the value against the grammar of the property, for now only an empty value is invalid
+            if declaration.value.is_empty() {
+                continue;
+            }
+        }
+        if let Some(index) = parsed_declarations
+            .iter()
+            .position(|parsed| parsed.name == declaration.name)
+        {
+            if parsed_declarations[index].important && !declaration.important {
+                continue;
+            }
+            parsed_declarations.remove(index);
+        }
+        parsed_declarations.push(declaration);
+    }
+    parsed_declarations
+}
+
+// turns the rules of the parser into css rules, none if the rule is not valid in the context
+fn css_rule(rule: Rule, context: Context, imports_allowed: bool) -> Option<CssRule> {
+    match rule {
+        Rule::QualifiedRule(rule) => style_rule(rule, context).map(CssRule::Style),
+        Rule::AtRule(rule) => at_rule(rule, context, imports_allowed),
+        // https://drafts.csswg.org/css-nesting-1/#nested-declarations-rule
+        Rule::NestedDeclarations(declarations) => (context == Context::Nested).then(|| {
+            CssRule::NestedDeclarations(NestedDeclarationsRule {
+                declarations: css_declarations(declarations),
+            })
+        }),
+    }
+}
+
+// the rules in the block of a group rule
+fn css_rules_of_block(block: Vec<BlockContent>, context: Context) -> Vec<CssRule> {
+    block
+        .into_iter()
+        .filter_map(|content| match content {
+            BlockContent::Rule(rule) => css_rule(rule, context, false),
+            // https://drafts.csswg.org/css-nesting-1/#nested-group-rules
+            // declarations directly in a nested group rule are wrapped in a nested declarations rule
+            BlockContent::Declarations(declarations) => {
+                css_rule(Rule::NestedDeclarations(declarations), context, false)
+            }
+        })
+        .collect()
+}
+
+// https://drafts.csswg.org/css-syntax-3/#style-rules
+fn style_rule(rule: QualifiedRule, context: Context) -> Option<StyleRule> {
+    // The prelude of the qualified rule is parsed as a <selector-list>. If this returns failure, the entire style rule is invalid.
+    // https://drafts.csswg.org/css-nesting-1/#syntax
+    // The prelude of a nested style rule is a <relative-selector-list>.
+    let prelude = serialize_component_values(&rule.prelude);
+    let selectors = match context {
+        Context::Nested => parse_a_relative_selector_list(&prelude),
+        _ => parse_a_selector(&prelude),
+    }
+    .ok()?;
+    Some(StyleRule {
+        selectors,
+        declarations: css_declarations(rule.declarations),
+        rules: rule
+            .child_rules
+            .into_iter()
+            .filter_map(|rule| css_rule(rule, Context::Nested, false))
+            .collect(),
+    })
+}
+
+fn at_rule(rule: AtRule, context: Context, imports_allowed: bool) -> Option<CssRule> {
+    let name = rule.name.to_ascii_lowercase();
+    // a nested group rule stays nested, a group rule at the top level can contain style rules
+    let group_context = match context {
+        Context::Nested => Context::Nested,
+        _ => Context::Group,
+    };
+    match (name.as_str(), rule.block) {
+        // https://drafts.csswg.org/css-cascade-5/#at-import
+        ("import", None) if context == Context::TopLevel && imports_allowed => {
+            import_rule(&rule.prelude).map(CssRule::Import)
+        }
+        // https://drafts.csswg.org/css-conditional-3/#at-media
+        ("media", Some(block)) => Some(CssRule::Media(MediaRule {
+            media: MediaQueryList::parse(rule.prelude),
+            rules: css_rules_of_block(block, group_context),
+        })),
+        // https://drafts.csswg.org/css-conditional-3/#at-supports
+        ("supports", Some(block)) => {
+            let condition_text = serialize_component_values(&rule.prelude).trim().to_string();
+            (!condition_text.is_empty()).then(|| {
+                CssRule::Supports(SupportsRule {
+                    condition_text,
+                    rules: css_rules_of_block(block, group_context),
+                })
+            })
+        }
+        // https://drafts.csswg.org/css-fonts-4/#font-face-rule
+        // The @font-face rule accepts only descriptors, and has no prelude.
+        ("font-face", Some(block)) if context != Context::Nested => {
+            is_whitespace_only(&rule.prelude).then(|| {
+                CssRule::FontFace(FontFaceRule {
+                    declarations: css_declarations(
+                        block
+                            .into_iter()
+                            .flat_map(|content| match content {
+                                BlockContent::Declarations(declarations) => declarations,
+                                BlockContent::Rule(_) => Vec::new(),
+                            })
+                            .collect(),
+                    ),
+                })
+            })
+        }
+        // https://drafts.csswg.org/css-cascade-5/#layer-block
+        // @layer <layer-name>? { <rule-list> }
+        ("layer", Some(block)) => {
+            let name = match layer_names(&rule.prelude)?.as_slice() {
+                [] => String::new(),
+                [name] => name.clone(),
+                _ => return None,
+            };
+            Some(CssRule::LayerBlock(LayerBlockRule {
+                name,
+                rules: css_rules_of_block(block, group_context),
+            }))
+        }
+        // https://drafts.csswg.org/css-cascade-5/#layer-empty
+        // @layer <layer-name>#;
+        ("layer", None) if context != Context::Nested => {
+            let name_list = layer_names(&rule.prelude)?;
+            (!name_list.is_empty())
+                .then_some(CssRule::LayerStatement(LayerStatementRule { name_list }))
+        }
+        // unknown at-rules and at-rules in the wrong place are ignored
+        _ => None,
+    }
+}
+
+fn is_whitespace_only(values: &[ComponentValue]) -> bool {
+    values
+        .iter()
+        .all(|value| *value == ComponentValue::Token(Token::Whitespace))
+}
+
+// https://drafts.csswg.org/css-cascade-5/#at-import
+// @import [ <url> | <string> ] [ layer | layer(<layer-name>) ]? <import-conditions> ;
+// <import-conditions> = [ supports( [ <supports-condition> | <declaration> ] ) ]? <media-query-list>?
+fn import_rule(prelude: &[ComponentValue]) -> Option<ImportRule> {
+    let mut values = prelude
+        .iter()
+        .skip_while(|value| **value == ComponentValue::Token(Token::Whitespace))
+        .peekable();
+    let href = match values.next()? {
+        ComponentValue::Token(Token::Url(url) | Token::String(url)) => url.clone(),
+        ComponentValue::Function(function) if function.name.eq_ignore_ascii_case("url") => {
+            match function
+                .value
+                .iter()
+                .filter(|value| **value != ComponentValue::Token(Token::Whitespace))
+                .collect::<Vec<_>>()
+                .as_slice()
+            {
+                [ComponentValue::Token(Token::String(url))] => url.clone(),
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+    let mut rest: Vec<ComponentValue> = values.cloned().collect();
+    // the conditions follow the optional layer
+    let next_non_whitespace = |rest: &mut Vec<ComponentValue>| {
+        let index = rest
+            .iter()
+            .position(|value| *value != ComponentValue::Token(Token::Whitespace))?;
+        rest.drain(..index);
+        rest.first().cloned()
+    };
+    let mut layer_name = None;
+    match next_non_whitespace(&mut rest) {
+        Some(ComponentValue::Token(Token::Ident(ident))) if ident.eq_ignore_ascii_case("layer") => {
+            rest.remove(0);
+            layer_name = Some(String::new());
+        }
+        Some(ComponentValue::Function(function)) if function.name.eq_ignore_ascii_case("layer") => {
+            match layer_names(&function.value)?.as_slice() {
+                [name] => layer_name = Some(name.clone()),
+                _ => return None,
+            }
+            rest.remove(0);
+        }
+        _ => {}
+    }
+    let mut supports_text = None;
+    if let Some(ComponentValue::Function(function)) = next_non_whitespace(&mut rest)
+        && function.name.eq_ignore_ascii_case("supports")
+    {
+        supports_text = Some(
+            serialize_component_values(&function.value)
+                .trim()
+                .to_string(),
+        );
+        rest.remove(0);
+    }
+    Some(ImportRule {
+        href,
+        layer_name,
+        supports_text,
+        media: MediaQueryList::parse(rest),
+        stylesheet: None,
+    })
+}
+
+// https://drafts.csswg.org/css-cascade-5/#typedef-layer-name
+// <layer-name> = <ident> [ '.' <ident> ]*, a comma separated list of them, with no whitespace inside a name
+fn layer_names(values: &[ComponentValue]) -> Option<Vec<String>> {
+    let mut names = Vec::new();
+    let mut name = String::new();
+    // whether the next value has to be an ident
+    let mut expect_ident = true;
+    let mut after_whitespace = false;
+    for value in values {
+        match value {
+            ComponentValue::Token(Token::Whitespace) => after_whitespace = true,
+            ComponentValue::Token(Token::Ident(ident)) if expect_ident => {
+                // the css-wide keywords are not valid layer names
+                if name.is_empty()
+                    && ["initial", "inherit", "unset", "revert", "revert-layer"]
+                        .iter()
+                        .any(|keyword| ident.eq_ignore_ascii_case(keyword))
+                {
+                    return None;
+                }
+                if !name.is_empty() && after_whitespace {
+                    return None;
+                }
+                name.push_str(ident);
+                expect_ident = false;
+                after_whitespace = false;
+            }
+            ComponentValue::Token(Token::Delim('.')) if !expect_ident && !after_whitespace => {
+                name.push('.');
+                expect_ident = true;
+            }
+            ComponentValue::Token(Token::Comma) if !expect_ident => {
+                names.push(std::mem::take(&mut name));
+                expect_ident = true;
+                after_whitespace = false;
+            }
+            _ => return None,
+        }
+    }
+    if !name.is_empty() {
+        if expect_ident {
+            return None;
+        }
+        names.push(name);
+    } else if !names.is_empty() {
+        // a trailing comma
+        return None;
+    }
+    Some(names)
+}
+
+impl CssRule {
+    // https://drafts.csswg.org/cssom/#serialize-a-css-rule
+    pub fn serialize(&self) -> String {
+        match self {
+            CssRule::Style(rule) => rule.serialize(),
+            // CSSImportRule
+            CssRule::Import(rule) => {
+                // The result of concatenating these strings:
+                // 1. The string "@import" followed by a single SPACE (U+0020).
+                // 2. The result of performing serialize a URL on the rule's location.
+                let mut s = format!("@import {}", serialize_a_url(&rule.href));
+                // 3. If the rule's associated layer name is not null: a single SPACE (U+0020), followed by "layer" and, if the name is not empty, "(" the name ")".
+                match rule.layer_name.as_deref() {
+                    Some("") => s.push_str(" layer"),
+                    Some(name) => s.push_str(&format!(" layer({name})")),
+                    None => {}
+                }
+                // 4. If the rule's associated supports condition is not null: a single SPACE (U+0020), followed by "supports(" the condition ")".
+                if let Some(supports_text) = &rule.supports_text {
+                    s.push_str(&format!(" supports({supports_text})"));
+                }
+                // 5. If the rule's associated media list is not empty, a single SPACE (U+0020) followed by the result of performing serialize a media query list on the media list.
+                if !rule.media.0.is_empty() {
+                    s.push(' ');
+                    s.push_str(&rule.media.serialize());
+                }
+                // 6. The string ";", i.e., SEMICOLON (U+003B).
+                s.push(';');
+                s
+            }
+            // CSSMediaRule
+            // The result of concatenating the following:
+            // 1. The string "@media", followed by a single SPACE (U+0020).
+            // 2. The result of performing serialize a media query list on rule's media query list.
+            // 3. A single SPACE (U+0020), followed by the string "{", i.e., LEFT CURLY BRACKET (U+007B), followed by a newline.
+            // 4. The result of performing serialize a CSS rule on each rule in the rule's cssRules list, separated by a newline and indented by two spaces.
+            // 5. A newline, followed by the string "}", i.e., RIGHT CURLY BRACKET (U+007D)
+            CssRule::Media(rule) => {
+                serialize_a_group_rule(&format!("@media {}", rule.media.serialize()), &rule.rules)
+            }
+            // CSSSupportsRule, like CSSMediaRule with the condition text
+            CssRule::Supports(rule) => {
+                serialize_a_group_rule(&format!("@supports {}", rule.condition_text), &rule.rules)
+            }
+            // CSSFontFaceRule
+            CssRule::FontFace(rule) => {
+                match serialize_a_css_declaration_block(&rule.declarations).as_str() {
+                    "" => "@font-face { }".to_string(),
+                    declarations => format!("@font-face {{ {declarations} }}"),
+                }
+            }
+            // CSSLayerBlockRule
+            // The result of concatenating "@layer", followed by a single SPACE (U+0020) and the layer name if not empty, followed by the rules like CSSMediaRule.
+            CssRule::LayerBlock(rule) if rule.name.is_empty() => {
+                serialize_a_group_rule("@layer", &rule.rules)
+            }
+            CssRule::LayerBlock(rule) => {
+                serialize_a_group_rule(&format!("@layer {}", rule.name), &rule.rules)
+            }
+            // CSSLayerStatementRule
+            // The result of concatenating "@layer", followed by a single SPACE (U+0020), followed by the layer names separated by ", ", followed by ";".
+            CssRule::LayerStatement(rule) => format!("@layer {};", rule.name_list.join(", ")),
+            // CSSNestedDeclarations
+            // The result of performing serialize a CSS declaration block on the rule's declarations.
+            CssRule::NestedDeclarations(rule) => {
+                serialize_a_css_declaration_block(&rule.declarations)
+            }
+        }
+    }
+}
+
+impl SupportsRule {
+    // https://drafts.csswg.org/css-conditional-3/#evaluate-a-supports-condition
+    // A condition that doesn't match the grammar is false.
+    pub fn matches(&self) -> bool {
+        evaluate_a_supports_condition(&parse_a_list_of_component_values(
+            self.condition_text.as_str(),
+        ))
+        .unwrap_or(false)
+    }
+}
+
+// https://drafts.csswg.org/css-conditional-3/#typedef-supports-condition
+// <supports-condition> = not <supports-in-parens> | <supports-in-parens> [ and <supports-in-parens> ]* | <supports-in-parens> [ or <supports-in-parens> ]*
+// none if the values don't match the grammar
+fn evaluate_a_supports_condition(values: &[ComponentValue]) -> Option<bool> {
+    let values: Vec<&ComponentValue> = values
+        .iter()
+        .filter(|value| **value != ComponentValue::Token(Token::Whitespace))
+        .collect();
+    let keyword = |value: &ComponentValue| match value {
+        ComponentValue::Token(Token::Ident(ident)) => Some(ident.to_ascii_lowercase()),
+        _ => None,
+    };
+    match values.as_slice() {
+        [not, value] if keyword(not).as_deref() == Some("not") => {
+            Some(!evaluate_a_supports_in_parens(value)?)
+        }
+        [first, rest @ ..] => {
+            let mut result = evaluate_a_supports_in_parens(first)?;
+            // and and or can't be mixed without parentheses
+            let operator = rest.first().and_then(|value| keyword(value));
+            for pair in rest.chunks(2) {
+                let [operator_value, value] = pair else {
+                    return None;
+                };
+                if keyword(operator_value) != operator {
+                    return None;
+                }
+                let value = evaluate_a_supports_in_parens(value)?;
+                match operator.as_deref() {
+                    Some("and") => result &= value,
+                    Some("or") => result |= value,
+                    _ => return None,
+                }
+            }
+            Some(result)
+        }
+        [] => None,
+    }
+}
+
+// <supports-in-parens> = ( <supports-condition> ) | <supports-feature> | <general-enclosed>
+fn evaluate_a_supports_in_parens(value: &ComponentValue) -> Option<bool> {
+    match value {
+        ComponentValue::SimpleBlock(block) if block.token == Token::OpenParenthesis => {
+            if let Some(result) = evaluate_a_supports_condition(&block.value) {
+                return Some(result);
+            }
+            // <supports-decl> = ( <declaration> )
+            // todo check that the property and the value are supported, for now every declaration is
+            Some(
+                parse_a_declaration(block.value.as_slice())
+                    .is_ok_and(|declaration| !declaration.value.is_empty()),
+            )
+        }
+        // <supports-selector-fn> = selector( <complex-selector> )
+        ComponentValue::Function(function) if function.name.eq_ignore_ascii_case("selector") => {
+            Some(parse_a_selector(&serialize_component_values(&function.value)).is_ok())
+        }
+        // <general-enclosed> is false
+        ComponentValue::Function(_) => Some(false),
+        _ => None,
+    }
+}
+
+impl StyleRule {
+    // https://drafts.csswg.org/cssom/#serialize-a-css-rule
+    // CSSStyleRule
+    pub fn serialize(&self) -> String {
+        // 1. Let s initially be the result of performing serialize a group of selectors on the rule's associated selectors, followed by the string " {", i.e., a single SPACE (U+0020), followed by LEFT CURLY BRACKET (U+007B).
+        let mut s = format!("{} {{", self.selectors.serialize());
+        // 2. Let decls be the result of performing serialize a CSS declaration block on the rule's associated declarations, or null if there are no such declarations.
+        let decls = serialize_a_css_declaration_block(&self.declarations);
+        // 3. Let rules be the result of performing serialize a CSS rule on each rule in the rule's cssRules list, or null if there are no such rules.
+        let mut rules: Vec<String> = self.rules.iter().map(CssRule::serialize).collect();
+        // 4. If decls and rules are both null, append " }" to s (i.e. a single SPACE (U+0020) followed by RIGHT CURLY BRACKET (U+007D)) and return s.
+        // 5. If rules is null:
+        if rules.is_empty() {
+            // 1. Append a single SPACE (U+0020) to s
+            // 2. Append decls to s
+            // 3. Append " }" to s (i.e. a single SPACE (U+0020) followed by RIGHT CURLY BRACKET (U+007D)).
+            // 4. Return s.
+            if !decls.is_empty() {
+                s.push(' ');
+                s.push_str(&decls);
+            }
+            s.push_str(" }");
+            return s;
+        }
+        // 6. Otherwise:
+        // 1. If decls is not null, prepend it to rules.
+        if !decls.is_empty() {
+            rules.insert(0, decls);
+        }
+        // 2. For each rule in rules:
+        for rule in rules {
+            // 1. If rule is the empty string, do nothing.
+            // 2. Otherwise:
+            // 1. Append a newline followed by two spaces to s.
+            // 2. Append rule to s.
+            if !rule.is_empty() {
+                s.push_str("\n  ");
+                s.push_str(&rule);
+            }
+        }
+        // 3. Append a newline followed by RIGHT CURLY BRACKET (U+007D) to s.
+        // 4. Return s.
+        s.push_str("\n}");
+        s
+    }
+}
+
+fn serialize_a_group_rule(prelude: &str, rules: &[CssRule]) -> String {
+    let mut s = format!("{prelude} {{");
+    for rule in rules {
+        s.push_str("\n  ");
+        s.push_str(&rule.serialize());
+    }
+    s.push_str("\n}");
+    s
+}
+
+// https://drafts.csswg.org/cssom/#serialize-a-css-declaration
+pub fn serialize_a_css_declaration(declaration: &Declaration) -> String {
+    // 1. Let s be the empty string.
+    // 2. Append property to s.
+    // 3. Append ": " (U+003A U+0020) to s.
+    // 4. Append value to s.
+    // 5. If the important flag is set, append " !important" (U+0020 U+0021 U+0069 U+006D U+0070 U+006F U+0072 U+0074 U+0061 U+006E U+0074) to s.
+    // 6. Append ";" (U+003B) to s.
+    // 7. Return s.
+    format!(
+        "{}: {}{};",
+        serialize_an_identifier(&declaration.name),
+        serialize_component_values(&declaration.value),
+        if declaration.important {
+            " !important"
+        } else {
+            ""
+        }
+    )
+}
+
+// https://drafts.csswg.org/cssom/#serialize-a-css-declaration-block
+// todo shorthands
+pub fn serialize_a_css_declaration_block(declarations: &[Declaration]) -> String {
+    declarations
+        .iter()
+        .map(serialize_a_css_declaration)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn round_trip(input: &str) -> String {
+        Stylesheet::parse(input).serialize()
+    }
+
+    #[test]
+    fn style_rules() {
+        assert_eq!(round_trip("a,B > c{}"), "a, B > c { }");
+        assert_eq!(
+            round_trip("p { COLOR : red ; margin:0 auto!important; color: blue }"),
+            "p { margin: 0 auto !important; color: blue; }"
+        );
+        assert_eq!(
+            round_trip("p { color: red !important; color: blue; --X: { a } ; width: ; }"),
+            "p { color: red !important; --X: { a }; }"
+        );
+        // invalid selectors and unknown at-rules are dropped
+        assert_eq!(round_trip("a) {} @unknown x { } b {}"), "b { }");
+    }
+
+    #[test]
+    fn nested_style_rules() {
+        assert_eq!(
+            round_trip("a { color: red; > b { c: d } & .e { } f: g; @media print { h: i } }"),
+            "a {\n  color: red;\n  & > b { c: d; }\n  & .e { }\n  f: g;\n  @media print {\n  h: i;\n}\n}"
+        );
+        let stylesheet = Stylesheet::parse(".a { .b & { } }");
+        let CssRule::Style(rule) = &stylesheet.rules[0] else {
+            panic!("expected a style rule");
+        };
+        let CssRule::Style(nested) = &rule.rules[0] else {
+            panic!("expected a nested style rule");
+        };
+        assert_eq!(nested.selectors.serialize(), ".b &");
+    }
+
+    #[test]
+    fn at_rules() {
+        assert_eq!(
+            round_trip(
+                "@layer base, components; @import 'a.css' layer(base) supports(display: grid) screen; @import url(b.css)"
+            ),
+            "@layer base, components;\n@import url(\"a.css\") layer(base) supports(display: grid) screen;\n@import url(\"b.css\");"
+        );
+        // @import after a style rule is ignored
+        assert_eq!(round_trip("a {} @import 'a.css';"), "a { }");
+        assert_eq!(
+            round_trip(
+                "@media screen AND (min-width:100px) { a { b: c } } @supports (display:grid) { }"
+            ),
+            "@media screen and (min-width: 100px) {\n  a { b: c; }\n}\n@supports (display:grid) {\n}"
+        );
+        assert_eq!(
+            round_trip(
+                "@font-face { font-family: x; src: url(x.woff) } @layer { a {} } @layer a.b { }"
+            ),
+            "@font-face { font-family: x; src: url(\"x.woff\"); }\n@layer {\n  a { }\n}\n@layer a.b {\n}"
+        );
+        // invalid layer names
+        assert_eq!(
+            round_trip("@layer a b; @layer a,; @layer a. b; @layer revert {}"),
+            ""
+        );
+        // declarations are only valid in a nested group rule
+        assert_eq!(round_trip("@media print { a: b }"), "@media print {\n}");
+    }
+
+    #[test]
+    fn declaration_block() {
+        let declarations =
+            parse_a_css_declaration_block("color: red; a {} ; Width: 1px !important; width: 2px");
+        assert_eq!(
+            serialize_a_css_declaration_block(&declarations),
+            "color: red; width: 1px !important;"
+        );
+    }
+
+    #[test]
+    fn supports_conditions() {
+        let matches = |condition| {
+            let stylesheet = Stylesheet::parse(&format!("@supports {condition} {{}}"));
+            let CssRule::Supports(rule) = &stylesheet.rules[0] else {
+                panic!("expected a @supports rule");
+            };
+            rule.matches()
+        };
+        assert!(matches("(display: grid)"));
+        assert!(!matches("not (display: grid)"));
+        assert!(matches(
+            "(display: grid) and (not (x:)) and selector(a > b)"
+        ));
+        assert!(matches("(a: b) or (c)"));
+        assert!(!matches("selector(a >) or foo(b)"));
+        // and and or can't be mixed
+        assert!(!matches("(a: b) and (c: d) or (e: f)"));
+        assert!(!matches("(a: b) (c: d)"));
+    }
+}
@@ -0,0 +1,256 @@
+// A reader for the JSON with comments test files in css-tests, the crate has no dependencies for serde.
+// Supports line and block comments and trailing commas, like the C# tests with ReadCommentHandling.Skip and AllowTrailingCommas.
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(members) => members
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+}
+
+pub fn parse(input: &str) -> Result<Value, String> {
+    let mut reader = Reader {
+        chars: input.chars().collect(),
+        index: 0,
+    };
+    let value = reader.value()?;
+    reader.skip_whitespace_and_comments();
+    match reader.peek() {
+        None => Ok(value),
+        Some(char) => Err(reader.error(&format!("unexpected {char:?} after the value"))),
+    }
+}
+
+pub fn parse_file(path: &str) -> Value {
+    let contents =
+        std::fs::read_to_string(path).unwrap_or_else(|error| panic!("can't read {path}: {error}"));
+    parse(&contents).unwrap_or_else(|error| panic!("can't parse {path}: {error}"))
+}
+
+struct Reader {
+    chars: Vec<char>,
+    index: usize,
+}
+
+impl Reader {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.index).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let char = self.peek();
+        self.index += 1;
+        char
+    }
+
+    fn error(&self, message: &str) -> String {
+        let line = self.chars[..self.index.min(self.chars.len())]
+            .iter()
+            .filter(|char| **char == '\n')
+            .count();
+        format!("line {}: {message}", line + 1)
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        match self.next() {
+            Some(char) if char == expected => Ok(()),
+            char => Err(self.error(&format!("expected {expected:?}, got {char:?}"))),
+        }
+    }
+
+    fn skip_whitespace_and_comments(&mut self) {
+        loop {
+            match (self.peek(), self.chars.get(self.index + 1)) {
+                (Some(char), _) if char.is_whitespace() => self.index += 1,
+                (Some('/'), Some('/')) => {
+                    while self.peek().is_some_and(|char| char != '\n') {
+                        self.index += 1;
+                    }
+                }
+                (Some('/'), Some('*')) => {
+                    self.index += 2;
+                    while self.peek().is_some()
+                        && !(self.peek() == Some('*')
+                            && self.chars.get(self.index + 1) == Some(&'/'))
+                    {
+                        self.index += 1;
+                    }
+                    self.index += 2;
+                }
+                _ => return,
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, String> {
+        self.skip_whitespace_and_comments();
+        match self.peek() {
+            Some('[') => {
+                self.index += 1;
+                let mut values = Vec::new();
+                while !self.end_of_list(']', values.is_empty())? {
+                    values.push(self.value()?);
+                }
+                Ok(Value::Array(values))
+            }
+            Some('{') => {
+                self.index += 1;
+                let mut members = Vec::new();
+                while !self.end_of_list('}', members.is_empty())? {
+                    let name = self.string()?;
+                    self.skip_whitespace_and_comments();
+                    self.expect(':')?;
+                    members.push((name, self.value()?));
+                }
+                Ok(Value::Object(members))
+            }
+            Some('"') => Ok(Value::String(self.string()?)),
+            Some('t') => self.keyword("true", Value::Bool(true)),
+            Some('f') => self.keyword("false", Value::Bool(false)),
+            Some('n') => self.keyword("null", Value::Null),
+            Some(char) if char == '-' || char.is_ascii_digit() => {
+                let start = self.index;
+                while self
+                    .peek()
+                    .is_some_and(|char| matches!(char, '-' | '+' | '.' | 'e' | 'E' | '0'..='9'))
+                {
+                    self.index += 1;
+                }
+                let number: String = self.chars[start..self.index].iter().collect();
+                number
+                    .parse()
+                    .map(Value::Number)
+                    .map_err(|_| self.error(&format!("invalid number {number}")))
+            }
+            char => Err(self.error(&format!("unexpected {char:?}"))),
+        }
+    }
+
+    // consumes the comma in front of the next item, true at the closing bracket
+    fn end_of_list(&mut self, closing: char, first: bool) -> Result<bool, String> {
+        self.skip_whitespace_and_comments();
+        if !first && self.peek() != Some(closing) {
+            self.expect(',')?;
+            self.skip_whitespace_and_comments();
+        }
+        match self.peek() {
+            Some(char) if char == closing => {
+                self.index += 1;
+                Ok(true)
+            }
+            Some(_) => Ok(false),
+            None => Err(self.error(&format!("expected {closing:?}"))),
+        }
+    }
+
+    fn keyword(&mut self, keyword: &str, value: Value) -> Result<Value, String> {
+        for expected in keyword.chars() {
+            self.expect(expected)?;
+        }
+        Ok(value)
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.expect('"')?;
+        let mut string = String::new();
+        loop {
+            match self.next() {
+                Some('"') => return Ok(string),
+                Some('\\') => match self.next() {
+                    Some('n') => string.push('\n'),
+                    Some('t') => string.push('\t'),
+                    Some('r') => string.push('\r'),
+                    Some('b') => string.push('\u{0008}'),
+                    Some('f') => string.push('\u{000C}'),
+                    Some('u') => {
+                        let mut code_point = self.hex4()?;
+                        // a surrogate pair
+                        if (0xD800..0xDC00).contains(&code_point)
+                            && self.peek() == Some('\\')
+                            && self.chars.get(self.index + 1) == Some(&'u')
+                        {
+                            self.index += 2;
+                            let low = self.hex4()?;
+                            code_point = 0x10000 + ((code_point - 0xD800) << 10) + (low - 0xDC00);
+                        }
+                        string.push(char::from_u32(code_point).unwrap_or('\u{FFFD}'));
+                    }
+                    Some(char) => string.push(char),
+                    None => return Err(self.error("unterminated string")),
+                },
+                Some(char) => string.push(char),
+                None => return Err(self.error("unterminated string")),
+            }
+        }
+    }
+
+    fn hex4(&mut self) -> Result<u32, String> {
+        let mut value = 0;
+        for _ in 0..4 {
+            let digit = self
+                .next()
+                .and_then(|char| char.to_digit(16))
+                .ok_or_else(|| self.error("invalid unicode escape"))?;
+            value = value * 16 + digit;
+        }
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Value, parse};
+
+    #[test]
+    fn jsonc() {
+        let value = parse(
+            r#"[
+                // a comment
+                {"Input": "a\"\\\u0041\ud83d\ude00", "Tokens": [1, -2.5, true, null,], /* a block comment */ },
+            ]"#,
+        )
+        .unwrap();
+        let entry = &value.as_array().unwrap()[0];
+        assert_eq!(entry.get("Input").unwrap().as_str(), Some("a\"\\A😀"));
+        assert_eq!(
+            entry.get("Tokens"),
+            Some(&Value::Array(vec![
+                Value::Number(1.0),
+                Value::Number(-2.5),
+                Value::Bool(true),
+                Value::Null
+            ]))
+        );
+        assert!(parse("[1 2]").is_err());
+        assert!(parse("[\"a]").is_err());
+    }
+}
@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+// Loads the style sheets of <link> elements and @import rules. There is no networking, a loader reads local resources.
+pub trait ResourceLoader {
+    // the text of the resource at the resolved url, none if it can't be loaded
+    fn load(&self, url: &str) -> Option<String>;
+}
+
+// the resources by url, e.g. for tests
+impl ResourceLoader for HashMap<String, String> {
+    fn load(&self, url: &str) -> Option<String> {
+        self.get(url).cloned()
+    }
+}
+
+// reads the resources from the files in a directory, the url is a path relative to it
+pub struct FileSystemLoader {
+    root: PathBuf,
+}
+
+impl FileSystemLoader {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        FileSystemLoader { root: root.into() }
+    }
+}
+
+impl ResourceLoader for FileSystemLoader {
+    fn load(&self, url: &str) -> Option<String> {
+        // a url with a scheme isn't a local file
+        if url.contains(':') {
+            return None;
+        }
+        // the url may not be resolved, a path that leaves the root isn't read
+        let mut path = self.root.clone();
+        let mut depth = 0;
+        for segment in url.split(['/', '\\']) {
+            match segment {
+                "" | "." => {}
+                ".." if depth == 0 => return None,
+                ".." => {
+                    path.pop();
+                    depth -= 1;
+                }
+                segment => {
+                    path.push(segment);
+                    depth += 1;
+                }
+            }
+        }
+        std::fs::read_to_string(path).ok()
+    }
+}
+
+// https://url.spec.whatwg.org/#concept-basic-url-parser
+// A simplified version of resolving a url against the url of the document or style sheet, only for paths.
+// The result has no dot segments and no leading slash, an absolute url with a scheme is returned as is.
+pub fn resolve_a_url(href: &str, base: Option<&str>) -> String {
+    let href = href.trim();
+    if let Some((scheme, _)) = href.split_once(':')
+        && !scheme.is_empty()
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+    {
+        return href.to_string();
+    }
+    // todo the query and fragment
+    // the url is special, a backslash is a path separator like a slash
+    let href = href.replace('\\', "/");
+    let path = match (href.starts_with('/'), base) {
+        (false, Some(base)) => match base.rfind('/') {
+            Some(index) => format!("{}/{href}", &base[..index]),
+            None => href.to_string(),
+        },
+        _ => href.to_string(),
+    };
+    let mut segments: Vec<&str> = Vec::new();
+    let parts: Vec<&str> = path.split('/').collect();
+    for (index, segment) in parts.iter().enumerate() {
+        let is_last = index == parts.len() - 1;
+        match *segment {
+            "" if !is_last => {}
+            _ if is_single_dot_segment(segment) => {
+                if is_last {
+                    segments.push("");
+                }
+            }
+            _ if is_double_dot_segment(segment) => {
+                segments.pop();
+                if is_last {
+                    segments.push("");
+                }
+            }
+            segment => segments.push(segment),
+        }
+    }
+    segments.join("/")
+}
+
+// https://url.spec.whatwg.org/#single-dot-path-segment
+// A single-dot URL path segment is a URL path segment that is "." or an ASCII case-insensitive match for "%2e".
+fn is_single_dot_segment(segment: &str) -> bool {
+    segment == "." || segment.eq_ignore_ascii_case("%2e")
+}
+
+// https://url.spec.whatwg.org/#double-dot-path-segment
+// A double-dot URL path segment is a URL path segment that is ".." or an ASCII case-insensitive match for ".%2e", "%2e.", or "%2e%2e".
+fn is_double_dot_segment(segment: &str) -> bool {
+    ["..", ".%2e", "%2e.", "%2e%2e"]
+        .iter()
+        .any(|double_dot| segment.eq_ignore_ascii_case(double_dot))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn resolve() {
+        assert_eq!(resolve_a_url("a.css", None), "a.css");
+        assert_eq!(resolve_a_url("b.css", Some("css/a.css")), "css/b.css");
+        assert_eq!(resolve_a_url("../b.css", Some("css/x/a.css")), "css/b.css");
+        assert_eq!(resolve_a_url("/b.css", Some("css/a.css")), "b.css");
+        assert_eq!(resolve_a_url("./x/../../../b.css", Some("a.css")), "b.css");
+        assert_eq!(
+            resolve_a_url(" https://example.com/a.css", None),
+            "https://example.com/a.css"
+        );
+        assert_eq!(resolve_a_url("css/", None), "css/");
+        assert_eq!(resolve_a_url("..\\b.css", Some("css/x/a.css")), "css/b.css");
+        assert_eq!(
+            resolve_a_url("%2E%2e/%2e/B.css", Some("css/x/a.css")),
+            "css/B.css"
+        );
+        assert_eq!(resolve_a_url("x/.", None), "x/");
+    }
+
+    #[test]
+    fn file_system_loader() {
+        let loader = FileSystemLoader::new("css-tests");
+        assert!(loader.load("tokenizer/test1.jsonc").is_some());
+        assert_eq!(loader.load("tokenizer/missing.css"), None);
+        assert_eq!(loader.load("file:///etc/hosts"), None);
+        assert!(loader.load("\\tokenizer\\.\\test1.jsonc").is_some());
+        assert!(loader.load("selector/../tokenizer/test1.jsonc").is_some());
+        // the paths leave the root
+        assert_eq!(loader.load("../Cargo.toml"), None);
+        assert_eq!(loader.load("tokenizer/../../Cargo.toml"), None);
+        assert_eq!(loader.load("..\\Cargo.toml"), None);
+        assert_eq!(loader.load("../../etc/passwd"), None);
+    }
+}
@@ -0,0 +1,846 @@
+use crate::css::parser::{
+    ComponentValue, ParserInput, parse_a_comma_separated_list_of_component_values,
+    serialize_component_values,
+};
+use crate::css::serializing::{
+    serialize_a_comma_separated_list, serialize_a_number, serialize_an_identifier,
+};
+use crate::css::tokenizer::Token;
+
+// https://drafts.csswg.org/mediaqueries-4/#media-query-list
+// An empty media query list matches everything.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MediaQueryList(pub Vec<MediaQuery>);
+
+// https://drafts.csswg.org/mediaqueries-4/#typedef-media-query
+#[derive(Debug, Clone, PartialEq)]
+pub struct MediaQuery {
+    pub modifier: Option<MediaQueryModifier>,
+    // lowercased, none if the query is only a condition
+    pub media_type: Option<String>,
+    pub condition: Option<MediaCondition>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MediaQueryModifier {
+    Not,
+    Only,
+}
+
+// https://drafts.csswg.org/mediaqueries-4/#typedef-media-condition
+#[derive(Debug, Clone, PartialEq)]
+pub enum MediaCondition {
+    Feature(MediaFeature),
+    Not(Box<MediaCondition>),
+    And(Vec<MediaCondition>),
+    Or(Vec<MediaCondition>),
+    // https://drafts.csswg.org/mediaqueries-4/#typedef-general-enclosed
+    // the serialized function or block, it never matches
+    GeneralEnclosed(String),
+}
+
+// https://drafts.csswg.org/mediaqueries-4/#typedef-media-feature
+#[derive(Debug, Clone, PartialEq)]
+pub enum MediaFeature {
+    // (color)
+    Boolean(String),
+    // (min-width: 600px)
+    Plain {
+        name: String,
+        value: MediaFeatureValue,
+    },
+    // (400px < width <= 700px)
+    Range {
+        name: String,
+        start: Option<(MediaFeatureValue, Comparison)>,
+        end: Option<(Comparison, MediaFeatureValue)>,
+    },
+}
+
+// https://drafts.csswg.org/mediaqueries-4/#typedef-mf-comparison
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Comparison {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+}
+
+// https://drafts.csswg.org/mediaqueries-4/#typedef-mf-value
+#[derive(Debug, Clone, PartialEq)]
+pub enum MediaFeatureValue {
+    Number(f64),
+    Dimension(f64, String),
+    Ratio(f64, f64),
+    Ident(String),
+}
+
+impl MediaQueryList {
+    // https://drafts.csswg.org/cssom/#parse-a-media-query-list
+    pub fn parse(input: impl ParserInput) -> MediaQueryList {
+        // To parse a media query list for a given string s into a media query list is defined in the Media Queries specification. Return the list of media queries that the algorithm defined there gives.
+        // https://drafts.csswg.org/mediaqueries-4/#mq-syntax
+        let groups = parse_a_comma_separated_list_of_component_values(input);
+        // An empty media query list (only whitespace) evaluates to true.
+        if let [group] = groups.as_slice()
+            && group
+                .iter()
+                .all(|value| *value == ComponentValue::Token(Token::Whitespace))
+        {
+            return MediaQueryList::default();
+        }
+        // A media query that does not match the grammar is replaced by not all.
+        MediaQueryList(
+            groups
+                .iter()
+                .map(|group| {
+                    parse_a_media_query(group).unwrap_or(MediaQuery {
+                        modifier: Some(MediaQueryModifier::Not),
+                        media_type: Some("all".to_string()),
+                        condition: None,
+                    })
+                })
+                .collect(),
+        )
+    }
+
+    // https://drafts.csswg.org/cssom/#serialize-a-media-query-list
+    pub fn serialize(&self) -> String {
+        // 1. If the media query list is empty, then return the empty string.
+        // 2. Serialize each media query in the list of media queries, in the same order as they appear in the media query list, and then serialize the list.
+        serialize_a_comma_separated_list(self.0.iter().map(MediaQuery::serialize))
+    }
+}
+
+impl MediaQuery {
+    // https://drafts.csswg.org/cssom/#serialize-a-media-query
+    pub fn serialize(&self) -> String {
+        // 1. Let s be the empty string.
+        let mut s = String::new();
+        // 2. If the media query is negated append "not", followed by a single SPACE (U+0020), to s.
+        // 3. Let type be the serialization as an identifier of the media type of the media query, converted to ASCII lowercase.
+        // 4. If the media query does not contain media features append type, to s, then return s.
+        // 5. If type is not "all" or if the media query is negated append type, followed by a single SPACE (U+0020), followed by "and", followed by a single SPACE (U+0020), to s.
+        match self.modifier {
+            Some(MediaQueryModifier::Not) => s.push_str("not "),
+            Some(MediaQueryModifier::Only) => s.push_str("only "),
+            None => {}
+        }
+        if let Some(media_type) = &self.media_type
+            && (self.modifier.is_some() || self.condition.is_none() || media_type != "all")
+        {
+            s.push_str(&serialize_an_identifier(media_type));
+            if self.condition.is_some() {
+                s.push_str(" and ");
+            }
+        }
+        // 6. Then, for each media feature: ... append the serialization of the media feature
+        if let Some(condition) = &self.condition {
+            s.push_str(&condition.serialize());
+        }
+        // 7. Return s.
+        s
+    }
+}
+
+impl MediaCondition {
+    pub fn serialize(&self) -> String {
+        match self {
+            MediaCondition::Feature(feature) => feature.serialize(),
+            MediaCondition::Not(condition) => format!("not {}", condition.serialize_in_parens()),
+            MediaCondition::And(conditions) => conditions
+                .iter()
+                .map(MediaCondition::serialize_in_parens)
+                .collect::<Vec<_>>()
+                .join(" and "),
+            MediaCondition::Or(conditions) => conditions
+                .iter()
+                .map(MediaCondition::serialize_in_parens)
+                .collect::<Vec<_>>()
+                .join(" or "),
+            MediaCondition::GeneralEnclosed(text) => text.clone(),
+        }
+    }
+
+    // https://drafts.csswg.org/mediaqueries-4/#typedef-media-in-parens
+    fn serialize_in_parens(&self) -> String {
+        match self {
+            MediaCondition::Feature(_) | MediaCondition::GeneralEnclosed(_) => self.serialize(),
+            _ => format!("({})", self.serialize()),
+        }
+    }
+}
+
+impl MediaFeature {
+    // https://drafts.csswg.org/cssom/#serialize-a-media-feature
+    pub fn serialize(&self) -> String {
+        // 1. Let s be the empty string.
+        // 2. Append a "(" (U+0028), followed by the media feature name, converted to ASCII lowercase, to s.
+        // 3. If a value is given append a ":" (U+003A), followed by a single SPACE (U+0020), followed by the serialized media feature value, to s.
+        // 4. Append a ")" (U+0029) to s.
+        // 5. Return s.
+        match self {
+            MediaFeature::Boolean(name) => format!("({})", serialize_an_identifier(name)),
+            MediaFeature::Plain { name, value } => {
+                format!("({}: {})", serialize_an_identifier(name), value.serialize())
+            }
+            MediaFeature::Range { name, start, end } => {
+                let mut s = String::from("(");
+                if let Some((value, comparison)) = start {
+                    s.push_str(&format!(
+                        "{} {} ",
+                        value.serialize(),
+                        comparison.serialize()
+                    ));
+                }
+                s.push_str(&serialize_an_identifier(name));
+                if let Some((comparison, value)) = end {
+                    s.push_str(&format!(
+                        " {} {}",
+                        comparison.serialize(),
+                        value.serialize()
+                    ));
+                }
+                s.push(')');
+                s
+            }
+        }
+    }
+}
+
+impl Comparison {
+    pub fn serialize(&self) -> &'static str {
+        match self {
+            Comparison::Lt => "<",
+            Comparison::Le => "<=",
+            Comparison::Gt => ">",
+            Comparison::Ge => ">=",
+            Comparison::Eq => "=",
+        }
+    }
+}
+
+impl MediaFeatureValue {
+    pub fn serialize(&self) -> String {
+        match self {
+            MediaFeatureValue::Number(value) => serialize_a_number(*value),
+            MediaFeatureValue::Dimension(value, unit) => {
+                format!("{}{}", serialize_a_number(*value), unit)
+            }
+            MediaFeatureValue::Ratio(numerator, denominator) => format!(
+                "{} / {}",
+                serialize_a_number(*numerator),
+                serialize_a_number(*denominator)
+            ),
+            MediaFeatureValue::Ident(value) => serialize_an_identifier(value),
+        }
+    }
+}
+
+// https://drafts.csswg.org/mediaqueries-4/#media
+// the device the media queries are evaluated for
+#[derive(Debug, Clone, PartialEq)]
+pub struct MediaEnvironment {
+    // screen or print
+    pub media_type: String,
+    // the viewport in CSS pixels
+    pub width: f64,
+    pub height: f64,
+    // dppx
+    pub resolution: f64,
+    // bits per color component, 0 for a monochrome device
+    pub color: u32,
+    pub prefers_color_scheme: String,
+}
+
+impl Default for MediaEnvironment {
+    fn default() -> Self {
+        MediaEnvironment {
+            media_type: "screen".to_string(),
+            width: 1280.0,
+            height: 720.0,
+            resolution: 1.0,
+            color: 8,
+            prefers_color_scheme: "light".to_string(),
+        }
+    }
+}
+
+// the value of a media feature in the environment
+#[derive(Debug, Clone, PartialEq)]
+enum EnvironmentValue {
+    // px
+    Length(f64),
+    Ratio(f64),
+    // dppx
+    Resolution(f64),
+    Integer(f64),
+    Keyword(String),
+}
+
+impl MediaEnvironment {
+    // https://drafts.csswg.org/mediaqueries-4/#mq-features
+    // none for an unknown feature
+    fn value(&self, name: &str) -> Option<EnvironmentValue> {
+        Some(match name {
+            "width" => EnvironmentValue::Length(self.width),
+            "height" => EnvironmentValue::Length(self.height),
+            "device-width" => EnvironmentValue::Length(self.width),
+            "device-height" => EnvironmentValue::Length(self.height),
+            "aspect-ratio" | "device-aspect-ratio" => {
+                EnvironmentValue::Ratio(self.width / self.height)
+            }
+            "orientation" if self.height >= self.width => {
+                EnvironmentValue::Keyword("portrait".to_string())
+            }
+            "orientation" => EnvironmentValue::Keyword("landscape".to_string()),
+            "resolution" => EnvironmentValue::Resolution(self.resolution),
+            "color" => EnvironmentValue::Integer(self.color as f64),
+            "color-index" => EnvironmentValue::Integer(0.0),
+            "monochrome" if self.color == 0 => EnvironmentValue::Integer(1.0),
+            "monochrome" => EnvironmentValue::Integer(0.0),
+            "grid" => EnvironmentValue::Integer(0.0),
+            "scan" => EnvironmentValue::Keyword("progressive".to_string()),
+            "update" if self.media_type == "print" => EnvironmentValue::Keyword("none".to_string()),
+            "update" => EnvironmentValue::Keyword("fast".to_string()),
+            "hover" | "any-hover" => EnvironmentValue::Keyword("hover".to_string()),
+            "pointer" | "any-pointer" => EnvironmentValue::Keyword("fine".to_string()),
+            "prefers-color-scheme" => EnvironmentValue::Keyword(self.prefers_color_scheme.clone()),
+            "prefers-reduced-motion" => EnvironmentValue::Keyword("no-preference".to_string()),
+            _ => return None,
+        })
+    }
+}
+
+impl MediaQueryList {
+    // https://drafts.csswg.org/mediaqueries-4/#mq-list
+    // The result of the query list is true if any of the media queries in the list is true, and false otherwise. An empty media query list evaluates to true.
+    pub fn matches(&self, environment: &MediaEnvironment) -> bool {
+        self.0.is_empty() || self.0.iter().any(|query| query.matches(environment))
+    }
+}
+
+impl MediaQuery {
+    // https://drafts.csswg.org/mediaqueries-4/#mq-syntax
+    pub fn matches(&self, environment: &MediaEnvironment) -> bool {
+        // A media query whose media type is unknown is false, "all" matches all devices.
+        let media_type = self
+            .media_type
+            .as_ref()
+            .is_none_or(|media_type| media_type == "all" || *media_type == environment.media_type);
+        // https://drafts.csswg.org/mediaqueries-4/#evaluating
+        // If the result of any of the above is used in any context that expects a two-valued boolean, "unknown" must be converted to "false".
+        let condition = self
+            .condition
+            .as_ref()
+            .is_none_or(|condition| condition.evaluate(environment) == Some(true));
+        // The not keyword negates the result of the query, only is ignored.
+        (media_type && condition) != (self.modifier == Some(MediaQueryModifier::Not))
+    }
+}
+
+impl MediaCondition {
+    // https://drafts.csswg.org/mediaqueries-4/#evaluating
+    // Each media feature is evaluated as true, false or unknown (none), the conditions use three-valued Kleene logic.
+    fn evaluate(&self, environment: &MediaEnvironment) -> Option<bool> {
+        match self {
+            MediaCondition::Feature(feature) => feature.evaluate(environment),
+            MediaCondition::Not(condition) => condition.evaluate(environment).map(|value| !value),
+            MediaCondition::And(conditions) => {
+                let values: Vec<Option<bool>> = conditions
+                    .iter()
+                    .map(|condition| condition.evaluate(environment))
+                    .collect();
+                if values.contains(&Some(false)) {
+                    Some(false)
+                } else if values.contains(&None) {
+                    None
+                } else {
+                    Some(true)
+                }
+            }
+            MediaCondition::Or(conditions) => {
+                let values: Vec<Option<bool>> = conditions
+                    .iter()
+                    .map(|condition| condition.evaluate(environment))
+                    .collect();
+                if values.contains(&Some(true)) {
+                    Some(true)
+                } else if values.contains(&None) {
+                    None
+                } else {
+                    Some(false)
+                }
+            }
+            // The result is always "unknown".
+            MediaCondition::GeneralEnclosed(_) => None,
+        }
+    }
+}
+
+impl MediaFeature {
+    fn evaluate(&self, environment: &MediaEnvironment) -> Option<bool> {
+        match self {
+            // https://drafts.csswg.org/mediaqueries-4/#mq-boolean-context
+            // If the feature would be true for any value other than the number 0, a dimension with the value 0, the keyword none, or a value explicitly defined by that media feature to evaluate as false in a boolean context, the media feature evaluates to true. Otherwise, it evaluates to false.
+            MediaFeature::Boolean(name) => Some(match environment.value(name)? {
+                EnvironmentValue::Length(value)
+                | EnvironmentValue::Ratio(value)
+                | EnvironmentValue::Resolution(value)
+                | EnvironmentValue::Integer(value) => value != 0.0,
+                EnvironmentValue::Keyword(keyword) => keyword != "none",
+            }),
+            // https://drafts.csswg.org/mediaqueries-4/#mq-min-max
+            // The min- and max- prefixes are the >= and <= comparisons.
+            MediaFeature::Plain { name, value } => {
+                let (name, comparison) = if let Some(name) = name.strip_prefix("min-") {
+                    (name, Comparison::Ge)
+                } else if let Some(name) = name.strip_prefix("max-") {
+                    (name, Comparison::Le)
+                } else {
+                    (name.as_str(), Comparison::Eq)
+                };
+                let environment_value = environment.value(name)?;
+                // a keyword feature has no min- and max- prefixes
+                if let EnvironmentValue::Keyword(keyword) = &environment_value {
+                    return match (comparison, value) {
+                        (Comparison::Eq, MediaFeatureValue::Ident(value)) => Some(keyword == value),
+                        _ => None,
+                    };
+                }
+                compare(&environment_value, comparison, value)
+            }
+            // https://drafts.csswg.org/mediaqueries-4/#mq-range-context
+            MediaFeature::Range { name, start, end } => {
+                let environment_value = environment.value(name)?;
+                if matches!(environment_value, EnvironmentValue::Keyword(_)) {
+                    return None;
+                }
+                // value < name is name > value
+                let start = match start {
+                    Some((value, comparison)) => {
+                        let flipped = match comparison {
+                            Comparison::Lt => Comparison::Gt,
+                            Comparison::Le => Comparison::Ge,
+                            Comparison::Gt => Comparison::Lt,
+                            Comparison::Ge => Comparison::Le,
+                            Comparison::Eq => Comparison::Eq,
+                        };
+                        compare(&environment_value, flipped, value)?
+                    }
+                    None => true,
+                };
+                let end = match end {
+                    Some((comparison, value)) => compare(&environment_value, *comparison, value)?,
+                    None => true,
+                };
+                Some(start && end)
+            }
+        }
+    }
+}
+
+// compares the value of the environment with the value of the query, none if they are of different types
+fn compare(
+    environment_value: &EnvironmentValue,
+    comparison: Comparison,
+    value: &MediaFeatureValue,
+) -> Option<bool> {
+    let (left, right) = match (environment_value, value) {
+        (EnvironmentValue::Length(left), value) => (*left, length_in_px(value)?),
+        (EnvironmentValue::Ratio(left), MediaFeatureValue::Ratio(numerator, denominator)) => {
+            (*left, numerator / denominator)
+        }
+        (EnvironmentValue::Ratio(left), MediaFeatureValue::Number(value)) => (*left, *value),
+        (EnvironmentValue::Resolution(left), MediaFeatureValue::Dimension(value, unit)) => {
+            // https://drafts.csswg.org/css-values-4/#resolution
+            let dppx = match unit.as_str() {
+                "dppx" | "x" => *value,
+                "dpi" => value / 96.0,
+                "dpcm" => value * 2.54 / 96.0,
+                _ => return None,
+            };
+            (*left, dppx)
+        }
+        (EnvironmentValue::Integer(left), MediaFeatureValue::Number(value)) => (*left, *value),
+        _ => return None,
+    };
+    Some(match comparison {
+        Comparison::Lt => left < right,
+        Comparison::Le => left <= right,
+        Comparison::Gt => left > right,
+        Comparison::Ge => left >= right,
+        Comparison::Eq => left == right,
+    })
+}
+
+// https://drafts.csswg.org/css-values-4/#absolute-lengths
+// the font relative lengths use the initial font size of 16px
+fn length_in_px(value: &MediaFeatureValue) -> Option<f64> {
+    match value {
+        MediaFeatureValue::Number(value) if *value == 0.0 => Some(0.0),
+        MediaFeatureValue::Dimension(value, unit) => Some(
+            value
+                * match unit.as_str() {
+                    "px" => 1.0,
+                    "em" | "rem" => 16.0,
+                    "in" => 96.0,
+                    "cm" => 96.0 / 2.54,
+                    "mm" => 96.0 / 25.4,
+                    "q" => 96.0 / 101.6,
+                    "pt" => 96.0 / 72.0,
+                    "pc" => 16.0,
+                    _ => return None,
+                },
+        ),
+        _ => None,
+    }
+}
+
+// walks over component values, skipping whitespace unless asked for the very next value
+struct Cursor<'a> {
+    values: &'a [ComponentValue],
+    index: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(values: &'a [ComponentValue]) -> Cursor<'a> {
+        Cursor { values, index: 0 }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.values.get(self.index) == Some(&ComponentValue::Token(Token::Whitespace)) {
+            self.index += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<&'a ComponentValue> {
+        self.skip_whitespace();
+        self.values.get(self.index)
+    }
+
+    fn next(&mut self) -> Option<&'a ComponentValue> {
+        let value = self.peek();
+        self.index += 1;
+        value
+    }
+
+    fn is_at_end(&mut self) -> bool {
+        self.peek().is_none()
+    }
+
+    // consumes the ident if it matches the keyword
+    fn keyword(&mut self, keyword: &str) -> bool {
+        match self.peek() {
+            Some(ComponentValue::Token(Token::Ident(ident)))
+                if ident.eq_ignore_ascii_case(keyword) =>
+            {
+                self.index += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    // a delim that directly follows the previous value, like the = of <=
+    fn adjacent_delim(&mut self, c: char) -> bool {
+        if self.values.get(self.index) == Some(&ComponentValue::Token(Token::Delim(c))) {
+            self.index += 1;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+// https://drafts.csswg.org/mediaqueries-4/#typedef-media-query
+// <media-query> = <media-condition> | [ not | only ]? <media-type> [ and <media-condition-without-or> ]?
+fn parse_a_media_query(values: &[ComponentValue]) -> Option<MediaQuery> {
+    let mut cursor = Cursor::new(values);
+    // a query that starts with a parenthesis or "not (" is a condition
+    let is_condition = match cursor.peek()? {
+        ComponentValue::Token(Token::Ident(ident)) if ident.eq_ignore_ascii_case("not") => {
+            let mut after_not = Cursor {
+                values,
+                index: cursor.index + 1,
+            };
+            !matches!(
+                after_not.peek(),
+                Some(ComponentValue::Token(Token::Ident(_)))
+            )
+        }
+        ComponentValue::Token(Token::Ident(_)) => false,
+        _ => true,
+    };
+    if is_condition {
+        let condition = parse_a_media_condition(&mut cursor, true)?;
+        return cursor.is_at_end().then_some(MediaQuery {
+            modifier: None,
+            media_type: None,
+            condition: Some(condition),
+        });
+    }
+    let modifier = if cursor.keyword("not") {
+        Some(MediaQueryModifier::Not)
+    } else if cursor.keyword("only") {
+        Some(MediaQueryModifier::Only)
+    } else {
+        None
+    };
+    // <media-type> = <ident>, excluding the keywords not, and, or, only and layer
+    let Some(ComponentValue::Token(Token::Ident(media_type))) = cursor.next() else {
+        return None;
+    };
+    let media_type = media_type.to_ascii_lowercase();
+    if ["not", "and", "or", "only", "layer"].contains(&media_type.as_str()) {
+        return None;
+    }
+    let condition = if cursor.keyword("and") {
+        Some(parse_a_media_condition(&mut cursor, false)?)
+    } else {
+        None
+    };
+    cursor.is_at_end().then_some(MediaQuery {
+        modifier,
+        media_type: Some(media_type),
+        condition,
+    })
+}
+
+// https://drafts.csswg.org/mediaqueries-4/#typedef-media-condition
+// <media-condition> = <media-not> | <media-in-parens> [ <media-and>* | <media-or>* ]
+// <media-condition-without-or> = <media-not> | <media-in-parens> <media-and>*
+fn parse_a_media_condition(cursor: &mut Cursor, allow_or: bool) -> Option<MediaCondition> {
+    // <media-not> = not <media-in-parens>
+    if cursor.keyword("not") {
+        return Some(MediaCondition::Not(Box::new(parse_a_media_in_parens(
+            cursor,
+        )?)));
+    }
+    let first = parse_a_media_in_parens(cursor)?;
+    for (keyword, allowed) in [("and", true), ("or", allow_or)] {
+        if allowed && cursor.keyword(keyword) {
+            let mut conditions = vec![first, parse_a_media_in_parens(cursor)?];
+            while cursor.keyword(keyword) {
+                conditions.push(parse_a_media_in_parens(cursor)?);
+            }
+            return Some(match keyword {
+                "and" => MediaCondition::And(conditions),
+                _ => MediaCondition::Or(conditions),
+            });
+        }
+    }
+    Some(first)
+}
+
+// https://drafts.csswg.org/mediaqueries-4/#typedef-media-in-parens
+// <media-in-parens> = ( <media-condition> ) | <media-feature> | <general-enclosed>
+fn parse_a_media_in_parens(cursor: &mut Cursor) -> Option<MediaCondition> {
+    match cursor.next()? {
+        ComponentValue::SimpleBlock(block) if block.token == Token::OpenParenthesis => {
+            let mut inner = Cursor::new(&block.value);
+            if let Some(condition) = parse_a_media_condition(&mut inner, true)
+                && inner.is_at_end()
+            {
+                return Some(condition);
+            }
+            if let Some(feature) = parse_a_media_feature(&block.value) {
+                return Some(MediaCondition::Feature(feature));
+            }
+            // <general-enclosed> = [ <function-token> <any-value>? ) ] | ( <any-value>? )
+            Some(MediaCondition::GeneralEnclosed(serialize_component_values(
+                std::slice::from_ref(cursor.values.get(cursor.index - 1)?),
+            )))
+        }
+        value @ ComponentValue::Function(_) => Some(MediaCondition::GeneralEnclosed(
+            serialize_component_values(std::slice::from_ref(value)),
+        )),
+        _ => None,
+    }
+}
+
+// https://drafts.csswg.org/mediaqueries-4/#typedef-media-feature
+// <media-feature> = ( [ <mf-plain> | <mf-boolean> | <mf-range> ] )
+fn parse_a_media_feature(values: &[ComponentValue]) -> Option<MediaFeature> {
+    let mut cursor = Cursor::new(values);
+    if let Some(ComponentValue::Token(Token::Ident(name))) = cursor.peek() {
+        let name = name.to_ascii_lowercase();
+        cursor.next();
+        // <mf-boolean> = <mf-name>
+        if cursor.is_at_end() {
+            return Some(MediaFeature::Boolean(name));
+        }
+        // <mf-plain> = <mf-name> : <mf-value>
+        if cursor.peek() == Some(&ComponentValue::Token(Token::Colon)) {
+            cursor.next();
+            let value = parse_a_media_feature_value(&mut cursor)?;
+            return cursor
+                .is_at_end()
+                .then_some(MediaFeature::Plain { name, value });
+        }
+        // <mf-range> = <mf-name> <mf-comparison> <mf-value>
+        let comparison = parse_a_comparison(&mut cursor)?;
+        let value = parse_a_media_feature_value(&mut cursor)?;
+        return cursor.is_at_end().then_some(MediaFeature::Range {
+            name,
+            start: None,
+            end: Some((comparison, value)),
+        });
+    }
+    // <mf-range> = <mf-value> <mf-comparison> <mf-name>
+    //  | <mf-value> <mf-lt> <mf-name> <mf-lt> <mf-value>
+    //  | <mf-value> <mf-gt> <mf-name> <mf-gt> <mf-value>
+    let start_value = parse_a_media_feature_value(&mut cursor)?;
+    let start_comparison = parse_a_comparison(&mut cursor)?;
+    let Some(ComponentValue::Token(Token::Ident(name))) = cursor.next() else {
+        return None;
+    };
+    let name = name.to_ascii_lowercase();
+    let mut end = None;
+    if !cursor.is_at_end() {
+        let end_comparison = parse_a_comparison(&mut cursor)?;
+        let is_lt = |comparison| matches!(comparison, Comparison::Lt | Comparison::Le);
+        let is_gt = |comparison| matches!(comparison, Comparison::Gt | Comparison::Ge);
+        if !(is_lt(start_comparison) && is_lt(end_comparison)
+            || is_gt(start_comparison) && is_gt(end_comparison))
+        {
+            return None;
+        }
+        end = Some((end_comparison, parse_a_media_feature_value(&mut cursor)?));
+    }
+    cursor.is_at_end().then_some(MediaFeature::Range {
+        name,
+        start: Some((start_value, start_comparison)),
+        end,
+    })
+}
+
+// https://drafts.csswg.org/mediaqueries-4/#typedef-mf-comparison
+// <mf-lt> = '<' '='?, <mf-gt> = '>' '='?, <mf-eq> = '=', with no space between < or > and =
+fn parse_a_comparison(cursor: &mut Cursor) -> Option<Comparison> {
+    let ComponentValue::Token(Token::Delim(c)) = cursor.next()? else {
+        return None;
+    };
+    match c {
+        '<' if cursor.adjacent_delim('=') => Some(Comparison::Le),
+        '<' => Some(Comparison::Lt),
+        '>' if cursor.adjacent_delim('=') => Some(Comparison::Ge),
+        '>' => Some(Comparison::Gt),
+        '=' => Some(Comparison::Eq),
+        _ => None,
+    }
+}
+
+// https://drafts.csswg.org/mediaqueries-4/#typedef-mf-value
+// <mf-value> = <number> | <dimension> | <ident> | <ratio>
+fn parse_a_media_feature_value(cursor: &mut Cursor) -> Option<MediaFeatureValue> {
+    match cursor.next()? {
+        ComponentValue::Token(Token::Number { value, .. }) => {
+            // <ratio> = <number [0,∞]> [ / <number [0,∞]> ]?
+            let mark = cursor.index;
+            if cursor.peek() == Some(&ComponentValue::Token(Token::Delim('/'))) {
+                cursor.next();
+                if let Some(ComponentValue::Token(Token::Number {
+                    value: denominator, ..
+                })) = cursor.next()
+                {
+                    return Some(MediaFeatureValue::Ratio(*value, *denominator));
+                }
+                return None;
+            }
+            cursor.index = mark;
+            Some(MediaFeatureValue::Number(*value))
+        }
+        ComponentValue::Token(Token::Dimension { value, unit, .. }) => Some(
+            MediaFeatureValue::Dimension(*value, unit.to_ascii_lowercase()),
+        ),
+        ComponentValue::Token(Token::Ident(value)) => {
+            Some(MediaFeatureValue::Ident(value.to_ascii_lowercase()))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn serialize(input: &str) -> String {
+        MediaQueryList::parse(input).serialize()
+    }
+
+    #[test]
+    fn media_query_list() {
+        assert_eq!(MediaQueryList::parse("  "), MediaQueryList::default());
+        assert_eq!(serialize("SCREEN, print"), "screen, print");
+        assert_eq!(
+            serialize("only screen and (color)"),
+            "only screen and (color)"
+        );
+        assert_eq!(serialize("all and (color)"), "(color)");
+        assert_eq!(serialize("not all and (color)"), "not all and (color)");
+        assert_eq!(
+            serialize("screen and (MIN-WIDTH:600PX) and (orientation: landscape)"),
+            "screen and (min-width: 600px) and (orientation: landscape)"
+        );
+        assert_eq!(serialize("(aspect-ratio: 16/9)"), "(aspect-ratio: 16 / 9)");
+        assert_eq!(
+            serialize("(400px<width<=700px), (height >= 10em)"),
+            "(400px < width <= 700px), (height >= 10em)"
+        );
+        assert_eq!(
+            serialize("not (color), (a) or ((b) and (c)), (d) and foo(e)"),
+            "not (color), (a) or ((b) and (c)), (d) and foo(e)"
+        );
+        assert_eq!(serialize("(unknown thing)"), "(unknown thing)");
+    }
+
+    #[test]
+    fn invalid_media_queries() {
+        assert_eq!(serialize("screen and"), "not all");
+        assert_eq!(serialize("and, screen"), "not all, screen");
+        assert_eq!(serialize("screen and (a) or (b)"), "not all");
+        assert_eq!(serialize("(a) and (b) or (c)"), "not all");
+        // a feature that doesn't match the grammar is still a <general-enclosed>
+        for input in ["(1px < width > 2px)", "(width < = 2px)"] {
+            assert_eq!(
+                MediaQueryList::parse(input).0[0].condition,
+                Some(MediaCondition::GeneralEnclosed(input.to_string()))
+            );
+        }
+        assert_eq!(serialize("screen,,print"), "screen, not all, print");
+        assert_eq!(serialize("{}"), "not all");
+    }
+
+    #[test]
+    fn evaluate() {
+        let environment = MediaEnvironment::default();
+        let matches = |input| MediaQueryList::parse(input).matches(&environment);
+        assert!(matches(""));
+        assert!(matches("all"));
+        assert!(matches("screen, print"));
+        assert!(!matches("print"));
+        assert!(!matches("tv"));
+        assert!(matches("not print"));
+        assert!(matches("only screen and (color)"));
+        assert!(!matches("(monochrome)"));
+        assert!(matches("(min-width: 600px) and (max-width: 80em)"));
+        assert!(!matches("(max-width: 600px)"));
+        assert!(matches("(600px < width <= 1280px)"));
+        assert!(!matches("(width > 1280px)"));
+        assert!(matches("(1000px >= height)"));
+        assert!(matches("(orientation: landscape) and (aspect-ratio: 16/9)"));
+        assert!(matches(
+            "(min-resolution: 96dpi) and (prefers-color-scheme: light)"
+        ));
+        assert!(matches("(max-width: 100px) or (hover)"));
+        // unknown is false, also when negated
+        assert!(!matches("(unknown)"));
+        assert!(!matches("not (unknown)"));
+        assert!(!matches("not (unknown: 1)"));
+        assert!(matches("(unknown) or (color)"));
+        assert!(!matches("(width: red)"));
+        assert!(matches("not all and (unknown)"));
+    }
+}
@@ -0,0 +1,25 @@
+// The CSS tokenizer and parser with the CSSOM, the author styles of a parsed document and the cascade.
+// The selector and media query syntax and the matching stay private, the types of the public API are re-exported here.
+mod author_styles;
+mod cssom;
+#[cfg(test)]
+mod jsonc;
+mod loader;
+mod media;
+pub mod parser;
+mod selector;
+mod serializing;
+mod style;
+pub mod tokenizer;
+
+pub use author_styles::AuthorStyles;
+pub use cssom::{
+    CssRule, FontFaceRule, ImportRule, LayerBlockRule, LayerStatementRule, MediaRule,
+    NestedDeclarationsRule, Origin, StyleRule, Stylesheet, SupportsRule,
+    parse_a_css_declaration_block, serialize_a_css_declaration, serialize_a_css_declaration_block,
+};
+pub use loader::{FileSystemLoader, ResourceLoader};
+pub use media::{MediaEnvironment, MediaQueryList};
+pub use parser::{ComponentValue, Declaration};
+pub use selector::{SelectorError, SelectorList};
+pub use style::{SpecifiedValues, compute_styles, compute_styles_for_media};
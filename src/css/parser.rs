@@ -0,0 +1,1026 @@
+use crate::css::serializing::{
+    serialize_a_number, serialize_a_string, serialize_a_url, serialize_an_identifier,
+};
+use crate::css::tokenizer::{HashType, Token, Tokenizer, is_ident_code_point};
+
+// https://drafts.csswg.org/css-syntax-3/#component-value
+#[derive(Debug, Clone, PartialEq)]
+pub enum ComponentValue {
+    // A preserved token. In a token stream that was not consumed yet this is any token, including a <function-token> or the start of a block.
+    Token(Token),
+    Function(Function),
+    SimpleBlock(SimpleBlock),
+}
+
+// https://drafts.csswg.org/css-syntax-3/#function
+#[derive(Debug, Clone, PartialEq)]
+pub struct Function {
+    pub name: String,
+    pub value: Vec<ComponentValue>,
+}
+
+// https://drafts.csswg.org/css-syntax-3/#simple-block
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimpleBlock {
+    // the <{-token>, <[-token> or <(-token> that started the block
+    pub token: Token,
+    pub value: Vec<ComponentValue>,
+}
+
+// https://drafts.csswg.org/css-syntax-3/#declaration
+#[derive(Debug, Clone, PartialEq)]
+pub struct Declaration {
+    pub name: String,
+    pub value: Vec<ComponentValue>,
+    pub important: bool,
+}
+
+// https://drafts.csswg.org/css-syntax-3/#css-rule
+#[derive(Debug, Clone, PartialEq)]
+pub enum Rule {
+    AtRule(AtRule),
+    QualifiedRule(QualifiedRule),
+    // https://drafts.csswg.org/css-nesting-1/#nested-declarations-rule
+    // the declarations of a block after a nested rule
+    NestedDeclarations(Vec<Declaration>),
+}
+
+// https://drafts.csswg.org/css-syntax-3/#at-rule
+#[derive(Debug, Clone, PartialEq)]
+pub struct AtRule {
+    pub name: String,
+    pub prelude: Vec<ComponentValue>,
+    // the child rules and declarations of the block, none for an at-rule that ends with a semicolon like @import
+    pub block: Option<Vec<BlockContent>>,
+}
+
+// https://drafts.csswg.org/css-syntax-3/#qualified-rule
+#[derive(Debug, Clone, PartialEq)]
+pub struct QualifiedRule {
+    pub prelude: Vec<ComponentValue>,
+    pub declarations: Vec<Declaration>,
+    pub child_rules: Vec<Rule>,
+}
+
+// https://drafts.csswg.org/css-syntax-3/#consume-block-contents
+// a block contains rules and lists of declarations
+#[derive(Debug, Clone, PartialEq)]
+pub enum BlockContent {
+    Rule(Rule),
+    Declarations(Vec<Declaration>),
+}
+
+// https://drafts.csswg.org/css-syntax-3/#parser-entry-points
+// the entry points return a syntax error if the input doesn't match what they parse
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntaxError {
+    pub error: &'static str,
+}
+
+// https://drafts.csswg.org/css-syntax-3/#css-token-stream
+pub struct TokenStream {
+    tokens: Vec<ComponentValue>,
+    index: usize,
+    marked_indexes: Vec<usize>,
+}
+
+static EOF: ComponentValue = ComponentValue::Token(Token::EOF);
+
+impl TokenStream {
+    // the next token, an <EOF-token> at the end
+    fn next_item(&self) -> &ComponentValue {
+        self.tokens.get(self.index).unwrap_or(&EOF)
+    }
+
+    // https://drafts.csswg.org/css-syntax-3/#token-stream-next-token
+    // none if the next token is a function or a simple block that was already consumed
+    fn next_token(&self) -> Option<&Token> {
+        match self.next_item() {
+            ComponentValue::Token(token) => Some(token),
+            _ => None,
+        }
+    }
+
+    // https://drafts.csswg.org/css-syntax-3/#token-stream-empty
+    fn is_empty(&self) -> bool {
+        self.next_token() == Some(&Token::EOF)
+    }
+
+    // https://drafts.csswg.org/css-syntax-3/#token-stream-consume-a-token
+    fn consume_a_token(&mut self) -> ComponentValue {
+        let token = self.next_item().clone();
+        self.discard_a_token();
+        token
+    }
+
+    // https://drafts.csswg.org/css-syntax-3/#token-stream-discard-a-token
+    fn discard_a_token(&mut self) {
+        if !self.is_empty() {
+            self.index += 1;
+        }
+    }
+
+    // https://drafts.csswg.org/css-syntax-3/#token-stream-mark
+    fn mark(&mut self) {
+        self.marked_indexes.push(self.index);
+    }
+
+    // https://drafts.csswg.org/css-syntax-3/#token-stream-restore-a-mark
+    fn restore_a_mark(&mut self) {
+        self.index = self.marked_indexes.pop().expect("a marked index");
+    }
+
+    // https://drafts.csswg.org/css-syntax-3/#token-stream-discard-a-mark
+    fn discard_a_mark(&mut self) {
+        self.marked_indexes.pop().expect("a marked index");
+    }
+
+    // https://drafts.csswg.org/css-syntax-3/#token-stream-discard-whitespace
+    fn discard_whitespace(&mut self) {
+        while self.next_token() == Some(&Token::Whitespace) {
+            self.discard_a_token();
+        }
+    }
+}
+
+// https://drafts.csswg.org/css-syntax-3/#normalize-into-a-token-stream
+// The parser entry points accept a string or component values, e.g. the prelude of a rule.
+pub trait ParserInput {
+    fn normalize_into_a_token_stream(self) -> TokenStream;
+}
+
+impl ParserInput for &str {
+    // If input is a string, then filter code points from input, tokenize the result, and create a new token stream with those tokens as its tokens, and return it.
+    fn normalize_into_a_token_stream(self) -> TokenStream {
+        TokenStream {
+            tokens: Tokenizer::new(self).map(ComponentValue::Token).collect(),
+            index: 0,
+            marked_indexes: Vec::new(),
+        }
+    }
+}
+
+impl ParserInput for Vec<ComponentValue> {
+    // If input is a list of CSS tokens and/or component values, create a new token stream with input as its tokens, and return it.
+    fn normalize_into_a_token_stream(self) -> TokenStream {
+        TokenStream {
+            tokens: self,
+            index: 0,
+            marked_indexes: Vec::new(),
+        }
+    }
+}
+
+impl ParserInput for &[ComponentValue] {
+    fn normalize_into_a_token_stream(self) -> TokenStream {
+        self.to_vec().normalize_into_a_token_stream()
+    }
+}
+
+// https://drafts.csswg.org/css-syntax-3/#parse-stylesheet-contents
+pub fn parse_a_stylesheets_contents(input: impl ParserInput) -> Vec<Rule> {
+    // 1. Normalize input, and set input to the result.
+    let mut input = input.normalize_into_a_token_stream();
+    // 2. Consume a stylesheet's contents from input, and return the result.
+    consume_a_stylesheets_contents(&mut input)
+}
+
+// https://drafts.csswg.org/css-syntax-3/#parse-block-contents
+pub fn parse_a_blocks_contents(input: impl ParserInput) -> Vec<BlockContent> {
+    // 1. Normalize input, and set input to the result.
+    let mut input = input.normalize_into_a_token_stream();
+    // 2. Consume a block's contents from input, and return the result.
+    consume_a_blocks_contents(&mut input)
+}
+
+// https://drafts.csswg.org/css-syntax-3/#parse-rule
+pub fn parse_a_rule(input: impl ParserInput) -> Result<Rule, SyntaxError> {
+    // 1. Normalize input, and set input to the result.
+    let mut input = input.normalize_into_a_token_stream();
+    // 2. Discard whitespace from input.
+    input.discard_whitespace();
+    let rule = match input.next_token() {
+        // 3. If the next token from input is an <EOF-token>, return a syntax error.
+        Some(Token::EOF) => {
+            return Err(SyntaxError {
+                error: "empty-input",
+            });
+        }
+        // Otherwise, if the next token from input is an <at-keyword-token>, consume an at-rule from input, and let rule be the return value.
+        Some(Token::AtKeyword(_)) => {
+            Rule::AtRule(consume_an_at_rule(&mut input, false).ok_or(SyntaxError {
+                error: "invalid-rule",
+            })?)
+        }
+        // Otherwise, consume a qualified rule from input and let rule be the return value. If nothing or an invalid rule error was returned, return a syntax error.
+        _ => Rule::QualifiedRule(consume_a_qualified_rule(&mut input, None, false).ok_or(
+            SyntaxError {
+                error: "invalid-rule",
+            },
+        )?),
+    };
+    // 4. Discard whitespace from input.
+    input.discard_whitespace();
+    // 5. If the next token from input is an <EOF-token>, return rule. Otherwise, return a syntax error.
+    if input.is_empty() {
+        Ok(rule)
+    } else {
+        Err(SyntaxError {
+            error: "expected-eof",
+        })
+    }
+}
+
+// https://drafts.csswg.org/css-syntax-3/#parse-declaration
+pub fn parse_a_declaration(input: impl ParserInput) -> Result<Declaration, SyntaxError> {
+    // 1. Normalize input, and set input to the result.
+    let mut input = input.normalize_into_a_token_stream();
+    // 2. Discard whitespace from input.
+    input.discard_whitespace();
+    // 3. Consume a declaration from input. If anything was returned, return it. Otherwise, return a syntax error.
+    consume_a_declaration(&mut input, false).ok_or(SyntaxError {
+        error: "invalid-declaration",
+    })
+}
+
+// https://drafts.csswg.org/css-syntax-3/#parse-component-value
+pub fn parse_a_component_value(input: impl ParserInput) -> Result<ComponentValue, SyntaxError> {
+    // 1. Normalize input, and set input to the result.
+    let mut input = input.normalize_into_a_token_stream();
+    // 2. Discard whitespace from input.
+    input.discard_whitespace();
+    // 3. If input is empty, return a syntax error.
+    if input.is_empty() {
+        return Err(SyntaxError {
+            error: "empty-input",
+        });
+    }
+    // 4. Consume a component value from input and let value be the return value.
+    let value = consume_a_component_value(&mut input);
+    // 5. Discard whitespace from input.
+    input.discard_whitespace();
+    // 6. If input is empty, return value. Otherwise, return a syntax error.
+    if input.is_empty() {
+        Ok(value)
+    } else {
+        Err(SyntaxError {
+            error: "expected-eof",
+        })
+    }
+}
+
+// https://drafts.csswg.org/css-syntax-3/#parse-list-of-component-values
+pub fn parse_a_list_of_component_values(input: impl ParserInput) -> Vec<ComponentValue> {
+    // 1. Normalize input, and set input to the result.
+    let mut input = input.normalize_into_a_token_stream();
+    // 2. Consume a list of component values from input, and return the result.
+    consume_a_list_of_component_values(&mut input, None, false)
+}
+
+// https://drafts.csswg.org/css-syntax-3/#parse-comma-separated-list-of-component-values
+pub fn parse_a_comma_separated_list_of_component_values(
+    input: impl ParserInput,
+) -> Vec<Vec<ComponentValue>> {
+    // 1. Normalize input, and set input to the result.
+    let mut input = input.normalize_into_a_token_stream();
+    // 2. Let groups be an empty list.
+    let mut groups = Vec::new();
+    // 3. While input is not empty:
+    while !input.is_empty() {
+        // 1. Consume a list of component values from input, with <comma-token> as the stop token, and append the result to groups.
+        groups.push(consume_a_list_of_component_values(
+            &mut input,
+            Some(&Token::Comma),
+            false,
+        ));
+        // 2. Discard a token from input.
+        input.discard_a_token();
+    }
+    // 4. Return groups.
+    groups
+}
+
+// https://drafts.csswg.org/css-syntax-3/#consume-stylesheet-contents
+fn consume_a_stylesheets_contents(input: &mut TokenStream) -> Vec<Rule> {
+    // Let rules be an initially empty list of rules.
+    let mut rules = Vec::new();
+    // Process input:
+    loop {
+        match input.next_token() {
+            // <whitespace-token>: Discard a token from input.
+            Some(Token::Whitespace) => input.discard_a_token(),
+            // <EOF-token>: Return rules.
+            Some(Token::EOF) => return rules,
+            // <CDO-token>, <CDC-token>: Discard a token from input.
+            Some(Token::CDO | Token::CDC) => input.discard_a_token(),
+            // <at-keyword-token>: Consume an at-rule from input. If anything is returned, append it to rules.
+            Some(Token::AtKeyword(_)) => {
+                if let Some(rule) = consume_an_at_rule(input, false) {
+                    rules.push(Rule::AtRule(rule));
+                }
+            }
+            // anything else: Consume a qualified rule from input. If a rule is returned, append it to rules.
+            _ => {
+                if let Some(rule) = consume_a_qualified_rule(input, None, false) {
+                    rules.push(Rule::QualifiedRule(rule));
+                }
+            }
+        }
+    }
+}
+
+// https://drafts.csswg.org/css-syntax-3/#consume-at-rule
+// Whether the rule is valid in the current context is checked when the rules are turned into the CSSOM.
+fn consume_an_at_rule(input: &mut TokenStream, nested: bool) -> Option<AtRule> {
+    // Assert: The next token is an <at-keyword-token>.
+    // Consume a token from input, and let rule be a new at-rule with its name set to the returned token's value, its prelude initially set to an empty list, and no declarations or child rules.
+    let ComponentValue::Token(Token::AtKeyword(name)) = input.consume_a_token() else {
+        unreachable!("expected an <at-keyword-token>");
+    };
+    let mut rule = AtRule {
+        name,
+        prelude: Vec::new(),
+        block: None,
+    };
+    // Process input:
+    loop {
+        match input.next_token() {
+            // <semicolon-token>, <EOF-token>: Discard a token from input. If rule is valid in the current context, return it; otherwise return nothing.
+            Some(Token::Semicolon | Token::EOF) => {
+                input.discard_a_token();
+                return Some(rule);
+            }
+            // <}-token>: If nested is true: If rule is valid in the current context, return it. Otherwise, return nothing.
+            Some(Token::CloseCurlyBracket) if nested => return Some(rule),
+            // Otherwise, consume a token and append the result to rule's prelude.
+            Some(Token::CloseCurlyBracket) => rule.prelude.push(input.consume_a_token()),
+            // <{-token>: Consume a block from input, and assign the result to rule's child rules.
+            // If rule is valid in the current context, return it. Otherwise, return nothing.
+            Some(Token::OpenCurlyBracket) => {
+                rule.block = Some(consume_a_block(input));
+                return Some(rule);
+            }
+            // anything else: Consume a component value from input and append the returned value to rule's prelude.
+            _ => rule.prelude.push(consume_a_component_value(input)),
+        }
+    }
+}
+
+// https://drafts.csswg.org/css-syntax-3/#consume-qualified-rule
+// Whether the rule is valid in the current context is checked when the rules are turned into the CSSOM, so an invalid rule error is never returned.
+fn consume_a_qualified_rule(
+    input: &mut TokenStream,
+    stop_token: Option<&Token>,
+    nested: bool,
+) -> Option<QualifiedRule> {
+    // Let rule be a new qualified rule with its prelude, declarations, and child rules all initially set to empty lists.
+    let mut rule = QualifiedRule {
+        prelude: Vec::new(),
+        declarations: Vec::new(),
+        child_rules: Vec::new(),
+    };
+    // Process input:
+    loop {
+        match input.next_token() {
+            // <EOF-token>, stop token (if passed): This is a parse error. Return nothing.
+            Some(Token::EOF) => return None,
+            Some(token) if stop_token == Some(token) => return None,
+            // <}-token>: This is a parse error. If nested is true, return nothing. Otherwise, consume a token and append the result to rule's prelude.
+            Some(Token::CloseCurlyBracket) if nested => return None,
+            Some(Token::CloseCurlyBracket) => rule.prelude.push(input.consume_a_token()),
+            // <{-token>
+            Some(Token::OpenCurlyBracket) => {
+                // If the first two non-<whitespace-token> values of rule's prelude are an <ident-token> whose value starts with "--" followed by a <colon-token>, then:
+                let mut values = rule
+                    .prelude
+                    .iter()
+                    .filter(|value| **value != ComponentValue::Token(Token::Whitespace));
+                if matches!(values.next(), Some(ComponentValue::Token(Token::Ident(name))) if name.starts_with("--"))
+                    && values.next() == Some(&ComponentValue::Token(Token::Colon))
+                {
+                    if nested {
+                        // If nested is true, consume the remnants of a bad declaration from input, with nested set to true, and return nothing.
+                        consume_the_remnants_of_a_bad_declaration(input, true);
+                    } else {
+                        // If nested is false, consume a block from input, and return nothing.
+                        consume_a_block(input);
+                    }
+                    return None;
+                }
+                // Otherwise, consume a block from input, and let child rules be the result.
+                let mut child_rules = consume_a_block(input).into_iter();
+                // If the first item of child rules is a list of declarations, remove it from child rules and assign it to rule's declarations.
+                let mut first = child_rules.next();
+                if let Some(BlockContent::Declarations(declarations)) = first {
+                    rule.declarations = declarations;
+                    first = None;
+                }
+                // If any remaining items of child rules are lists of declarations, replace them with nested declarations rules containing the list as its sole child. Assign child rules to rule's child rules.
+                rule.child_rules = first
+                    .into_iter()
+                    .chain(child_rules)
+                    .map(|item| match item {
+                        BlockContent::Rule(rule) => rule,
+                        BlockContent::Declarations(declarations) => {
+                            Rule::NestedDeclarations(declarations)
+                        }
+                    })
+                    .collect();
+                // If rule is valid in the current context, return it; otherwise return an invalid rule error.
+                return Some(rule);
+            }
+            // anything else: Consume a component value from input and append the result to rule's prelude.
+            _ => rule.prelude.push(consume_a_component_value(input)),
+        }
+    }
+}
+
+// https://drafts.csswg.org/css-syntax-3/#consume-block
+fn consume_a_block(input: &mut TokenStream) -> Vec<BlockContent> {
+    // Assert: The next token is a <{-token>.
+    debug_assert_eq!(input.next_token(), Some(&Token::OpenCurlyBracket));
+    // Discard a token from input. Consume a block's contents from input and let rules be the result. Discard a token from input.
+    input.discard_a_token();
+    let rules = consume_a_blocks_contents(input);
+    input.discard_a_token();
+    // Return rules.
+    rules
+}
+
+// https://drafts.csswg.org/css-syntax-3/#consume-block-contents
+fn consume_a_blocks_contents(input: &mut TokenStream) -> Vec<BlockContent> {
+    // Let rules be an empty list, containing either rules or lists of declarations.
+    let mut rules = Vec::new();
+    // Let decls be an empty list of declarations.
+    let mut decls = Vec::new();
+    // If decls is not empty, append decls to rules, and set decls to a fresh empty list of declarations.
+    let append_decls = |rules: &mut Vec<BlockContent>, decls: &mut Vec<Declaration>| {
+        if !decls.is_empty() {
+            rules.push(BlockContent::Declarations(std::mem::take(decls)));
+        }
+    };
+    // Process input:
+    loop {
+        match input.next_token() {
+            // <whitespace-token>, <semicolon-token>: Discard a token from input.
+            Some(Token::Whitespace | Token::Semicolon) => input.discard_a_token(),
+            // <EOF-token>, <}-token>: Return rules.
+            // the declarations at the end of the block are part of the block too
+            Some(Token::EOF | Token::CloseCurlyBracket) => {
+                append_decls(&mut rules, &mut decls);
+                return rules;
+            }
+            // <at-keyword-token>: If decls is not empty, append decls to rules, and set decls to a fresh empty list of declarations.
+            // Consume an at-rule from input, with nested set to true. If a rule was returned, append it to rules.
+            Some(Token::AtKeyword(_)) => {
+                append_decls(&mut rules, &mut decls);
+                if let Some(rule) = consume_an_at_rule(input, true) {
+                    rules.push(BlockContent::Rule(Rule::AtRule(rule)));
+                }
+            }
+            // anything else
+            _ => {
+                // Mark input.
+                input.mark();
+                // Consume a declaration from input, with nested set to true. If a declaration was returned, append it to decls, and discard a mark from input.
+                if let Some(declaration) = consume_a_declaration(input, true) {
+                    decls.push(declaration);
+                    input.discard_a_mark();
+                } else {
+                    // Otherwise, restore a mark from input, then consume a qualified rule from input, with nested set to true, and <semicolon-token> as the stop token.
+                    input.restore_a_mark();
+                    // If nothing was returned: Do nothing.
+                    // If a rule was returned: If decls is not empty, append decls to rules, and set decls to a fresh empty list of declarations. Append the rule to rules.
+                    if let Some(rule) =
+                        consume_a_qualified_rule(input, Some(&Token::Semicolon), true)
+                    {
+                        append_decls(&mut rules, &mut decls);
+                        rules.push(BlockContent::Rule(Rule::QualifiedRule(rule)));
+                    }
+                }
+            }
+        }
+    }
+}
+
+// https://drafts.csswg.org/css-syntax-3/#consume-declaration
+fn consume_a_declaration(input: &mut TokenStream, nested: bool) -> Option<Declaration> {
+    // Let decl be a new declaration, with an initially empty name and a value set to an empty list.
+    // 1. If the next token is an <ident-token>, consume a token from input and set decl's name to the token's value.
+    // Otherwise, consume the remnants of a bad declaration from input, with nested, and return nothing.
+    let Some(Token::Ident(name)) = input.next_token() else {
+        consume_the_remnants_of_a_bad_declaration(input, nested);
+        return None;
+    };
+    let mut decl = Declaration {
+        name: name.clone(),
+        value: Vec::new(),
+        important: false,
+    };
+    input.discard_a_token();
+    // 2. Discard whitespace from input.
+    input.discard_whitespace();
+    // 3. If the next token is a <colon-token>, discard a token from input.
+    // Otherwise, consume the remnants of a bad declaration from input, with nested, and return nothing.
+    if input.next_token() != Some(&Token::Colon) {
+        consume_the_remnants_of_a_bad_declaration(input, nested);
+        return None;
+    }
+    input.discard_a_token();
+    // 4. Discard whitespace from input.
+    input.discard_whitespace();
+    // 5. Consume a list of component values from input, with nested, and with <semicolon-token> as the stop token, and set decl's value to the result.
+    decl.value = consume_a_list_of_component_values(input, Some(&Token::Semicolon), nested);
+    // 6. If the last two non-<whitespace-token>s in decl's value are a <delim-token> with the value "!" followed by an <ident-token> with a value that is an ASCII case-insensitive match for "important", remove them from decl's value and set decl's important flag.
+    let mut non_whitespace = decl
+        .value
+        .iter()
+        .enumerate()
+        .rev()
+        .filter(|(_, value)| **value != ComponentValue::Token(Token::Whitespace));
+    if let Some((important, ComponentValue::Token(Token::Ident(ident)))) = non_whitespace.next()
+        && ident.eq_ignore_ascii_case("important")
+        && let Some((bang, ComponentValue::Token(Token::Delim('!')))) = non_whitespace.next()
+    {
+        decl.value.remove(important);
+        decl.value.remove(bang);
+        decl.important = true;
+    }
+    // 7. While the last item in decl's value is a <whitespace-token>, remove that token.
+    while decl.value.last() == Some(&ComponentValue::Token(Token::Whitespace)) {
+        decl.value.pop();
+    }
+    // 8. If decl's name is a custom property name string, then set decl's original text to the segment of the original source text string corresponding to the tokens of decl's value.
+    // todo original text, the value of a custom property is serialized from its tokens
+    if !decl.name.starts_with("--") {
+        // Otherwise, if decl's value contains a top-level simple block with an associated token of <{-token>, and also contains any other non-<whitespace-token> value, return nothing. (That is, a top-level {}-block is only allowed as the entire value of a non-custom property.)
+        let is_curly_block = |value: &ComponentValue| matches!(value, ComponentValue::SimpleBlock(block) if block.token == Token::OpenCurlyBracket);
+        if decl.value.iter().any(is_curly_block)
+            && decl.value.iter().any(|value| {
+                !is_curly_block(value) && *value != ComponentValue::Token(Token::Whitespace)
+            })
+        {
+            return None;
+        }
+        // Otherwise, if decl's name is an ASCII case-insensitive match for "unicode-range", consume the value of a unicode-range descriptor from the segment of the original source text string corresponding to the tokens returned by the consume a list of component values call, and replace decl's value with the result.
+        // the tokenizer already produces <unicode-range-token>s
+    }
+    // 9. If decl is valid in the current context, return it; otherwise return nothing.
+    Some(decl)
+}
+
+// https://drafts.csswg.org/css-syntax-3/#consume-the-remnants-of-a-bad-declaration
+fn consume_the_remnants_of_a_bad_declaration(input: &mut TokenStream, nested: bool) {
+    // Process input:
+    loop {
+        match input.next_token() {
+            // <eof-token>, <semicolon-token>: Discard a token from input, and return nothing.
+            Some(Token::EOF | Token::Semicolon) => {
+                input.discard_a_token();
+                return;
+            }
+            // <}-token>: If nested is true, return nothing. Otherwise, discard a token.
+            Some(Token::CloseCurlyBracket) if nested => return,
+            Some(Token::CloseCurlyBracket) => input.discard_a_token(),
+            // anything else: Consume a component value from input, and do nothing.
+            _ => {
+                consume_a_component_value(input);
+            }
+        }
+    }
+}
+
+// https://drafts.csswg.org/css-syntax-3/#consume-list-of-components
+fn consume_a_list_of_component_values(
+    input: &mut TokenStream,
+    stop_token: Option<&Token>,
+    nested: bool,
+) -> Vec<ComponentValue> {
+    // Let values be an empty list of component values.
+    let mut values = Vec::new();
+    // Process input:
+    loop {
+        match input.next_token() {
+            // <eof-token>, stop token (if passed): Return values.
+            Some(Token::EOF) => return values,
+            Some(token) if stop_token == Some(token) => return values,
+            // <}-token>: If nested is true, return values.
+            Some(Token::CloseCurlyBracket) if nested => return values,
+            // Otherwise, this is a parse error. Consume a token from input and append the result to values.
+            Some(Token::CloseCurlyBracket) => values.push(input.consume_a_token()),
+            // anything else: Consume a component value from input, and append the result to values.
+            _ => values.push(consume_a_component_value(input)),
+        }
+    }
+}
+
+// https://drafts.csswg.org/css-syntax-3/#consume-component-value
+fn consume_a_component_value(input: &mut TokenStream) -> ComponentValue {
+    // Process input:
+    match input.next_token() {
+        // <{-token>, <[-token>, <(-token>: Consume a simple block from input and return the result.
+        Some(Token::OpenCurlyBracket | Token::OpenSquareBracket | Token::OpenParenthesis) => {
+            ComponentValue::SimpleBlock(consume_a_simple_block(input))
+        }
+        // <function-token>: Consume a function from input and return the result.
+        Some(Token::Function(_)) => ComponentValue::Function(consume_a_function(input)),
+        // anything else: Consume a token from input and return the result.
+        _ => input.consume_a_token(),
+    }
+}
+
+// https://drafts.csswg.org/css-syntax-3/#consume-simple-block
+fn consume_a_simple_block(input: &mut TokenStream) -> SimpleBlock {
+    // Assert: the next token of input is <{-token>, <[-token>, or <(-token>.
+    // Let ending token be the mirror variant of the next token. (E.g. if it was called with <[-token>, the ending token is <]-token>.)
+    // Let block be a new simple block with its associated token set to the next token and with its value initially set to an empty list.
+    let ComponentValue::Token(token) = input.consume_a_token() else {
+        unreachable!("expected the start of a simple block");
+    };
+    let ending_token = match token {
+        Token::OpenCurlyBracket => Token::CloseCurlyBracket,
+        Token::OpenSquareBracket => Token::CloseSquareBracket,
+        Token::OpenParenthesis => Token::CloseParenthesis,
+        _ => unreachable!("expected the start of a simple block"),
+    };
+    let mut block = SimpleBlock {
+        token,
+        value: Vec::new(),
+    };
+    // Discard a token from input.
+    // (the token was consumed above)
+    // Process input:
+    loop {
+        match input.next_token() {
+            // <eof-token>, ending token: Discard a token from input. Return block.
+            Some(Token::EOF) => return block,
+            Some(token) if *token == ending_token => {
+                input.discard_a_token();
+                return block;
+            }
+            // anything else: Consume a component value from input and append the result to block's value.
+            _ => block.value.push(consume_a_component_value(input)),
+        }
+    }
+}
+
+// https://drafts.csswg.org/css-syntax-3/#consume-function
+fn consume_a_function(input: &mut TokenStream) -> Function {
+    // Assert: The next token is a <function-token>.
+    // Consume a token from input, and let function be a new function with its name equal the returned token's value, and a value set to an empty list.
+    let ComponentValue::Token(Token::Function(name)) = input.consume_a_token() else {
+        unreachable!("expected a <function-token>");
+    };
+    let mut function = Function {
+        name,
+        value: Vec::new(),
+    };
+    // Process input:
+    loop {
+        match input.next_token() {
+            // <eof-token>, <)-token>: Discard a token from input. Return function.
+            Some(Token::EOF | Token::CloseParenthesis) => {
+                input.discard_a_token();
+                return function;
+            }
+            // anything else: Consume a component value from input and append the result to function's value.
+            _ => function.value.push(consume_a_component_value(input)),
+        }
+    }
+}
+
+// https://drafts.csswg.org/css-syntax-3/#serialization
+// The component values are serialized so that they are tokenized to the same tokens again, a comment separates two tokens that would otherwise be read as one.
+pub fn serialize_component_values(values: &[ComponentValue]) -> String {
+    let mut s = String::new();
+    let mut previous: Option<&ComponentValue> = None;
+    for value in values {
+        if let Some(previous) = previous
+            && needs_a_comment_between(previous, value)
+        {
+            s.push_str("/**/");
+        }
+        serialize_a_component_value(value, &mut s);
+        previous = Some(value);
+    }
+    s
+}
+
+fn serialize_a_component_value(value: &ComponentValue, s: &mut String) {
+    match value {
+        ComponentValue::Token(token) => serialize_a_token(token, s),
+        ComponentValue::Function(function) => {
+            s.push_str(&serialize_an_identifier(&function.name));
+            s.push('(');
+            s.push_str(&serialize_component_values(&function.value));
+            s.push(')');
+        }
+        ComponentValue::SimpleBlock(block) => {
+            let (start, end) = match block.token {
+                Token::OpenCurlyBracket => ('{', '}'),
+                Token::OpenSquareBracket => ('[', ']'),
+                _ => ('(', ')'),
+            };
+            s.push(start);
+            s.push_str(&serialize_component_values(&block.value));
+            s.push(end);
+        }
+    }
+}
+
+fn serialize_a_token(token: &Token, s: &mut String) {
+    match token {
+        Token::Ident(value) => s.push_str(&serialize_an_identifier(value)),
+        Token::Function(value) => {
+            s.push_str(&serialize_an_identifier(value));
+            s.push('(');
+        }
+        Token::AtKeyword(value) => {
+            s.push('@');
+            s.push_str(&serialize_an_identifier(value));
+        }
+        Token::Hash { value, type_flag } => {
+            s.push('#');
+            match type_flag {
+                HashType::Id => s.push_str(&serialize_an_identifier(value)),
+                // the value doesn't start an ident sequence, e.g. #123, only the code points that aren't ident code points are escaped
+                HashType::Unrestricted => {
+                    for c in value.chars() {
+                        if is_ident_code_point(c) {
+                            s.push(c);
+                        } else {
+                            s.push_str(&serialize_an_identifier(&c.to_string()));
+                        }
+                    }
+                }
+            }
+        }
+        Token::String(value) => s.push_str(&serialize_a_string(value)),
+        Token::Url(value) => s.push_str(&serialize_a_url(value)),
+        // a bad string or url can't be written so that it is tokenized again, it is left out like an invalid declaration
+        Token::BadString | Token::BadUrl => {}
+        // a reverse solidus that isn't an escape is followed by a newline
+        Token::Delim('\\') => s.push_str("\\\n"),
+        Token::Delim(value) => s.push(*value),
+        Token::Number { value, .. } => s.push_str(&serialize_a_number(*value)),
+        Token::Percentage { value, .. } => {
+            s.push_str(&serialize_a_number(*value));
+            s.push('%');
+        }
+        Token::Dimension { value, unit, .. } => {
+            s.push_str(&serialize_a_number(*value));
+            // a unit like "e3" would be read as the exponent of the number
+            let unit_identifier = serialize_an_identifier(unit);
+            let is_exponent = unit.strip_prefix(['e', 'E']).is_some_and(|rest| {
+                let rest = rest.strip_prefix(['+', '-']).unwrap_or(rest);
+                rest.starts_with(|c: char| c.is_ascii_digit())
+            });
+            if is_exponent {
+                s.push_str("\\65 ");
+                s.push_str(&unit_identifier[1..]);
+            } else {
+                s.push_str(&unit_identifier);
+            }
+        }
+        Token::UnicodeRange { start, end } if start == end => s.push_str(&format!("U+{start:X}")),
+        Token::UnicodeRange { start, end } => s.push_str(&format!("U+{start:X}-{end:X}")),
+        Token::Whitespace => s.push(' '),
+        Token::CDO => s.push_str("<!--"),
+        Token::CDC => s.push_str("-->"),
+        Token::Colon => s.push(':'),
+        Token::Semicolon => s.push(';'),
+        Token::Comma => s.push(','),
+        Token::OpenSquareBracket => s.push('['),
+        Token::CloseSquareBracket => s.push(']'),
+        Token::OpenParenthesis => s.push('('),
+        Token::CloseParenthesis => s.push(')'),
+        Token::OpenCurlyBracket => s.push('{'),
+        Token::CloseCurlyBracket => s.push('}'),
+        Token::EOF => {}
+    }
+}
+
+// the token types of the table in https://drafts.csswg.org/css-syntax-3/#serialization
+#[derive(PartialEq)]
+enum Kind {
+    Ident,
+    Function,
+    Url,
+    AtKeyword,
+    Hash,
+    Number,
+    Percentage,
+    Dimension,
+    CDC,
+    OpenParenthesis,
+    Delim(char),
+    Other,
+}
+
+fn kind_of_token(token: &Token) -> Kind {
+    match token {
+        Token::Ident(_) => Kind::Ident,
+        Token::Function(_) => Kind::Function,
+        Token::Url(_) | Token::BadUrl => Kind::Url,
+        Token::AtKeyword(_) => Kind::AtKeyword,
+        Token::Hash { .. } => Kind::Hash,
+        Token::Number { .. } => Kind::Number,
+        Token::Percentage { .. } => Kind::Percentage,
+        Token::Dimension { .. } => Kind::Dimension,
+        Token::CDC => Kind::CDC,
+        Token::OpenParenthesis => Kind::OpenParenthesis,
+        Token::Delim(c) => Kind::Delim(*c),
+        _ => Kind::Other,
+    }
+}
+
+// https://drafts.csswg.org/css-syntax-3/#serialization
+// Insert a comment between consecutive tokens when the first token's type is in the left column of the table and the second token's type is marked in the top row.
+fn needs_a_comment_between(first: &ComponentValue, second: &ComponentValue) -> bool {
+    // a function or a block ends with a bracket
+    let ComponentValue::Token(first) = first else {
+        return false;
+    };
+    let second = match second {
+        ComponentValue::Token(token) => kind_of_token(token),
+        ComponentValue::Function(_) => Kind::Function,
+        ComponentValue::SimpleBlock(block) => kind_of_token(&block.token),
+    };
+    let ident_like = matches!(
+        second,
+        Kind::Ident | Kind::Function | Kind::Url | Kind::Delim('-')
+    );
+    let numeric = matches!(second, Kind::Number | Kind::Percentage | Kind::Dimension);
+    match kind_of_token(first) {
+        Kind::Ident => ident_like || numeric || matches!(second, Kind::CDC | Kind::OpenParenthesis),
+        Kind::AtKeyword | Kind::Hash | Kind::Dimension => {
+            ident_like || numeric || second == Kind::CDC
+        }
+        Kind::Delim('#' | '-') => ident_like || numeric,
+        Kind::Number => {
+            matches!(
+                second,
+                Kind::Ident | Kind::Function | Kind::Url | Kind::Delim('%')
+            ) || numeric
+        }
+        Kind::Delim('@') => ident_like,
+        Kind::Delim('.' | '+') => numeric,
+        Kind::Delim('/') => second == Kind::Delim('*'),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn ident(value: &str) -> ComponentValue {
+        ComponentValue::Token(Token::Ident(value.to_string()))
+    }
+
+    #[test]
+    fn stylesheet_contents() {
+        let rules = parse_a_stylesheets_contents(
+            "<!-- @import 'a.css'; a, b { color: red !IMPORTANT; --x: {a} } -->",
+        );
+        assert_eq!(rules.len(), 2);
+        let Rule::AtRule(import) = &rules[0] else {
+            panic!("expected an at-rule");
+        };
+        assert_eq!(import.name, "import");
+        assert_eq!(import.block, None);
+        assert_eq!(serialize_component_values(&import.prelude), " \"a.css\"");
+        let Rule::QualifiedRule(style) = &rules[1] else {
+            panic!("expected a qualified rule");
+        };
+        assert_eq!(serialize_component_values(&style.prelude), "a, b ");
+        assert_eq!(
+            style.declarations,
+            [
+                Declaration {
+                    name: "color".to_string(),
+                    value: vec![ident("red")],
+                    important: true,
+                },
+                Declaration {
+                    name: "--x".to_string(),
+                    value: vec![ComponentValue::SimpleBlock(SimpleBlock {
+                        token: Token::OpenCurlyBracket,
+                        value: vec![ident("a")],
+                    })],
+                    important: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn error_recovery() {
+        // the bad declarations are skipped up to the next semicolon, a stray } ends the block
+        let contents = parse_a_blocks_contents("a; 1: b; c: d; e: f {g} h; i: j } k: l");
+        let names: Vec<&str> = contents
+            .iter()
+            .flat_map(|content| match content {
+                BlockContent::Declarations(declarations) => declarations.as_slice(),
+                BlockContent::Rule(_) => &[],
+            })
+            .map(|declaration| declaration.name.as_str())
+            .collect();
+        assert_eq!(names, ["c", "i"]);
+        // a declaration that isn't valid is parsed again as a nested rule
+        assert!(
+            matches!(&contents[1], BlockContent::Rule(Rule::QualifiedRule(rule)) if serialize_component_values(&rule.prelude) == "e: f ")
+        );
+        assert_eq!(contents.len(), 3);
+
+        // a rule that isn't closed ends at the end of the input
+        let rules = parse_a_stylesheets_contents("a { b: c; d { e: f");
+        let Rule::QualifiedRule(rule) = &rules[0] else {
+            panic!("expected a qualified rule");
+        };
+        assert_eq!(rule.declarations[0].name, "b");
+        assert!(
+            matches!(&rule.child_rules[0], Rule::QualifiedRule(child) if child.declarations[0].name == "e")
+        );
+
+        // a qualified rule that looks like a custom property is dropped
+        assert_eq!(parse_a_stylesheets_contents("--a: { b: c } d {}").len(), 1);
+        // a prelude without a block is dropped
+        assert_eq!(parse_a_stylesheets_contents("a b c").len(), 0);
+    }
+
+    #[test]
+    fn nested_rules() {
+        let rules =
+            parse_a_stylesheets_contents("a { b: c; &:hover { d: e } f: g; @media x { h: i } }");
+        let Rule::QualifiedRule(rule) = &rules[0] else {
+            panic!("expected a qualified rule");
+        };
+        assert_eq!(rule.declarations.len(), 1);
+        assert!(
+            matches!(&rule.child_rules[0], Rule::QualifiedRule(child) if serialize_component_values(&child.prelude) == "&:hover ")
+        );
+        assert!(
+            matches!(&rule.child_rules[1], Rule::NestedDeclarations(declarations) if declarations[0].name == "f")
+        );
+        let Rule::AtRule(media) = &rule.child_rules[2] else {
+            panic!("expected an at-rule");
+        };
+        assert!(
+            matches!(&media.block.as_deref(), Some([BlockContent::Declarations(declarations)]) if declarations[0].name == "h")
+        );
+    }
+
+    #[test]
+    fn entry_points() {
+        assert!(parse_a_rule(" a { } ").is_ok());
+        assert_eq!(
+            parse_a_rule("a {} b {}"),
+            Err(SyntaxError {
+                error: "expected-eof"
+            })
+        );
+        assert_eq!(
+            parse_a_rule("  "),
+            Err(SyntaxError {
+                error: "empty-input"
+            })
+        );
+        assert_eq!(
+            parse_a_declaration(" width : 1px ").map(|declaration| declaration.name),
+            Ok("width".to_string())
+        );
+        assert!(parse_a_declaration("1px").is_err());
+        assert_eq!(parse_a_component_value(" foo "), Ok(ident("foo")));
+        assert!(parse_a_component_value("foo bar").is_err());
+        let groups: Vec<String> = parse_a_comma_separated_list_of_component_values("a, b(c, d) ,e")
+            .iter()
+            .map(|group| serialize_component_values(group))
+            .collect();
+        assert_eq!(groups, ["a", " b(c, d) ", "e"]);
+    }
+
+    #[test]
+    fn serialization() {
+        let serialize =
+            |input| serialize_component_values(&parse_a_list_of_component_values(input));
+        assert_eq!(
+            serialize("rgb(1, 2.50 ,3%) url( a.png ) [x=\"y\"] {-1e3px}"),
+            "rgb(1, 2.5 ,3%) url(\"a.png\") [x=\"y\"] {-1000px}"
+        );
+        assert_eq!(serialize("#a1 #1a \\61 b 'c\\\nd'"), "#a1 #1a ab \"cd\"");
+        assert_eq!(
+            serialize("1\\65 3 1em U+4?? !\\\n"),
+            "1\\65 3 1em U+400-4FF !\\\n "
+        );
+        // the comments keep the tokens apart
+        assert_eq!(
+            serialize("a/**/b 1/**/2 -/**/x #/**/y"),
+            "a/**/b 1/**/2 -/**/x #/**/y"
+        );
+        assert_eq!(
+            serialize("a/**/(b) 1/**/% //**/*"),
+            "a/**/(b) 1/**/% //**/*"
+        );
+    }
+}
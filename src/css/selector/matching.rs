@@ -0,0 +1,455 @@
+use crate::atom;
+use crate::css::selector::{
+    AnB, AttributeMatcher, AttributeSelector, CaseSensitivity, Combinator, ComplexSelector,
+    CompoundSelector, NamespacePrefix, PseudoClass, SelectorError, SelectorList, SimpleSelector,
+    parse_a_selector,
+};
+use crate::html::tree_builder::tree::{Element, Namespace, Node, Tree};
+
+impl Tree<Node> {
+    // https://dom.spec.whatwg.org/#dom-parentnode-queryselector
+    // The querySelector(selectors) method steps are to return the first result of running scope-match a selectors string selectors against this, if the result is not an empty list; otherwise null.
+    pub fn query_selector(
+        &self,
+        node: usize,
+        selectors: &str,
+    ) -> Result<Option<usize>, SelectorError> {
+        let selector = parse_a_selector(selectors)?;
+        Ok(self
+            .descendants(node)
+            .map(|(index, _)| index)
+            .find(|index| self.match_a_selector_against_an_element(&selector, *index)))
+    }
+
+    // https://dom.spec.whatwg.org/#dom-parentnode-queryselectorall
+    // The querySelectorAll(selectors) method steps are to return the static result of running scope-match a selectors string selectors against this.
+    pub fn query_selector_all(
+        &self,
+        node: usize,
+        selectors: &str,
+    ) -> Result<Vec<usize>, SelectorError> {
+        // https://dom.spec.whatwg.org/#scope-match-a-selectors-string
+        // 1. Let s be the result of parse a selector selectors.
+        // 2. If s is failure, then throw a "SyntaxError" DOMException.
+        let selector = parse_a_selector(selectors)?;
+        // 3. Return the result of match a selector against a tree with s and node's root, using scoping root node.
+        Ok(self
+            .descendants(node)
+            .map(|(index, _)| index)
+            .filter(|index| self.match_a_selector_against_an_element(&selector, *index))
+            .collect())
+    }
+
+    // https://dom.spec.whatwg.org/#dom-element-matches
+    pub fn matches(&self, element: usize, selectors: &str) -> Result<bool, SelectorError> {
+        // 1. Let s be the result of parse a selector from selectors.
+        // 2. If s is failure, then throw a "SyntaxError" DOMException.
+        let selector = parse_a_selector(selectors)?;
+        // 3. If the result of match a selector against an element, using s, this, and scoping root this, returns success, then return true; otherwise, return false.
+        Ok(self.match_a_selector_against_an_element(&selector, element))
+    }
+
+    // https://drafts.csswg.org/selectors-4/#match-a-selector-against-an-element
+    // A selector is evaluated right to left: the last compound selector is matched against the element, then the combinators walk to the ancestors or previous siblings.
+    pub fn match_a_selector_against_an_element(
+        &self,
+        selector_list: &SelectorList,
+        element: usize,
+    ) -> bool {
+        self.get_element(element).is_some()
+            && selector_list.0.iter().any(|complex_selector| {
+                self.match_a_complex_selector_against_an_element(complex_selector, element)
+            })
+    }
+
+    // one selector of a list, the cascade needs the specificity of the selector that matched
+    pub fn match_a_complex_selector_against_an_element(
+        &self,
+        complex_selector: &ComplexSelector,
+        element: usize,
+    ) -> bool {
+        // pseudo-elements never match an element
+        self.get_element(element).is_some()
+            && complex_selector.pseudo_element.is_none()
+            && self.match_complex_selector(
+                complex_selector,
+                complex_selector.compound_selectors.len() - 1,
+                element,
+            )
+    }
+
+    // matches the compound selectors up to and including the one at index against the element
+    fn match_complex_selector(
+        &self,
+        complex_selector: &ComplexSelector,
+        index: usize,
+        element: usize,
+    ) -> bool {
+        if !self.match_compound_selector(&complex_selector.compound_selectors[index], element) {
+            return false;
+        }
+        if index == 0 {
+            return true;
+        }
+        let mut candidates: Box<dyn Iterator<Item = usize>> = match complex_selector.combinators
+            [index - 1]
+        {
+            Combinator::Descendant => Box::new(self.parent_elements(element)),
+            Combinator::Child => Box::new(self.parent_elements(element).take(1)),
+            Combinator::NextSibling => Box::new(self.previous_element_siblings(element).take(1)),
+            Combinator::SubsequentSibling => Box::new(self.previous_element_siblings(element)),
+        };
+        candidates
+            .any(|candidate| self.match_complex_selector(complex_selector, index - 1, candidate))
+    }
+
+    fn match_compound_selector(
+        &self,
+        compound_selector: &CompoundSelector,
+        element: usize,
+    ) -> bool {
+        compound_selector
+            .0
+            .iter()
+            .all(|simple_selector| self.match_simple_selector(simple_selector, element))
+    }
+
+    fn match_simple_selector(&self, simple_selector: &SimpleSelector, index: usize) -> bool {
+        let element = self.get_element(index).expect("only elements are matched");
+        match simple_selector {
+            // https://drafts.csswg.org/selectors-4/#type-selectors
+            SimpleSelector::Type(name) => {
+                match_namespace_prefix(name.prefix)
+                    && if element.namespace == Namespace::Html {
+                        // the names of HTML elements are ASCII case-insensitive in HTML documents
+                        element.local_name == name.local_name.to_ascii_lowercase()
+                    } else {
+                        element.local_name == name.local_name
+                    }
+            }
+            // https://drafts.csswg.org/selectors-4/#the-universal-selector
+            SimpleSelector::Universal(prefix) => match_namespace_prefix(*prefix),
+            SimpleSelector::Attribute(attribute_selector) => {
+                match_attribute_selector(attribute_selector, element)
+            }
+            // https://drafts.csswg.org/selectors-4/#class-html
+            // todo compare ASCII case-insensitively in quirks mode
+            SimpleSelector::Class(class) => element.class_list().any(|item| item == class),
+            // https://drafts.csswg.org/selectors-4/#id-selectors
+            SimpleSelector::Id(id) => element.id() == Some(id),
+            SimpleSelector::PseudoClass(pseudo_class) => {
+                self.match_pseudo_class(pseudo_class, index, element)
+            }
+            // https://drafts.csswg.org/css-nesting-1/#nest-selector
+            // The nesting selector of a nested style rule stands for the elements matched by the parent style rule, outside of a style rule it represents the scoping root, which is the root element here.
+            SimpleSelector::Nesting => self.match_pseudo_class(&PseudoClass::Root, index, element),
+        }
+    }
+
+    fn match_pseudo_class(
+        &self,
+        pseudo_class: &PseudoClass,
+        index: usize,
+        element: &Element,
+    ) -> bool {
+        let is_of_type = |sibling: &usize| {
+            self.get_element(*sibling).is_some_and(|sibling| {
+                sibling.namespace == element.namespace && sibling.local_name == element.local_name
+            })
+        };
+        match pseudo_class {
+            // https://drafts.csswg.org/selectors-4/#negation
+            PseudoClass::Not(selector_list) => {
+                !self.match_a_selector_against_an_element(selector_list, index)
+            }
+            // https://drafts.csswg.org/selectors-4/#matches
+            // https://drafts.csswg.org/selectors-4/#zero-matches
+            // :where() only differs from :is() in the specificity
+            PseudoClass::Is(selector_list) | PseudoClass::Where(selector_list) => {
+                self.match_a_selector_against_an_element(selector_list, index)
+            }
+            // https://drafts.csswg.org/selectors-4/#the-root-pseudo
+            // The :root pseudo-class represents an element that is the root of the document.
+            PseudoClass::Root => self
+                .parent(index)
+                .is_some_and(|parent| matches!(self.get_node(parent).data, Node::Document(_))),
+            // https://drafts.csswg.org/selectors-4/#the-empty-pseudo
+            // The :empty pseudo-class represents an element that has no children except, optionally, document white space characters.
+            // browsers only allow comments
+            PseudoClass::Empty => self.get_node(index).children.iter().all(|child| {
+                match &self.get_node(*child).data {
+                    Node::Text(text) => text.data.is_empty(),
+                    Node::Comment(_) => true,
+                    _ => false,
+                }
+            }),
+            // https://drafts.csswg.org/selectors-4/#the-nth-child-pseudo
+            PseudoClass::NthChild(an_b) => {
+                matches_index(an_b, self.previous_element_siblings(index).count())
+            }
+            PseudoClass::NthLastChild(an_b) => {
+                matches_index(an_b, self.next_element_siblings(index).count())
+            }
+            PseudoClass::FirstChild => self.previous_element_siblings(index).next().is_none(),
+            PseudoClass::LastChild => self.next_element_siblings(index).next().is_none(),
+            PseudoClass::OnlyChild => {
+                self.previous_element_siblings(index).next().is_none()
+                    && self.next_element_siblings(index).next().is_none()
+            }
+            // https://drafts.csswg.org/selectors-4/#the-nth-of-type-pseudo
+            PseudoClass::NthOfType(an_b) => matches_index(
+                an_b,
+                self.previous_element_siblings(index)
+                    .filter(is_of_type)
+                    .count(),
+            ),
+            PseudoClass::NthLastOfType(an_b) => matches_index(
+                an_b,
+                self.next_element_siblings(index).filter(is_of_type).count(),
+            ),
+            PseudoClass::FirstOfType => !self
+                .previous_element_siblings(index)
+                .any(|s| is_of_type(&s)),
+            PseudoClass::LastOfType => !self.next_element_siblings(index).any(|s| is_of_type(&s)),
+            PseudoClass::OnlyOfType => {
+                !self
+                    .previous_element_siblings(index)
+                    .any(|s| is_of_type(&s))
+                    && !self.next_element_siblings(index).any(|s| is_of_type(&s))
+            }
+            // https://drafts.csswg.org/selectors-4/#the-lang-pseudo
+            PseudoClass::Lang(ranges) => {
+                // the language of the element is the lang attribute of the nearest ancestor that has one
+                let language = std::iter::once(index)
+                    .chain(self.ancestors(index))
+                    .filter_map(|ancestor| self.get_element(ancestor))
+                    .find_map(|ancestor| {
+                        ancestor.attributes.iter().find_map(|attribute| {
+                            (attribute.name == atom!("lang")
+                                && matches!(attribute.namespace, None | Some(Namespace::Xml)))
+                            .then(|| attribute.value.to_ascii_lowercase())
+                        })
+                    });
+                // implicit wildcard matching, "en" matches "en" and "en-US"
+                language.is_some_and(|language| {
+                    ranges.iter().any(|range| {
+                        let range = range.to_ascii_lowercase();
+                        language == range
+                            || language.starts_with(&range)
+                                && language[range.len()..].starts_with('-')
+                    })
+                })
+            }
+            PseudoClass::Other(name) => match name.as_str() {
+                // https://html.spec.whatwg.org/multipage/semantics-other.html#selector-any-link
+                // a and area elements that have an href attribute, there is no history so all links are unvisited
+                "any-link" | "link" => {
+                    element.is_html(&[atom!("a"), atom!("area")])
+                        && element.get_attribute("href").is_some()
+                }
+                // https://html.spec.whatwg.org/multipage/semantics-other.html#selector-checked
+                "checked" => {
+                    (element.is_html(&[atom!("input")])
+                        && element.get_attribute("checked").is_some()
+                        && element.get_attribute("type").is_some_and(|t| {
+                            t.eq_ignore_ascii_case("checkbox") || t.eq_ignore_ascii_case("radio")
+                        }))
+                        || (element.is_html(&[atom!("option")])
+                            && element.get_attribute("selected").is_some())
+                }
+                // https://html.spec.whatwg.org/multipage/semantics-other.html#selector-disabled
+                // todo disabled by a fieldset or an optgroup ancestor
+                "disabled" | "enabled" => {
+                    element.is_html(&[
+                        atom!("button"),
+                        atom!("input"),
+                        atom!("select"),
+                        atom!("textarea"),
+                        atom!("optgroup"),
+                        atom!("option"),
+                        atom!("fieldset"),
+                    ]) && (element.get_attribute("disabled").is_some() == (name == "disabled"))
+                }
+                // https://html.spec.whatwg.org/multipage/semantics-other.html#selector-defined
+                // there are no custom elements
+                "defined" => true,
+                // the user action and the form validation pseudo-classes need state the tree doesn't have
+                _ => false,
+            },
+        }
+    }
+
+    // the ancestors that are elements, the document is not an element
+    fn parent_elements(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        self.ancestors(index)
+            .filter(|ancestor| self.get_element(*ancestor).is_some())
+    }
+
+    fn previous_element_siblings(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        std::iter::successors(self.prev_sibling(index), |sibling| {
+            self.prev_sibling(*sibling)
+        })
+        .filter(|sibling| self.get_element(*sibling).is_some())
+    }
+
+    fn next_element_siblings(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        std::iter::successors(self.next_sibling(index), |sibling| {
+            self.next_sibling(*sibling)
+        })
+        .filter(|sibling| self.get_element(*sibling).is_some())
+    }
+}
+
+// the 1-based index is the number of siblings before it plus one
+fn matches_index(an_b: &AnB, siblings_before: usize) -> bool {
+    an_b.matches(siblings_before as i32 + 1)
+}
+
+// https://drafts.csswg.org/selectors-4/#type-nmsp
+fn match_namespace_prefix(prefix: NamespacePrefix) -> bool {
+    match prefix {
+        // there is no default namespace declared, so elements in any namespace match
+        NamespacePrefix::Default | NamespacePrefix::Any => true,
+        // all elements of the tree are in a namespace
+        NamespacePrefix::Null => false,
+    }
+}
+
+// https://drafts.csswg.org/selectors-4/#attribute-selectors
+fn match_attribute_selector(attribute_selector: &AttributeSelector, element: &Element) -> bool {
+    let name = &attribute_selector.name;
+    // the attribute names of HTML elements are ASCII case-insensitive in HTML documents
+    let local_name = if element.namespace == Namespace::Html {
+        name.local_name.to_ascii_lowercase()
+    } else {
+        name.local_name.clone()
+    };
+    element
+        .attributes
+        .iter()
+        .filter(|attribute| attribute.name == local_name)
+        .filter(|attribute| match name.prefix {
+            // https://drafts.csswg.org/selectors-4/#attrnmsp
+            // an attribute selector without a prefix only matches attributes without a namespace
+            NamespacePrefix::Default | NamespacePrefix::Null => attribute.namespace.is_none(),
+            NamespacePrefix::Any => true,
+        })
+        .any(|attribute| {
+            let Some((matcher, value)) = &attribute_selector.matcher else {
+                // [att] Represents an element with the att attribute, whatever the value of the attribute.
+                return true;
+            };
+            // https://drafts.csswg.org/selectors-4/#attribute-case
+            // todo the html attributes that are case-insensitive by default, like type
+            let (attribute_value, value) = match attribute_selector.case_sensitivity {
+                CaseSensitivity::Insensitive => (
+                    attribute.value.to_ascii_lowercase(),
+                    value.to_ascii_lowercase(),
+                ),
+                CaseSensitivity::Default | CaseSensitivity::Sensitive => {
+                    (attribute.value.to_string(), value.clone())
+                }
+            };
+            match matcher {
+                // [att=val] Represents an element with the att attribute whose value is exactly "val".
+                AttributeMatcher::Exactly => attribute_value == value,
+                // [att~=val] Represents an element with the att attribute whose value is a whitespace-separated list of words, one of which is exactly "val". If "val" contains whitespace, it will never represent anything (since the words are separated by spaces). Also if "val" is the empty string, it will never represent anything.
+                AttributeMatcher::ContainsWhitespaceSeparated => {
+                    !value.is_empty()
+                        && !value.contains(super::parser::is_whitespace)
+                        && attribute_value
+                            .split(super::parser::is_whitespace)
+                            .any(|word| word == value)
+                }
+                // [att|=val] Represents an element with the att attribute, its value either being exactly "val" or beginning with "val" immediately followed by "-" (U+002D).
+                AttributeMatcher::StartsWithDashSeparated => {
+                    attribute_value == value
+                        || attribute_value.starts_with(&value)
+                            && attribute_value[value.len()..].starts_with('-')
+                }
+                // [att^=val] Represents an element with the att attribute whose value begins with the prefix "val". If "val" is the empty string then the selector does not represent anything.
+                AttributeMatcher::StartsWithString => {
+                    !value.is_empty() && attribute_value.starts_with(&value)
+                }
+                // [att$=val] Represents an element with the att attribute whose value ends with the suffix "val". If "val" is the empty string then the selector does not represent anything.
+                AttributeMatcher::EndsWithString => {
+                    !value.is_empty() && attribute_value.ends_with(&value)
+                }
+                // [att*=val] Represents an element with the att attribute whose value contains at least one instance of the substring "val". If "val" is the empty string then the selector does not represent anything.
+                AttributeMatcher::ContainsSubString => {
+                    !value.is_empty() && attribute_value.contains(&value)
+                }
+            }
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::html::tokenizer::Tokenizer;
+    use crate::html::tree_builder::{ParserOptions, TreeBuilder};
+
+    fn parse(contents: &str) -> TreeBuilder {
+        let mut tree_builder = TreeBuilder::new(ParserOptions::default());
+        tree_builder.build(Tokenizer::new(contents));
+        tree_builder
+    }
+
+    // the ids of the matched elements, or the local names for elements without an id
+    fn query(tree_builder: &TreeBuilder, selectors: &str) -> Vec<String> {
+        let tree = tree_builder.get_tree();
+        tree.query_selector_all(tree.get_root().unwrap(), selectors)
+            .unwrap()
+            .into_iter()
+            .map(|index| {
+                let element = tree.get_element(index).unwrap();
+                element.id().unwrap_or(&element.local_name).to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn query_selector_all() {
+        let tree_builder = parse(
+            r#"<ul id=list class="a b"><li id=one lang=en-US><li id=two class=b><li id=three data-x="Foo bar"></ul><p id=p><!-- c --></p><svg><foreignObject id=f></foreignObject></svg>"#,
+        );
+        assert_eq!(query(&tree_builder, "li"), ["one", "two", "three"]);
+        assert_eq!(query(&tree_builder, "LI"), ["one", "two", "three"]);
+        assert_eq!(query(&tree_builder, ".b"), ["list", "two"]);
+        assert_eq!(query(&tree_builder, "ul > #two"), ["two"]);
+        assert_eq!(query(&tree_builder, "#one ~ li"), ["two", "three"]);
+        assert_eq!(query(&tree_builder, "#one + li"), ["two"]);
+        assert_eq!(query(&tree_builder, "body li:last-child"), ["three"]);
+        assert_eq!(query(&tree_builder, "li:nth-child(2n+1)"), ["one", "three"]);
+        assert_eq!(query(&tree_builder, "li:nth-last-child(1)"), ["three"]);
+        assert_eq!(query(&tree_builder, "li:not(.b, #one)"), ["three"]);
+        assert_eq!(query(&tree_builder, ":is(ul, p):where(#p)"), ["p"]);
+        assert_eq!(query(&tree_builder, "[data-x~=bar]"), ["three"]);
+        assert_eq!(query(&tree_builder, "[data-x^=foo]"), Vec::<String>::new());
+        assert_eq!(query(&tree_builder, "[data-x^=foo i]"), ["three"]);
+        assert_eq!(query(&tree_builder, "[lang|=en]"), ["one"]);
+        assert_eq!(query(&tree_builder, ":lang(en)"), ["one"]);
+        assert_eq!(query(&tree_builder, "p:empty"), ["p"]);
+        assert_eq!(query(&tree_builder, ":root"), ["html"]);
+        assert_eq!(query(&tree_builder, "svg > foreignObject"), ["f"]);
+        assert_eq!(
+            query(&tree_builder, "svg > foreignobject"),
+            Vec::<String>::new()
+        );
+        assert_eq!(query(&tree_builder, "p:only-of-type"), ["p"]);
+        assert_eq!(query(&tree_builder, "li::before"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn query_selector_and_matches() {
+        let tree_builder = parse("<div id=a><span id=b></span><span id=c></span></div>");
+        let tree = tree_builder.get_tree();
+        let root = tree.get_root().unwrap();
+        let a = tree.get_element_by_id("a").unwrap();
+        let c = tree.get_element_by_id("c").unwrap();
+        assert_eq!(tree.query_selector(root, "span:last-of-type"), Ok(Some(c)));
+        assert_eq!(tree.query_selector(a, "div"), Ok(None));
+        assert_eq!(tree.matches(c, "div > span + span"), Ok(true));
+        assert_eq!(tree.matches(c, "span:first-child"), Ok(false));
+        assert!(tree.matches(c, "div >").is_err());
+    }
+}
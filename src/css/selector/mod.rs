@@ -0,0 +1,584 @@
+mod matching;
+mod parser;
+
+pub use parser::{SelectorError, parse_a_relative_selector_list, parse_a_selector};
+
+use crate::css::serializing::{
+    serialize_a_comma_separated_list, serialize_a_string, serialize_an_identifier,
+};
+
+// https://drafts.csswg.org/selectors-4/#selector-list
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelectorList(pub Vec<ComplexSelector>);
+
+// https://drafts.csswg.org/selectors-4/#complex
+// A complex selector is a sequence of one or more compound selectors and/or pseudo-compound selectors, with compound selectors separated by combinators.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComplexSelector {
+    // the compound selectors from left to right
+    pub compound_selectors: Vec<CompoundSelector>,
+    // the combinator between compound_selectors[i] and compound_selectors[i + 1]
+    pub combinators: Vec<Combinator>,
+    // https://drafts.csswg.org/selectors-4/#pseudo-element
+    // a pseudo-element can only follow the last compound selector
+    pub pseudo_element: Option<String>,
+}
+
+// https://drafts.csswg.org/selectors-4/#compound
+// If it contains a type selector or universal selector, that selector must come first in the sequence. Only one type selector or universal selector is allowed in the sequence.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompoundSelector(pub Vec<SimpleSelector>);
+
+// https://drafts.csswg.org/selectors-4/#selector-combinator
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Combinator {
+    // whitespace
+    Descendant,
+    // >
+    Child,
+    // +
+    NextSibling,
+    // ~
+    SubsequentSibling,
+}
+
+// https://drafts.csswg.org/css-namespaces-3/#css-qnames
+// there is no @namespace rule for querySelector, so only the prefixes without a name are valid
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NamespacePrefix {
+    // no prefix: any namespace for elements, no namespace for attributes
+    Default,
+    // *|
+    Any,
+    // |
+    Null,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QualifiedName {
+    pub prefix: NamespacePrefix,
+    pub local_name: String,
+}
+
+// https://drafts.csswg.org/selectors-4/#simple
+#[derive(Debug, Clone, PartialEq)]
+pub enum SimpleSelector {
+    Type(QualifiedName),
+    Universal(NamespacePrefix),
+    Attribute(AttributeSelector),
+    Class(String),
+    Id(String),
+    PseudoClass(PseudoClass),
+    // https://drafts.csswg.org/css-nesting-1/#nest-selector
+    // &
+    Nesting,
+}
+
+// https://drafts.csswg.org/selectors-4/#attribute-selectors
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttributeSelector {
+    pub name: QualifiedName,
+    // the matcher and the value, the selector only checks the presence of the attribute without them
+    pub matcher: Option<(AttributeMatcher, String)>,
+    pub case_sensitivity: CaseSensitivity,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AttributeMatcher {
+    // =
+    Exactly,
+    // ~=
+    ContainsWhitespaceSeparated,
+    // |=
+    StartsWithDashSeparated,
+    // ^=
+    StartsWithString,
+    // $=
+    EndsWithString,
+    // *=
+    ContainsSubString,
+}
+
+// https://drafts.csswg.org/selectors-4/#attribute-case
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CaseSensitivity {
+    Default,
+    // i
+    Insensitive,
+    // s
+    Sensitive,
+}
+
+// https://drafts.csswg.org/selectors-4/#pseudo-classes
+#[derive(Debug, Clone, PartialEq)]
+pub enum PseudoClass {
+    // https://drafts.csswg.org/selectors-4/#logical-combination
+    Not(SelectorList),
+    Is(SelectorList),
+    Where(SelectorList),
+    // https://drafts.csswg.org/selectors-4/#structural-pseudos
+    Root,
+    Empty,
+    NthChild(AnB),
+    NthLastChild(AnB),
+    FirstChild,
+    LastChild,
+    OnlyChild,
+    NthOfType(AnB),
+    NthLastOfType(AnB),
+    FirstOfType,
+    LastOfType,
+    OnlyOfType,
+    // https://drafts.csswg.org/selectors-4/#the-lang-pseudo
+    Lang(Vec<String>),
+    // the location, user action and input pseudo-classes without arguments, e.g. :hover
+    Other(String),
+}
+
+// https://drafts.csswg.org/selectors-4/#specificity
+// Specificities are compared by comparing the three components in order: the specificity with a larger A value is more specific; if the two A values are tied, then the specificity with a larger B value is more specific; if the two B values are also tied, then the specificity with a larger C value is more specific; if all the values are tied, the two specificities are equal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct Specificity {
+    pub a: u32,
+    pub b: u32,
+    pub c: u32,
+}
+
+// https://drafts.csswg.org/css-syntax-3/#anb-microsyntax
+// An+B matches every element at index A * n + B for n = 0, 1, 2, ...
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnB {
+    pub a: i32,
+    pub b: i32,
+}
+
+impl SelectorList {
+    // https://drafts.csswg.org/cssom/#serialize-a-group-of-selectors
+    // To serialize a group of selectors serialize each selector in the group of selectors and then serialize a comma-separated list of these serializations.
+    pub fn serialize(&self) -> String {
+        serialize_a_comma_separated_list(self.0.iter().map(ComplexSelector::serialize))
+    }
+}
+
+impl ComplexSelector {
+    // https://drafts.csswg.org/cssom/#serialize-a-selector
+    pub fn serialize(&self) -> String {
+        // To serialize a selector let s be the empty string, run the steps below for each part of the chain of the selector, and finally return s:
+        let mut s = String::new();
+        for (index, compound_selector) in self.compound_selectors.iter().enumerate() {
+            // 1. If there is only one simple selector in the compound selectors which is a universal selector, append the result of serializing the universal selector to s.
+            // 2. Otherwise, for each simple selector in the compound selectors that is not a universal selector of which the namespace prefix maps to a namespace that is not the default namespace serialize the simple selector and append the result to s.
+            s.push_str(&compound_selector.serialize());
+            // 3. If this is not the last part of the chain of the selector append a single SPACE (U+0020), followed by the combinator ">", "+", "~", ">>", "||", as appropriate, followed by another single SPACE (U+0020) if the combinator was not whitespace, to s.
+            if let Some(combinator) = self.combinators.get(index) {
+                s.push(' ');
+                match combinator {
+                    Combinator::Descendant => {}
+                    Combinator::Child => s.push_str("> "),
+                    Combinator::NextSibling => s.push_str("+ "),
+                    Combinator::SubsequentSibling => s.push_str("~ "),
+                }
+            }
+        }
+        // 4. If this is the last part of the chain of the selector and there is a pseudo-element, append "::" followed by the name of the pseudo-element, to s.
+        if let Some(pseudo_element) = &self.pseudo_element {
+            s.push_str("::");
+            s.push_str(&serialize_an_identifier(pseudo_element));
+        }
+        s
+    }
+}
+
+impl ComplexSelector {
+    // whether a simple selector, or a selector in the argument of a pseudo-class, is the nesting selector
+    pub fn contains_the_nesting_selector(&self) -> bool {
+        self.compound_selectors
+            .iter()
+            .flat_map(|compound_selector| &compound_selector.0)
+            .any(|simple_selector| match simple_selector {
+                SimpleSelector::Nesting => true,
+                SimpleSelector::PseudoClass(
+                    PseudoClass::Not(selector_list)
+                    | PseudoClass::Is(selector_list)
+                    | PseudoClass::Where(selector_list),
+                ) => selector_list
+                    .0
+                    .iter()
+                    .any(ComplexSelector::contains_the_nesting_selector),
+                _ => false,
+            })
+    }
+
+    // https://drafts.csswg.org/css-nesting-1/#nest-selector
+    // The nesting selector of a nested style rule represents the elements matched by the parent style rule, it is replaced by :is() with the selectors of the parent rule.
+    pub fn replace_the_nesting_selector(&self, parent: &SelectorList) -> ComplexSelector {
+        let replace = |selector_list: &SelectorList| {
+            SelectorList(
+                selector_list
+                    .0
+                    .iter()
+                    .map(|complex_selector| complex_selector.replace_the_nesting_selector(parent))
+                    .collect(),
+            )
+        };
+        let compound_selectors = self
+            .compound_selectors
+            .iter()
+            .map(|compound_selector| {
+                CompoundSelector(
+                    compound_selector
+                        .0
+                        .iter()
+                        .map(|simple_selector| match simple_selector {
+                            SimpleSelector::Nesting => {
+                                SimpleSelector::PseudoClass(PseudoClass::Is(parent.clone()))
+                            }
+                            SimpleSelector::PseudoClass(PseudoClass::Not(selector_list)) => {
+                                SimpleSelector::PseudoClass(PseudoClass::Not(replace(
+                                    selector_list,
+                                )))
+                            }
+                            SimpleSelector::PseudoClass(PseudoClass::Is(selector_list)) => {
+                                SimpleSelector::PseudoClass(PseudoClass::Is(replace(selector_list)))
+                            }
+                            SimpleSelector::PseudoClass(PseudoClass::Where(selector_list)) => {
+                                SimpleSelector::PseudoClass(PseudoClass::Where(replace(
+                                    selector_list,
+                                )))
+                            }
+                            simple_selector => simple_selector.clone(),
+                        })
+                        .collect(),
+                )
+            })
+            .collect();
+        ComplexSelector {
+            compound_selectors,
+            combinators: self.combinators.clone(),
+            pseudo_element: self.pseudo_element.clone(),
+        }
+    }
+
+    // https://drafts.csswg.org/selectors-4/#specificity-rules
+    // A selector's specificity is calculated for a given element as follows:
+    pub fn specificity(&self) -> Specificity {
+        let mut specificity = self
+            .compound_selectors
+            .iter()
+            .flat_map(|compound_selector| &compound_selector.0)
+            .map(SimpleSelector::specificity)
+            .fold(Specificity::default(), |sum, specificity| Specificity {
+                a: sum.a + specificity.a,
+                b: sum.b + specificity.b,
+                c: sum.c + specificity.c,
+            });
+        // count the number of type selectors and pseudo-elements in the selector (= C)
+        if self.pseudo_element.is_some() {
+            specificity.c += 1;
+        }
+        specificity
+    }
+}
+
+impl SelectorList {
+    // https://drafts.csswg.org/selectors-4/#specificity-rules
+    // the specificity of the most specific complex selector in the list, e.g. for :is()
+    pub fn specificity(&self) -> Specificity {
+        self.0
+            .iter()
+            .map(ComplexSelector::specificity)
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+impl SimpleSelector {
+    fn specificity(&self) -> Specificity {
+        let (a, b, c) = match self {
+            // count the number of ID selectors in the selector (= A)
+            SimpleSelector::Id(_) => (1, 0, 0),
+            // count the number of class selectors, attributes selectors, and pseudo-classes in the selector (= B)
+            SimpleSelector::Class(_) | SimpleSelector::Attribute(_) => (0, 1, 0),
+            // count the number of type selectors and pseudo-elements in the selector (= C)
+            SimpleSelector::Type(_) => (0, 0, 1),
+            // ignore the universal selector
+            SimpleSelector::Universal(_) => (0, 0, 0),
+            // The specificity of an :is() or :not() pseudo-class is replaced by the specificity of the most specific complex selector in its selector list argument.
+            SimpleSelector::PseudoClass(PseudoClass::Is(selector_list))
+            | SimpleSelector::PseudoClass(PseudoClass::Not(selector_list)) => {
+                return selector_list.specificity();
+            }
+            // The specificity of a :where() pseudo-class is replaced by zero.
+            SimpleSelector::PseudoClass(PseudoClass::Where(_)) => (0, 0, 0),
+            SimpleSelector::PseudoClass(_) => (0, 1, 0),
+            // https://drafts.csswg.org/css-nesting-1/#nest-selector
+            // The specificity of the nesting selector is equal to the largest specificity among the complex selectors in the parent style rule's selector list, outside of a style rule it is :scope.
+            SimpleSelector::Nesting => (0, 1, 0),
+        };
+        Specificity { a, b, c }
+    }
+}
+
+impl CompoundSelector {
+    fn serialize(&self) -> String {
+        match &self.0[..] {
+            [SimpleSelector::Universal(_)] => self.0[0].serialize(),
+            simple_selectors => simple_selectors
+                .iter()
+                .filter(|simple_selector| {
+                    !matches!(
+                        simple_selector,
+                        SimpleSelector::Universal(NamespacePrefix::Default)
+                    )
+                })
+                .map(SimpleSelector::serialize)
+                .collect(),
+        }
+    }
+}
+
+impl NamespacePrefix {
+    fn serialize(&self) -> &'static str {
+        match self {
+            NamespacePrefix::Default => "",
+            NamespacePrefix::Any => "*|",
+            NamespacePrefix::Null => "|",
+        }
+    }
+}
+
+impl SimpleSelector {
+    // https://drafts.csswg.org/cssom/#serialize-a-simple-selector
+    pub fn serialize(&self) -> String {
+        let mut s = String::new();
+        match self {
+            // type selector, universal selector
+            SimpleSelector::Type(QualifiedName { prefix, .. })
+            | SimpleSelector::Universal(prefix) => {
+                // 1. If the namespace prefix maps to a namespace that is not the default namespace and is not the null namespace (not in a namespace) append the serialization of the namespace prefix as an identifier, followed by a "|" (U+007C) to s.
+                // 2. If the namespace prefix maps to a namespace that is the null namespace (not in a namespace) append "|" (U+007C) to s.
+                s.push_str(prefix.serialize());
+                match self {
+                    // 3. If this is a type selector append the serialization of the element name as an identifier to s.
+                    SimpleSelector::Type(name) => {
+                        s.push_str(&serialize_an_identifier(&name.local_name))
+                    }
+                    // 4. If this is a universal selector append "*" (U+002A) to s.
+                    _ => s.push('*'),
+                }
+            }
+            // attribute selector
+            SimpleSelector::Attribute(attribute_selector) => {
+                // 1. Append "[" (U+005B) to s.
+                s.push('[');
+                // 2. If the namespace prefix maps to a namespace that is not the null namespace (not in a namespace) append the serialization of the namespace prefix as an identifier, followed by a "|" (U+007C) to s.
+                s.push_str(attribute_selector.name.prefix.serialize());
+                // 3. Append the serialization of the attribute name as an identifier to s.
+                s.push_str(&serialize_an_identifier(
+                    &attribute_selector.name.local_name,
+                ));
+                // 4. If there is an attribute value specified, append "=", "~=", "|=", "^=", "$=", or "*=" as appropriate (depending on the type of attribute selector), followed by the serialization of the attribute value as a string, to s.
+                if let Some((matcher, value)) = &attribute_selector.matcher {
+                    s.push_str(match matcher {
+                        AttributeMatcher::Exactly => "=",
+                        AttributeMatcher::ContainsWhitespaceSeparated => "~=",
+                        AttributeMatcher::StartsWithDashSeparated => "|=",
+                        AttributeMatcher::StartsWithString => "^=",
+                        AttributeMatcher::EndsWithString => "$=",
+                        AttributeMatcher::ContainsSubString => "*=",
+                    });
+                    s.push_str(&serialize_a_string(value));
+                }
+                // 5. If the attribute selector has the case-sensitivity flag present, append " i" (U+0020 U+0069) to s.
+                // the spec misses the s flag, it is serialized the same way
+                match attribute_selector.case_sensitivity {
+                    CaseSensitivity::Default => {}
+                    CaseSensitivity::Insensitive => s.push_str(" i"),
+                    CaseSensitivity::Sensitive => s.push_str(" s"),
+                }
+                // 6. Append "]" (U+005D) to s.
+                s.push(']');
+            }
+            // class selector
+            SimpleSelector::Class(class) => {
+                // Append a "." (U+002E), followed by the serialization of the class name as an identifier to s.
+                s.push('.');
+                s.push_str(&serialize_an_identifier(class));
+            }
+            // ID selector
+            SimpleSelector::Id(id) => {
+                // Append a "#" (U+0023), followed by the serialization of the ID as an identifier to s.
+                s.push('#');
+                s.push_str(&serialize_an_identifier(id));
+            }
+            // pseudo-class
+            SimpleSelector::PseudoClass(pseudo_class) => {
+                // If the pseudo-class does not accept arguments append ":" (U+003A), followed by the name of the pseudo-class, to s.
+                // Otherwise, append ":" (U+003A), followed by the name of the pseudo-class, followed by "(" (U+0028), followed by the value of the pseudo-class argument(s) determined as per below, followed by ")" (U+0029), to s.
+                s.push(':');
+                s.push_str(pseudo_class.name());
+                let arguments = match pseudo_class {
+                    // :lang(): The serialization of a comma-separated list of each argument's serialization as a string, preserving relative order.
+                    // browsers serialize identifiers as identifiers
+                    PseudoClass::Lang(ranges) => Some(serialize_a_comma_separated_list(
+                        ranges.iter().map(|range| serialize_an_identifier(range)),
+                    )),
+                    // :nth-child(), :nth-last-child(), :nth-of-type(), :nth-last-of-type(): The result of serializing the value using the rules to serialize an <an+b> value.
+                    PseudoClass::NthChild(an_b)
+                    | PseudoClass::NthLastChild(an_b)
+                    | PseudoClass::NthOfType(an_b)
+                    | PseudoClass::NthLastOfType(an_b) => Some(an_b.serialize()),
+                    // :not(): The result of serializing the value using the rules for serializing a group of selectors.
+                    PseudoClass::Not(selector_list)
+                    | PseudoClass::Is(selector_list)
+                    | PseudoClass::Where(selector_list) => Some(selector_list.serialize()),
+                    _ => None,
+                };
+                if let Some(arguments) = arguments {
+                    s.push('(');
+                    s.push_str(&arguments);
+                    s.push(')');
+                }
+            }
+            // https://drafts.csswg.org/css-nesting-1/#cssom
+            SimpleSelector::Nesting => s.push('&'),
+        }
+        s
+    }
+}
+
+impl PseudoClass {
+    pub fn name(&self) -> &str {
+        match self {
+            PseudoClass::Not(_) => "not",
+            PseudoClass::Is(_) => "is",
+            PseudoClass::Where(_) => "where",
+            PseudoClass::Root => "root",
+            PseudoClass::Empty => "empty",
+            PseudoClass::NthChild(_) => "nth-child",
+            PseudoClass::NthLastChild(_) => "nth-last-child",
+            PseudoClass::FirstChild => "first-child",
+            PseudoClass::LastChild => "last-child",
+            PseudoClass::OnlyChild => "only-child",
+            PseudoClass::NthOfType(_) => "nth-of-type",
+            PseudoClass::NthLastOfType(_) => "nth-last-of-type",
+            PseudoClass::FirstOfType => "first-of-type",
+            PseudoClass::LastOfType => "last-of-type",
+            PseudoClass::OnlyOfType => "only-of-type",
+            PseudoClass::Lang(_) => "lang",
+            PseudoClass::Other(name) => name,
+        }
+    }
+}
+
+impl AnB {
+    // https://drafts.csswg.org/css-syntax-3/#serializing-anb
+    pub fn serialize(&self) -> String {
+        let AnB { a, b } = *self;
+        // 1. If A is zero, return the serialization of B.
+        if a == 0 {
+            return b.to_string();
+        }
+        // 2. Otherwise, let result initially be an empty string.
+        // 3. If A is 1, append "n" to result. If A is -1, append "-n" to result. Otherwise, append the serialization of A followed by "n" to result.
+        let mut result = match a {
+            1 => "n".to_string(),
+            -1 => "-n".to_string(),
+            _ => format!("{a}n"),
+        };
+        // 4. If B is greater than zero, append "+" to result, followed by the serialization of B.
+        // 5. If B is less than zero, append the serialization of B to result.
+        if b > 0 {
+            result.push('+');
+        }
+        if b != 0 {
+            result.push_str(&b.to_string());
+        }
+        // 6. Return result.
+        result
+    }
+
+    // whether the 1-based index is A * n + B for some n >= 0
+    pub fn matches(&self, index: i32) -> bool {
+        if self.a == 0 {
+            return index == self.b;
+        }
+        let n = (index - self.b) / self.a;
+        n >= 0 && (index - self.b) % self.a == 0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::css::jsonc;
+    use crate::css::selector::{
+        SelectorList, Specificity, parse_a_relative_selector_list, parse_a_selector,
+    };
+
+    fn run_test_file(file_path: &str) {
+        let tests = jsonc::parse_file(file_path);
+        for (index, test) in tests.as_array().unwrap().iter().enumerate() {
+            let input = test.get("Input").and_then(|input| input.as_str()).unwrap();
+            // null for invalid selectors
+            let expected = test
+                .get("Serialized")
+                .and_then(|serialized| serialized.as_str());
+            let serialized = parse_a_selector(input)
+                .ok()
+                .map(|selector_list| selector_list.serialize());
+            assert_eq!(
+                serialized.as_deref(),
+                expected,
+                "{file_path} {index}: |{input}|"
+            );
+        }
+    }
+
+    #[test]
+    fn selector_test1() {
+        run_test_file("css-tests/selector/test1.jsonc");
+    }
+
+    #[test]
+    fn selector_test2() {
+        run_test_file("css-tests/selector/test2.jsonc");
+    }
+
+    #[test]
+    fn specificity() {
+        let specificity = |input| {
+            let Specificity { a, b, c } = parse_a_selector(input).unwrap().specificity();
+            (a, b, c)
+        };
+        assert_eq!(specificity("*"), (0, 0, 0));
+        assert_eq!(specificity("li"), (0, 0, 1));
+        assert_eq!(specificity("ul li::marker"), (0, 0, 3));
+        assert_eq!(specificity("ul ol+li"), (0, 0, 3));
+        assert_eq!(specificity("h1 + *[rel=up]"), (0, 1, 1));
+        assert_eq!(specificity("ul ol li.red"), (0, 1, 3));
+        assert_eq!(specificity("li.red.level"), (0, 2, 1));
+        assert_eq!(specificity("#x34y"), (1, 0, 0));
+        assert_eq!(specificity("#s12:not(FOO)"), (1, 0, 1));
+        assert_eq!(specificity(".foo :is(.bar, #baz)"), (1, 1, 0));
+        assert_eq!(specificity(":where(#a) b, .c"), (0, 1, 0));
+        assert_eq!(specificity("&"), (0, 1, 0));
+    }
+
+    #[test]
+    fn replace_the_nesting_selector() {
+        let parent = parse_a_selector("#a, b").unwrap();
+        let nested = parse_a_relative_selector_list("> c, :not(&) d").unwrap();
+        let replaced = SelectorList(
+            nested
+                .0
+                .iter()
+                .map(|complex_selector| complex_selector.replace_the_nesting_selector(&parent))
+                .collect(),
+        );
+        assert_eq!(replaced.serialize(), ":is(#a, b) > c, :not(:is(#a, b)) d");
+        assert_eq!(
+            replaced.0[0].specificity(),
+            Specificity { a: 1, b: 0, c: 1 }
+        );
+    }
+}
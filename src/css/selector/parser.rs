@@ -0,0 +1,652 @@
+use crate::css::selector::{
+    AnB, AttributeMatcher, AttributeSelector, CaseSensitivity, Combinator, ComplexSelector,
+    CompoundSelector, NamespacePrefix, PseudoClass, QualifiedName, SelectorList, SimpleSelector,
+};
+
+// https://drafts.csswg.org/selectors-4/#invalid
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelectorError {
+    // the index of the character where the selector became invalid
+    pub position: usize,
+    pub error: &'static str,
+}
+
+// the pseudo-classes without arguments that aren't structural, they are valid but only some of them match
+const OTHER_PSEUDO_CLASSES: [&str; 24] = [
+    "active",
+    "any-link",
+    "checked",
+    "default",
+    "defined",
+    "disabled",
+    "enabled",
+    "focus",
+    "focus-visible",
+    "focus-within",
+    "hover",
+    "in-range",
+    "indeterminate",
+    "invalid",
+    "link",
+    "optional",
+    "out-of-range",
+    "placeholder-shown",
+    "read-only",
+    "read-write",
+    "required",
+    "target",
+    "valid",
+    "visited",
+];
+
+// https://drafts.csswg.org/selectors-4/#parse-a-selector
+// The selector is parsed directly from the characters, the grammar only needs a few of the CSS tokens.
+pub fn parse_a_selector(input: &str) -> Result<SelectorList, SelectorError> {
+    let mut parser = Parser {
+        chars: input.chars().collect(),
+        index: 0,
+    };
+    let selector_list = parser.selector_list()?;
+    if parser.peek(0).is_some() {
+        return Err(parser.error("unexpected-character"));
+    }
+    Ok(selector_list)
+}
+
+// https://drafts.csswg.org/css-nesting-1/#syntax
+// The prelude of a nested style rule is a <relative-selector-list>. A relative selector is made absolute by putting a nesting selector in front of it, e.g. "> a" is "& > a" and "a" is "& a".
+// A selector that doesn't start with a combinator and contains a nesting selector is already absolute.
+pub fn parse_a_relative_selector_list(input: &str) -> Result<SelectorList, SelectorError> {
+    let mut parser = Parser {
+        chars: input.chars().collect(),
+        index: 0,
+    };
+    let selector_list = parser.relative_selector_list()?;
+    if parser.peek(0).is_some() {
+        return Err(parser.error("unexpected-character"));
+    }
+    Ok(selector_list)
+}
+
+struct Parser {
+    chars: Vec<char>,
+    index: usize,
+}
+
+// https://drafts.csswg.org/css-syntax-3/#whitespace
+pub(super) fn is_whitespace(c: char) -> bool {
+    matches!(c, '\n' | '\r' | '\u{000C}' | '\t' | ' ')
+}
+
+// https://drafts.csswg.org/css-syntax-3/#ident-start-code-point
+fn is_ident_start_code_point(c: char) -> bool {
+    c.is_ascii_alphabetic() || c >= '\u{0080}' || c == '_'
+}
+
+// https://drafts.csswg.org/css-syntax-3/#ident-code-point
+fn is_ident_code_point(c: char) -> bool {
+    is_ident_start_code_point(c) || c.is_ascii_digit() || c == '-'
+}
+
+impl Parser {
+    fn peek(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.index + offset).copied()
+    }
+
+    fn next_is(&self, c: char) -> bool {
+        self.peek(0) == Some(c)
+    }
+
+    fn error(&self, error: &'static str) -> SelectorError {
+        SelectorError {
+            position: self.index,
+            error,
+        }
+    }
+
+    fn expect(&mut self, c: char, error: &'static str) -> Result<(), SelectorError> {
+        if !self.next_is(c) {
+            return Err(self.error(error));
+        }
+        self.index += 1;
+        Ok(())
+    }
+
+    // true if there was whitespace
+    fn skip_whitespace(&mut self) -> bool {
+        let start = self.index;
+        while self.peek(0).is_some_and(is_whitespace) {
+            self.index += 1;
+        }
+        self.index > start
+    }
+
+    // https://drafts.csswg.org/css-syntax-3/#starts-with-a-valid-escape
+    fn is_valid_escape(&self, offset: usize) -> bool {
+        // If the first code point is not U+005C REVERSE SOLIDUS (\), return false.
+        // Otherwise, if the second code point is a newline, return false.
+        // Otherwise, return true.
+        self.peek(offset) == Some('\\') && self.peek(offset + 1).is_none_or(|c| c != '\n')
+    }
+
+    // https://drafts.csswg.org/css-syntax-3/#would-start-an-identifier
+    fn would_start_an_identifier(&self, offset: usize) -> bool {
+        match self.peek(offset) {
+            // U+002D HYPHEN-MINUS: If the second code point is an ident-start code point or a U+002D HYPHEN-MINUS, or the second and third code points are a valid escape, return true. Otherwise, return false.
+            Some('-') => {
+                self.peek(offset + 1)
+                    .is_some_and(|c| is_ident_start_code_point(c) || c == '-')
+                    || self.is_valid_escape(offset + 1)
+            }
+            // ident-start code point: Return true.
+            Some(c) if is_ident_start_code_point(c) => true,
+            // U+005C REVERSE SOLIDUS (\): If the first and second code points are a valid escape, return true. Otherwise, return false.
+            Some('\\') => self.is_valid_escape(offset),
+            // anything else: Return false.
+            _ => false,
+        }
+    }
+
+    // https://drafts.csswg.org/css-syntax-3/#consume-an-escaped-code-point
+    // the U+005C REVERSE SOLIDUS (\) is already consumed
+    fn consume_an_escaped_code_point(&mut self) -> char {
+        match self.peek(0) {
+            // hex digit: Consume as many hex digits as possible, but no more than 5. Note that this means 1-6 hex digits have been consumed in total.
+            Some(c) if c.is_ascii_hexdigit() => {
+                let mut value = 0;
+                let mut digits = 0;
+                while digits < 6
+                    && let Some(digit) = self.peek(0).and_then(|c| c.to_digit(16))
+                {
+                    value = value * 16 + digit;
+                    digits += 1;
+                    self.index += 1;
+                }
+                // If the next input code point is whitespace, consume it as well.
+                if self.peek(0).is_some_and(is_whitespace) {
+                    self.index += 1;
+                }
+                // If this number is zero, or is for a surrogate, or is greater than the maximum allowed code point, return U+FFFD REPLACEMENT CHARACTER (�).
+                // Otherwise, return the code point with that value.
+                char::from_u32(value)
+                    .filter(|c| *c != '\u{0000}')
+                    .unwrap_or('\u{FFFD}')
+            }
+            // EOF: This is a parse error. Return U+FFFD REPLACEMENT CHARACTER (�).
+            None => '\u{FFFD}',
+            // anything else: Return the current input code point.
+            Some(c) => {
+                self.index += 1;
+                c
+            }
+        }
+    }
+
+    // https://drafts.csswg.org/css-syntax-3/#consume-name
+    fn consume_an_ident_sequence(&mut self) -> String {
+        let mut result = String::new();
+        loop {
+            match self.peek(0) {
+                // ident code point: Append the code point to result.
+                Some(c) if is_ident_code_point(c) => {
+                    result.push(c);
+                    self.index += 1;
+                }
+                // the stream starts with a valid escape: Consume an escaped code point. Append the returned code point to result.
+                Some('\\') if self.is_valid_escape(0) => {
+                    self.index += 1;
+                    result.push(self.consume_an_escaped_code_point());
+                }
+                // anything else: Reconsume the current input code point. Return result.
+                _ => return result,
+            }
+        }
+    }
+
+    // an <ident-token>
+    fn consume_an_identifier(&mut self) -> Option<String> {
+        self.would_start_an_identifier(0)
+            .then(|| self.consume_an_ident_sequence())
+    }
+
+    // https://drafts.csswg.org/css-syntax-3/#consume-string-token
+    fn consume_a_string(&mut self) -> Result<String, SelectorError> {
+        let ending_code_point = self.peek(0).expect("a string starts with a quote");
+        self.index += 1;
+        let mut string = String::new();
+        loop {
+            match self.peek(0) {
+                // ending code point: Return the <string-token>.
+                Some(c) if c == ending_code_point => {
+                    self.index += 1;
+                    return Ok(string);
+                }
+                // EOF: This is a parse error. Return the <string-token>.
+                None => return Ok(string),
+                // newline: This is a parse error. Reconsume the current input code point, create a <bad-string-token>, and return it.
+                Some('\n') => return Err(self.error("bad-string")),
+                // U+005C REVERSE SOLIDUS (\)
+                Some('\\') => {
+                    self.index += 1;
+                    match self.peek(0) {
+                        // If the next input code point is EOF, do nothing.
+                        None => {}
+                        // Otherwise, if the next input code point is a newline, consume it.
+                        Some('\n') => self.index += 1,
+                        // Otherwise, (the stream starts with a valid escape) consume an escaped code point and append the returned code point to the <string-token>'s value.
+                        Some(_) => string.push(self.consume_an_escaped_code_point()),
+                    }
+                }
+                // anything else: Append the current input code point to the <string-token>'s value.
+                Some(c) => {
+                    string.push(c);
+                    self.index += 1;
+                }
+            }
+        }
+    }
+
+    // https://drafts.csswg.org/selectors-4/#typedef-complex-selector-list
+    // <complex-selector-list> = <complex-selector>#
+    fn selector_list(&mut self) -> Result<SelectorList, SelectorError> {
+        let mut complex_selectors = Vec::new();
+        loop {
+            self.skip_whitespace();
+            complex_selectors.push(self.complex_selector()?);
+            self.skip_whitespace();
+            if !self.next_is(',') {
+                return Ok(SelectorList(complex_selectors));
+            }
+            self.index += 1;
+        }
+    }
+
+    // https://drafts.csswg.org/selectors-4/#typedef-relative-selector-list
+    // <relative-selector-list> = <relative-selector>#
+    // <relative-selector> = <combinator>? <complex-selector>
+    fn relative_selector_list(&mut self) -> Result<SelectorList, SelectorError> {
+        let mut complex_selectors = Vec::new();
+        loop {
+            self.skip_whitespace();
+            let combinator = match self.peek(0) {
+                Some('>') => Some(Combinator::Child),
+                Some('+') => Some(Combinator::NextSibling),
+                Some('~') => Some(Combinator::SubsequentSibling),
+                _ => None,
+            };
+            if combinator.is_some() {
+                self.index += 1;
+                self.skip_whitespace();
+            }
+            let mut complex_selector = self.complex_selector()?;
+            if combinator.is_some() || !complex_selector.contains_the_nesting_selector() {
+                complex_selector
+                    .compound_selectors
+                    .insert(0, CompoundSelector(vec![SimpleSelector::Nesting]));
+                complex_selector
+                    .combinators
+                    .insert(0, combinator.unwrap_or(Combinator::Descendant));
+            }
+            complex_selectors.push(complex_selector);
+            self.skip_whitespace();
+            if !self.next_is(',') {
+                return Ok(SelectorList(complex_selectors));
+            }
+            self.index += 1;
+        }
+    }
+
+    // https://drafts.csswg.org/selectors-4/#typedef-complex-selector
+    // <complex-selector> = <compound-selector> [ <combinator>? <compound-selector> ]*
+    fn complex_selector(&mut self) -> Result<ComplexSelector, SelectorError> {
+        let mut complex_selector = ComplexSelector {
+            compound_selectors: Vec::new(),
+            combinators: Vec::new(),
+            pseudo_element: None,
+        };
+        let compound_selector = self
+            .compound_selector(&mut complex_selector.pseudo_element)?
+            .ok_or_else(|| self.error("expected-selector"))?;
+        complex_selector.compound_selectors.push(compound_selector);
+
+        while complex_selector.pseudo_element.is_none() {
+            let start = self.index;
+            // Whitespace is required between two <complex-selector-unit>s if the <combinator> between them is omitted. (This indicates the descendant combinator is being used.)
+            let has_whitespace = self.skip_whitespace();
+            let combinator = match self.peek(0) {
+                // <combinator> = '>' | '+' | '~' | [ '|' '|' ]
+                Some('>') => Combinator::Child,
+                Some('+') => Combinator::NextSibling,
+                Some('~') => Combinator::SubsequentSibling,
+                Some(',' | ')') | None => {
+                    self.index = start;
+                    break;
+                }
+                _ if has_whitespace => Combinator::Descendant,
+                _ => return Err(self.error("unexpected-character")),
+            };
+            if combinator != Combinator::Descendant {
+                self.index += 1;
+                self.skip_whitespace();
+            }
+            let compound_selector = self
+                .compound_selector(&mut complex_selector.pseudo_element)?
+                .ok_or_else(|| self.error("expected-selector-after-combinator"))?;
+            complex_selector.combinators.push(combinator);
+            complex_selector.compound_selectors.push(compound_selector);
+        }
+        Ok(complex_selector)
+    }
+
+    // https://drafts.csswg.org/selectors-4/#typedef-compound-selector
+    // <compound-selector> = [ <type-selector>? <subclass-selector>* ]!
+    // a compound selector followed by a pseudo-element ends the complex selector, a pseudo-element alone has an empty compound selector that matches every element
+    fn compound_selector(
+        &mut self,
+        pseudo_element: &mut Option<String>,
+    ) -> Result<Option<CompoundSelector>, SelectorError> {
+        let mut simple_selectors = Vec::new();
+        if let Some(type_selector) = self.type_selector()? {
+            simple_selectors.push(type_selector);
+        }
+        while let Some(c) = self.peek(0) {
+            let simple_selector = match c {
+                // <id-selector> = <hash-token>
+                '#' => {
+                    self.index += 1;
+                    let id = self
+                        .consume_an_identifier()
+                        .ok_or_else(|| self.error("expected-identifier"))?;
+                    SimpleSelector::Id(id)
+                }
+                // <class-selector> = '.' <ident-token>
+                '.' => {
+                    self.index += 1;
+                    let class = self
+                        .consume_an_identifier()
+                        .ok_or_else(|| self.error("expected-identifier"))?;
+                    SimpleSelector::Class(class)
+                }
+                '[' => SimpleSelector::Attribute(self.attribute_selector()?),
+                // https://drafts.csswg.org/css-nesting-1/#nest-selector
+                '&' => {
+                    self.index += 1;
+                    SimpleSelector::Nesting
+                }
+                // <pseudo-element-selector> = ':' <pseudo-class-selector> | <legacy-pseudo-element-selector>
+                ':' if self.peek(1) == Some(':') => {
+                    self.index += 2;
+                    let name = self
+                        .consume_an_identifier()
+                        .ok_or_else(|| self.error("expected-identifier"))?;
+                    *pseudo_element = Some(name.to_ascii_lowercase());
+                    break;
+                }
+                ':' => {
+                    self.index += 1;
+                    match self.pseudo_class_selector()? {
+                        Ok(pseudo_class) => SimpleSelector::PseudoClass(pseudo_class),
+                        // <legacy-pseudo-element-selector> = ':' [before | after | first-line | first-letter]
+                        Err(legacy_pseudo_element) => {
+                            *pseudo_element = Some(legacy_pseudo_element);
+                            break;
+                        }
+                    }
+                }
+                _ => break,
+            };
+            simple_selectors.push(simple_selector);
+        }
+        if simple_selectors.is_empty() && pseudo_element.is_none() {
+            return Ok(None);
+        }
+        Ok(Some(CompoundSelector(simple_selectors)))
+    }
+
+    // <ns-prefix> = [ <ident-token> | '*' ]? '|'
+    // the prefix isn't followed by a '=', that would be the |= attribute matcher
+    fn namespace_prefix(&mut self) -> Result<Option<NamespacePrefix>, SelectorError> {
+        let is_prefix_end =
+            |offset| self.peek(offset) == Some('|') && self.peek(offset + 1) != Some('=');
+        if is_prefix_end(0) {
+            self.index += 1;
+            return Ok(Some(NamespacePrefix::Null));
+        }
+        if self.next_is('*') && is_prefix_end(1) {
+            self.index += 2;
+            return Ok(Some(NamespacePrefix::Any));
+        }
+        if self.would_start_an_identifier(0) {
+            let start = self.index;
+            self.consume_an_ident_sequence();
+            if self.next_is('|') && self.peek(1) != Some('=') {
+                // there is no way to declare a namespace prefix for a selector outside a stylesheet
+                return Err(self.error("unknown-namespace-prefix"));
+            }
+            self.index = start;
+        }
+        Ok(None)
+    }
+
+    // <type-selector> = <wq-name> | <ns-prefix>? '*'
+    // <wq-name> = <ns-prefix>? <ident-token>
+    fn type_selector(&mut self) -> Result<Option<SimpleSelector>, SelectorError> {
+        let prefix = self.namespace_prefix()?;
+        if self.next_is('*') {
+            self.index += 1;
+            return Ok(Some(SimpleSelector::Universal(
+                prefix.unwrap_or(NamespacePrefix::Default),
+            )));
+        }
+        match self.consume_an_identifier() {
+            Some(local_name) => Ok(Some(SimpleSelector::Type(QualifiedName {
+                prefix: prefix.unwrap_or(NamespacePrefix::Default),
+                local_name,
+            }))),
+            None if prefix.is_some() => Err(self.error("expected-identifier")),
+            None => Ok(None),
+        }
+    }
+
+    // <attribute-selector> = '[' <wq-name> ']' | '[' <wq-name> <attr-matcher> [ <string-token> | <ident-token> ] <attr-modifier>? ']'
+    fn attribute_selector(&mut self) -> Result<AttributeSelector, SelectorError> {
+        self.index += 1;
+        self.skip_whitespace();
+        let prefix = self.namespace_prefix()?;
+        let local_name = self
+            .consume_an_identifier()
+            .ok_or_else(|| self.error("expected-identifier"))?;
+        let name = QualifiedName {
+            prefix: prefix.unwrap_or(NamespacePrefix::Default),
+            local_name,
+        };
+        self.skip_whitespace();
+        if self.next_is(']') {
+            self.index += 1;
+            return Ok(AttributeSelector {
+                name,
+                matcher: None,
+                case_sensitivity: CaseSensitivity::Default,
+            });
+        }
+
+        // <attr-matcher> = [ '~' | '|' | '^' | '$' | '*' ]? '='
+        let matcher = match self.peek(0) {
+            Some('=') => AttributeMatcher::Exactly,
+            Some('~') => AttributeMatcher::ContainsWhitespaceSeparated,
+            Some('|') => AttributeMatcher::StartsWithDashSeparated,
+            Some('^') => AttributeMatcher::StartsWithString,
+            Some('$') => AttributeMatcher::EndsWithString,
+            Some('*') => AttributeMatcher::ContainsSubString,
+            _ => return Err(self.error("expected-attribute-matcher")),
+        };
+        if matcher != AttributeMatcher::Exactly {
+            self.index += 1;
+        }
+        self.expect('=', "expected-attribute-matcher")?;
+        self.skip_whitespace();
+        let value = match self.peek(0) {
+            Some('"' | '\'') => self.consume_a_string()?,
+            _ => self
+                .consume_an_identifier()
+                .ok_or_else(|| self.error("expected-attribute-value"))?,
+        };
+        self.skip_whitespace();
+
+        // <attr-modifier> = i | s
+        let mut case_sensitivity = CaseSensitivity::Default;
+        if let Some(c) = self.peek(0)
+            && !self.peek(1).is_some_and(is_ident_code_point)
+        {
+            case_sensitivity = match c {
+                'i' | 'I' => CaseSensitivity::Insensitive,
+                's' | 'S' => CaseSensitivity::Sensitive,
+                _ => CaseSensitivity::Default,
+            };
+            if case_sensitivity != CaseSensitivity::Default {
+                self.index += 1;
+                self.skip_whitespace();
+            }
+        }
+        self.expect(']', "expected-end-of-attribute-selector")?;
+        Ok(AttributeSelector {
+            name,
+            matcher: Some((matcher, value)),
+            case_sensitivity,
+        })
+    }
+
+    // <pseudo-class-selector> = ':' <ident-token> | ':' <function-token> <any-value> ')'
+    // the ':' is already consumed, the legacy pseudo-elements are returned as the error
+    fn pseudo_class_selector(&mut self) -> Result<Result<PseudoClass, String>, SelectorError> {
+        let name = self
+            .consume_an_identifier()
+            .ok_or_else(|| self.error("expected-identifier"))?
+            .to_ascii_lowercase();
+        if !self.next_is('(') {
+            return Ok(Ok(match name.as_str() {
+                "root" => PseudoClass::Root,
+                "empty" => PseudoClass::Empty,
+                "first-child" => PseudoClass::FirstChild,
+                "last-child" => PseudoClass::LastChild,
+                "only-child" => PseudoClass::OnlyChild,
+                "first-of-type" => PseudoClass::FirstOfType,
+                "last-of-type" => PseudoClass::LastOfType,
+                "only-of-type" => PseudoClass::OnlyOfType,
+                "before" | "after" | "first-line" | "first-letter" => return Ok(Err(name)),
+                _ if OTHER_PSEUDO_CLASSES.contains(&name.as_str()) => PseudoClass::Other(name),
+                _ => return Err(self.error("unknown-pseudo-class")),
+            }));
+        }
+        self.index += 1;
+        self.skip_whitespace();
+        let pseudo_class = match name.as_str() {
+            "not" => PseudoClass::Not(self.selector_list()?),
+            "is" => PseudoClass::Is(self.selector_list()?),
+            "where" => PseudoClass::Where(self.selector_list()?),
+            "nth-child" => PseudoClass::NthChild(self.an_b()?),
+            "nth-last-child" => PseudoClass::NthLastChild(self.an_b()?),
+            "nth-of-type" => PseudoClass::NthOfType(self.an_b()?),
+            "nth-last-of-type" => PseudoClass::NthLastOfType(self.an_b()?),
+            "lang" => {
+                // :lang() = :lang( <language-range># )
+                let mut ranges = Vec::new();
+                loop {
+                    let range = match self.peek(0) {
+                        Some('"' | '\'') => self.consume_a_string()?,
+                        _ => self
+                            .consume_an_identifier()
+                            .ok_or_else(|| self.error("expected-identifier"))?,
+                    };
+                    ranges.push(range);
+                    self.skip_whitespace();
+                    if !self.next_is(',') {
+                        break;
+                    }
+                    self.index += 1;
+                    self.skip_whitespace();
+                }
+                PseudoClass::Lang(ranges)
+            }
+            _ => return Err(self.error("unknown-pseudo-class")),
+        };
+        self.skip_whitespace();
+        self.expect(')', "expected-closing-parenthesis")?;
+        Ok(Ok(pseudo_class))
+    }
+
+    fn integer(&mut self) -> Option<i32> {
+        let start = self.index;
+        while self.peek(0).is_some_and(|c| c.is_ascii_digit()) {
+            self.index += 1;
+        }
+        let digits: String = self.chars[start..self.index].iter().collect();
+        digits.parse().ok()
+    }
+
+    // https://drafts.csswg.org/css-syntax-3/#anb-microsyntax
+    // odd | even | <integer> | <n-dimension> ['+' | '-'] <signless-integer> and so on, with the whitespace allowed between the tokens
+    fn an_b(&mut self) -> Result<AnB, SelectorError> {
+        let start = self.index;
+        if let Some(keyword) = self.consume_an_identifier() {
+            match keyword.to_ascii_lowercase().as_str() {
+                "odd" => return Ok(AnB { a: 2, b: 1 }),
+                "even" => return Ok(AnB { a: 2, b: 0 }),
+                _ => self.index = start,
+            }
+        }
+        let invalid = |parser: &Self| parser.error("invalid-an+b");
+        // the sign of A, or of B without A, is part of the token
+        let sign = match self.peek(0) {
+            Some('+') => {
+                self.index += 1;
+                1
+            }
+            Some('-') => {
+                self.index += 1;
+                -1
+            }
+            _ => 1,
+        };
+        let number = self.integer();
+        if !matches!(self.peek(0), Some('n' | 'N')) {
+            // B alone
+            return number
+                .map(|b| AnB { a: 0, b: sign * b })
+                .ok_or_else(|| invalid(self));
+        }
+        self.index += 1;
+        let a = sign * number.unwrap_or(1);
+        // the "n-" of an ident like "n-1"
+        let mut b_sign = None;
+        if self.next_is('-') {
+            self.index += 1;
+            b_sign = Some(-1);
+            if let Some(b) = self.integer() {
+                return Ok(AnB { a, b: -b });
+            }
+        }
+        self.skip_whitespace();
+        if b_sign.is_none() {
+            b_sign = match self.peek(0) {
+                Some('+') => Some(1),
+                Some('-') => Some(-1),
+                _ => return Ok(AnB { a, b: 0 }),
+            };
+            self.index += 1;
+            // a signed integer after whitespace, e.g. "n -1"
+            if let Some(b) = self.integer() {
+                return Ok(AnB {
+                    a,
+                    b: b_sign.unwrap() * b,
+                });
+            }
+            self.skip_whitespace();
+        }
+        let b = self.integer().ok_or_else(|| invalid(self))?;
+        Ok(AnB {
+            a,
+            b: b_sign.unwrap() * b,
+        })
+    }
+}
@@ -0,0 +1,115 @@
+// https://drafts.csswg.org/cssom/#common-serializing-idioms
+
+// https://drafts.csswg.org/cssom/#escape-a-character
+// To escape a character means to create a string of "\" (U+005C), followed by the character.
+fn escape_a_character(c: char, s: &mut String) {
+    s.push('\\');
+    s.push(c);
+}
+
+// https://drafts.csswg.org/cssom/#escape-a-character-as-code-point
+// To escape a character as code point means to create a string of "\" (U+005C), followed by the Unicode code point as the smallest possible number of hexadecimal digits in the range 0-9 a-f (U+0030 to U+0039 and U+0061 to U+0066) to represent the code point in base 16, followed by a single SPACE (U+0020).
+fn escape_a_character_as_code_point(c: char, s: &mut String) {
+    s.push_str(&format!("\\{:x} ", c as u32));
+}
+
+// https://drafts.csswg.org/cssom/#serialize-an-identifier
+pub fn serialize_an_identifier(identifier: &str) -> String {
+    // To serialize an identifier means to create a string represented by the concatenation of, for each character of the identifier:
+    let mut s = String::new();
+    let chars: Vec<char> = identifier.chars().collect();
+    for (index, c) in chars.iter().copied().enumerate() {
+        match c {
+            // If the character is NULL (U+0000), then the REPLACEMENT CHARACTER (U+FFFD).
+            '\u{0000}' => s.push('\u{FFFD}'),
+            // If the character is in the range [\1-\1f] (U+0001 to U+001F) or is U+007F, then the character escaped as code point.
+            '\u{0001}'..='\u{001F}' | '\u{007F}' => escape_a_character_as_code_point(c, &mut s),
+            // If the character is the first character and is in the range [0-9] (U+0030 to U+0039), then the character escaped as code point.
+            '0'..='9' if index == 0 => escape_a_character_as_code_point(c, &mut s),
+            // If the character is the second character and is in the range [0-9] (U+0030 to U+0039) and the first character is a "-" (U+002D), then the character escaped as code point.
+            '0'..='9' if index == 1 && chars[0] == '-' => {
+                escape_a_character_as_code_point(c, &mut s)
+            }
+            // If the character is the first character and is a "-" (U+002D), and there is no second character, then the escaped character.
+            '-' if index == 0 && chars.len() == 1 => escape_a_character(c, &mut s),
+            // If the character is not handled by one of the above rules and is greater than or equal to U+0080, is "-" (U+002D) or "_" (U+005F), or is in one of the ranges [0-9] (U+0030 to U+0039), [A-Z] (U+0041 to U+005A), or \[a-z] (U+0061 to U+007A), then the character itself.
+            _ if c >= '\u{0080}' || c == '-' || c == '_' || c.is_ascii_alphanumeric() => s.push(c),
+            // Otherwise, the escaped character.
+            _ => escape_a_character(c, &mut s),
+        }
+    }
+    s
+}
+
+// https://drafts.csswg.org/cssom/#serialize-a-string
+pub fn serialize_a_string(string: &str) -> String {
+    // To serialize a string means to create a string represented by '"' (U+0022), followed by the result of applying the rules below to each character of the given string, followed by '"' (U+0022):
+    let mut s = String::from('"');
+    for c in string.chars() {
+        match c {
+            // If the character is NULL (U+0000), then the REPLACEMENT CHARACTER (U+FFFD).
+            '\u{0000}' => s.push('\u{FFFD}'),
+            // If the character is in the range [\1-\1f] (U+0001 to U+001F) or is U+007F, the character escaped as code point.
+            '\u{0001}'..='\u{001F}' | '\u{007F}' => escape_a_character_as_code_point(c, &mut s),
+            // If the character is '"' (U+0022) or "\" (U+005C), the escaped character.
+            '"' | '\\' => escape_a_character(c, &mut s),
+            // Otherwise, the character itself.
+            _ => s.push(c),
+        }
+    }
+    s.push('"');
+    s
+}
+
+// https://drafts.csswg.org/cssom/#serialize-a-url
+// To serialize a URL means to create a string represented by "url(", followed by the serialization of the URL as a string, followed by ")".
+pub fn serialize_a_url(url: &str) -> String {
+    format!("url({})", serialize_a_string(url))
+}
+
+// https://drafts.csswg.org/cssom/#serializing-css-values
+// <number>: A base-ten number using digits 0-9 (U+0030 to U+0039) in the shortest form possible, using "." to separate decimals (if any), rounding the value if necessary to not produce more than 6 decimals, preceded by "-" (U+002D) if it is negative.
+pub fn serialize_a_number(value: f64) -> String {
+    // adding 0 turns a negative zero into a positive one
+    let rounded = (value * 1e6).round() / 1e6 + 0.0;
+    rounded.to_string()
+}
+
+// https://drafts.csswg.org/cssom/#serialize-a-comma-separated-list
+// To serialize a comma-separated list concatenate all items of the list in list order while separating them by ", ", i.e., COMMA (U+002C) followed by a single SPACE (U+0020).
+pub fn serialize_a_comma_separated_list(items: impl IntoIterator<Item = String>) -> String {
+    items.into_iter().collect::<Vec<_>>().join(", ")
+}
+
+#[cfg(test)]
+mod test {
+    use super::{serialize_a_number, serialize_a_string, serialize_an_identifier};
+
+    #[test]
+    fn identifier() {
+        assert_eq!(serialize_an_identifier("foo-bar_1"), "foo-bar_1");
+        assert_eq!(serialize_an_identifier("1a"), "\\31 a");
+        assert_eq!(serialize_an_identifier("-1"), "-\\31 ");
+        assert_eq!(serialize_an_identifier("-"), "\\-");
+        assert_eq!(serialize_an_identifier("--"), "--");
+        assert_eq!(serialize_an_identifier("a b.c"), "a\\ b\\.c");
+        assert_eq!(
+            serialize_an_identifier("\u{0000}é\u{0007}"),
+            "\u{FFFD}é\\7 "
+        );
+    }
+
+    #[test]
+    fn string() {
+        assert_eq!(serialize_a_string("fun"), "\"fun\"");
+        assert_eq!(serialize_a_string("a\"b\\c\n"), "\"a\\\"b\\\\c\\a \"");
+    }
+
+    #[test]
+    fn number() {
+        assert_eq!(serialize_a_number(1.0), "1");
+        assert_eq!(serialize_a_number(-0.5), "-0.5");
+        assert_eq!(serialize_a_number(-0.0), "0");
+        assert_eq!(serialize_a_number(1.0 / 3.0), "0.333333");
+    }
+}
@@ -0,0 +1,147 @@
+use crate::css::selector::{Combinator, ComplexSelector, SimpleSelector};
+use crate::html::tree_builder::tree::Element;
+
+// A counting bloom filter of the names, ids and classes of the ancestors of the element that is matched.
+// A selector like ".a .b" can't match if no ancestor has the class a, which the filter can tell without walking the ancestors. The filter can have false positives, but never false negatives.
+// https://en.wikipedia.org/wiki/Bloom_filter
+pub struct AncestorFilter {
+    // the counters make it possible to remove the hashes of an ancestor when the traversal leaves it
+    counters: Vec<u8>,
+}
+
+const FILTER_SIZE: usize = 4096;
+
+impl AncestorFilter {
+    pub fn new() -> Self {
+        AncestorFilter {
+            counters: vec![0; FILTER_SIZE],
+        }
+    }
+
+    pub fn push_ancestor(&mut self, element: &Element) {
+        for hash in element_hashes(element) {
+            self.insert(hash);
+        }
+    }
+
+    pub fn pop_ancestor(&mut self, element: &Element) {
+        for hash in element_hashes(element) {
+            self.remove(hash);
+        }
+    }
+
+    // false if an ancestor with the hash is definitely missing
+    pub fn might_contain(&self, hash: u32) -> bool {
+        let (first, second) = indexes(hash);
+        self.counters[first] > 0 && self.counters[second] > 0
+    }
+
+    fn insert(&mut self, hash: u32) {
+        let (first, second) = indexes(hash);
+        // a counter that overflowed stays at the maximum, the hash is never removed then
+        for index in [first, second] {
+            self.counters[index] = self.counters[index].saturating_add(1);
+        }
+    }
+
+    fn remove(&mut self, hash: u32) {
+        let (first, second) = indexes(hash);
+        for index in [first, second] {
+            if self.counters[index] != u8::MAX {
+                self.counters[index] -= 1;
+            }
+        }
+    }
+}
+
+// two hash functions from the parts of one hash
+fn indexes(hash: u32) -> (usize, usize) {
+    (
+        (hash & 0xfff) as usize % FILTER_SIZE,
+        ((hash >> 12) & 0xfff) as usize % FILTER_SIZE,
+    )
+}
+
+// https://en.wikipedia.org/wiki/Fowler%E2%80%93Noll%E2%80%93Vo_hash_function
+// the kind keeps an id and a class with the same name apart
+fn hash(kind: u8, name: &str) -> u32 {
+    let mut hash: u32 = 0x811c9dc5;
+    for byte in std::iter::once(kind).chain(name.bytes()) {
+        hash ^= byte as u32;
+        hash = hash.wrapping_mul(0x01000193);
+    }
+    hash
+}
+
+// the local name, the id and the classes of the element
+fn element_hashes(element: &Element) -> impl Iterator<Item = u32> + '_ {
+    // the type selectors of HTML elements are ASCII case-insensitive, the other names are compared lowercased too
+    std::iter::once(hash(b't', &element.local_name.to_ascii_lowercase()))
+        .chain(element.id().map(|id| hash(b'#', id)))
+        .chain(element.class_list().map(|class| hash(b'.', class)))
+}
+
+// The hashes of the simple selectors that an ancestor of the element has to match, for the compound selectors to the left of a descendant or child combinator.
+// A compound selector to the left of a sibling combinator matches a sibling of the element or of an ancestor, which is not in the filter.
+pub fn ancestor_hashes(complex_selector: &ComplexSelector) -> Vec<u32> {
+    let mut hashes = Vec::new();
+    let mut is_ancestor = false;
+    for index in (0..complex_selector.compound_selectors.len()).rev() {
+        if is_ancestor {
+            for simple_selector in &complex_selector.compound_selectors[index].0 {
+                match simple_selector {
+                    SimpleSelector::Type(name) => {
+                        hashes.push(hash(b't', &name.local_name.to_ascii_lowercase()))
+                    }
+                    SimpleSelector::Id(id) => hashes.push(hash(b'#', id)),
+                    SimpleSelector::Class(class) => hashes.push(hash(b'.', class)),
+                    _ => {}
+                }
+            }
+        }
+        if index > 0 {
+            is_ancestor = matches!(
+                complex_selector.combinators[index - 1],
+                Combinator::Descendant | Combinator::Child
+            );
+        }
+    }
+    hashes
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::css::selector::parse_a_selector;
+    use crate::html::tokenizer::Attribute;
+
+    fn element(local_name: &str, class: &str) -> Element {
+        let mut element = Element::new(local_name);
+        element.attributes.push(Attribute::new("class", class));
+        element
+    }
+
+    #[test]
+    fn ancestor_filter() {
+        let mut filter = AncestorFilter::new();
+        let div = element("div", "a b");
+        let span = element("span", "a");
+        filter.push_ancestor(&div);
+        filter.push_ancestor(&span);
+        let might_match = |filter: &AncestorFilter, selector| {
+            let selector = parse_a_selector(selector).unwrap();
+            ancestor_hashes(&selector.0[0])
+                .into_iter()
+                .all(|hash| filter.might_contain(hash))
+        };
+        assert!(might_match(&filter, "DIV.b > span .c"));
+        assert!(!might_match(&filter, "p .c"));
+        // the compound selector before a sibling combinator isn't an ancestor
+        assert!(might_match(&filter, "div > p + .c"));
+        filter.pop_ancestor(&div);
+        assert!(might_match(&filter, ".a .c"));
+        assert!(!might_match(&filter, ".b .c"));
+        filter.pop_ancestor(&span);
+        assert!(filter.counters.iter().all(|counter| *counter == 0));
+    }
+}
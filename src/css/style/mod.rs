@@ -0,0 +1,780 @@
+mod bloom;
+
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::css::cssom::{CssRule, Origin, Stylesheet};
+use crate::css::media::MediaEnvironment;
+use crate::css::parser::{ComponentValue, Declaration, serialize_component_values};
+use crate::css::selector::{ComplexSelector, SelectorList, Specificity};
+use crate::css::tokenizer::Token;
+use crate::html::tree_builder::tree::{Node, Tree};
+use bloom::{AncestorFilter, ancestor_hashes};
+
+// https://drafts.csswg.org/css-cascade-5/#specified
+// The specified values of the properties of an element by property name. A property without a value has its initial value.
+// todo the initial values, and the computed values
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SpecifiedValues(pub BTreeMap<String, Vec<ComponentValue>>);
+
+impl SpecifiedValues {
+    pub fn get(&self, property: &str) -> Option<&[ComponentValue]> {
+        self.0.get(property).map(Vec::as_slice)
+    }
+
+    // https://drafts.csswg.org/cssom/#dom-cssstyledeclaration-getpropertyvalue
+    // the serialized value, the empty string for a property without a value
+    pub fn get_property_value(&self, property: &str) -> String {
+        self.get(property)
+            .map(serialize_component_values)
+            .unwrap_or_default()
+    }
+}
+
+// https://drafts.csswg.org/css-cascade-5/#cascade
+// The specified values of all elements of the tree, with the style attributes of the elements. The media queries are evaluated for the default screen.
+pub fn compute_styles(
+    tree: &Tree<Node>,
+    stylesheets: &[Stylesheet],
+) -> HashMap<usize, SpecifiedValues> {
+    compute_styles_for_media(
+        tree,
+        stylesheets,
+        &tree.style_attributes(),
+        &MediaEnvironment::default(),
+    )
+}
+
+// the style attributes are the declarations by element, e.g. the style attributes of the author styles
+pub fn compute_styles_for_media(
+    tree: &Tree<Node>,
+    stylesheets: &[Stylesheet],
+    style_attributes: &[(usize, Vec<Declaration>)],
+    environment: &MediaEnvironment,
+) -> HashMap<usize, SpecifiedValues> {
+    let mut collector = RuleCollector {
+        environment,
+        layers: LayerOrder::default(),
+        rules: Vec::new(),
+    };
+    for stylesheet in stylesheets {
+        if stylesheet.media.matches(environment) {
+            collector.collect(&stylesheet.rules, stylesheet.origin, None, &[]);
+        }
+    }
+    let rules = collector.rules;
+    let style_attributes: HashMap<usize, Vec<Declaration>> = style_attributes
+        .iter()
+        .map(|(element, declarations)| (*element, expand_shorthands(declarations)))
+        .collect();
+
+    let mut styles: HashMap<usize, SpecifiedValues> = HashMap::new();
+    let Some(root) = tree.get_root() else {
+        return styles;
+    };
+    let mut filter = AncestorFilter::new();
+    // the ancestors that are in the filter, with their depth
+    let mut ancestors: Vec<(usize, usize)> = Vec::new();
+    for (index, depth) in tree.descendants(root) {
+        while let Some((ancestor, ancestor_depth)) = ancestors.last().copied()
+            && ancestor_depth >= depth
+        {
+            filter.pop_ancestor(tree.get_element(ancestor).expect("an element"));
+            ancestors.pop();
+        }
+        let Some(element) = tree.get_element(index) else {
+            continue;
+        };
+        let parent = ancestors
+            .last()
+            .filter(|(ancestor, _)| tree.parent(index) == Some(*ancestor))
+            .and_then(|(ancestor, _)| styles.get(ancestor));
+        let style_attribute = style_attributes.get(&index).map_or(&[][..], Vec::as_slice);
+        let specified_values = cascade(tree, index, &rules, &filter, style_attribute, parent);
+        styles.insert(index, specified_values);
+        filter.push_ancestor(element);
+        ancestors.push((index, depth));
+    }
+    styles
+}
+
+// a style rule with the nesting selectors replaced and the shorthands expanded
+struct CascadeRule {
+    // the complex selectors with their specificity and the hashes for the ancestor filter
+    selectors: Vec<(ComplexSelector, Specificity, Vec<u32>)>,
+    declarations: Vec<Declaration>,
+    origin: Origin,
+    layer: Vec<usize>,
+}
+
+struct RuleCollector<'a> {
+    environment: &'a MediaEnvironment,
+    layers: LayerOrder,
+    // in the order of appearance
+    rules: Vec<CascadeRule>,
+}
+
+impl RuleCollector<'_> {
+    // the style rules that apply, in the conditional group rules that match and the imported style sheets
+    fn collect(
+        &mut self,
+        rules: &[CssRule],
+        origin: Origin,
+        parent: Option<&SelectorList>,
+        layer: &[String],
+    ) {
+        for rule in rules {
+            match rule {
+                CssRule::Style(rule) => {
+                    // https://drafts.csswg.org/css-nesting-1/#nest-selector
+                    let selectors = match parent {
+                        Some(parent) => SelectorList(
+                            rule.selectors
+                                .0
+                                .iter()
+                                .map(|selector| selector.replace_the_nesting_selector(parent))
+                                .collect(),
+                        ),
+                        None => rule.selectors.clone(),
+                    };
+                    self.push(&selectors, &rule.declarations, origin, layer);
+                    self.collect(&rule.rules, origin, Some(&selectors), layer);
+                }
+                // https://drafts.csswg.org/css-nesting-1/#nested-declarations-rule
+                // The nested declarations rule matches the same elements as its parent style rule, with the same specificity behavior.
+                CssRule::NestedDeclarations(rule) => {
+                    if let Some(parent) = parent {
+                        self.push(parent, &rule.declarations, origin, layer);
+                    }
+                }
+                CssRule::Media(rule) => {
+                    if rule.media.matches(self.environment) {
+                        self.collect(&rule.rules, origin, parent, layer);
+                    }
+                }
+                CssRule::Supports(rule) => {
+                    if rule.matches() {
+                        self.collect(&rule.rules, origin, parent, layer);
+                    }
+                }
+                // https://drafts.csswg.org/css-cascade-5/#at-import
+                // The imported style sheet is in the layer of the @import rule, and only applies if its conditions match.
+                CssRule::Import(rule) => {
+                    let Some(stylesheet) = &rule.stylesheet else {
+                        continue;
+                    };
+                    // todo the supports condition of the import
+                    if !rule.media.matches(self.environment) {
+                        continue;
+                    }
+                    let layer = match &rule.layer_name {
+                        Some(name) => self.layers.declare(layer, name),
+                        None => layer.to_vec(),
+                    };
+                    self.collect(&stylesheet.rules, origin, None, &layer);
+                }
+                // https://drafts.csswg.org/css-cascade-5/#layer-block
+                CssRule::LayerBlock(rule) => {
+                    let layer = self.layers.declare(layer, &rule.name);
+                    self.collect(&rule.rules, origin, parent, &layer);
+                }
+                // https://drafts.csswg.org/css-cascade-5/#layer-empty
+                // The statement establishes the order of the layers without assigning any styles.
+                CssRule::LayerStatement(rule) => {
+                    for name in &rule.name_list {
+                        self.layers.declare(layer, name);
+                    }
+                }
+                // the descriptors of @font-face are not properties of elements
+                CssRule::FontFace(_) => {}
+            }
+        }
+    }
+
+    fn push(
+        &mut self,
+        selectors: &SelectorList,
+        declarations: &[Declaration],
+        origin: Origin,
+        layer: &[String],
+    ) {
+        if declarations.is_empty() {
+            return;
+        }
+        self.rules.push(CascadeRule {
+            selectors: selectors
+                .0
+                .iter()
+                .map(|selector| {
+                    (
+                        selector.clone(),
+                        selector.specificity(),
+                        ancestor_hashes(selector),
+                    )
+                })
+                .collect(),
+            declarations: expand_shorthands(declarations),
+            origin,
+            layer: self.layers.sort_key(layer),
+        });
+    }
+}
+
+// https://drafts.csswg.org/css-cascade-5/#layer-ordering
+// The layers are ordered by their first declaration, a sublayer comes before the styles of its parent layer that are not in a sublayer.
+#[derive(Default)]
+struct LayerOrder {
+    // the position of a layer among the sublayers of its parent, by the names of the layer and its parents
+    positions: HashMap<Vec<String>, usize>,
+    // the number of sublayers, by the names of the parent layer
+    sublayers: HashMap<Vec<String>, usize>,
+    anonymous_layers: usize,
+}
+
+impl LayerOrder {
+    // declares the dotted layer name in the parent layer and returns the full names of the layer, an empty name is a new anonymous layer
+    fn declare(&mut self, parent: &[String], name: &str) -> Vec<String> {
+        let mut layer = parent.to_vec();
+        if name.is_empty() {
+            // the name can't be written in a style sheet
+            self.anonymous_layers += 1;
+            layer.push(format!("\0{}", self.anonymous_layers));
+        } else {
+            layer.extend(name.split('.').map(str::to_string));
+        }
+        for length in parent.len() + 1..=layer.len() {
+            let name = layer[..length].to_vec();
+            if !self.positions.contains_key(&name) {
+                let count = self
+                    .sublayers
+                    .entry(layer[..length - 1].to_vec())
+                    .or_default();
+                self.positions.insert(name, *count);
+                *count += 1;
+            }
+        }
+        layer
+    }
+
+    // the positions of the layer and its parents, followed by the maximum for the styles that are directly in the layer, so that sort keys compare like the layers
+    fn sort_key(&self, layer: &[String]) -> Vec<usize> {
+        (1..=layer.len())
+            .map(|length| self.positions[&layer[..length]])
+            .chain(std::iter::once(usize::MAX))
+            .collect()
+    }
+}
+
+// https://drafts.csswg.org/css-cascade-5/#declared
+// a declaration that applies to the element
+struct Candidate<'a> {
+    declaration: &'a Declaration,
+    origin: Origin,
+    // https://drafts.csswg.org/css-cascade-5/#style-attr
+    style_attribute: bool,
+    layer: &'a [usize],
+    specificity: Specificity,
+    // the position of the rule and the declaration in it
+    order: (usize, usize),
+}
+
+impl Candidate<'_> {
+    // https://drafts.csswg.org/css-cascade-5/#cascade-origin
+    // Declarations from origins earlier in this list win over declarations from later origins:
+    // Transition declarations, Important user agent declarations, Important user declarations, Important author declarations, Animation declarations, Normal author declarations, Normal user declarations, Normal user agent declarations
+    fn origin_and_importance(&self) -> u8 {
+        match (self.origin, self.declaration.important) {
+            (Origin::UserAgent, false) => 0,
+            (Origin::User, false) => 1,
+            (Origin::Author, false) => 2,
+            (Origin::Author, true) => 3,
+            (Origin::User, true) => 4,
+            (Origin::UserAgent, true) => 5,
+        }
+    }
+
+    // https://drafts.csswg.org/css-cascade-5/#cascade-sort
+    // ascending, the last declaration wins
+    fn cascade_order(&self, other: &Candidate) -> Ordering {
+        // Origin and Importance
+        self.origin_and_importance()
+            .cmp(&other.origin_and_importance())
+            // Element-Attached Styles: declarations that are attached to an element win over declarations that are mapped to it by a selector
+            .then(self.style_attribute.cmp(&other.style_attribute))
+            // Layers: for normal rules, declarations in later layers win, for important rules, declarations in earlier layers win
+            .then_with(|| match self.declaration.important {
+                false => self.layer.cmp(other.layer),
+                true => other.layer.cmp(self.layer),
+            })
+            // Specificity
+            .then(self.specificity.cmp(&other.specificity))
+            // Order of Appearance
+            .then(self.order.cmp(&other.order))
+    }
+}
+
+// https://drafts.csswg.org/css-cascade-5/#value-stages
+// the specified values of the element, from the declarations of the rules that match and of the style attribute
+fn cascade(
+    tree: &Tree<Node>,
+    element: usize,
+    rules: &[CascadeRule],
+    filter: &AncestorFilter,
+    style_attribute: &[Declaration],
+    parent: Option<&SpecifiedValues>,
+) -> SpecifiedValues {
+    // https://drafts.csswg.org/css-cascade-5/#filtering
+    // the declarations of the rules whose selector matches the element
+    let mut candidates = Vec::new();
+    for (rule_index, rule) in rules.iter().enumerate() {
+        // the specificity of the most specific selector that matches
+        let specificity = rule
+            .selectors
+            .iter()
+            .filter(|(_, _, hashes)| hashes.iter().all(|hash| filter.might_contain(*hash)))
+            .filter(|(selector, _, _)| {
+                tree.match_a_complex_selector_against_an_element(selector, element)
+            })
+            .map(|(_, specificity, _)| *specificity)
+            .max();
+        let Some(specificity) = specificity else {
+            continue;
+        };
+        candidates.extend(rule.declarations.iter().enumerate().map(
+            |(declaration_index, declaration)| Candidate {
+                declaration,
+                origin: rule.origin,
+                style_attribute: false,
+                layer: &rule.layer,
+                specificity,
+                order: (rule_index, declaration_index),
+            },
+        ));
+    }
+    // the style attribute is in the author origin and is not in a layer
+    let unlayered = [usize::MAX];
+    candidates.extend(style_attribute.iter().enumerate().map(
+        |(declaration_index, declaration)| Candidate {
+            declaration,
+            origin: Origin::Author,
+            style_attribute: true,
+            layer: &unlayered,
+            specificity: Specificity::default(),
+            order: (usize::MAX, declaration_index),
+        },
+    ));
+    // https://drafts.csswg.org/css-cascade-5/#cascaded
+    // The cascaded value is the declared value that wins the cascade, the candidates are sorted with the winner first.
+    candidates.sort_by(|a, b| b.cascade_order(a));
+    let mut by_property: BTreeMap<&str, Vec<&Candidate>> = BTreeMap::new();
+    for candidate in &candidates {
+        by_property
+            .entry(candidate.declaration.name.as_str())
+            .or_default()
+            .push(candidate);
+    }
+
+    // https://drafts.csswg.org/css-cascade-5/#inheriting
+    // the inherited properties that are not cascaded have the value of the parent
+    let mut specified_values = SpecifiedValues(
+        parent
+            .map(|parent| {
+                parent
+                    .0
+                    .iter()
+                    .filter(|(property, _)| is_inherited(property))
+                    .map(|(property, value)| (property.clone(), value.clone()))
+                    .collect()
+            })
+            .unwrap_or_default(),
+    );
+    for (property, candidates) in by_property {
+        match cascaded_value(property, &candidates) {
+            CascadedValue::Value(value) => {
+                specified_values
+                    .0
+                    .insert(property.to_string(), value.to_vec());
+            }
+            CascadedValue::Inherit => {
+                match parent.and_then(|parent| parent.0.get(property)) {
+                    Some(value) => specified_values
+                        .0
+                        .insert(property.to_string(), value.clone()),
+                    None => specified_values.0.remove(property),
+                };
+            }
+            CascadedValue::Initial => {
+                specified_values.0.remove(property);
+            }
+        }
+    }
+    specified_values
+}
+
+enum CascadedValue<'a> {
+    Value(&'a [ComponentValue]),
+    Inherit,
+    Initial,
+}
+
+// https://drafts.csswg.org/css-cascade-5/#defaulting-keywords
+// the value that wins the cascade, after the css-wide keywords
+fn cascaded_value<'a>(property: &str, candidates: &[&Candidate<'a>]) -> CascadedValue<'a> {
+    // https://drafts.csswg.org/css-cascade-5/#inherit-initial
+    // If the cascaded value of a property is the unset keyword, then if it is an inherited property, this is treated as inherit, and if it is not, this is treated as initial.
+    let unset = if is_inherited(property) {
+        CascadedValue::Inherit
+    } else {
+        CascadedValue::Initial
+    };
+    // the origins and the layers that were reverted
+    let mut reverted_origins: Vec<Origin> = Vec::new();
+    let mut reverted_layers: Vec<(u8, &[usize])> = Vec::new();
+    for candidate in candidates {
+        if reverted_origins.contains(&candidate.origin)
+            || reverted_layers.contains(&(candidate.origin_and_importance(), candidate.layer))
+        {
+            continue;
+        }
+        let value = &candidate.declaration.value;
+        match css_wide_keyword(value).as_deref() {
+            // https://drafts.csswg.org/css-cascade-5/#initial
+            Some("initial") => return CascadedValue::Initial,
+            // https://drafts.csswg.org/css-cascade-5/#inherit
+            Some("inherit") => return CascadedValue::Inherit,
+            Some("unset") => return unset,
+            // https://drafts.csswg.org/css-cascade-5/#revert-keyword
+            // The revert keyword rolls back the cascaded value to the previous cascade origin, in the user-agent origin it is unset.
+            Some("revert") => reverted_origins.push(candidate.origin),
+            // https://drafts.csswg.org/css-cascade-5/#revert-layer
+            // The revert-layer keyword rolls back the value to the previous cascade layer, or to the previous origin if there is none.
+            Some("revert-layer") => {
+                reverted_layers.push((candidate.origin_and_importance(), candidate.layer))
+            }
+            _ => return CascadedValue::Value(value),
+        }
+    }
+    // https://drafts.csswg.org/css-cascade-5/#defaulting
+    // When the cascade does not result in a value, the specified value is found by inheriting or by the initial value.
+    unset
+}
+
+// https://drafts.csswg.org/css-values-4/#common-keywords
+fn css_wide_keyword(value: &[ComponentValue]) -> Option<String> {
+    match non_whitespace(value).as_slice() {
+        [ComponentValue::Token(Token::Ident(ident))] => {
+            let ident = ident.to_ascii_lowercase();
+            ["initial", "inherit", "unset", "revert", "revert-layer"]
+                .contains(&ident.as_str())
+                .then_some(ident)
+        }
+        _ => None,
+    }
+}
+
+fn non_whitespace(value: &[ComponentValue]) -> Vec<&ComponentValue> {
+    value
+        .iter()
+        .filter(|value| **value != ComponentValue::Token(Token::Whitespace))
+        .collect()
+}
+
+// https://drafts.csswg.org/css-cascade-5/#inherited-property
+// the properties that are inherited by default, custom properties are inherited too
+static INHERITED_PROPERTIES: &[&str] = &[
+    "border-collapse",
+    "border-spacing",
+    "caption-side",
+    "color",
+    "cursor",
+    "direction",
+    "empty-cells",
+    "font",
+    "font-family",
+    "font-size",
+    "font-style",
+    "font-variant",
+    "font-weight",
+    "letter-spacing",
+    "line-height",
+    "list-style",
+    "list-style-image",
+    "list-style-position",
+    "list-style-type",
+    "orphans",
+    "quotes",
+    "tab-size",
+    "text-align",
+    "text-indent",
+    "text-shadow",
+    "text-transform",
+    "visibility",
+    "white-space",
+    "widows",
+    "word-break",
+    "word-spacing",
+    "writing-mode",
+];
+
+fn is_inherited(property: &str) -> bool {
+    property.starts_with("--") || INHERITED_PROPERTIES.contains(&property)
+}
+
+// https://drafts.csswg.org/css-cascade-5/#shorthand
+// The shorthands with one to four values for the sides, e.g. margin: 1px 2px is margin-top: 1px, margin-right: 2px, margin-bottom: 1px, margin-left: 2px
+// todo the other shorthands
+static BOX_SHORTHANDS: &[(&str, [&str; 4])] = &[
+    (
+        "margin",
+        ["margin-top", "margin-right", "margin-bottom", "margin-left"],
+    ),
+    (
+        "padding",
+        [
+            "padding-top",
+            "padding-right",
+            "padding-bottom",
+            "padding-left",
+        ],
+    ),
+    (
+        "border-width",
+        [
+            "border-top-width",
+            "border-right-width",
+            "border-bottom-width",
+            "border-left-width",
+        ],
+    ),
+    (
+        "border-style",
+        [
+            "border-top-style",
+            "border-right-style",
+            "border-bottom-style",
+            "border-left-style",
+        ],
+    ),
+    (
+        "border-color",
+        [
+            "border-top-color",
+            "border-right-color",
+            "border-bottom-color",
+            "border-left-color",
+        ],
+    ),
+    ("inset", ["top", "right", "bottom", "left"]),
+];
+
+// the declarations with the shorthands replaced by their longhands, a shorthand with an invalid value is dropped
+fn expand_shorthands(declarations: &[Declaration]) -> Vec<Declaration> {
+    let mut expanded = Vec::new();
+    for declaration in declarations {
+        let Some((_, longhands)) = BOX_SHORTHANDS
+            .iter()
+            .find(|(shorthand, _)| *shorthand == declaration.name)
+        else {
+            expanded.push(declaration.clone());
+            continue;
+        };
+        // todo a value with var() is substituted before it is expanded
+        if contains_var(&declaration.value) {
+            expanded.push(declaration.clone());
+            continue;
+        }
+        // A css-wide keyword in a shorthand sets all of its longhands.
+        let values: Vec<Vec<ComponentValue>> = if css_wide_keyword(&declaration.value).is_some() {
+            vec![declaration.value.clone()]
+        } else {
+            non_whitespace(&declaration.value)
+                .into_iter()
+                .map(|value| vec![value.clone()])
+                .collect()
+        };
+        // top, right = top, bottom = top, left = right
+        let sides = match values.len() {
+            1 => [0, 0, 0, 0],
+            2 => [0, 1, 0, 1],
+            3 => [0, 1, 2, 1],
+            4 => [0, 1, 2, 3],
+            _ => continue,
+        };
+        for (longhand, side) in longhands.iter().zip(sides) {
+            expanded.push(Declaration {
+                name: longhand.to_string(),
+                value: values[side].clone(),
+                important: declaration.important,
+            });
+        }
+    }
+    expanded
+}
+
+fn contains_var(value: &[ComponentValue]) -> bool {
+    value.iter().any(|value| match value {
+        ComponentValue::Function(function) => {
+            function.name.eq_ignore_ascii_case("var") || contains_var(&function.value)
+        }
+        ComponentValue::SimpleBlock(block) => contains_var(&block.value),
+        ComponentValue::Token(_) => false,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::html::tokenizer::Tokenizer;
+    use crate::html::tree_builder::{ParserOptions, TreeBuilder};
+
+    fn parse(contents: &str) -> TreeBuilder {
+        let mut tree_builder = TreeBuilder::new(ParserOptions::default());
+        tree_builder.build(Tokenizer::new(contents));
+        tree_builder
+    }
+
+    // the value of the property of the element with the id
+    fn property_value(
+        tree: &Tree<Node>,
+        styles: &HashMap<usize, SpecifiedValues>,
+        id: &str,
+        property: &str,
+    ) -> String {
+        styles[&tree.get_element_by_id(id).unwrap()].get_property_value(property)
+    }
+
+    #[test]
+    fn cascade_order() {
+        let tree_builder = parse(
+            r#"<div id=a class=x><p id=b class=y style="color: green; margin: 1px 2px"></p><p id=c></p></div>"#,
+        );
+        let tree = tree_builder.get_tree();
+        let stylesheets = [Stylesheet::parse(
+            "p { color: red; width: 1px !important; width: 2px }
+            .x .y { color: blue; margin-left: 5px !important }
+            #b { width: 3px; }
+            div > p { height: 1px } p { height: 2px }
+            :where(#c) { height: 3px }
+            .x { display: block; color: black }",
+        )];
+        let styles = compute_styles(tree, &stylesheets);
+        // the style attribute wins over any selector, but not over an important declaration
+        assert_eq!(property_value(tree, &styles, "b", "color"), "green");
+        assert_eq!(property_value(tree, &styles, "b", "margin-top"), "1px");
+        assert_eq!(property_value(tree, &styles, "b", "margin-left"), "5px");
+        assert_eq!(property_value(tree, &styles, "b", "margin-right"), "2px");
+        assert_eq!(property_value(tree, &styles, "b", "width"), "1px");
+        // specificity, then the order of appearance
+        assert_eq!(property_value(tree, &styles, "c", "height"), "1px");
+        assert_eq!(property_value(tree, &styles, "c", "color"), "red");
+        assert_eq!(property_value(tree, &styles, "a", "display"), "block");
+        // display isn't inherited
+        assert_eq!(property_value(tree, &styles, "c", "display"), "");
+    }
+
+    #[test]
+    fn origins_and_layers() {
+        let tree_builder = parse(r#"<p id=p class=x>"#);
+        let tree = tree_builder.get_tree();
+        let user_agent = Stylesheet {
+            origin: Origin::UserAgent,
+            ..Stylesheet::parse(
+                "p { display: block; color: gray !important; margin: 1em } p { width: 1px }",
+            )
+        };
+        let author = Stylesheet::parse(
+            "@layer base, theme;
+            @layer theme { p { width: 3px; height: 3px !important } }
+            @layer base { #p.x { width: 2px; height: 2px !important } }
+            @layer base.reset { p { top: 1px } }
+            @layer base { p { top: 2px } }
+            p { display: inline; color: black; margin: revert; left: revert-layer }
+            @layer theme { p { left: 1px } }",
+        );
+        let styles = compute_styles(tree, &[user_agent, author]);
+        let value = |property| property_value(tree, &styles, "p", property);
+        assert_eq!(value("display"), "inline");
+        // an important user agent declaration wins over the author
+        assert_eq!(value("color"), "gray");
+        // unlayered styles win over layers for normal declarations, the last layer wins over the earlier ones
+        assert_eq!(value("width"), "3px");
+        // for important declarations the earlier layers win
+        assert_eq!(value("height"), "2px");
+        // the styles of a layer win over its sublayers
+        assert_eq!(value("top"), "2px");
+        // revert rolls back to the user agent origin, revert-layer to the previous layer
+        assert_eq!(value("margin-top"), "1em");
+        assert_eq!(value("left"), "1px");
+    }
+
+    #[test]
+    fn inheritance() {
+        let tree_builder = parse(
+            r#"<div id=a style="color: red; --x: 1; border-width: 2px"><p id=b style="border-width: inherit"><span id=c style="color: initial"></span><b id=d style="color: unset; --x: initial"></b></p></div>"#,
+        );
+        let tree = tree_builder.get_tree();
+        let styles = compute_styles(tree, &[]);
+        assert_eq!(property_value(tree, &styles, "b", "color"), "red");
+        assert_eq!(property_value(tree, &styles, "b", "--x"), "1");
+        assert_eq!(
+            property_value(tree, &styles, "b", "border-top-width"),
+            "2px"
+        );
+        assert_eq!(property_value(tree, &styles, "c", "color"), "");
+        assert_eq!(property_value(tree, &styles, "c", "border-top-width"), "");
+        assert_eq!(property_value(tree, &styles, "d", "color"), "red");
+        assert_eq!(property_value(tree, &styles, "d", "--x"), "");
+    }
+
+    #[test]
+    fn conditional_and_nested_rules() {
+        let tree_builder = parse(
+            r#"<link rel=stylesheet href=a.css><ul id=list class=menu style="right: 1px"><li id=one><a id=link href=x>x</a></li></ul>"#,
+        );
+        let tree = tree_builder.get_tree();
+        let loader = HashMap::from([
+            (
+                "a.css".to_string(),
+                "@import 'b.css' layer(base) print;
+            @import 'c.css' screen;
+            .menu { color: red; > li { color: blue; &:first-child a { color: green } } }
+            .menu { @media (min-width: 100px) { width: 1px } @media print { height: 1px } }
+            @supports (display: grid) { ul { display: grid } }
+            @supports not (display: grid) { ul { display: block } }"
+                    .to_string(),
+            ),
+            ("b.css".to_string(), "ul { top: 1px }".to_string()),
+            ("c.css".to_string(), "ul { left: 1px }".to_string()),
+        ]);
+        let author_styles = tree.author_styles(&loader);
+        let styles = compute_styles(tree, &author_styles.stylesheets);
+        assert_eq!(property_value(tree, &styles, "list", "color"), "red");
+        assert_eq!(property_value(tree, &styles, "list", "right"), "1px");
+        assert_eq!(property_value(tree, &styles, "one", "color"), "blue");
+        assert_eq!(property_value(tree, &styles, "link", "color"), "green");
+        assert_eq!(property_value(tree, &styles, "list", "width"), "1px");
+        assert_eq!(property_value(tree, &styles, "list", "height"), "");
+        assert_eq!(property_value(tree, &styles, "list", "display"), "grid");
+        assert_eq!(property_value(tree, &styles, "list", "top"), "");
+        assert_eq!(property_value(tree, &styles, "list", "left"), "1px");
+
+        // the same style sheet for print
+        let print = MediaEnvironment {
+            media_type: "print".to_string(),
+            ..MediaEnvironment::default()
+        };
+        let styles = compute_styles_for_media(
+            tree,
+            &author_styles.stylesheets,
+            &author_styles.style_attributes,
+            &print,
+        );
+        assert_eq!(property_value(tree, &styles, "list", "height"), "1px");
+        assert_eq!(property_value(tree, &styles, "list", "top"), "1px");
+        assert_eq!(property_value(tree, &styles, "list", "left"), "");
+        assert_eq!(property_value(tree, &styles, "list", "right"), "1px");
+        // without the style attributes
+        let styles = compute_styles_for_media(tree, &author_styles.stylesheets, &[], &print);
+        assert_eq!(property_value(tree, &styles, "list", "right"), "");
+    }
+}
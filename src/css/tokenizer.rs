@@ -0,0 +1,886 @@
+use std::fmt;
+
+// https://drafts.csswg.org/css-syntax-3/#tokenization
+#[derive(Debug, Clone, PartialEq)]
+pub enum Token {
+    Ident(String),
+    Function(String),
+    AtKeyword(String),
+    Hash {
+        value: String,
+        type_flag: HashType,
+    },
+    String(String),
+    BadString,
+    Url(String),
+    BadUrl,
+    Delim(char),
+    Number {
+        value: f64,
+        type_flag: NumberType,
+    },
+    Percentage {
+        value: f64,
+        type_flag: NumberType,
+    },
+    Dimension {
+        value: f64,
+        type_flag: NumberType,
+        unit: String,
+    },
+    // https://www.w3.org/TR/2014/CR-css-syntax-3-20140220/#typedef-unicode-range-token
+    UnicodeRange {
+        start: u32,
+        end: u32,
+    },
+    Whitespace,
+    CDO,
+    CDC,
+    Colon,
+    Semicolon,
+    Comma,
+    OpenSquareBracket,
+    CloseSquareBracket,
+    OpenParenthesis,
+    CloseParenthesis,
+    OpenCurlyBracket,
+    CloseCurlyBracket,
+    EOF,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HashType {
+    Id,
+    Unrestricted,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NumberType {
+    Integer,
+    Number,
+}
+
+impl fmt::Display for HashType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            HashType::Id => "id",
+            HashType::Unrestricted => "unrestricted",
+        })
+    }
+}
+
+impl fmt::Display for NumberType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            NumberType::Integer => "integer",
+            NumberType::Number => "number",
+        })
+    }
+}
+
+// The tokens are written like in the css-tests, e.g. <ident-token 'foo'> or <number-token 1 integer>, an empty value is left out.
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value_token = |f: &mut fmt::Formatter<'_>, name: &str, value: &str| {
+            if value.is_empty() {
+                write!(f, "<{name}-token>")
+            } else {
+                write!(f, "<{name}-token '{value}'>")
+            }
+        };
+        // -0 is written as 0, adding 0 turns a negative zero into a positive one
+        let number = |value: &f64| value + 0.0;
+        match self {
+            Token::Ident(value) => value_token(f, "ident", value),
+            Token::Function(value) => value_token(f, "function", value),
+            Token::AtKeyword(value) => value_token(f, "at-keyword", value),
+            Token::Hash { value, type_flag } => write!(f, "<hash-token '{value}' {type_flag}>"),
+            Token::String(value) => value_token(f, "string", value),
+            Token::BadString => f.write_str("<bad-string-token>"),
+            Token::Url(value) => value_token(f, "url", value),
+            Token::BadUrl => f.write_str("<bad-url-token>"),
+            Token::Delim(value) => write!(f, "<delim-token {value}>"),
+            Token::Number { value, type_flag } => {
+                write!(f, "<number-token {} {type_flag}>", number(value))
+            }
+            Token::Percentage { value, type_flag } => {
+                write!(f, "<percentage-token {} {type_flag}>", number(value))
+            }
+            Token::Dimension {
+                value,
+                type_flag,
+                unit,
+            } => write!(f, "<dimension-token {} {unit} {type_flag}>", number(value)),
+            Token::UnicodeRange { start, end } => {
+                write!(f, "<unicode-range-token {start:x}-{end:x}>")
+            }
+            Token::Whitespace => f.write_str("<whitespace-token>"),
+            Token::CDO => f.write_str("<CDO-token>"),
+            Token::CDC => f.write_str("<CDC-token>"),
+            Token::Colon => f.write_str("<colon-token>"),
+            Token::Semicolon => f.write_str("<semicolon-token>"),
+            Token::Comma => f.write_str("<comma-token>"),
+            Token::OpenSquareBracket => f.write_str("<[-token>"),
+            Token::CloseSquareBracket => f.write_str("<]-token>"),
+            Token::OpenParenthesis => f.write_str("<(-token>"),
+            Token::CloseParenthesis => f.write_str("<)-token>"),
+            Token::OpenCurlyBracket => f.write_str("<{-token>"),
+            Token::CloseCurlyBracket => f.write_str("<}-token>"),
+            Token::EOF => f.write_str("<EOF-token>"),
+        }
+    }
+}
+
+// https://drafts.csswg.org/css-syntax-3/#css-parse-error
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    // the index of the character where the error was found
+    pub position: usize,
+    pub error: &'static str,
+}
+
+// https://drafts.csswg.org/css-syntax-3/#maximum-allowed-code-point
+const MAXIMUM_ALLOWED_CODE_POINT: u32 = 0x10FFFF;
+
+pub struct Tokenizer {
+    chars: Vec<char>,
+    index: usize,
+    errors: Vec<ParseError>,
+}
+
+// the tokens up to the <EOF-token>, which is not returned
+impl Iterator for Tokenizer {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Token> {
+        match self.consume_a_token() {
+            Token::EOF => None,
+            token => Some(token),
+        }
+    }
+}
+
+// tokenizes the whole input
+pub fn tokenize(input: &str) -> (Vec<Token>, Vec<ParseError>) {
+    let mut tokenizer = Tokenizer::new(input);
+    let tokens = tokenizer.by_ref().collect();
+    (tokens, tokenizer.errors)
+}
+
+// https://drafts.csswg.org/css-syntax-3/#newline
+fn is_newline(c: char) -> bool {
+    c == '\n'
+}
+
+// https://drafts.csswg.org/css-syntax-3/#whitespace
+fn is_whitespace(c: char) -> bool {
+    matches!(c, '\n' | '\t' | ' ')
+}
+
+// https://drafts.csswg.org/css-syntax-3/#ident-start-code-point
+pub(crate) fn is_ident_start_code_point(c: char) -> bool {
+    c.is_ascii_alphabetic() || c >= '\u{0080}' || c == '_'
+}
+
+// https://drafts.csswg.org/css-syntax-3/#ident-code-point
+pub(crate) fn is_ident_code_point(c: char) -> bool {
+    is_ident_start_code_point(c) || c.is_ascii_digit() || c == '-'
+}
+
+// https://drafts.csswg.org/css-syntax-3/#non-printable-code-point
+fn is_non_printable_code_point(c: char) -> bool {
+    matches!(c, '\u{0000}'..='\u{0008}' | '\u{000B}' | '\u{000E}'..='\u{001F}' | '\u{007F}')
+}
+
+// https://drafts.csswg.org/css-syntax-3/#input-preprocessing
+fn preprocess(input: &str) -> Vec<char> {
+    let mut chars = Vec::with_capacity(input.len());
+    let mut input = input.chars().peekable();
+    while let Some(c) = input.next() {
+        match c {
+            // Replace any U+000D CARRIAGE RETURN (CR) code points, U+000C FORM FEED (FF) code points, or pairs of U+000D CARRIAGE RETURN (CR) followed by U+000A LINE FEED (LF) in input by a single U+000A LINE FEED (LF) code point.
+            '\r' => {
+                input.next_if_eq(&'\n');
+                chars.push('\n');
+            }
+            '\u{000C}' => chars.push('\n'),
+            // Replace any U+0000 NULL or surrogate code points in input with U+FFFD REPLACEMENT CHARACTER (�).
+            // a Rust string has no surrogates
+            '\u{0000}' => chars.push('\u{FFFD}'),
+            _ => chars.push(c),
+        }
+    }
+    chars
+}
+
+impl Tokenizer {
+    pub fn new(input: &str) -> Self {
+        Tokenizer {
+            chars: preprocess(input),
+            index: 0,
+            errors: Vec::new(),
+        }
+    }
+
+    pub fn errors(&self) -> &[ParseError] {
+        &self.errors
+    }
+
+    fn parse_error(&mut self, error: &'static str) {
+        self.errors.push(ParseError {
+            position: self.index,
+            error,
+        });
+    }
+
+    // the code point at the offset from the next input code point
+    fn peek(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.index + offset).copied()
+    }
+
+    fn consume_the_next_input_code_point(&mut self) -> Option<char> {
+        let c = self.peek(0);
+        // the index also moves past the end of the input so that reconsuming an EOF works
+        self.index += 1;
+        c
+    }
+
+    fn reconsume_the_current_input_code_point(&mut self) {
+        self.index -= 1;
+    }
+
+    // https://drafts.csswg.org/css-syntax-3/#consume-token
+    pub fn consume_a_token(&mut self) -> Token {
+        // Consume comments.
+        self.consume_comments();
+        // Consume the next input code point.
+        match self.consume_the_next_input_code_point() {
+            // whitespace: Consume as much whitespace as possible. Return a <whitespace-token>.
+            Some(c) if is_whitespace(c) => {
+                while self.peek(0).is_some_and(is_whitespace) {
+                    self.index += 1;
+                }
+                Token::Whitespace
+            }
+            // U+0022 QUOTATION MARK ("): Consume a string token and return it.
+            Some('"') => self.consume_a_string_token('"'),
+            // U+0023 NUMBER SIGN (#)
+            Some('#') => {
+                // If the next input code point is an ident code point or the next two input code points are a valid escape, then:
+                if self.peek(0).is_some_and(is_ident_code_point) || self.are_a_valid_escape(0) {
+                    // 1. Create a <hash-token>.
+                    // 2. If the next 3 input code points would start an ident sequence, set the <hash-token>'s type flag to "id".
+                    let type_flag = if self.would_start_an_ident_sequence(0) {
+                        HashType::Id
+                    } else {
+                        HashType::Unrestricted
+                    };
+                    // 3. Consume an ident sequence, and set the <hash-token>'s value to the returned string.
+                    let value = self.consume_an_ident_sequence();
+                    // 4. Return the <hash-token>.
+                    Token::Hash { value, type_flag }
+                } else {
+                    // Otherwise, return a <delim-token> with its value set to the current input code point.
+                    Token::Delim('#')
+                }
+            }
+            // U+0027 APOSTROPHE ('): Consume a string token and return it.
+            Some('\'') => self.consume_a_string_token('\''),
+            // U+0028 LEFT PARENTHESIS ((): Return a <(-token>.
+            Some('(') => Token::OpenParenthesis,
+            // U+0029 RIGHT PARENTHESIS ()): Return a <)-token>.
+            Some(')') => Token::CloseParenthesis,
+            // U+002B PLUS SIGN (+): If the input stream starts with a number, reconsume the current input code point, consume a numeric token, and return it.
+            // Otherwise, return a <delim-token> with its value set to the current input code point.
+            Some('+') => {
+                if self.would_start_a_number(-1) {
+                    self.reconsume_the_current_input_code_point();
+                    self.consume_a_numeric_token()
+                } else {
+                    Token::Delim('+')
+                }
+            }
+            // U+002C COMMA (,): Return a <comma-token>.
+            Some(',') => Token::Comma,
+            // U+002D HYPHEN-MINUS (-)
+            Some('-') => {
+                if self.would_start_a_number(-1) {
+                    // If the input stream starts with a number, reconsume the current input code point, consume a numeric token, and return it.
+                    self.reconsume_the_current_input_code_point();
+                    self.consume_a_numeric_token()
+                } else if self.peek(0) == Some('-') && self.peek(1) == Some('>') {
+                    // Otherwise, if the next 2 input code points are U+002D HYPHEN-MINUS U+003E GREATER-THAN SIGN (->), consume them and return a <CDC-token>.
+                    self.index += 2;
+                    Token::CDC
+                } else if self.would_start_an_ident_sequence(-1) {
+                    // Otherwise, if the input stream starts with an ident sequence, reconsume the current input code point, consume an ident-like token, and return it.
+                    self.reconsume_the_current_input_code_point();
+                    self.consume_an_ident_like_token()
+                } else {
+                    // Otherwise, return a <delim-token> with its value set to the current input code point.
+                    Token::Delim('-')
+                }
+            }
+            // U+002E FULL STOP (.): If the input stream starts with a number, reconsume the current input code point, consume a numeric token, and return it.
+            // Otherwise, return a <delim-token> with its value set to the current input code point.
+            Some('.') => {
+                if self.would_start_a_number(-1) {
+                    self.reconsume_the_current_input_code_point();
+                    self.consume_a_numeric_token()
+                } else {
+                    Token::Delim('.')
+                }
+            }
+            // U+003A COLON (:): Return a <colon-token>.
+            Some(':') => Token::Colon,
+            // U+003B SEMICOLON (;): Return a <semicolon-token>.
+            Some(';') => Token::Semicolon,
+            // U+003C LESS-THAN SIGN (<): If the next 3 input code points are U+0021 EXCLAMATION MARK U+002D HYPHEN-MINUS U+002D HYPHEN-MINUS (!--), consume them and return a <CDO-token>.
+            // Otherwise, return a <delim-token> with its value set to the current input code point.
+            Some('<') => {
+                if self.peek(0) == Some('!')
+                    && self.peek(1) == Some('-')
+                    && self.peek(2) == Some('-')
+                {
+                    self.index += 3;
+                    Token::CDO
+                } else {
+                    Token::Delim('<')
+                }
+            }
+            // U+0040 COMMERCIAL AT (@): If the next 3 input code points would start an ident sequence, consume an ident sequence, create an <at-keyword-token> with its value set to the returned value, and return it.
+            // Otherwise, return a <delim-token> with its value set to the current input code point.
+            Some('@') => {
+                if self.would_start_an_ident_sequence(0) {
+                    Token::AtKeyword(self.consume_an_ident_sequence())
+                } else {
+                    Token::Delim('@')
+                }
+            }
+            // U+005B LEFT SQUARE BRACKET ([): Return a <[-token>.
+            Some('[') => Token::OpenSquareBracket,
+            // U+005C REVERSE SOLIDUS (\)
+            Some('\\') => {
+                if self.are_a_valid_escape(-1) {
+                    // If the input stream starts with a valid escape, reconsume the current input code point, consume an ident-like token, and return it.
+                    self.reconsume_the_current_input_code_point();
+                    self.consume_an_ident_like_token()
+                } else {
+                    // Otherwise, this is a parse error. Return a <delim-token> with its value set to the current input code point.
+                    self.parse_error("invalid-escape");
+                    Token::Delim('\\')
+                }
+            }
+            // U+005D RIGHT SQUARE BRACKET (]): Return a <]-token>.
+            Some(']') => Token::CloseSquareBracket,
+            // U+007B LEFT CURLY BRACKET ({): Return a <{-token>.
+            Some('{') => Token::OpenCurlyBracket,
+            // U+007D RIGHT CURLY BRACKET (}): Return a <}-token>.
+            Some('}') => Token::CloseCurlyBracket,
+            // digit: Reconsume the current input code point, consume a numeric token, and return it.
+            Some(c) if c.is_ascii_digit() => {
+                self.reconsume_the_current_input_code_point();
+                self.consume_a_numeric_token()
+            }
+            // https://www.w3.org/TR/2014/CR-css-syntax-3-20140220/#consume-a-token
+            // U+0055 LATIN CAPITAL LETTER U, U+0075 LATIN SMALL LETTER U: If the next two input code points are U+002B PLUS SIGN (+) followed by a hex digit or U+003F QUESTION MARK (?), consume the next input code point. Note: don't consume both of them. Consume a unicode-range token and return it.
+            // Otherwise, reconsume the current input code point, consume an ident-like token, and return it.
+            Some('U' | 'u')
+                if self.peek(0) == Some('+')
+                    && self
+                        .peek(1)
+                        .is_some_and(|c| c.is_ascii_hexdigit() || c == '?') =>
+            {
+                self.index += 1;
+                self.consume_a_unicode_range_token()
+            }
+            // ident-start code point: Reconsume the current input code point, consume an ident-like token, and return it.
+            Some(c) if is_ident_start_code_point(c) => {
+                self.reconsume_the_current_input_code_point();
+                self.consume_an_ident_like_token()
+            }
+            // EOF: Return an <EOF-token>.
+            None => Token::EOF,
+            // anything else: Return a <delim-token> with its value set to the current input code point.
+            Some(c) => Token::Delim(c),
+        }
+    }
+
+    // https://drafts.csswg.org/css-syntax-3/#consume-comment
+    fn consume_comments(&mut self) {
+        // If the next two input code point are U+002F SOLIDUS (/) followed by a U+002A ASTERISK (*), consume them and all following code points up to and including the first U+002A ASTERISK (*) followed by a U+002F SOLIDUS (/), or up to an EOF code point.
+        // Return to the start of this step.
+        while self.peek(0) == Some('/') && self.peek(1) == Some('*') {
+            self.index += 2;
+            loop {
+                match self.consume_the_next_input_code_point() {
+                    Some('*') if self.peek(0) == Some('/') => {
+                        self.index += 1;
+                        break;
+                    }
+                    Some(_) => {}
+                    // If the preceding paragraph ended by consuming an EOF code point, this is a parse error.
+                    None => {
+                        self.reconsume_the_current_input_code_point();
+                        self.parse_error("eof-in-comment");
+                        return;
+                    }
+                }
+            }
+        }
+        // Return nothing.
+    }
+
+    // https://drafts.csswg.org/css-syntax-3/#consume-numeric-token
+    fn consume_a_numeric_token(&mut self) -> Token {
+        // Consume a number and let number be the result.
+        let (value, type_flag) = self.consume_a_number();
+        if self.would_start_an_ident_sequence(0) {
+            // If the next 3 input code points would start an ident sequence, then:
+            // 1. Create a <dimension-token> with the same value and type flag as number, and a unit set initially to the empty string.
+            // 2. Consume an ident sequence. Set the <dimension-token>'s unit to the returned value.
+            // 3. Return the <dimension-token>.
+            let unit = self.consume_an_ident_sequence();
+            Token::Dimension {
+                value,
+                type_flag,
+                unit,
+            }
+        } else if self.peek(0) == Some('%') {
+            // Otherwise, if the next input code point is U+0025 PERCENTAGE SIGN (%), consume it. Create a <percentage-token> with the same value as number, and return it.
+            self.index += 1;
+            Token::Percentage { value, type_flag }
+        } else {
+            // Otherwise, create a <number-token> with the same value and type flag as number, and return it.
+            Token::Number { value, type_flag }
+        }
+    }
+
+    // https://drafts.csswg.org/css-syntax-3/#consume-ident-like-token
+    fn consume_an_ident_like_token(&mut self) -> Token {
+        // Consume an ident sequence, and let string be the result.
+        let string = self.consume_an_ident_sequence();
+        if string.eq_ignore_ascii_case("url") && self.peek(0) == Some('(') {
+            // If string's value is an ASCII case-insensitive match for "url", and the next input code point is U+0028 LEFT PARENTHESIS ((), consume it.
+            self.index += 1;
+            // While the next two input code points are whitespace, consume the next input code point.
+            while self.peek(0).is_some_and(is_whitespace) && self.peek(1).is_some_and(is_whitespace)
+            {
+                self.index += 1;
+            }
+            // If the next one or two input code points are U+0022 QUOTATION MARK ("), U+0027 APOSTROPHE ('), or whitespace followed by U+0022 QUOTATION MARK (") or U+0027 APOSTROPHE ('), then create a <function-token> with its value set to string and return it.
+            let is_quote = |c: Option<char>| matches!(c, Some('"' | '\''));
+            if is_quote(self.peek(0))
+                || (self.peek(0).is_some_and(is_whitespace) && is_quote(self.peek(1)))
+            {
+                Token::Function(string)
+            } else {
+                // Otherwise, consume a url token, and return it.
+                self.consume_a_url_token()
+            }
+        } else if self.peek(0) == Some('(') {
+            // Otherwise, if the next input code point is U+0028 LEFT PARENTHESIS ((), consume it. Create a <function-token> with its value set to string and return it.
+            self.index += 1;
+            Token::Function(string)
+        } else {
+            // Otherwise, create an <ident-token> with its value set to string and return it.
+            Token::Ident(string)
+        }
+    }
+
+    // https://drafts.csswg.org/css-syntax-3/#consume-string-token
+    fn consume_a_string_token(&mut self, ending_code_point: char) -> Token {
+        // Initially create a <string-token> with its value set to the empty string.
+        let mut value = String::new();
+        // Repeatedly consume the next input code point from the stream:
+        loop {
+            match self.consume_the_next_input_code_point() {
+                // ending code point: Return the <string-token>.
+                Some(c) if c == ending_code_point => return Token::String(value),
+                // EOF: This is a parse error. Return the <string-token>.
+                None => {
+                    self.reconsume_the_current_input_code_point();
+                    self.parse_error("unexpected-eof-in-string");
+                    return Token::String(value);
+                }
+                // newline: This is a parse error. Reconsume the current input code point, create a <bad-string-token>, and return it.
+                Some(c) if is_newline(c) => {
+                    self.reconsume_the_current_input_code_point();
+                    self.parse_error("unexpected-newline-in-string");
+                    return Token::BadString;
+                }
+                // U+005C REVERSE SOLIDUS (\)
+                Some('\\') => match self.peek(0) {
+                    // If the next input code point is EOF, do nothing.
+                    None => {}
+                    // Otherwise, if the next input code point is a newline, consume it.
+                    Some(c) if is_newline(c) => self.index += 1,
+                    // Otherwise, (the stream starts with a valid escape) consume an escaped code point and append the returned code point to the <string-token>'s value.
+                    Some(_) => value.push(self.consume_an_escaped_code_point()),
+                },
+                // anything else: Append the current input code point to the <string-token>'s value.
+                Some(c) => value.push(c),
+            }
+        }
+    }
+
+    // https://drafts.csswg.org/css-syntax-3/#consume-url-token
+    // the "url(" is already consumed
+    fn consume_a_url_token(&mut self) -> Token {
+        // 1. Initially create a <url-token> with its value set to the empty string.
+        let mut value = String::new();
+        // 2. Consume as much whitespace as possible.
+        while self.peek(0).is_some_and(is_whitespace) {
+            self.index += 1;
+        }
+        // 3. Repeatedly consume the next input code point from the stream:
+        loop {
+            match self.consume_the_next_input_code_point() {
+                // U+0029 RIGHT PARENTHESIS ()): Return the <url-token>.
+                Some(')') => return Token::Url(value),
+                // EOF: This is a parse error. Return the <url-token>.
+                None => {
+                    self.reconsume_the_current_input_code_point();
+                    self.parse_error("unexpected-eof-in-url");
+                    return Token::Url(value);
+                }
+                // whitespace: Consume as much whitespace as possible.
+                Some(c) if is_whitespace(c) => {
+                    while self.peek(0).is_some_and(is_whitespace) {
+                        self.index += 1;
+                    }
+                    match self.peek(0) {
+                        // If the next input code point is U+0029 RIGHT PARENTHESIS ()) or EOF, consume it and return the <url-token> (if EOF was encountered, this is a parse error);
+                        Some(')') => {
+                            self.index += 1;
+                            return Token::Url(value);
+                        }
+                        None => {
+                            self.parse_error("unexpected-eof-in-url");
+                            return Token::Url(value);
+                        }
+                        // otherwise, consume the remnants of a bad url, create a <bad-url-token>, and return it.
+                        Some(_) => {
+                            self.consume_the_remnants_of_a_bad_url();
+                            return Token::BadUrl;
+                        }
+                    }
+                }
+                // U+0022 QUOTATION MARK ("), U+0027 APOSTROPHE ('), U+0028 LEFT PARENTHESIS ((), non-printable code point:
+                // This is a parse error. Consume the remnants of a bad url, create a <bad-url-token>, and return it.
+                Some(c) if matches!(c, '"' | '\'' | '(') || is_non_printable_code_point(c) => {
+                    self.parse_error("unexpected-character-in-url");
+                    self.consume_the_remnants_of_a_bad_url();
+                    return Token::BadUrl;
+                }
+                // U+005C REVERSE SOLIDUS (\)
+                Some('\\') => {
+                    if self.are_a_valid_escape(-1) {
+                        // If the stream starts with a valid escape, consume an escaped code point and append the returned code point to the <url-token>'s value.
+                        value.push(self.consume_an_escaped_code_point());
+                    } else {
+                        // Otherwise, this is a parse error. Consume the remnants of a bad url, create a <bad-url-token>, and return it.
+                        self.parse_error("invalid-escape-in-url");
+                        self.consume_the_remnants_of_a_bad_url();
+                        return Token::BadUrl;
+                    }
+                }
+                // anything else: Append the current input code point to the <url-token>'s value.
+                Some(c) => value.push(c),
+            }
+        }
+    }
+
+    // https://www.w3.org/TR/2014/CR-css-syntax-3-20140220/#consume-a-unicode-range-token
+    // the "U+" is already consumed
+    fn consume_a_unicode_range_token(&mut self) -> Token {
+        // Consume as many hex digits as possible, but no more than 6.
+        let mut digits = self.consume_hex_digits(6);
+        // If less than 6 hex digits were consumed, consume as many U+003F QUESTION MARK (?) code points as possible, but no more than enough to make the total of hex digits and U+003F QUESTION MARK (?) code points equal to 6.
+        let mut question_marks = 0;
+        while digits.len() + question_marks < 6 && self.peek(0) == Some('?') {
+            self.index += 1;
+            question_marks += 1;
+        }
+        let interpret = |digits: &str| u32::from_str_radix(digits, 16).unwrap_or(0);
+        if question_marks > 0 {
+            // If any U+003F QUESTION MARK (?) code points were consumed, then:
+            // 1. Interpret the consumed code points as a hexadecimal number, with the U+003F QUESTION MARK (?) code points replaced by U+0030 DIGIT ZERO (0) code points. This is the start of the range.
+            // 2. Interpret the consumed code points as a hexadecimal number again, with the U+003F QUESTION MARK (?) code point replaced by U+0046 LATIN CAPITAL LETTER F (F) code points. This is the end of the range.
+            // 3. Return a new <unicode-range-token> with the above start and end.
+            let start = interpret(&format!("{digits}{}", "0".repeat(question_marks)));
+            let end = interpret(&format!("{digits}{}", "F".repeat(question_marks)));
+            return Token::UnicodeRange { start, end };
+        }
+        // Otherwise, interpret the digits as a hexadecimal number. This is the start of the range.
+        let start = interpret(&digits);
+        // If the next 2 input code points are U+002D HYPHEN-MINUS (-) followed by a hex digit, then:
+        let end =
+            if self.peek(0) == Some('-') && self.peek(1).is_some_and(|c| c.is_ascii_hexdigit()) {
+                // 1. Consume the next input code point.
+                self.index += 1;
+                // 2. Consume as many hex digits as possible, but no more than 6. Interpret the digits as a hexadecimal number. This is the end of the range.
+                digits = self.consume_hex_digits(6);
+                interpret(&digits)
+            } else {
+                // Otherwise, set the end of the range to the start of the range.
+                start
+            };
+        // Return the <unicode-range-token> with the above start and end.
+        Token::UnicodeRange { start, end }
+    }
+
+    fn consume_hex_digits(&mut self, max: usize) -> String {
+        let mut digits = String::new();
+        while digits.len() < max
+            && let Some(c) = self.peek(0).filter(char::is_ascii_hexdigit)
+        {
+            digits.push(c);
+            self.index += 1;
+        }
+        digits
+    }
+
+    // https://drafts.csswg.org/css-syntax-3/#consume-escaped-code-point
+    // the U+005C REVERSE SOLIDUS (\) is already consumed
+    fn consume_an_escaped_code_point(&mut self) -> char {
+        // Consume the next input code point.
+        match self.consume_the_next_input_code_point() {
+            // hex digit: Consume as many hex digits as possible, but no more than 5. Note that this means 1-6 hex digits have been consumed in total.
+            Some(c) if c.is_ascii_hexdigit() => {
+                let digits = format!("{c}{}", self.consume_hex_digits(5));
+                // If the next input code point is whitespace, consume it as well.
+                if self.peek(0).is_some_and(is_whitespace) {
+                    self.index += 1;
+                }
+                // Interpret the hex digits as a hexadecimal number. If this number is zero, or is for a surrogate, or is greater than the maximum allowed code point, return U+FFFD REPLACEMENT CHARACTER (�).
+                // Otherwise, return the code point with that value.
+                let code = u32::from_str_radix(&digits, 16).unwrap_or(0);
+                match char::from_u32(code) {
+                    Some(c) if code != 0 && code <= MAXIMUM_ALLOWED_CODE_POINT => c,
+                    _ => '\u{FFFD}',
+                }
+            }
+            // EOF: This is a parse error. Return U+FFFD REPLACEMENT CHARACTER (�).
+            None => {
+                self.reconsume_the_current_input_code_point();
+                self.parse_error("unexpected-eof-in-escape");
+                '\u{FFFD}'
+            }
+            // anything else: Return the current input code point.
+            Some(c) => c,
+        }
+    }
+
+    // The algorithms that check code points look at the code points from the offset on, an offset of -1 is the current input code point.
+    fn code_point_at(&self, offset: isize) -> Option<char> {
+        self.index
+            .checked_add_signed(offset)
+            .and_then(|index| self.chars.get(index).copied())
+    }
+
+    // https://drafts.csswg.org/css-syntax-3/#starts-with-a-valid-escape
+    fn are_a_valid_escape(&self, offset: isize) -> bool {
+        // If the first code point is not U+005C REVERSE SOLIDUS (\), return false.
+        // Otherwise, if the second code point is a newline, return false.
+        // Otherwise, return true.
+        self.code_point_at(offset) == Some('\\')
+            && self
+                .code_point_at(offset + 1)
+                .is_none_or(|c| !is_newline(c))
+    }
+
+    // https://drafts.csswg.org/css-syntax-3/#would-start-an-identifier
+    fn would_start_an_ident_sequence(&self, offset: isize) -> bool {
+        // Look at the first code point:
+        match self.code_point_at(offset) {
+            // U+002D HYPHEN-MINUS: If the second code point is an ident-start code point or a U+002D HYPHEN-MINUS, or the second and third code points are a valid escape, return true. Otherwise, return false.
+            Some('-') => {
+                self.code_point_at(offset + 1)
+                    .is_some_and(|c| is_ident_start_code_point(c) || c == '-')
+                    || self.are_a_valid_escape(offset + 1)
+            }
+            // ident-start code point: Return true.
+            Some(c) if is_ident_start_code_point(c) => true,
+            // U+005C REVERSE SOLIDUS (\): If the first and second code points are a valid escape, return true. Otherwise, return false.
+            Some('\\') => self.are_a_valid_escape(offset),
+            // anything else: Return false.
+            _ => false,
+        }
+    }
+
+    // https://drafts.csswg.org/css-syntax-3/#starts-with-a-number
+    fn would_start_a_number(&self, offset: isize) -> bool {
+        let is_digit = |c: Option<char>| c.is_some_and(|c| c.is_ascii_digit());
+        // Look at the first code point:
+        match self.code_point_at(offset) {
+            // U+002B PLUS SIGN (+), U+002D HYPHEN-MINUS (-): If the second code point is a digit, return true.
+            // Otherwise, if the second code point is a U+002E FULL STOP (.) and the third code point is a digit, return true.
+            // Otherwise, return false.
+            Some('+' | '-') => {
+                is_digit(self.code_point_at(offset + 1))
+                    || (self.code_point_at(offset + 1) == Some('.')
+                        && is_digit(self.code_point_at(offset + 2)))
+            }
+            // U+002E FULL STOP (.): If the second code point is a digit, return true. Otherwise, return false.
+            Some('.') => is_digit(self.code_point_at(offset + 1)),
+            // digit: Return true.
+            Some(c) if c.is_ascii_digit() => true,
+            // anything else: Return false.
+            _ => false,
+        }
+    }
+
+    // https://drafts.csswg.org/css-syntax-3/#consume-name
+    fn consume_an_ident_sequence(&mut self) -> String {
+        // Let result initially be an empty string.
+        let mut result = String::new();
+        // Repeatedly consume the next input code point from the stream:
+        loop {
+            match self.consume_the_next_input_code_point() {
+                // ident code point: Append the code point to result.
+                Some(c) if is_ident_code_point(c) => result.push(c),
+                // the stream starts with a valid escape: Consume an escaped code point. Append the returned code point to result.
+                Some('\\') if self.are_a_valid_escape(-1) => {
+                    result.push(self.consume_an_escaped_code_point())
+                }
+                // anything else: Reconsume the current input code point. Return result.
+                _ => {
+                    self.reconsume_the_current_input_code_point();
+                    return result;
+                }
+            }
+        }
+    }
+
+    // https://drafts.csswg.org/css-syntax-3/#consume-number
+    fn consume_a_number(&mut self) -> (f64, NumberType) {
+        // 1. Let type be the string "integer". Let number part and exponent part be the empty string.
+        let mut type_flag = NumberType::Integer;
+        let start = self.index;
+        let consume_digits = |tokenizer: &mut Self| {
+            while tokenizer.peek(0).is_some_and(|c| c.is_ascii_digit()) {
+                tokenizer.index += 1;
+            }
+        };
+        // 2. If the next input code point is U+002B PLUS SIGN (+) or U+002D HYPHEN-MINUS (-), consume it and append it to number part.
+        if matches!(self.peek(0), Some('+' | '-')) {
+            self.index += 1;
+        }
+        // 3. While the next input code point is a digit, consume it and append it to number part.
+        consume_digits(self);
+        // 4. If the next 2 input code points are U+002E FULL STOP (.) followed by a digit, then:
+        if self.peek(0) == Some('.') && self.peek(1).is_some_and(|c| c.is_ascii_digit()) {
+            // 1. Consume the next input code point and append it to number part.
+            // 2. While the next input code point is a digit, consume it and append it to number part.
+            // 3. Set type to "number".
+            self.index += 1;
+            consume_digits(self);
+            type_flag = NumberType::Number;
+        }
+        // 5. If the next 2 or 3 input code points are U+0045 LATIN CAPITAL LETTER E (E) or U+0065 LATIN SMALL LETTER E (e), optionally followed by U+002D HYPHEN-MINUS (-) or U+002B PLUS SIGN (+), followed by a digit, then:
+        let sign = usize::from(matches!(self.peek(1), Some('+' | '-')));
+        if matches!(self.peek(0), Some('E' | 'e'))
+            && self.peek(1 + sign).is_some_and(|c| c.is_ascii_digit())
+        {
+            // 1. Consume the next input code point.
+            // 2. If the next input code point is "+" or "-", consume it and append it to exponent part.
+            // 3. While the next input code point is a digit, consume it and append it to exponent part.
+            // 4. Set type to "number".
+            self.index += 1 + sign;
+            consume_digits(self);
+            type_flag = NumberType::Number;
+        }
+        // 6. Let value be the result of interpreting number part as a base-10 number.
+        // 7. If exponent part is non-empty, interpret it as a base-10 integer, then raise 10 to the power of the result, multiply it by value, and set value to that result.
+        // the consumed code points are a valid Rust float, which parses them exactly rounded
+        let repr: String = self.chars[start..self.index].iter().collect();
+        let value = repr.parse().unwrap_or(0.0);
+        // 8. Return value and type.
+        (value, type_flag)
+    }
+
+    // https://drafts.csswg.org/css-syntax-3/#consume-remnants-of-bad-url
+    fn consume_the_remnants_of_a_bad_url(&mut self) {
+        // Repeatedly consume the next input code point from the stream:
+        loop {
+            match self.consume_the_next_input_code_point() {
+                // U+0029 RIGHT PARENTHESIS ()), EOF: Return.
+                Some(')') => return,
+                None => {
+                    self.reconsume_the_current_input_code_point();
+                    return;
+                }
+                // the input stream starts with a valid escape: Consume an escaped code point. This allows an escaped right parenthesis ("\)") to be encountered without ending the <bad-url-token>.
+                Some('\\') if self.are_a_valid_escape(-1) => {
+                    self.consume_an_escaped_code_point();
+                }
+                // anything else: Do nothing.
+                Some(_) => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::css::jsonc;
+
+    fn strings(value: Option<&jsonc::Value>) -> Vec<&str> {
+        value
+            .and_then(|value| value.as_array())
+            .unwrap()
+            .iter()
+            .map(|value| value.as_str().unwrap())
+            .collect()
+    }
+
+    // the tests of rust.jsonc replace the tests of the shared files with the same input
+    const RUST_TESTS: &str = "css-tests/tokenizer/rust.jsonc";
+
+    fn run_test_file(file_path: &str) {
+        let replaced = jsonc::parse_file(RUST_TESTS);
+        let replaced: Vec<&str> = replaced
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|test| test.get("Input").and_then(|input| input.as_str()))
+            .collect();
+        let tests = jsonc::parse_file(file_path);
+        for (index, test) in tests.as_array().unwrap().iter().enumerate() {
+            let input = test.get("Input").and_then(|input| input.as_str()).unwrap();
+            if file_path != RUST_TESTS && replaced.contains(&input) {
+                continue;
+            }
+            let (tokens, errors) = tokenize(input);
+            let tokens: Vec<String> = tokens.iter().map(|token| token.to_string()).collect();
+            let errors: Vec<&str> = errors.iter().map(|error| error.error).collect();
+            assert_eq!(
+                tokens,
+                strings(test.get("Tokens")),
+                "{file_path} {index}: |{input}|"
+            );
+            assert_eq!(
+                errors,
+                strings(test.get("Errors")),
+                "{file_path} {index}: |{input}|"
+            );
+        }
+    }
+
+    #[test]
+    fn tokenizer_test1() {
+        run_test_file("css-tests/tokenizer/test1.jsonc");
+    }
+
+    #[test]
+    fn tokenizer_test2() {
+        run_test_file("css-tests/tokenizer/test2.jsonc");
+    }
+
+    #[test]
+    fn tokenizer_rust() {
+        run_test_file(RUST_TESTS);
+    }
+}
@@ -0,0 +1,228 @@
+pub mod static_atoms;
+
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    fmt,
+    hash::{BuildHasherDefault, Hasher},
+    ops::Deref,
+    sync::{Arc, OnceLock},
+};
+
+use self::static_atoms::STATIC_ATOMS;
+
+// A local name, an attribute name or a tag name.
+// The names of HTML, SVG and MathML are interned at compile time, atom!("div") is the constant of such a name, also in a match pattern.
+// Comparing two of them compares two integers.
+// Other names are owned by the atom and dropped with its last clone.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Atom(Repr);
+
+// a name is either static or dynamic, never both, so the derived comparisons compare names
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum Repr {
+    // the index in STATIC_ATOMS
+    Static(u32),
+    Dynamic(Arc<str>),
+}
+
+// FNV-1a, the names are short, so a simple hash beats the default SipHash
+#[derive(Default)]
+struct FnvHasher(u64);
+
+impl Hasher for FnvHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        let mut hash = if self.0 == 0 {
+            0xcbf29ce484222325
+        } else {
+            self.0
+        };
+        for byte in bytes {
+            hash ^= *byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+        self.0 = hash;
+    }
+}
+
+// the ids of the static atoms by name, built on first use
+fn static_ids() -> &'static HashMap<&'static str, u32, BuildHasherDefault<FnvHasher>> {
+    static IDS: OnceLock<HashMap<&'static str, u32, BuildHasherDefault<FnvHasher>>> =
+        OnceLock::new();
+    IDS.get_or_init(|| {
+        STATIC_ATOMS
+            .iter()
+            .enumerate()
+            .map(|(id, name)| (*name, id as u32))
+            .collect()
+    })
+}
+
+impl Atom {
+    const fn new_static(id: u32) -> Self {
+        Atom(Repr::Static(id))
+    }
+
+    pub fn as_str(&self) -> &str {
+        match &self.0 {
+            Repr::Static(id) => STATIC_ATOMS[*id as usize],
+            Repr::Dynamic(name) => name,
+        }
+    }
+
+    // whether the name was interned at compile time
+    pub fn is_static(&self) -> bool {
+        matches!(self.0, Repr::Static(_))
+    }
+}
+
+impl From<&str> for Atom {
+    fn from(name: &str) -> Self {
+        match static_ids().get(name) {
+            Some(&id) => Atom::new_static(id),
+            None => Atom(Repr::Dynamic(name.into())),
+        }
+    }
+}
+
+impl From<String> for Atom {
+    fn from(name: String) -> Self {
+        match static_ids().get(name.as_str()) {
+            Some(&id) => Atom::new_static(id),
+            None => Atom(Repr::Dynamic(name.into())),
+        }
+    }
+}
+
+impl Default for Atom {
+    fn default() -> Self {
+        crate::atom!("")
+    }
+}
+
+impl Deref for Atom {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for Atom {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl PartialEq<str> for Atom {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Atom {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<String> for Atom {
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<Atom> for str {
+    fn eq(&self, other: &Atom) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl PartialEq<Atom> for &str {
+    fn eq(&self, other: &Atom) -> bool {
+        *self == other.as_str()
+    }
+}
+
+// atoms sort like their names
+impl Ord for Atom {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if self == other {
+            return Ordering::Equal;
+        }
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl PartialOrd for Atom {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Atom {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl fmt::Debug for Atom {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::atom;
+
+    #[test]
+    fn static_atoms() {
+        assert!(STATIC_ATOMS.is_sorted());
+        assert_eq!(Atom::from("div"), atom!("div"));
+        assert_eq!(atom!("foreignObject").as_str(), "foreignObject");
+        assert_ne!(atom!("foreignObject"), atom!("foreignobject"));
+        assert!(atom!("annotation-xml").is_static());
+        assert_eq!(Atom::default(), atom!(""));
+        assert!(matches!(Atom::from("h2"), atom!("h1") | atom!("h2")));
+        // the generated table has the names of static_atoms.txt, see examples/static_atoms.rs
+        let mut names: Vec<&str> = include_str!("static_atoms.txt").lines().collect();
+        names.push("");
+        names.sort();
+        names.dedup();
+        assert_eq!(STATIC_ATOMS[..], names);
+    }
+
+    #[test]
+    fn dynamic_atoms() {
+        let atom = Atom::from("my-custom-element");
+        assert!(!atom.is_static());
+        assert_eq!(atom, Atom::from(String::from("my-custom-element")));
+        assert_ne!(atom, Atom::from("my-other-element"));
+        assert_eq!(atom.as_str(), "my-custom-element");
+        assert_eq!(Atom::from(String::from("div")), atom!("div"));
+        assert!(Atom::from(String::from("div")).is_static());
+        assert_eq!(atom, "my-custom-element");
+        assert_eq!(
+            format!("{atom} {atom:?}"),
+            r#"my-custom-element "my-custom-element""#
+        );
+    }
+
+    #[test]
+    fn atoms_sort_like_their_names() {
+        let mut atoms = [
+            Atom::from("zzz-element"),
+            atom!("div"),
+            Atom::from("aaa-element"),
+            atom!("p"),
+        ];
+        atoms.sort();
+        assert_eq!(atoms, ["aaa-element", "div", "p", "zzz-element"]);
+    }
+}
@@ -0,0 +1,3303 @@
+// The names interned at compile time: the HTML elements and attributes, and the SVG and MathML names of the tree construction.
+// Sorted by name, the index of a name is the id of its atom.
+// Generated by examples/static_atoms.rs from static_atoms.txt, add a name there and run: cargo run --example static_atoms > src/html/atom/static_atoms.rs
+use super::Atom;
+
+pub(crate) static STATIC_ATOMS: [&str; 658] = [
+    "",
+    "a",
+    "abbr",
+    "accept",
+    "accept-charset",
+    "accesskey",
+    "acronym",
+    "action",
+    "actuate",
+    "address",
+    "align",
+    "alink",
+    "allow",
+    "allowfullscreen",
+    "alt",
+    "altGlyph",
+    "altGlyphDef",
+    "altGlyphItem",
+    "altglyph",
+    "altglyphdef",
+    "altglyphitem",
+    "animate",
+    "animateColor",
+    "animateMotion",
+    "animateTransform",
+    "animatecolor",
+    "animatemotion",
+    "animatetransform",
+    "annotation",
+    "annotation-xml",
+    "applet",
+    "archive",
+    "arcrole",
+    "area",
+    "article",
+    "aside",
+    "async",
+    "attributeName",
+    "attributeType",
+    "attributename",
+    "attributetype",
+    "audio",
+    "autocapitalize",
+    "autocomplete",
+    "autocorrect",
+    "autofocus",
+    "autoplay",
+    "axis",
+    "b",
+    "background",
+    "base",
+    "baseFrequency",
+    "baseProfile",
+    "basefont",
+    "basefrequency",
+    "baseprofile",
+    "bdi",
+    "bdo",
+    "bgcolor",
+    "bgsound",
+    "big",
+    "blink",
+    "blocking",
+    "blockquote",
+    "body",
+    "border",
+    "br",
+    "button",
+    "calcMode",
+    "calcmode",
+    "canvas",
+    "caption",
+    "cellpadding",
+    "cellspacing",
+    "center",
+    "char",
+    "charoff",
+    "charset",
+    "checked",
+    "circle",
+    "cite",
+    "class",
+    "classid",
+    "clear",
+    "clipPath",
+    "clipPathUnits",
+    "clippath",
+    "clippathunits",
+    "code",
+    "codebase",
+    "codetype",
+    "col",
+    "colgroup",
+    "color",
+    "cols",
+    "colspan",
+    "compact",
+    "content",
+    "contenteditable",
+    "controls",
+    "coords",
+    "crossorigin",
+    "cx",
+    "cy",
+    "d",
+    "data",
+    "datalist",
+    "datetime",
+    "dd",
+    "declare",
+    "decoding",
+    "default",
+    "defer",
+    "definitionURL",
+    "definitionurl",
+    "defs",
+    "del",
+    "desc",
+    "details",
+    "dfn",
+    "dialog",
+    "diffuseConstant",
+    "diffuseconstant",
+    "dir",
+    "dirname",
+    "disabled",
+    "div",
+    "dl",
+    "download",
+    "draggable",
+    "dt",
+    "edgeMode",
+    "edgemode",
+    "ellipse",
+    "em",
+    "embed",
+    "encoding",
+    "enctype",
+    "enterkeyhint",
+    "face",
+    "feBlend",
+    "feColorMatrix",
+    "feComponentTransfer",
+    "feComposite",
+    "feConvolveMatrix",
+    "feDiffuseLighting",
+    "feDisplacementMap",
+    "feDistantLight",
+    "feDropShadow",
+    "feFlood",
+    "feFuncA",
+    "feFuncB",
+    "feFuncG",
+    "feFuncR",
+    "feGaussianBlur",
+    "feImage",
+    "feMerge",
+    "feMergeNode",
+    "feMorphology",
+    "feOffset",
+    "fePointLight",
+    "feSpecularLighting",
+    "feSpotLight",
+    "feTile",
+    "feTurbulence",
+    "feblend",
+    "fecolormatrix",
+    "fecomponenttransfer",
+    "fecomposite",
+    "feconvolvematrix",
+    "fediffuselighting",
+    "fedisplacementmap",
+    "fedistantlight",
+    "fedropshadow",
+    "feflood",
+    "fefunca",
+    "fefuncb",
+    "fefuncg",
+    "fefuncr",
+    "fegaussianblur",
+    "feimage",
+    "femerge",
+    "femergenode",
+    "femorphology",
+    "feoffset",
+    "fepointlight",
+    "fespecularlighting",
+    "fespotlight",
+    "fetchpriority",
+    "fetile",
+    "feturbulence",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "fill",
+    "filter",
+    "filterUnits",
+    "filterunits",
+    "font",
+    "footer",
+    "for",
+    "foreignObject",
+    "foreignobject",
+    "form",
+    "formaction",
+    "formenctype",
+    "formmethod",
+    "formnovalidate",
+    "formtarget",
+    "frame",
+    "frameborder",
+    "frameset",
+    "g",
+    "glyphRef",
+    "glyphref",
+    "gradientTransform",
+    "gradientUnits",
+    "gradienttransform",
+    "gradientunits",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "head",
+    "header",
+    "headers",
+    "height",
+    "hgroup",
+    "hidden",
+    "high",
+    "hr",
+    "href",
+    "hreflang",
+    "hspace",
+    "html",
+    "http-equiv",
+    "i",
+    "id",
+    "iframe",
+    "image",
+    "img",
+    "inert",
+    "input",
+    "inputmode",
+    "ins",
+    "integrity",
+    "is",
+    "isindex",
+    "ismap",
+    "itemid",
+    "itemprop",
+    "itemref",
+    "itemscope",
+    "itemtype",
+    "kbd",
+    "kernelMatrix",
+    "kernelUnitLength",
+    "kernelmatrix",
+    "kernelunitlength",
+    "keyPoints",
+    "keySplines",
+    "keyTimes",
+    "keygen",
+    "keypoints",
+    "keysplines",
+    "keytimes",
+    "kind",
+    "label",
+    "lang",
+    "language",
+    "legend",
+    "lengthAdjust",
+    "lengthadjust",
+    "li",
+    "limitingConeAngle",
+    "limitingconeangle",
+    "line",
+    "linearGradient",
+    "lineargradient",
+    "link",
+    "list",
+    "listing",
+    "loading",
+    "longdesc",
+    "loop",
+    "low",
+    "maction",
+    "main",
+    "malignmark",
+    "map",
+    "marginheight",
+    "marginwidth",
+    "mark",
+    "marker",
+    "markerHeight",
+    "markerUnits",
+    "markerWidth",
+    "markerheight",
+    "markerunits",
+    "markerwidth",
+    "marquee",
+    "mask",
+    "maskContentUnits",
+    "maskUnits",
+    "maskcontentunits",
+    "maskunits",
+    "math",
+    "max",
+    "maxlength",
+    "media",
+    "menu",
+    "menuitem",
+    "merror",
+    "meta",
+    "metadata",
+    "meter",
+    "method",
+    "mfrac",
+    "mglyph",
+    "mi",
+    "min",
+    "minlength",
+    "mmultiscripts",
+    "mn",
+    "mo",
+    "mover",
+    "mpadded",
+    "mphantom",
+    "mprescripts",
+    "mroot",
+    "mrow",
+    "ms",
+    "mspace",
+    "msqrt",
+    "mstyle",
+    "msub",
+    "msubsup",
+    "msup",
+    "mtable",
+    "mtd",
+    "mtext",
+    "mtr",
+    "multicol",
+    "multiple",
+    "munder",
+    "munderover",
+    "muted",
+    "name",
+    "nav",
+    "nextid",
+    "nobr",
+    "noembed",
+    "noframes",
+    "nohref",
+    "none",
+    "noresize",
+    "noscript",
+    "noshade",
+    "novalidate",
+    "nowrap",
+    "numOctaves",
+    "numoctaves",
+    "object",
+    "ol",
+    "onabort",
+    "onafterprint",
+    "onauxclick",
+    "onbeforeprint",
+    "onbeforeunload",
+    "onblur",
+    "oncancel",
+    "oncanplay",
+    "oncanplaythrough",
+    "onchange",
+    "onclick",
+    "onclose",
+    "oncontextmenu",
+    "oncopy",
+    "oncuechange",
+    "oncut",
+    "ondblclick",
+    "ondrag",
+    "ondragend",
+    "ondragenter",
+    "ondragleave",
+    "ondragover",
+    "ondragstart",
+    "ondrop",
+    "ondurationchange",
+    "onemptied",
+    "onended",
+    "onerror",
+    "onfocus",
+    "onhashchange",
+    "oninput",
+    "oninvalid",
+    "onkeydown",
+    "onkeypress",
+    "onkeyup",
+    "onload",
+    "onloadeddata",
+    "onloadedmetadata",
+    "onloadstart",
+    "onmessage",
+    "onmousedown",
+    "onmouseenter",
+    "onmouseleave",
+    "onmousemove",
+    "onmouseout",
+    "onmouseover",
+    "onmouseup",
+    "onoffline",
+    "ononline",
+    "onpagehide",
+    "onpageshow",
+    "onpaste",
+    "onpause",
+    "onplay",
+    "onplaying",
+    "onpopstate",
+    "onprogress",
+    "onratechange",
+    "onreset",
+    "onresize",
+    "onscroll",
+    "onsecuritypolicyviolation",
+    "onseeked",
+    "onseeking",
+    "onselect",
+    "onstalled",
+    "onstorage",
+    "onsubmit",
+    "onsuspend",
+    "ontimeupdate",
+    "ontoggle",
+    "onunload",
+    "onvolumechange",
+    "onwaiting",
+    "onwheel",
+    "open",
+    "optgroup",
+    "optimum",
+    "option",
+    "output",
+    "p",
+    "param",
+    "path",
+    "pathLength",
+    "pathlength",
+    "pattern",
+    "patternContentUnits",
+    "patternTransform",
+    "patternUnits",
+    "patterncontentunits",
+    "patterntransform",
+    "patternunits",
+    "picture",
+    "ping",
+    "placeholder",
+    "plaintext",
+    "playsinline",
+    "points",
+    "pointsAtX",
+    "pointsAtY",
+    "pointsAtZ",
+    "pointsatx",
+    "pointsaty",
+    "pointsatz",
+    "polygon",
+    "polyline",
+    "popover",
+    "popovertarget",
+    "popovertargetaction",
+    "poster",
+    "pre",
+    "preload",
+    "preserveAlpha",
+    "preserveAspectRatio",
+    "preservealpha",
+    "preserveaspectratio",
+    "primitiveUnits",
+    "primitiveunits",
+    "profile",
+    "progress",
+    "prompt",
+    "q",
+    "r",
+    "radialGradient",
+    "radialgradient",
+    "rb",
+    "readonly",
+    "rect",
+    "refX",
+    "refY",
+    "referrerpolicy",
+    "refx",
+    "refy",
+    "rel",
+    "repeatCount",
+    "repeatDur",
+    "repeatcount",
+    "repeatdur",
+    "required",
+    "requiredExtensions",
+    "requiredFeatures",
+    "requiredextensions",
+    "requiredfeatures",
+    "rev",
+    "reversed",
+    "role",
+    "rows",
+    "rowspan",
+    "rp",
+    "rt",
+    "rtc",
+    "ruby",
+    "rules",
+    "rx",
+    "ry",
+    "s",
+    "samp",
+    "sandbox",
+    "scheme",
+    "scope",
+    "script",
+    "scrolling",
+    "search",
+    "section",
+    "select",
+    "selected",
+    "semantics",
+    "set",
+    "shadowrootclonable",
+    "shadowrootdelegatesfocus",
+    "shadowrootmode",
+    "shape",
+    "show",
+    "size",
+    "sizes",
+    "slot",
+    "small",
+    "source",
+    "space",
+    "spacer",
+    "span",
+    "specularConstant",
+    "specularExponent",
+    "specularconstant",
+    "specularexponent",
+    "spellcheck",
+    "spreadMethod",
+    "spreadmethod",
+    "src",
+    "srcdoc",
+    "srclang",
+    "srcset",
+    "standby",
+    "start",
+    "startOffset",
+    "startoffset",
+    "stdDeviation",
+    "stddeviation",
+    "step",
+    "stitchTiles",
+    "stitchtiles",
+    "stop",
+    "strike",
+    "stroke",
+    "strong",
+    "style",
+    "sub",
+    "summary",
+    "sup",
+    "surfaceScale",
+    "surfacescale",
+    "svg",
+    "switch",
+    "symbol",
+    "systemLanguage",
+    "systemlanguage",
+    "tabindex",
+    "table",
+    "tableValues",
+    "tablevalues",
+    "target",
+    "targetX",
+    "targetY",
+    "targetx",
+    "targety",
+    "tbody",
+    "td",
+    "template",
+    "text",
+    "textLength",
+    "textPath",
+    "textarea",
+    "textlength",
+    "textpath",
+    "tfoot",
+    "th",
+    "thead",
+    "time",
+    "title",
+    "tr",
+    "track",
+    "transform",
+    "translate",
+    "tspan",
+    "tt",
+    "type",
+    "u",
+    "ul",
+    "use",
+    "usemap",
+    "valign",
+    "value",
+    "valuetype",
+    "var",
+    "version",
+    "video",
+    "view",
+    "viewBox",
+    "viewTarget",
+    "viewbox",
+    "viewtarget",
+    "vlink",
+    "vspace",
+    "wbr",
+    "width",
+    "wrap",
+    "writingsuggestions",
+    "x",
+    "x1",
+    "x2",
+    "xChannelSelector",
+    "xchannelselector",
+    "xlink",
+    "xlink:actuate",
+    "xlink:arcrole",
+    "xlink:href",
+    "xlink:role",
+    "xlink:show",
+    "xlink:title",
+    "xlink:type",
+    "xml:lang",
+    "xml:space",
+    "xmlns",
+    "xmlns:xlink",
+    "xmp",
+    "y",
+    "y1",
+    "y2",
+    "yChannelSelector",
+    "ychannelselector",
+    "zoomAndPan",
+    "zoomandpan",
+];
+
+pub const EMPTY: Atom = Atom::new_static(0);
+pub const A: Atom = Atom::new_static(1);
+pub const ABBR: Atom = Atom::new_static(2);
+pub const ACCEPT: Atom = Atom::new_static(3);
+pub const ACCEPT_CHARSET: Atom = Atom::new_static(4);
+pub const ACCESSKEY: Atom = Atom::new_static(5);
+pub const ACRONYM: Atom = Atom::new_static(6);
+pub const ACTION: Atom = Atom::new_static(7);
+pub const ACTUATE: Atom = Atom::new_static(8);
+pub const ADDRESS: Atom = Atom::new_static(9);
+pub const ALIGN: Atom = Atom::new_static(10);
+pub const ALINK: Atom = Atom::new_static(11);
+pub const ALLOW: Atom = Atom::new_static(12);
+pub const ALLOWFULLSCREEN: Atom = Atom::new_static(13);
+pub const ALT: Atom = Atom::new_static(14);
+pub const ALT_GLYPH: Atom = Atom::new_static(15);
+pub const ALT_GLYPH_DEF: Atom = Atom::new_static(16);
+pub const ALT_GLYPH_ITEM: Atom = Atom::new_static(17);
+pub const ALTGLYPH: Atom = Atom::new_static(18);
+pub const ALTGLYPHDEF: Atom = Atom::new_static(19);
+pub const ALTGLYPHITEM: Atom = Atom::new_static(20);
+pub const ANIMATE: Atom = Atom::new_static(21);
+pub const ANIMATE_COLOR: Atom = Atom::new_static(22);
+pub const ANIMATE_MOTION: Atom = Atom::new_static(23);
+pub const ANIMATE_TRANSFORM: Atom = Atom::new_static(24);
+pub const ANIMATECOLOR: Atom = Atom::new_static(25);
+pub const ANIMATEMOTION: Atom = Atom::new_static(26);
+pub const ANIMATETRANSFORM: Atom = Atom::new_static(27);
+pub const ANNOTATION: Atom = Atom::new_static(28);
+pub const ANNOTATION_XML: Atom = Atom::new_static(29);
+pub const APPLET: Atom = Atom::new_static(30);
+pub const ARCHIVE: Atom = Atom::new_static(31);
+pub const ARCROLE: Atom = Atom::new_static(32);
+pub const AREA: Atom = Atom::new_static(33);
+pub const ARTICLE: Atom = Atom::new_static(34);
+pub const ASIDE: Atom = Atom::new_static(35);
+pub const ASYNC: Atom = Atom::new_static(36);
+pub const ATTRIBUTE_NAME: Atom = Atom::new_static(37);
+pub const ATTRIBUTE_TYPE: Atom = Atom::new_static(38);
+pub const ATTRIBUTENAME: Atom = Atom::new_static(39);
+pub const ATTRIBUTETYPE: Atom = Atom::new_static(40);
+pub const AUDIO: Atom = Atom::new_static(41);
+pub const AUTOCAPITALIZE: Atom = Atom::new_static(42);
+pub const AUTOCOMPLETE: Atom = Atom::new_static(43);
+pub const AUTOCORRECT: Atom = Atom::new_static(44);
+pub const AUTOFOCUS: Atom = Atom::new_static(45);
+pub const AUTOPLAY: Atom = Atom::new_static(46);
+pub const AXIS: Atom = Atom::new_static(47);
+pub const B: Atom = Atom::new_static(48);
+pub const BACKGROUND: Atom = Atom::new_static(49);
+pub const BASE: Atom = Atom::new_static(50);
+pub const BASE_FREQUENCY: Atom = Atom::new_static(51);
+pub const BASE_PROFILE: Atom = Atom::new_static(52);
+pub const BASEFONT: Atom = Atom::new_static(53);
+pub const BASEFREQUENCY: Atom = Atom::new_static(54);
+pub const BASEPROFILE: Atom = Atom::new_static(55);
+pub const BDI: Atom = Atom::new_static(56);
+pub const BDO: Atom = Atom::new_static(57);
+pub const BGCOLOR: Atom = Atom::new_static(58);
+pub const BGSOUND: Atom = Atom::new_static(59);
+pub const BIG: Atom = Atom::new_static(60);
+pub const BLINK: Atom = Atom::new_static(61);
+pub const BLOCKING: Atom = Atom::new_static(62);
+pub const BLOCKQUOTE: Atom = Atom::new_static(63);
+pub const BODY: Atom = Atom::new_static(64);
+pub const BORDER: Atom = Atom::new_static(65);
+pub const BR: Atom = Atom::new_static(66);
+pub const BUTTON: Atom = Atom::new_static(67);
+pub const CALC_MODE: Atom = Atom::new_static(68);
+pub const CALCMODE: Atom = Atom::new_static(69);
+pub const CANVAS: Atom = Atom::new_static(70);
+pub const CAPTION: Atom = Atom::new_static(71);
+pub const CELLPADDING: Atom = Atom::new_static(72);
+pub const CELLSPACING: Atom = Atom::new_static(73);
+pub const CENTER: Atom = Atom::new_static(74);
+pub const CHAR: Atom = Atom::new_static(75);
+pub const CHAROFF: Atom = Atom::new_static(76);
+pub const CHARSET: Atom = Atom::new_static(77);
+pub const CHECKED: Atom = Atom::new_static(78);
+pub const CIRCLE: Atom = Atom::new_static(79);
+pub const CITE: Atom = Atom::new_static(80);
+pub const CLASS: Atom = Atom::new_static(81);
+pub const CLASSID: Atom = Atom::new_static(82);
+pub const CLEAR: Atom = Atom::new_static(83);
+pub const CLIP_PATH: Atom = Atom::new_static(84);
+pub const CLIP_PATH_UNITS: Atom = Atom::new_static(85);
+pub const CLIPPATH: Atom = Atom::new_static(86);
+pub const CLIPPATHUNITS: Atom = Atom::new_static(87);
+pub const CODE: Atom = Atom::new_static(88);
+pub const CODEBASE: Atom = Atom::new_static(89);
+pub const CODETYPE: Atom = Atom::new_static(90);
+pub const COL: Atom = Atom::new_static(91);
+pub const COLGROUP: Atom = Atom::new_static(92);
+pub const COLOR: Atom = Atom::new_static(93);
+pub const COLS: Atom = Atom::new_static(94);
+pub const COLSPAN: Atom = Atom::new_static(95);
+pub const COMPACT: Atom = Atom::new_static(96);
+pub const CONTENT: Atom = Atom::new_static(97);
+pub const CONTENTEDITABLE: Atom = Atom::new_static(98);
+pub const CONTROLS: Atom = Atom::new_static(99);
+pub const COORDS: Atom = Atom::new_static(100);
+pub const CROSSORIGIN: Atom = Atom::new_static(101);
+pub const CX: Atom = Atom::new_static(102);
+pub const CY: Atom = Atom::new_static(103);
+pub const D: Atom = Atom::new_static(104);
+pub const DATA: Atom = Atom::new_static(105);
+pub const DATALIST: Atom = Atom::new_static(106);
+pub const DATETIME: Atom = Atom::new_static(107);
+pub const DD: Atom = Atom::new_static(108);
+pub const DECLARE: Atom = Atom::new_static(109);
+pub const DECODING: Atom = Atom::new_static(110);
+pub const DEFAULT: Atom = Atom::new_static(111);
+pub const DEFER: Atom = Atom::new_static(112);
+pub const DEFINITION_URL: Atom = Atom::new_static(113);
+pub const DEFINITIONURL: Atom = Atom::new_static(114);
+pub const DEFS: Atom = Atom::new_static(115);
+pub const DEL: Atom = Atom::new_static(116);
+pub const DESC: Atom = Atom::new_static(117);
+pub const DETAILS: Atom = Atom::new_static(118);
+pub const DFN: Atom = Atom::new_static(119);
+pub const DIALOG: Atom = Atom::new_static(120);
+pub const DIFFUSE_CONSTANT: Atom = Atom::new_static(121);
+pub const DIFFUSECONSTANT: Atom = Atom::new_static(122);
+pub const DIR: Atom = Atom::new_static(123);
+pub const DIRNAME: Atom = Atom::new_static(124);
+pub const DISABLED: Atom = Atom::new_static(125);
+pub const DIV: Atom = Atom::new_static(126);
+pub const DL: Atom = Atom::new_static(127);
+pub const DOWNLOAD: Atom = Atom::new_static(128);
+pub const DRAGGABLE: Atom = Atom::new_static(129);
+pub const DT: Atom = Atom::new_static(130);
+pub const EDGE_MODE: Atom = Atom::new_static(131);
+pub const EDGEMODE: Atom = Atom::new_static(132);
+pub const ELLIPSE: Atom = Atom::new_static(133);
+pub const EM: Atom = Atom::new_static(134);
+pub const EMBED: Atom = Atom::new_static(135);
+pub const ENCODING: Atom = Atom::new_static(136);
+pub const ENCTYPE: Atom = Atom::new_static(137);
+pub const ENTERKEYHINT: Atom = Atom::new_static(138);
+pub const FACE: Atom = Atom::new_static(139);
+pub const FE_BLEND: Atom = Atom::new_static(140);
+pub const FE_COLOR_MATRIX: Atom = Atom::new_static(141);
+pub const FE_COMPONENT_TRANSFER: Atom = Atom::new_static(142);
+pub const FE_COMPOSITE: Atom = Atom::new_static(143);
+pub const FE_CONVOLVE_MATRIX: Atom = Atom::new_static(144);
+pub const FE_DIFFUSE_LIGHTING: Atom = Atom::new_static(145);
+pub const FE_DISPLACEMENT_MAP: Atom = Atom::new_static(146);
+pub const FE_DISTANT_LIGHT: Atom = Atom::new_static(147);
+pub const FE_DROP_SHADOW: Atom = Atom::new_static(148);
+pub const FE_FLOOD: Atom = Atom::new_static(149);
+pub const FE_FUNC_A: Atom = Atom::new_static(150);
+pub const FE_FUNC_B: Atom = Atom::new_static(151);
+pub const FE_FUNC_G: Atom = Atom::new_static(152);
+pub const FE_FUNC_R: Atom = Atom::new_static(153);
+pub const FE_GAUSSIAN_BLUR: Atom = Atom::new_static(154);
+pub const FE_IMAGE: Atom = Atom::new_static(155);
+pub const FE_MERGE: Atom = Atom::new_static(156);
+pub const FE_MERGE_NODE: Atom = Atom::new_static(157);
+pub const FE_MORPHOLOGY: Atom = Atom::new_static(158);
+pub const FE_OFFSET: Atom = Atom::new_static(159);
+pub const FE_POINT_LIGHT: Atom = Atom::new_static(160);
+pub const FE_SPECULAR_LIGHTING: Atom = Atom::new_static(161);
+pub const FE_SPOT_LIGHT: Atom = Atom::new_static(162);
+pub const FE_TILE: Atom = Atom::new_static(163);
+pub const FE_TURBULENCE: Atom = Atom::new_static(164);
+pub const FEBLEND: Atom = Atom::new_static(165);
+pub const FECOLORMATRIX: Atom = Atom::new_static(166);
+pub const FECOMPONENTTRANSFER: Atom = Atom::new_static(167);
+pub const FECOMPOSITE: Atom = Atom::new_static(168);
+pub const FECONVOLVEMATRIX: Atom = Atom::new_static(169);
+pub const FEDIFFUSELIGHTING: Atom = Atom::new_static(170);
+pub const FEDISPLACEMENTMAP: Atom = Atom::new_static(171);
+pub const FEDISTANTLIGHT: Atom = Atom::new_static(172);
+pub const FEDROPSHADOW: Atom = Atom::new_static(173);
+pub const FEFLOOD: Atom = Atom::new_static(174);
+pub const FEFUNCA: Atom = Atom::new_static(175);
+pub const FEFUNCB: Atom = Atom::new_static(176);
+pub const FEFUNCG: Atom = Atom::new_static(177);
+pub const FEFUNCR: Atom = Atom::new_static(178);
+pub const FEGAUSSIANBLUR: Atom = Atom::new_static(179);
+pub const FEIMAGE: Atom = Atom::new_static(180);
+pub const FEMERGE: Atom = Atom::new_static(181);
+pub const FEMERGENODE: Atom = Atom::new_static(182);
+pub const FEMORPHOLOGY: Atom = Atom::new_static(183);
+pub const FEOFFSET: Atom = Atom::new_static(184);
+pub const FEPOINTLIGHT: Atom = Atom::new_static(185);
+pub const FESPECULARLIGHTING: Atom = Atom::new_static(186);
+pub const FESPOTLIGHT: Atom = Atom::new_static(187);
+pub const FETCHPRIORITY: Atom = Atom::new_static(188);
+pub const FETILE: Atom = Atom::new_static(189);
+pub const FETURBULENCE: Atom = Atom::new_static(190);
+pub const FIELDSET: Atom = Atom::new_static(191);
+pub const FIGCAPTION: Atom = Atom::new_static(192);
+pub const FIGURE: Atom = Atom::new_static(193);
+pub const FILL: Atom = Atom::new_static(194);
+pub const FILTER: Atom = Atom::new_static(195);
+pub const FILTER_UNITS: Atom = Atom::new_static(196);
+pub const FILTERUNITS: Atom = Atom::new_static(197);
+pub const FONT: Atom = Atom::new_static(198);
+pub const FOOTER: Atom = Atom::new_static(199);
+pub const FOR: Atom = Atom::new_static(200);
+pub const FOREIGN_OBJECT: Atom = Atom::new_static(201);
+pub const FOREIGNOBJECT: Atom = Atom::new_static(202);
+pub const FORM: Atom = Atom::new_static(203);
+pub const FORMACTION: Atom = Atom::new_static(204);
+pub const FORMENCTYPE: Atom = Atom::new_static(205);
+pub const FORMMETHOD: Atom = Atom::new_static(206);
+pub const FORMNOVALIDATE: Atom = Atom::new_static(207);
+pub const FORMTARGET: Atom = Atom::new_static(208);
+pub const FRAME: Atom = Atom::new_static(209);
+pub const FRAMEBORDER: Atom = Atom::new_static(210);
+pub const FRAMESET: Atom = Atom::new_static(211);
+pub const G: Atom = Atom::new_static(212);
+pub const GLYPH_REF: Atom = Atom::new_static(213);
+pub const GLYPHREF: Atom = Atom::new_static(214);
+pub const GRADIENT_TRANSFORM: Atom = Atom::new_static(215);
+pub const GRADIENT_UNITS: Atom = Atom::new_static(216);
+pub const GRADIENTTRANSFORM: Atom = Atom::new_static(217);
+pub const GRADIENTUNITS: Atom = Atom::new_static(218);
+pub const H1: Atom = Atom::new_static(219);
+pub const H2: Atom = Atom::new_static(220);
+pub const H3: Atom = Atom::new_static(221);
+pub const H4: Atom = Atom::new_static(222);
+pub const H5: Atom = Atom::new_static(223);
+pub const H6: Atom = Atom::new_static(224);
+pub const HEAD: Atom = Atom::new_static(225);
+pub const HEADER: Atom = Atom::new_static(226);
+pub const HEADERS: Atom = Atom::new_static(227);
+pub const HEIGHT: Atom = Atom::new_static(228);
+pub const HGROUP: Atom = Atom::new_static(229);
+pub const HIDDEN: Atom = Atom::new_static(230);
+pub const HIGH: Atom = Atom::new_static(231);
+pub const HR: Atom = Atom::new_static(232);
+pub const HREF: Atom = Atom::new_static(233);
+pub const HREFLANG: Atom = Atom::new_static(234);
+pub const HSPACE: Atom = Atom::new_static(235);
+pub const HTML: Atom = Atom::new_static(236);
+pub const HTTP_EQUIV: Atom = Atom::new_static(237);
+pub const I: Atom = Atom::new_static(238);
+pub const ID: Atom = Atom::new_static(239);
+pub const IFRAME: Atom = Atom::new_static(240);
+pub const IMAGE: Atom = Atom::new_static(241);
+pub const IMG: Atom = Atom::new_static(242);
+pub const INERT: Atom = Atom::new_static(243);
+pub const INPUT: Atom = Atom::new_static(244);
+pub const INPUTMODE: Atom = Atom::new_static(245);
+pub const INS: Atom = Atom::new_static(246);
+pub const INTEGRITY: Atom = Atom::new_static(247);
+pub const IS: Atom = Atom::new_static(248);
+pub const ISINDEX: Atom = Atom::new_static(249);
+pub const ISMAP: Atom = Atom::new_static(250);
+pub const ITEMID: Atom = Atom::new_static(251);
+pub const ITEMPROP: Atom = Atom::new_static(252);
+pub const ITEMREF: Atom = Atom::new_static(253);
+pub const ITEMSCOPE: Atom = Atom::new_static(254);
+pub const ITEMTYPE: Atom = Atom::new_static(255);
+pub const KBD: Atom = Atom::new_static(256);
+pub const KERNEL_MATRIX: Atom = Atom::new_static(257);
+pub const KERNEL_UNIT_LENGTH: Atom = Atom::new_static(258);
+pub const KERNELMATRIX: Atom = Atom::new_static(259);
+pub const KERNELUNITLENGTH: Atom = Atom::new_static(260);
+pub const KEY_POINTS: Atom = Atom::new_static(261);
+pub const KEY_SPLINES: Atom = Atom::new_static(262);
+pub const KEY_TIMES: Atom = Atom::new_static(263);
+pub const KEYGEN: Atom = Atom::new_static(264);
+pub const KEYPOINTS: Atom = Atom::new_static(265);
+pub const KEYSPLINES: Atom = Atom::new_static(266);
+pub const KEYTIMES: Atom = Atom::new_static(267);
+pub const KIND: Atom = Atom::new_static(268);
+pub const LABEL: Atom = Atom::new_static(269);
+pub const LANG: Atom = Atom::new_static(270);
+pub const LANGUAGE: Atom = Atom::new_static(271);
+pub const LEGEND: Atom = Atom::new_static(272);
+pub const LENGTH_ADJUST: Atom = Atom::new_static(273);
+pub const LENGTHADJUST: Atom = Atom::new_static(274);
+pub const LI: Atom = Atom::new_static(275);
+pub const LIMITING_CONE_ANGLE: Atom = Atom::new_static(276);
+pub const LIMITINGCONEANGLE: Atom = Atom::new_static(277);
+pub const LINE: Atom = Atom::new_static(278);
+pub const LINEAR_GRADIENT: Atom = Atom::new_static(279);
+pub const LINEARGRADIENT: Atom = Atom::new_static(280);
+pub const LINK: Atom = Atom::new_static(281);
+pub const LIST: Atom = Atom::new_static(282);
+pub const LISTING: Atom = Atom::new_static(283);
+pub const LOADING: Atom = Atom::new_static(284);
+pub const LONGDESC: Atom = Atom::new_static(285);
+pub const LOOP: Atom = Atom::new_static(286);
+pub const LOW: Atom = Atom::new_static(287);
+pub const MACTION: Atom = Atom::new_static(288);
+pub const MAIN: Atom = Atom::new_static(289);
+pub const MALIGNMARK: Atom = Atom::new_static(290);
+pub const MAP: Atom = Atom::new_static(291);
+pub const MARGINHEIGHT: Atom = Atom::new_static(292);
+pub const MARGINWIDTH: Atom = Atom::new_static(293);
+pub const MARK: Atom = Atom::new_static(294);
+pub const MARKER: Atom = Atom::new_static(295);
+pub const MARKER_HEIGHT: Atom = Atom::new_static(296);
+pub const MARKER_UNITS: Atom = Atom::new_static(297);
+pub const MARKER_WIDTH: Atom = Atom::new_static(298);
+pub const MARKERHEIGHT: Atom = Atom::new_static(299);
+pub const MARKERUNITS: Atom = Atom::new_static(300);
+pub const MARKERWIDTH: Atom = Atom::new_static(301);
+pub const MARQUEE: Atom = Atom::new_static(302);
+pub const MASK: Atom = Atom::new_static(303);
+pub const MASK_CONTENT_UNITS: Atom = Atom::new_static(304);
+pub const MASK_UNITS: Atom = Atom::new_static(305);
+pub const MASKCONTENTUNITS: Atom = Atom::new_static(306);
+pub const MASKUNITS: Atom = Atom::new_static(307);
+pub const MATH: Atom = Atom::new_static(308);
+pub const MAX: Atom = Atom::new_static(309);
+pub const MAXLENGTH: Atom = Atom::new_static(310);
+pub const MEDIA: Atom = Atom::new_static(311);
+pub const MENU: Atom = Atom::new_static(312);
+pub const MENUITEM: Atom = Atom::new_static(313);
+pub const MERROR: Atom = Atom::new_static(314);
+pub const META: Atom = Atom::new_static(315);
+pub const METADATA: Atom = Atom::new_static(316);
+pub const METER: Atom = Atom::new_static(317);
+pub const METHOD: Atom = Atom::new_static(318);
+pub const MFRAC: Atom = Atom::new_static(319);
+pub const MGLYPH: Atom = Atom::new_static(320);
+pub const MI: Atom = Atom::new_static(321);
+pub const MIN: Atom = Atom::new_static(322);
+pub const MINLENGTH: Atom = Atom::new_static(323);
+pub const MMULTISCRIPTS: Atom = Atom::new_static(324);
+pub const MN: Atom = Atom::new_static(325);
+pub const MO: Atom = Atom::new_static(326);
+pub const MOVER: Atom = Atom::new_static(327);
+pub const MPADDED: Atom = Atom::new_static(328);
+pub const MPHANTOM: Atom = Atom::new_static(329);
+pub const MPRESCRIPTS: Atom = Atom::new_static(330);
+pub const MROOT: Atom = Atom::new_static(331);
+pub const MROW: Atom = Atom::new_static(332);
+pub const MS: Atom = Atom::new_static(333);
+pub const MSPACE: Atom = Atom::new_static(334);
+pub const MSQRT: Atom = Atom::new_static(335);
+pub const MSTYLE: Atom = Atom::new_static(336);
+pub const MSUB: Atom = Atom::new_static(337);
+pub const MSUBSUP: Atom = Atom::new_static(338);
+pub const MSUP: Atom = Atom::new_static(339);
+pub const MTABLE: Atom = Atom::new_static(340);
+pub const MTD: Atom = Atom::new_static(341);
+pub const MTEXT: Atom = Atom::new_static(342);
+pub const MTR: Atom = Atom::new_static(343);
+pub const MULTICOL: Atom = Atom::new_static(344);
+pub const MULTIPLE: Atom = Atom::new_static(345);
+pub const MUNDER: Atom = Atom::new_static(346);
+pub const MUNDEROVER: Atom = Atom::new_static(347);
+pub const MUTED: Atom = Atom::new_static(348);
+pub const NAME: Atom = Atom::new_static(349);
+pub const NAV: Atom = Atom::new_static(350);
+pub const NEXTID: Atom = Atom::new_static(351);
+pub const NOBR: Atom = Atom::new_static(352);
+pub const NOEMBED: Atom = Atom::new_static(353);
+pub const NOFRAMES: Atom = Atom::new_static(354);
+pub const NOHREF: Atom = Atom::new_static(355);
+pub const NONE: Atom = Atom::new_static(356);
+pub const NORESIZE: Atom = Atom::new_static(357);
+pub const NOSCRIPT: Atom = Atom::new_static(358);
+pub const NOSHADE: Atom = Atom::new_static(359);
+pub const NOVALIDATE: Atom = Atom::new_static(360);
+pub const NOWRAP: Atom = Atom::new_static(361);
+pub const NUM_OCTAVES: Atom = Atom::new_static(362);
+pub const NUMOCTAVES: Atom = Atom::new_static(363);
+pub const OBJECT: Atom = Atom::new_static(364);
+pub const OL: Atom = Atom::new_static(365);
+pub const ONABORT: Atom = Atom::new_static(366);
+pub const ONAFTERPRINT: Atom = Atom::new_static(367);
+pub const ONAUXCLICK: Atom = Atom::new_static(368);
+pub const ONBEFOREPRINT: Atom = Atom::new_static(369);
+pub const ONBEFOREUNLOAD: Atom = Atom::new_static(370);
+pub const ONBLUR: Atom = Atom::new_static(371);
+pub const ONCANCEL: Atom = Atom::new_static(372);
+pub const ONCANPLAY: Atom = Atom::new_static(373);
+pub const ONCANPLAYTHROUGH: Atom = Atom::new_static(374);
+pub const ONCHANGE: Atom = Atom::new_static(375);
+pub const ONCLICK: Atom = Atom::new_static(376);
+pub const ONCLOSE: Atom = Atom::new_static(377);
+pub const ONCONTEXTMENU: Atom = Atom::new_static(378);
+pub const ONCOPY: Atom = Atom::new_static(379);
+pub const ONCUECHANGE: Atom = Atom::new_static(380);
+pub const ONCUT: Atom = Atom::new_static(381);
+pub const ONDBLCLICK: Atom = Atom::new_static(382);
+pub const ONDRAG: Atom = Atom::new_static(383);
+pub const ONDRAGEND: Atom = Atom::new_static(384);
+pub const ONDRAGENTER: Atom = Atom::new_static(385);
+pub const ONDRAGLEAVE: Atom = Atom::new_static(386);
+pub const ONDRAGOVER: Atom = Atom::new_static(387);
+pub const ONDRAGSTART: Atom = Atom::new_static(388);
+pub const ONDROP: Atom = Atom::new_static(389);
+pub const ONDURATIONCHANGE: Atom = Atom::new_static(390);
+pub const ONEMPTIED: Atom = Atom::new_static(391);
+pub const ONENDED: Atom = Atom::new_static(392);
+pub const ONERROR: Atom = Atom::new_static(393);
+pub const ONFOCUS: Atom = Atom::new_static(394);
+pub const ONHASHCHANGE: Atom = Atom::new_static(395);
+pub const ONINPUT: Atom = Atom::new_static(396);
+pub const ONINVALID: Atom = Atom::new_static(397);
+pub const ONKEYDOWN: Atom = Atom::new_static(398);
+pub const ONKEYPRESS: Atom = Atom::new_static(399);
+pub const ONKEYUP: Atom = Atom::new_static(400);
+pub const ONLOAD: Atom = Atom::new_static(401);
+pub const ONLOADEDDATA: Atom = Atom::new_static(402);
+pub const ONLOADEDMETADATA: Atom = Atom::new_static(403);
+pub const ONLOADSTART: Atom = Atom::new_static(404);
+pub const ONMESSAGE: Atom = Atom::new_static(405);
+pub const ONMOUSEDOWN: Atom = Atom::new_static(406);
+pub const ONMOUSEENTER: Atom = Atom::new_static(407);
+pub const ONMOUSELEAVE: Atom = Atom::new_static(408);
+pub const ONMOUSEMOVE: Atom = Atom::new_static(409);
+pub const ONMOUSEOUT: Atom = Atom::new_static(410);
+pub const ONMOUSEOVER: Atom = Atom::new_static(411);
+pub const ONMOUSEUP: Atom = Atom::new_static(412);
+pub const ONOFFLINE: Atom = Atom::new_static(413);
+pub const ONONLINE: Atom = Atom::new_static(414);
+pub const ONPAGEHIDE: Atom = Atom::new_static(415);
+pub const ONPAGESHOW: Atom = Atom::new_static(416);
+pub const ONPASTE: Atom = Atom::new_static(417);
+pub const ONPAUSE: Atom = Atom::new_static(418);
+pub const ONPLAY: Atom = Atom::new_static(419);
+pub const ONPLAYING: Atom = Atom::new_static(420);
+pub const ONPOPSTATE: Atom = Atom::new_static(421);
+pub const ONPROGRESS: Atom = Atom::new_static(422);
+pub const ONRATECHANGE: Atom = Atom::new_static(423);
+pub const ONRESET: Atom = Atom::new_static(424);
+pub const ONRESIZE: Atom = Atom::new_static(425);
+pub const ONSCROLL: Atom = Atom::new_static(426);
+pub const ONSECURITYPOLICYVIOLATION: Atom = Atom::new_static(427);
+pub const ONSEEKED: Atom = Atom::new_static(428);
+pub const ONSEEKING: Atom = Atom::new_static(429);
+pub const ONSELECT: Atom = Atom::new_static(430);
+pub const ONSTALLED: Atom = Atom::new_static(431);
+pub const ONSTORAGE: Atom = Atom::new_static(432);
+pub const ONSUBMIT: Atom = Atom::new_static(433);
+pub const ONSUSPEND: Atom = Atom::new_static(434);
+pub const ONTIMEUPDATE: Atom = Atom::new_static(435);
+pub const ONTOGGLE: Atom = Atom::new_static(436);
+pub const ONUNLOAD: Atom = Atom::new_static(437);
+pub const ONVOLUMECHANGE: Atom = Atom::new_static(438);
+pub const ONWAITING: Atom = Atom::new_static(439);
+pub const ONWHEEL: Atom = Atom::new_static(440);
+pub const OPEN: Atom = Atom::new_static(441);
+pub const OPTGROUP: Atom = Atom::new_static(442);
+pub const OPTIMUM: Atom = Atom::new_static(443);
+pub const OPTION: Atom = Atom::new_static(444);
+pub const OUTPUT: Atom = Atom::new_static(445);
+pub const P: Atom = Atom::new_static(446);
+pub const PARAM: Atom = Atom::new_static(447);
+pub const PATH: Atom = Atom::new_static(448);
+pub const PATH_LENGTH: Atom = Atom::new_static(449);
+pub const PATHLENGTH: Atom = Atom::new_static(450);
+pub const PATTERN: Atom = Atom::new_static(451);
+pub const PATTERN_CONTENT_UNITS: Atom = Atom::new_static(452);
+pub const PATTERN_TRANSFORM: Atom = Atom::new_static(453);
+pub const PATTERN_UNITS: Atom = Atom::new_static(454);
+pub const PATTERNCONTENTUNITS: Atom = Atom::new_static(455);
+pub const PATTERNTRANSFORM: Atom = Atom::new_static(456);
+pub const PATTERNUNITS: Atom = Atom::new_static(457);
+pub const PICTURE: Atom = Atom::new_static(458);
+pub const PING: Atom = Atom::new_static(459);
+pub const PLACEHOLDER: Atom = Atom::new_static(460);
+pub const PLAINTEXT: Atom = Atom::new_static(461);
+pub const PLAYSINLINE: Atom = Atom::new_static(462);
+pub const POINTS: Atom = Atom::new_static(463);
+pub const POINTS_AT_X: Atom = Atom::new_static(464);
+pub const POINTS_AT_Y: Atom = Atom::new_static(465);
+pub const POINTS_AT_Z: Atom = Atom::new_static(466);
+pub const POINTSATX: Atom = Atom::new_static(467);
+pub const POINTSATY: Atom = Atom::new_static(468);
+pub const POINTSATZ: Atom = Atom::new_static(469);
+pub const POLYGON: Atom = Atom::new_static(470);
+pub const POLYLINE: Atom = Atom::new_static(471);
+pub const POPOVER: Atom = Atom::new_static(472);
+pub const POPOVERTARGET: Atom = Atom::new_static(473);
+pub const POPOVERTARGETACTION: Atom = Atom::new_static(474);
+pub const POSTER: Atom = Atom::new_static(475);
+pub const PRE: Atom = Atom::new_static(476);
+pub const PRELOAD: Atom = Atom::new_static(477);
+pub const PRESERVE_ALPHA: Atom = Atom::new_static(478);
+pub const PRESERVE_ASPECT_RATIO: Atom = Atom::new_static(479);
+pub const PRESERVEALPHA: Atom = Atom::new_static(480);
+pub const PRESERVEASPECTRATIO: Atom = Atom::new_static(481);
+pub const PRIMITIVE_UNITS: Atom = Atom::new_static(482);
+pub const PRIMITIVEUNITS: Atom = Atom::new_static(483);
+pub const PROFILE: Atom = Atom::new_static(484);
+pub const PROGRESS: Atom = Atom::new_static(485);
+pub const PROMPT: Atom = Atom::new_static(486);
+pub const Q: Atom = Atom::new_static(487);
+pub const R: Atom = Atom::new_static(488);
+pub const RADIAL_GRADIENT: Atom = Atom::new_static(489);
+pub const RADIALGRADIENT: Atom = Atom::new_static(490);
+pub const RB: Atom = Atom::new_static(491);
+pub const READONLY: Atom = Atom::new_static(492);
+pub const RECT: Atom = Atom::new_static(493);
+pub const REF_X: Atom = Atom::new_static(494);
+pub const REF_Y: Atom = Atom::new_static(495);
+pub const REFERRERPOLICY: Atom = Atom::new_static(496);
+pub const REFX: Atom = Atom::new_static(497);
+pub const REFY: Atom = Atom::new_static(498);
+pub const REL: Atom = Atom::new_static(499);
+pub const REPEAT_COUNT: Atom = Atom::new_static(500);
+pub const REPEAT_DUR: Atom = Atom::new_static(501);
+pub const REPEATCOUNT: Atom = Atom::new_static(502);
+pub const REPEATDUR: Atom = Atom::new_static(503);
+pub const REQUIRED: Atom = Atom::new_static(504);
+pub const REQUIRED_EXTENSIONS: Atom = Atom::new_static(505);
+pub const REQUIRED_FEATURES: Atom = Atom::new_static(506);
+pub const REQUIREDEXTENSIONS: Atom = Atom::new_static(507);
+pub const REQUIREDFEATURES: Atom = Atom::new_static(508);
+pub const REV: Atom = Atom::new_static(509);
+pub const REVERSED: Atom = Atom::new_static(510);
+pub const ROLE: Atom = Atom::new_static(511);
+pub const ROWS: Atom = Atom::new_static(512);
+pub const ROWSPAN: Atom = Atom::new_static(513);
+pub const RP: Atom = Atom::new_static(514);
+pub const RT: Atom = Atom::new_static(515);
+pub const RTC: Atom = Atom::new_static(516);
+pub const RUBY: Atom = Atom::new_static(517);
+pub const RULES: Atom = Atom::new_static(518);
+pub const RX: Atom = Atom::new_static(519);
+pub const RY: Atom = Atom::new_static(520);
+pub const S: Atom = Atom::new_static(521);
+pub const SAMP: Atom = Atom::new_static(522);
+pub const SANDBOX: Atom = Atom::new_static(523);
+pub const SCHEME: Atom = Atom::new_static(524);
+pub const SCOPE: Atom = Atom::new_static(525);
+pub const SCRIPT: Atom = Atom::new_static(526);
+pub const SCROLLING: Atom = Atom::new_static(527);
+pub const SEARCH: Atom = Atom::new_static(528);
+pub const SECTION: Atom = Atom::new_static(529);
+pub const SELECT: Atom = Atom::new_static(530);
+pub const SELECTED: Atom = Atom::new_static(531);
+pub const SEMANTICS: Atom = Atom::new_static(532);
+pub const SET: Atom = Atom::new_static(533);
+pub const SHADOWROOTCLONABLE: Atom = Atom::new_static(534);
+pub const SHADOWROOTDELEGATESFOCUS: Atom = Atom::new_static(535);
+pub const SHADOWROOTMODE: Atom = Atom::new_static(536);
+pub const SHAPE: Atom = Atom::new_static(537);
+pub const SHOW: Atom = Atom::new_static(538);
+pub const SIZE: Atom = Atom::new_static(539);
+pub const SIZES: Atom = Atom::new_static(540);
+pub const SLOT: Atom = Atom::new_static(541);
+pub const SMALL: Atom = Atom::new_static(542);
+pub const SOURCE: Atom = Atom::new_static(543);
+pub const SPACE: Atom = Atom::new_static(544);
+pub const SPACER: Atom = Atom::new_static(545);
+pub const SPAN: Atom = Atom::new_static(546);
+pub const SPECULAR_CONSTANT: Atom = Atom::new_static(547);
+pub const SPECULAR_EXPONENT: Atom = Atom::new_static(548);
+pub const SPECULARCONSTANT: Atom = Atom::new_static(549);
+pub const SPECULAREXPONENT: Atom = Atom::new_static(550);
+pub const SPELLCHECK: Atom = Atom::new_static(551);
+pub const SPREAD_METHOD: Atom = Atom::new_static(552);
+pub const SPREADMETHOD: Atom = Atom::new_static(553);
+pub const SRC: Atom = Atom::new_static(554);
+pub const SRCDOC: Atom = Atom::new_static(555);
+pub const SRCLANG: Atom = Atom::new_static(556);
+pub const SRCSET: Atom = Atom::new_static(557);
+pub const STANDBY: Atom = Atom::new_static(558);
+pub const START: Atom = Atom::new_static(559);
+pub const START_OFFSET: Atom = Atom::new_static(560);
+pub const STARTOFFSET: Atom = Atom::new_static(561);
+pub const STD_DEVIATION: Atom = Atom::new_static(562);
+pub const STDDEVIATION: Atom = Atom::new_static(563);
+pub const STEP: Atom = Atom::new_static(564);
+pub const STITCH_TILES: Atom = Atom::new_static(565);
+pub const STITCHTILES: Atom = Atom::new_static(566);
+pub const STOP: Atom = Atom::new_static(567);
+pub const STRIKE: Atom = Atom::new_static(568);
+pub const STROKE: Atom = Atom::new_static(569);
+pub const STRONG: Atom = Atom::new_static(570);
+pub const STYLE: Atom = Atom::new_static(571);
+pub const SUB: Atom = Atom::new_static(572);
+pub const SUMMARY: Atom = Atom::new_static(573);
+pub const SUP: Atom = Atom::new_static(574);
+pub const SURFACE_SCALE: Atom = Atom::new_static(575);
+pub const SURFACESCALE: Atom = Atom::new_static(576);
+pub const SVG: Atom = Atom::new_static(577);
+pub const SWITCH: Atom = Atom::new_static(578);
+pub const SYMBOL: Atom = Atom::new_static(579);
+pub const SYSTEM_LANGUAGE: Atom = Atom::new_static(580);
+pub const SYSTEMLANGUAGE: Atom = Atom::new_static(581);
+pub const TABINDEX: Atom = Atom::new_static(582);
+pub const TABLE: Atom = Atom::new_static(583);
+pub const TABLE_VALUES: Atom = Atom::new_static(584);
+pub const TABLEVALUES: Atom = Atom::new_static(585);
+pub const TARGET: Atom = Atom::new_static(586);
+pub const TARGET_X: Atom = Atom::new_static(587);
+pub const TARGET_Y: Atom = Atom::new_static(588);
+pub const TARGETX: Atom = Atom::new_static(589);
+pub const TARGETY: Atom = Atom::new_static(590);
+pub const TBODY: Atom = Atom::new_static(591);
+pub const TD: Atom = Atom::new_static(592);
+pub const TEMPLATE: Atom = Atom::new_static(593);
+pub const TEXT: Atom = Atom::new_static(594);
+pub const TEXT_LENGTH: Atom = Atom::new_static(595);
+pub const TEXT_PATH: Atom = Atom::new_static(596);
+pub const TEXTAREA: Atom = Atom::new_static(597);
+pub const TEXTLENGTH: Atom = Atom::new_static(598);
+pub const TEXTPATH: Atom = Atom::new_static(599);
+pub const TFOOT: Atom = Atom::new_static(600);
+pub const TH: Atom = Atom::new_static(601);
+pub const THEAD: Atom = Atom::new_static(602);
+pub const TIME: Atom = Atom::new_static(603);
+pub const TITLE: Atom = Atom::new_static(604);
+pub const TR: Atom = Atom::new_static(605);
+pub const TRACK: Atom = Atom::new_static(606);
+pub const TRANSFORM: Atom = Atom::new_static(607);
+pub const TRANSLATE: Atom = Atom::new_static(608);
+pub const TSPAN: Atom = Atom::new_static(609);
+pub const TT: Atom = Atom::new_static(610);
+pub const TYPE: Atom = Atom::new_static(611);
+pub const U: Atom = Atom::new_static(612);
+pub const UL: Atom = Atom::new_static(613);
+pub const USE: Atom = Atom::new_static(614);
+pub const USEMAP: Atom = Atom::new_static(615);
+pub const VALIGN: Atom = Atom::new_static(616);
+pub const VALUE: Atom = Atom::new_static(617);
+pub const VALUETYPE: Atom = Atom::new_static(618);
+pub const VAR: Atom = Atom::new_static(619);
+pub const VERSION: Atom = Atom::new_static(620);
+pub const VIDEO: Atom = Atom::new_static(621);
+pub const VIEW: Atom = Atom::new_static(622);
+pub const VIEW_BOX: Atom = Atom::new_static(623);
+pub const VIEW_TARGET: Atom = Atom::new_static(624);
+pub const VIEWBOX: Atom = Atom::new_static(625);
+pub const VIEWTARGET: Atom = Atom::new_static(626);
+pub const VLINK: Atom = Atom::new_static(627);
+pub const VSPACE: Atom = Atom::new_static(628);
+pub const WBR: Atom = Atom::new_static(629);
+pub const WIDTH: Atom = Atom::new_static(630);
+pub const WRAP: Atom = Atom::new_static(631);
+pub const WRITINGSUGGESTIONS: Atom = Atom::new_static(632);
+pub const X: Atom = Atom::new_static(633);
+pub const X1: Atom = Atom::new_static(634);
+pub const X2: Atom = Atom::new_static(635);
+pub const X_CHANNEL_SELECTOR: Atom = Atom::new_static(636);
+pub const XCHANNELSELECTOR: Atom = Atom::new_static(637);
+pub const XLINK: Atom = Atom::new_static(638);
+pub const XLINK_ACTUATE: Atom = Atom::new_static(639);
+pub const XLINK_ARCROLE: Atom = Atom::new_static(640);
+pub const XLINK_HREF: Atom = Atom::new_static(641);
+pub const XLINK_ROLE: Atom = Atom::new_static(642);
+pub const XLINK_SHOW: Atom = Atom::new_static(643);
+pub const XLINK_TITLE: Atom = Atom::new_static(644);
+pub const XLINK_TYPE: Atom = Atom::new_static(645);
+pub const XML_LANG: Atom = Atom::new_static(646);
+pub const XML_SPACE: Atom = Atom::new_static(647);
+pub const XMLNS: Atom = Atom::new_static(648);
+pub const XMLNS_XLINK: Atom = Atom::new_static(649);
+pub const XMP: Atom = Atom::new_static(650);
+pub const Y: Atom = Atom::new_static(651);
+pub const Y1: Atom = Atom::new_static(652);
+pub const Y2: Atom = Atom::new_static(653);
+pub const Y_CHANNEL_SELECTOR: Atom = Atom::new_static(654);
+pub const YCHANNELSELECTOR: Atom = Atom::new_static(655);
+pub const ZOOM_AND_PAN: Atom = Atom::new_static(656);
+pub const ZOOMANDPAN: Atom = Atom::new_static(657);
+
+// atom!("div") is the constant of a name interned at compile time, a name without an atom does not compile
+#[macro_export]
+macro_rules! atom {
+    ("") => {
+        $crate::atom::static_atoms::EMPTY
+    };
+    ("a") => {
+        $crate::atom::static_atoms::A
+    };
+    ("abbr") => {
+        $crate::atom::static_atoms::ABBR
+    };
+    ("accept") => {
+        $crate::atom::static_atoms::ACCEPT
+    };
+    ("accept-charset") => {
+        $crate::atom::static_atoms::ACCEPT_CHARSET
+    };
+    ("accesskey") => {
+        $crate::atom::static_atoms::ACCESSKEY
+    };
+    ("acronym") => {
+        $crate::atom::static_atoms::ACRONYM
+    };
+    ("action") => {
+        $crate::atom::static_atoms::ACTION
+    };
+    ("actuate") => {
+        $crate::atom::static_atoms::ACTUATE
+    };
+    ("address") => {
+        $crate::atom::static_atoms::ADDRESS
+    };
+    ("align") => {
+        $crate::atom::static_atoms::ALIGN
+    };
+    ("alink") => {
+        $crate::atom::static_atoms::ALINK
+    };
+    ("allow") => {
+        $crate::atom::static_atoms::ALLOW
+    };
+    ("allowfullscreen") => {
+        $crate::atom::static_atoms::ALLOWFULLSCREEN
+    };
+    ("alt") => {
+        $crate::atom::static_atoms::ALT
+    };
+    ("altGlyph") => {
+        $crate::atom::static_atoms::ALT_GLYPH
+    };
+    ("altGlyphDef") => {
+        $crate::atom::static_atoms::ALT_GLYPH_DEF
+    };
+    ("altGlyphItem") => {
+        $crate::atom::static_atoms::ALT_GLYPH_ITEM
+    };
+    ("altglyph") => {
+        $crate::atom::static_atoms::ALTGLYPH
+    };
+    ("altglyphdef") => {
+        $crate::atom::static_atoms::ALTGLYPHDEF
+    };
+    ("altglyphitem") => {
+        $crate::atom::static_atoms::ALTGLYPHITEM
+    };
+    ("animate") => {
+        $crate::atom::static_atoms::ANIMATE
+    };
+    ("animateColor") => {
+        $crate::atom::static_atoms::ANIMATE_COLOR
+    };
+    ("animateMotion") => {
+        $crate::atom::static_atoms::ANIMATE_MOTION
+    };
+    ("animateTransform") => {
+        $crate::atom::static_atoms::ANIMATE_TRANSFORM
+    };
+    ("animatecolor") => {
+        $crate::atom::static_atoms::ANIMATECOLOR
+    };
+    ("animatemotion") => {
+        $crate::atom::static_atoms::ANIMATEMOTION
+    };
+    ("animatetransform") => {
+        $crate::atom::static_atoms::ANIMATETRANSFORM
+    };
+    ("annotation") => {
+        $crate::atom::static_atoms::ANNOTATION
+    };
+    ("annotation-xml") => {
+        $crate::atom::static_atoms::ANNOTATION_XML
+    };
+    ("applet") => {
+        $crate::atom::static_atoms::APPLET
+    };
+    ("archive") => {
+        $crate::atom::static_atoms::ARCHIVE
+    };
+    ("arcrole") => {
+        $crate::atom::static_atoms::ARCROLE
+    };
+    ("area") => {
+        $crate::atom::static_atoms::AREA
+    };
+    ("article") => {
+        $crate::atom::static_atoms::ARTICLE
+    };
+    ("aside") => {
+        $crate::atom::static_atoms::ASIDE
+    };
+    ("async") => {
+        $crate::atom::static_atoms::ASYNC
+    };
+    ("attributeName") => {
+        $crate::atom::static_atoms::ATTRIBUTE_NAME
+    };
+    ("attributeType") => {
+        $crate::atom::static_atoms::ATTRIBUTE_TYPE
+    };
+    ("attributename") => {
+        $crate::atom::static_atoms::ATTRIBUTENAME
+    };
+    ("attributetype") => {
+        $crate::atom::static_atoms::ATTRIBUTETYPE
+    };
+    ("audio") => {
+        $crate::atom::static_atoms::AUDIO
+    };
+    ("autocapitalize") => {
+        $crate::atom::static_atoms::AUTOCAPITALIZE
+    };
+    ("autocomplete") => {
+        $crate::atom::static_atoms::AUTOCOMPLETE
+    };
+    ("autocorrect") => {
+        $crate::atom::static_atoms::AUTOCORRECT
+    };
+    ("autofocus") => {
+        $crate::atom::static_atoms::AUTOFOCUS
+    };
+    ("autoplay") => {
+        $crate::atom::static_atoms::AUTOPLAY
+    };
+    ("axis") => {
+        $crate::atom::static_atoms::AXIS
+    };
+    ("b") => {
+        $crate::atom::static_atoms::B
+    };
+    ("background") => {
+        $crate::atom::static_atoms::BACKGROUND
+    };
+    ("base") => {
+        $crate::atom::static_atoms::BASE
+    };
+    ("baseFrequency") => {
+        $crate::atom::static_atoms::BASE_FREQUENCY
+    };
+    ("baseProfile") => {
+        $crate::atom::static_atoms::BASE_PROFILE
+    };
+    ("basefont") => {
+        $crate::atom::static_atoms::BASEFONT
+    };
+    ("basefrequency") => {
+        $crate::atom::static_atoms::BASEFREQUENCY
+    };
+    ("baseprofile") => {
+        $crate::atom::static_atoms::BASEPROFILE
+    };
+    ("bdi") => {
+        $crate::atom::static_atoms::BDI
+    };
+    ("bdo") => {
+        $crate::atom::static_atoms::BDO
+    };
+    ("bgcolor") => {
+        $crate::atom::static_atoms::BGCOLOR
+    };
+    ("bgsound") => {
+        $crate::atom::static_atoms::BGSOUND
+    };
+    ("big") => {
+        $crate::atom::static_atoms::BIG
+    };
+    ("blink") => {
+        $crate::atom::static_atoms::BLINK
+    };
+    ("blocking") => {
+        $crate::atom::static_atoms::BLOCKING
+    };
+    ("blockquote") => {
+        $crate::atom::static_atoms::BLOCKQUOTE
+    };
+    ("body") => {
+        $crate::atom::static_atoms::BODY
+    };
+    ("border") => {
+        $crate::atom::static_atoms::BORDER
+    };
+    ("br") => {
+        $crate::atom::static_atoms::BR
+    };
+    ("button") => {
+        $crate::atom::static_atoms::BUTTON
+    };
+    ("calcMode") => {
+        $crate::atom::static_atoms::CALC_MODE
+    };
+    ("calcmode") => {
+        $crate::atom::static_atoms::CALCMODE
+    };
+    ("canvas") => {
+        $crate::atom::static_atoms::CANVAS
+    };
+    ("caption") => {
+        $crate::atom::static_atoms::CAPTION
+    };
+    ("cellpadding") => {
+        $crate::atom::static_atoms::CELLPADDING
+    };
+    ("cellspacing") => {
+        $crate::atom::static_atoms::CELLSPACING
+    };
+    ("center") => {
+        $crate::atom::static_atoms::CENTER
+    };
+    ("char") => {
+        $crate::atom::static_atoms::CHAR
+    };
+    ("charoff") => {
+        $crate::atom::static_atoms::CHAROFF
+    };
+    ("charset") => {
+        $crate::atom::static_atoms::CHARSET
+    };
+    ("checked") => {
+        $crate::atom::static_atoms::CHECKED
+    };
+    ("circle") => {
+        $crate::atom::static_atoms::CIRCLE
+    };
+    ("cite") => {
+        $crate::atom::static_atoms::CITE
+    };
+    ("class") => {
+        $crate::atom::static_atoms::CLASS
+    };
+    ("classid") => {
+        $crate::atom::static_atoms::CLASSID
+    };
+    ("clear") => {
+        $crate::atom::static_atoms::CLEAR
+    };
+    ("clipPath") => {
+        $crate::atom::static_atoms::CLIP_PATH
+    };
+    ("clipPathUnits") => {
+        $crate::atom::static_atoms::CLIP_PATH_UNITS
+    };
+    ("clippath") => {
+        $crate::atom::static_atoms::CLIPPATH
+    };
+    ("clippathunits") => {
+        $crate::atom::static_atoms::CLIPPATHUNITS
+    };
+    ("code") => {
+        $crate::atom::static_atoms::CODE
+    };
+    ("codebase") => {
+        $crate::atom::static_atoms::CODEBASE
+    };
+    ("codetype") => {
+        $crate::atom::static_atoms::CODETYPE
+    };
+    ("col") => {
+        $crate::atom::static_atoms::COL
+    };
+    ("colgroup") => {
+        $crate::atom::static_atoms::COLGROUP
+    };
+    ("color") => {
+        $crate::atom::static_atoms::COLOR
+    };
+    ("cols") => {
+        $crate::atom::static_atoms::COLS
+    };
+    ("colspan") => {
+        $crate::atom::static_atoms::COLSPAN
+    };
+    ("compact") => {
+        $crate::atom::static_atoms::COMPACT
+    };
+    ("content") => {
+        $crate::atom::static_atoms::CONTENT
+    };
+    ("contenteditable") => {
+        $crate::atom::static_atoms::CONTENTEDITABLE
+    };
+    ("controls") => {
+        $crate::atom::static_atoms::CONTROLS
+    };
+    ("coords") => {
+        $crate::atom::static_atoms::COORDS
+    };
+    ("crossorigin") => {
+        $crate::atom::static_atoms::CROSSORIGIN
+    };
+    ("cx") => {
+        $crate::atom::static_atoms::CX
+    };
+    ("cy") => {
+        $crate::atom::static_atoms::CY
+    };
+    ("d") => {
+        $crate::atom::static_atoms::D
+    };
+    ("data") => {
+        $crate::atom::static_atoms::DATA
+    };
+    ("datalist") => {
+        $crate::atom::static_atoms::DATALIST
+    };
+    ("datetime") => {
+        $crate::atom::static_atoms::DATETIME
+    };
+    ("dd") => {
+        $crate::atom::static_atoms::DD
+    };
+    ("declare") => {
+        $crate::atom::static_atoms::DECLARE
+    };
+    ("decoding") => {
+        $crate::atom::static_atoms::DECODING
+    };
+    ("default") => {
+        $crate::atom::static_atoms::DEFAULT
+    };
+    ("defer") => {
+        $crate::atom::static_atoms::DEFER
+    };
+    ("definitionURL") => {
+        $crate::atom::static_atoms::DEFINITION_URL
+    };
+    ("definitionurl") => {
+        $crate::atom::static_atoms::DEFINITIONURL
+    };
+    ("defs") => {
+        $crate::atom::static_atoms::DEFS
+    };
+    ("del") => {
+        $crate::atom::static_atoms::DEL
+    };
+    ("desc") => {
+        $crate::atom::static_atoms::DESC
+    };
+    ("details") => {
+        $crate::atom::static_atoms::DETAILS
+    };
+    ("dfn") => {
+        $crate::atom::static_atoms::DFN
+    };
+    ("dialog") => {
+        $crate::atom::static_atoms::DIALOG
+    };
+    ("diffuseConstant") => {
+        $crate::atom::static_atoms::DIFFUSE_CONSTANT
+    };
+    ("diffuseconstant") => {
+        $crate::atom::static_atoms::DIFFUSECONSTANT
+    };
+    ("dir") => {
+        $crate::atom::static_atoms::DIR
+    };
+    ("dirname") => {
+        $crate::atom::static_atoms::DIRNAME
+    };
+    ("disabled") => {
+        $crate::atom::static_atoms::DISABLED
+    };
+    ("div") => {
+        $crate::atom::static_atoms::DIV
+    };
+    ("dl") => {
+        $crate::atom::static_atoms::DL
+    };
+    ("download") => {
+        $crate::atom::static_atoms::DOWNLOAD
+    };
+    ("draggable") => {
+        $crate::atom::static_atoms::DRAGGABLE
+    };
+    ("dt") => {
+        $crate::atom::static_atoms::DT
+    };
+    ("edgeMode") => {
+        $crate::atom::static_atoms::EDGE_MODE
+    };
+    ("edgemode") => {
+        $crate::atom::static_atoms::EDGEMODE
+    };
+    ("ellipse") => {
+        $crate::atom::static_atoms::ELLIPSE
+    };
+    ("em") => {
+        $crate::atom::static_atoms::EM
+    };
+    ("embed") => {
+        $crate::atom::static_atoms::EMBED
+    };
+    ("encoding") => {
+        $crate::atom::static_atoms::ENCODING
+    };
+    ("enctype") => {
+        $crate::atom::static_atoms::ENCTYPE
+    };
+    ("enterkeyhint") => {
+        $crate::atom::static_atoms::ENTERKEYHINT
+    };
+    ("face") => {
+        $crate::atom::static_atoms::FACE
+    };
+    ("feBlend") => {
+        $crate::atom::static_atoms::FE_BLEND
+    };
+    ("feColorMatrix") => {
+        $crate::atom::static_atoms::FE_COLOR_MATRIX
+    };
+    ("feComponentTransfer") => {
+        $crate::atom::static_atoms::FE_COMPONENT_TRANSFER
+    };
+    ("feComposite") => {
+        $crate::atom::static_atoms::FE_COMPOSITE
+    };
+    ("feConvolveMatrix") => {
+        $crate::atom::static_atoms::FE_CONVOLVE_MATRIX
+    };
+    ("feDiffuseLighting") => {
+        $crate::atom::static_atoms::FE_DIFFUSE_LIGHTING
+    };
+    ("feDisplacementMap") => {
+        $crate::atom::static_atoms::FE_DISPLACEMENT_MAP
+    };
+    ("feDistantLight") => {
+        $crate::atom::static_atoms::FE_DISTANT_LIGHT
+    };
+    ("feDropShadow") => {
+        $crate::atom::static_atoms::FE_DROP_SHADOW
+    };
+    ("feFlood") => {
+        $crate::atom::static_atoms::FE_FLOOD
+    };
+    ("feFuncA") => {
+        $crate::atom::static_atoms::FE_FUNC_A
+    };
+    ("feFuncB") => {
+        $crate::atom::static_atoms::FE_FUNC_B
+    };
+    ("feFuncG") => {
+        $crate::atom::static_atoms::FE_FUNC_G
+    };
+    ("feFuncR") => {
+        $crate::atom::static_atoms::FE_FUNC_R
+    };
+    ("feGaussianBlur") => {
+        $crate::atom::static_atoms::FE_GAUSSIAN_BLUR
+    };
+    ("feImage") => {
+        $crate::atom::static_atoms::FE_IMAGE
+    };
+    ("feMerge") => {
+        $crate::atom::static_atoms::FE_MERGE
+    };
+    ("feMergeNode") => {
+        $crate::atom::static_atoms::FE_MERGE_NODE
+    };
+    ("feMorphology") => {
+        $crate::atom::static_atoms::FE_MORPHOLOGY
+    };
+    ("feOffset") => {
+        $crate::atom::static_atoms::FE_OFFSET
+    };
+    ("fePointLight") => {
+        $crate::atom::static_atoms::FE_POINT_LIGHT
+    };
+    ("feSpecularLighting") => {
+        $crate::atom::static_atoms::FE_SPECULAR_LIGHTING
+    };
+    ("feSpotLight") => {
+        $crate::atom::static_atoms::FE_SPOT_LIGHT
+    };
+    ("feTile") => {
+        $crate::atom::static_atoms::FE_TILE
+    };
+    ("feTurbulence") => {
+        $crate::atom::static_atoms::FE_TURBULENCE
+    };
+    ("feblend") => {
+        $crate::atom::static_atoms::FEBLEND
+    };
+    ("fecolormatrix") => {
+        $crate::atom::static_atoms::FECOLORMATRIX
+    };
+    ("fecomponenttransfer") => {
+        $crate::atom::static_atoms::FECOMPONENTTRANSFER
+    };
+    ("fecomposite") => {
+        $crate::atom::static_atoms::FECOMPOSITE
+    };
+    ("feconvolvematrix") => {
+        $crate::atom::static_atoms::FECONVOLVEMATRIX
+    };
+    ("fediffuselighting") => {
+        $crate::atom::static_atoms::FEDIFFUSELIGHTING
+    };
+    ("fedisplacementmap") => {
+        $crate::atom::static_atoms::FEDISPLACEMENTMAP
+    };
+    ("fedistantlight") => {
+        $crate::atom::static_atoms::FEDISTANTLIGHT
+    };
+    ("fedropshadow") => {
+        $crate::atom::static_atoms::FEDROPSHADOW
+    };
+    ("feflood") => {
+        $crate::atom::static_atoms::FEFLOOD
+    };
+    ("fefunca") => {
+        $crate::atom::static_atoms::FEFUNCA
+    };
+    ("fefuncb") => {
+        $crate::atom::static_atoms::FEFUNCB
+    };
+    ("fefuncg") => {
+        $crate::atom::static_atoms::FEFUNCG
+    };
+    ("fefuncr") => {
+        $crate::atom::static_atoms::FEFUNCR
+    };
+    ("fegaussianblur") => {
+        $crate::atom::static_atoms::FEGAUSSIANBLUR
+    };
+    ("feimage") => {
+        $crate::atom::static_atoms::FEIMAGE
+    };
+    ("femerge") => {
+        $crate::atom::static_atoms::FEMERGE
+    };
+    ("femergenode") => {
+        $crate::atom::static_atoms::FEMERGENODE
+    };
+    ("femorphology") => {
+        $crate::atom::static_atoms::FEMORPHOLOGY
+    };
+    ("feoffset") => {
+        $crate::atom::static_atoms::FEOFFSET
+    };
+    ("fepointlight") => {
+        $crate::atom::static_atoms::FEPOINTLIGHT
+    };
+    ("fespecularlighting") => {
+        $crate::atom::static_atoms::FESPECULARLIGHTING
+    };
+    ("fespotlight") => {
+        $crate::atom::static_atoms::FESPOTLIGHT
+    };
+    ("fetchpriority") => {
+        $crate::atom::static_atoms::FETCHPRIORITY
+    };
+    ("fetile") => {
+        $crate::atom::static_atoms::FETILE
+    };
+    ("feturbulence") => {
+        $crate::atom::static_atoms::FETURBULENCE
+    };
+    ("fieldset") => {
+        $crate::atom::static_atoms::FIELDSET
+    };
+    ("figcaption") => {
+        $crate::atom::static_atoms::FIGCAPTION
+    };
+    ("figure") => {
+        $crate::atom::static_atoms::FIGURE
+    };
+    ("fill") => {
+        $crate::atom::static_atoms::FILL
+    };
+    ("filter") => {
+        $crate::atom::static_atoms::FILTER
+    };
+    ("filterUnits") => {
+        $crate::atom::static_atoms::FILTER_UNITS
+    };
+    ("filterunits") => {
+        $crate::atom::static_atoms::FILTERUNITS
+    };
+    ("font") => {
+        $crate::atom::static_atoms::FONT
+    };
+    ("footer") => {
+        $crate::atom::static_atoms::FOOTER
+    };
+    ("for") => {
+        $crate::atom::static_atoms::FOR
+    };
+    ("foreignObject") => {
+        $crate::atom::static_atoms::FOREIGN_OBJECT
+    };
+    ("foreignobject") => {
+        $crate::atom::static_atoms::FOREIGNOBJECT
+    };
+    ("form") => {
+        $crate::atom::static_atoms::FORM
+    };
+    ("formaction") => {
+        $crate::atom::static_atoms::FORMACTION
+    };
+    ("formenctype") => {
+        $crate::atom::static_atoms::FORMENCTYPE
+    };
+    ("formmethod") => {
+        $crate::atom::static_atoms::FORMMETHOD
+    };
+    ("formnovalidate") => {
+        $crate::atom::static_atoms::FORMNOVALIDATE
+    };
+    ("formtarget") => {
+        $crate::atom::static_atoms::FORMTARGET
+    };
+    ("frame") => {
+        $crate::atom::static_atoms::FRAME
+    };
+    ("frameborder") => {
+        $crate::atom::static_atoms::FRAMEBORDER
+    };
+    ("frameset") => {
+        $crate::atom::static_atoms::FRAMESET
+    };
+    ("g") => {
+        $crate::atom::static_atoms::G
+    };
+    ("glyphRef") => {
+        $crate::atom::static_atoms::GLYPH_REF
+    };
+    ("glyphref") => {
+        $crate::atom::static_atoms::GLYPHREF
+    };
+    ("gradientTransform") => {
+        $crate::atom::static_atoms::GRADIENT_TRANSFORM
+    };
+    ("gradientUnits") => {
+        $crate::atom::static_atoms::GRADIENT_UNITS
+    };
+    ("gradienttransform") => {
+        $crate::atom::static_atoms::GRADIENTTRANSFORM
+    };
+    ("gradientunits") => {
+        $crate::atom::static_atoms::GRADIENTUNITS
+    };
+    ("h1") => {
+        $crate::atom::static_atoms::H1
+    };
+    ("h2") => {
+        $crate::atom::static_atoms::H2
+    };
+    ("h3") => {
+        $crate::atom::static_atoms::H3
+    };
+    ("h4") => {
+        $crate::atom::static_atoms::H4
+    };
+    ("h5") => {
+        $crate::atom::static_atoms::H5
+    };
+    ("h6") => {
+        $crate::atom::static_atoms::H6
+    };
+    ("head") => {
+        $crate::atom::static_atoms::HEAD
+    };
+    ("header") => {
+        $crate::atom::static_atoms::HEADER
+    };
+    ("headers") => {
+        $crate::atom::static_atoms::HEADERS
+    };
+    ("height") => {
+        $crate::atom::static_atoms::HEIGHT
+    };
+    ("hgroup") => {
+        $crate::atom::static_atoms::HGROUP
+    };
+    ("hidden") => {
+        $crate::atom::static_atoms::HIDDEN
+    };
+    ("high") => {
+        $crate::atom::static_atoms::HIGH
+    };
+    ("hr") => {
+        $crate::atom::static_atoms::HR
+    };
+    ("href") => {
+        $crate::atom::static_atoms::HREF
+    };
+    ("hreflang") => {
+        $crate::atom::static_atoms::HREFLANG
+    };
+    ("hspace") => {
+        $crate::atom::static_atoms::HSPACE
+    };
+    ("html") => {
+        $crate::atom::static_atoms::HTML
+    };
+    ("http-equiv") => {
+        $crate::atom::static_atoms::HTTP_EQUIV
+    };
+    ("i") => {
+        $crate::atom::static_atoms::I
+    };
+    ("id") => {
+        $crate::atom::static_atoms::ID
+    };
+    ("iframe") => {
+        $crate::atom::static_atoms::IFRAME
+    };
+    ("image") => {
+        $crate::atom::static_atoms::IMAGE
+    };
+    ("img") => {
+        $crate::atom::static_atoms::IMG
+    };
+    ("inert") => {
+        $crate::atom::static_atoms::INERT
+    };
+    ("input") => {
+        $crate::atom::static_atoms::INPUT
+    };
+    ("inputmode") => {
+        $crate::atom::static_atoms::INPUTMODE
+    };
+    ("ins") => {
+        $crate::atom::static_atoms::INS
+    };
+    ("integrity") => {
+        $crate::atom::static_atoms::INTEGRITY
+    };
+    ("is") => {
+        $crate::atom::static_atoms::IS
+    };
+    ("isindex") => {
+        $crate::atom::static_atoms::ISINDEX
+    };
+    ("ismap") => {
+        $crate::atom::static_atoms::ISMAP
+    };
+    ("itemid") => {
+        $crate::atom::static_atoms::ITEMID
+    };
+    ("itemprop") => {
+        $crate::atom::static_atoms::ITEMPROP
+    };
+    ("itemref") => {
+        $crate::atom::static_atoms::ITEMREF
+    };
+    ("itemscope") => {
+        $crate::atom::static_atoms::ITEMSCOPE
+    };
+    ("itemtype") => {
+        $crate::atom::static_atoms::ITEMTYPE
+    };
+    ("kbd") => {
+        $crate::atom::static_atoms::KBD
+    };
+    ("kernelMatrix") => {
+        $crate::atom::static_atoms::KERNEL_MATRIX
+    };
+    ("kernelUnitLength") => {
+        $crate::atom::static_atoms::KERNEL_UNIT_LENGTH
+    };
+    ("kernelmatrix") => {
+        $crate::atom::static_atoms::KERNELMATRIX
+    };
+    ("kernelunitlength") => {
+        $crate::atom::static_atoms::KERNELUNITLENGTH
+    };
+    ("keyPoints") => {
+        $crate::atom::static_atoms::KEY_POINTS
+    };
+    ("keySplines") => {
+        $crate::atom::static_atoms::KEY_SPLINES
+    };
+    ("keyTimes") => {
+        $crate::atom::static_atoms::KEY_TIMES
+    };
+    ("keygen") => {
+        $crate::atom::static_atoms::KEYGEN
+    };
+    ("keypoints") => {
+        $crate::atom::static_atoms::KEYPOINTS
+    };
+    ("keysplines") => {
+        $crate::atom::static_atoms::KEYSPLINES
+    };
+    ("keytimes") => {
+        $crate::atom::static_atoms::KEYTIMES
+    };
+    ("kind") => {
+        $crate::atom::static_atoms::KIND
+    };
+    ("label") => {
+        $crate::atom::static_atoms::LABEL
+    };
+    ("lang") => {
+        $crate::atom::static_atoms::LANG
+    };
+    ("language") => {
+        $crate::atom::static_atoms::LANGUAGE
+    };
+    ("legend") => {
+        $crate::atom::static_atoms::LEGEND
+    };
+    ("lengthAdjust") => {
+        $crate::atom::static_atoms::LENGTH_ADJUST
+    };
+    ("lengthadjust") => {
+        $crate::atom::static_atoms::LENGTHADJUST
+    };
+    ("li") => {
+        $crate::atom::static_atoms::LI
+    };
+    ("limitingConeAngle") => {
+        $crate::atom::static_atoms::LIMITING_CONE_ANGLE
+    };
+    ("limitingconeangle") => {
+        $crate::atom::static_atoms::LIMITINGCONEANGLE
+    };
+    ("line") => {
+        $crate::atom::static_atoms::LINE
+    };
+    ("linearGradient") => {
+        $crate::atom::static_atoms::LINEAR_GRADIENT
+    };
+    ("lineargradient") => {
+        $crate::atom::static_atoms::LINEARGRADIENT
+    };
+    ("link") => {
+        $crate::atom::static_atoms::LINK
+    };
+    ("list") => {
+        $crate::atom::static_atoms::LIST
+    };
+    ("listing") => {
+        $crate::atom::static_atoms::LISTING
+    };
+    ("loading") => {
+        $crate::atom::static_atoms::LOADING
+    };
+    ("longdesc") => {
+        $crate::atom::static_atoms::LONGDESC
+    };
+    ("loop") => {
+        $crate::atom::static_atoms::LOOP
+    };
+    ("low") => {
+        $crate::atom::static_atoms::LOW
+    };
+    ("maction") => {
+        $crate::atom::static_atoms::MACTION
+    };
+    ("main") => {
+        $crate::atom::static_atoms::MAIN
+    };
+    ("malignmark") => {
+        $crate::atom::static_atoms::MALIGNMARK
+    };
+    ("map") => {
+        $crate::atom::static_atoms::MAP
+    };
+    ("marginheight") => {
+        $crate::atom::static_atoms::MARGINHEIGHT
+    };
+    ("marginwidth") => {
+        $crate::atom::static_atoms::MARGINWIDTH
+    };
+    ("mark") => {
+        $crate::atom::static_atoms::MARK
+    };
+    ("marker") => {
+        $crate::atom::static_atoms::MARKER
+    };
+    ("markerHeight") => {
+        $crate::atom::static_atoms::MARKER_HEIGHT
+    };
+    ("markerUnits") => {
+        $crate::atom::static_atoms::MARKER_UNITS
+    };
+    ("markerWidth") => {
+        $crate::atom::static_atoms::MARKER_WIDTH
+    };
+    ("markerheight") => {
+        $crate::atom::static_atoms::MARKERHEIGHT
+    };
+    ("markerunits") => {
+        $crate::atom::static_atoms::MARKERUNITS
+    };
+    ("markerwidth") => {
+        $crate::atom::static_atoms::MARKERWIDTH
+    };
+    ("marquee") => {
+        $crate::atom::static_atoms::MARQUEE
+    };
+    ("mask") => {
+        $crate::atom::static_atoms::MASK
+    };
+    ("maskContentUnits") => {
+        $crate::atom::static_atoms::MASK_CONTENT_UNITS
+    };
+    ("maskUnits") => {
+        $crate::atom::static_atoms::MASK_UNITS
+    };
+    ("maskcontentunits") => {
+        $crate::atom::static_atoms::MASKCONTENTUNITS
+    };
+    ("maskunits") => {
+        $crate::atom::static_atoms::MASKUNITS
+    };
+    ("math") => {
+        $crate::atom::static_atoms::MATH
+    };
+    ("max") => {
+        $crate::atom::static_atoms::MAX
+    };
+    ("maxlength") => {
+        $crate::atom::static_atoms::MAXLENGTH
+    };
+    ("media") => {
+        $crate::atom::static_atoms::MEDIA
+    };
+    ("menu") => {
+        $crate::atom::static_atoms::MENU
+    };
+    ("menuitem") => {
+        $crate::atom::static_atoms::MENUITEM
+    };
+    ("merror") => {
+        $crate::atom::static_atoms::MERROR
+    };
+    ("meta") => {
+        $crate::atom::static_atoms::META
+    };
+    ("metadata") => {
+        $crate::atom::static_atoms::METADATA
+    };
+    ("meter") => {
+        $crate::atom::static_atoms::METER
+    };
+    ("method") => {
+        $crate::atom::static_atoms::METHOD
+    };
+    ("mfrac") => {
+        $crate::atom::static_atoms::MFRAC
+    };
+    ("mglyph") => {
+        $crate::atom::static_atoms::MGLYPH
+    };
+    ("mi") => {
+        $crate::atom::static_atoms::MI
+    };
+    ("min") => {
+        $crate::atom::static_atoms::MIN
+    };
+    ("minlength") => {
+        $crate::atom::static_atoms::MINLENGTH
+    };
+    ("mmultiscripts") => {
+        $crate::atom::static_atoms::MMULTISCRIPTS
+    };
+    ("mn") => {
+        $crate::atom::static_atoms::MN
+    };
+    ("mo") => {
+        $crate::atom::static_atoms::MO
+    };
+    ("mover") => {
+        $crate::atom::static_atoms::MOVER
+    };
+    ("mpadded") => {
+        $crate::atom::static_atoms::MPADDED
+    };
+    ("mphantom") => {
+        $crate::atom::static_atoms::MPHANTOM
+    };
+    ("mprescripts") => {
+        $crate::atom::static_atoms::MPRESCRIPTS
+    };
+    ("mroot") => {
+        $crate::atom::static_atoms::MROOT
+    };
+    ("mrow") => {
+        $crate::atom::static_atoms::MROW
+    };
+    ("ms") => {
+        $crate::atom::static_atoms::MS
+    };
+    ("mspace") => {
+        $crate::atom::static_atoms::MSPACE
+    };
+    ("msqrt") => {
+        $crate::atom::static_atoms::MSQRT
+    };
+    ("mstyle") => {
+        $crate::atom::static_atoms::MSTYLE
+    };
+    ("msub") => {
+        $crate::atom::static_atoms::MSUB
+    };
+    ("msubsup") => {
+        $crate::atom::static_atoms::MSUBSUP
+    };
+    ("msup") => {
+        $crate::atom::static_atoms::MSUP
+    };
+    ("mtable") => {
+        $crate::atom::static_atoms::MTABLE
+    };
+    ("mtd") => {
+        $crate::atom::static_atoms::MTD
+    };
+    ("mtext") => {
+        $crate::atom::static_atoms::MTEXT
+    };
+    ("mtr") => {
+        $crate::atom::static_atoms::MTR
+    };
+    ("multicol") => {
+        $crate::atom::static_atoms::MULTICOL
+    };
+    ("multiple") => {
+        $crate::atom::static_atoms::MULTIPLE
+    };
+    ("munder") => {
+        $crate::atom::static_atoms::MUNDER
+    };
+    ("munderover") => {
+        $crate::atom::static_atoms::MUNDEROVER
+    };
+    ("muted") => {
+        $crate::atom::static_atoms::MUTED
+    };
+    ("name") => {
+        $crate::atom::static_atoms::NAME
+    };
+    ("nav") => {
+        $crate::atom::static_atoms::NAV
+    };
+    ("nextid") => {
+        $crate::atom::static_atoms::NEXTID
+    };
+    ("nobr") => {
+        $crate::atom::static_atoms::NOBR
+    };
+    ("noembed") => {
+        $crate::atom::static_atoms::NOEMBED
+    };
+    ("noframes") => {
+        $crate::atom::static_atoms::NOFRAMES
+    };
+    ("nohref") => {
+        $crate::atom::static_atoms::NOHREF
+    };
+    ("none") => {
+        $crate::atom::static_atoms::NONE
+    };
+    ("noresize") => {
+        $crate::atom::static_atoms::NORESIZE
+    };
+    ("noscript") => {
+        $crate::atom::static_atoms::NOSCRIPT
+    };
+    ("noshade") => {
+        $crate::atom::static_atoms::NOSHADE
+    };
+    ("novalidate") => {
+        $crate::atom::static_atoms::NOVALIDATE
+    };
+    ("nowrap") => {
+        $crate::atom::static_atoms::NOWRAP
+    };
+    ("numOctaves") => {
+        $crate::atom::static_atoms::NUM_OCTAVES
+    };
+    ("numoctaves") => {
+        $crate::atom::static_atoms::NUMOCTAVES
+    };
+    ("object") => {
+        $crate::atom::static_atoms::OBJECT
+    };
+    ("ol") => {
+        $crate::atom::static_atoms::OL
+    };
+    ("onabort") => {
+        $crate::atom::static_atoms::ONABORT
+    };
+    ("onafterprint") => {
+        $crate::atom::static_atoms::ONAFTERPRINT
+    };
+    ("onauxclick") => {
+        $crate::atom::static_atoms::ONAUXCLICK
+    };
+    ("onbeforeprint") => {
+        $crate::atom::static_atoms::ONBEFOREPRINT
+    };
+    ("onbeforeunload") => {
+        $crate::atom::static_atoms::ONBEFOREUNLOAD
+    };
+    ("onblur") => {
+        $crate::atom::static_atoms::ONBLUR
+    };
+    ("oncancel") => {
+        $crate::atom::static_atoms::ONCANCEL
+    };
+    ("oncanplay") => {
+        $crate::atom::static_atoms::ONCANPLAY
+    };
+    ("oncanplaythrough") => {
+        $crate::atom::static_atoms::ONCANPLAYTHROUGH
+    };
+    ("onchange") => {
+        $crate::atom::static_atoms::ONCHANGE
+    };
+    ("onclick") => {
+        $crate::atom::static_atoms::ONCLICK
+    };
+    ("onclose") => {
+        $crate::atom::static_atoms::ONCLOSE
+    };
+    ("oncontextmenu") => {
+        $crate::atom::static_atoms::ONCONTEXTMENU
+    };
+    ("oncopy") => {
+        $crate::atom::static_atoms::ONCOPY
+    };
+    ("oncuechange") => {
+        $crate::atom::static_atoms::ONCUECHANGE
+    };
+    ("oncut") => {
+        $crate::atom::static_atoms::ONCUT
+    };
+    ("ondblclick") => {
+        $crate::atom::static_atoms::ONDBLCLICK
+    };
+    ("ondrag") => {
+        $crate::atom::static_atoms::ONDRAG
+    };
+    ("ondragend") => {
+        $crate::atom::static_atoms::ONDRAGEND
+    };
+    ("ondragenter") => {
+        $crate::atom::static_atoms::ONDRAGENTER
+    };
+    ("ondragleave") => {
+        $crate::atom::static_atoms::ONDRAGLEAVE
+    };
+    ("ondragover") => {
+        $crate::atom::static_atoms::ONDRAGOVER
+    };
+    ("ondragstart") => {
+        $crate::atom::static_atoms::ONDRAGSTART
+    };
+    ("ondrop") => {
+        $crate::atom::static_atoms::ONDROP
+    };
+    ("ondurationchange") => {
+        $crate::atom::static_atoms::ONDURATIONCHANGE
+    };
+    ("onemptied") => {
+        $crate::atom::static_atoms::ONEMPTIED
+    };
+    ("onended") => {
+        $crate::atom::static_atoms::ONENDED
+    };
+    ("onerror") => {
+        $crate::atom::static_atoms::ONERROR
+    };
+    ("onfocus") => {
+        $crate::atom::static_atoms::ONFOCUS
+    };
+    ("onhashchange") => {
+        $crate::atom::static_atoms::ONHASHCHANGE
+    };
+    ("oninput") => {
+        $crate::atom::static_atoms::ONINPUT
+    };
+    ("oninvalid") => {
+        $crate::atom::static_atoms::ONINVALID
+    };
+    ("onkeydown") => {
+        $crate::atom::static_atoms::ONKEYDOWN
+    };
+    ("onkeypress") => {
+        $crate::atom::static_atoms::ONKEYPRESS
+    };
+    ("onkeyup") => {
+        $crate::atom::static_atoms::ONKEYUP
+    };
+    ("onload") => {
+        $crate::atom::static_atoms::ONLOAD
+    };
+    ("onloadeddata") => {
+        $crate::atom::static_atoms::ONLOADEDDATA
+    };
+    ("onloadedmetadata") => {
+        $crate::atom::static_atoms::ONLOADEDMETADATA
+    };
+    ("onloadstart") => {
+        $crate::atom::static_atoms::ONLOADSTART
+    };
+    ("onmessage") => {
+        $crate::atom::static_atoms::ONMESSAGE
+    };
+    ("onmousedown") => {
+        $crate::atom::static_atoms::ONMOUSEDOWN
+    };
+    ("onmouseenter") => {
+        $crate::atom::static_atoms::ONMOUSEENTER
+    };
+    ("onmouseleave") => {
+        $crate::atom::static_atoms::ONMOUSELEAVE
+    };
+    ("onmousemove") => {
+        $crate::atom::static_atoms::ONMOUSEMOVE
+    };
+    ("onmouseout") => {
+        $crate::atom::static_atoms::ONMOUSEOUT
+    };
+    ("onmouseover") => {
+        $crate::atom::static_atoms::ONMOUSEOVER
+    };
+    ("onmouseup") => {
+        $crate::atom::static_atoms::ONMOUSEUP
+    };
+    ("onoffline") => {
+        $crate::atom::static_atoms::ONOFFLINE
+    };
+    ("ononline") => {
+        $crate::atom::static_atoms::ONONLINE
+    };
+    ("onpagehide") => {
+        $crate::atom::static_atoms::ONPAGEHIDE
+    };
+    ("onpageshow") => {
+        $crate::atom::static_atoms::ONPAGESHOW
+    };
+    ("onpaste") => {
+        $crate::atom::static_atoms::ONPASTE
+    };
+    ("onpause") => {
+        $crate::atom::static_atoms::ONPAUSE
+    };
+    ("onplay") => {
+        $crate::atom::static_atoms::ONPLAY
+    };
+    ("onplaying") => {
+        $crate::atom::static_atoms::ONPLAYING
+    };
+    ("onpopstate") => {
+        $crate::atom::static_atoms::ONPOPSTATE
+    };
+    ("onprogress") => {
+        $crate::atom::static_atoms::ONPROGRESS
+    };
+    ("onratechange") => {
+        $crate::atom::static_atoms::ONRATECHANGE
+    };
+    ("onreset") => {
+        $crate::atom::static_atoms::ONRESET
+    };
+    ("onresize") => {
+        $crate::atom::static_atoms::ONRESIZE
+    };
+    ("onscroll") => {
+        $crate::atom::static_atoms::ONSCROLL
+    };
+    ("onsecuritypolicyviolation") => {
+        $crate::atom::static_atoms::ONSECURITYPOLICYVIOLATION
+    };
+    ("onseeked") => {
+        $crate::atom::static_atoms::ONSEEKED
+    };
+    ("onseeking") => {
+        $crate::atom::static_atoms::ONSEEKING
+    };
+    ("onselect") => {
+        $crate::atom::static_atoms::ONSELECT
+    };
+    ("onstalled") => {
+        $crate::atom::static_atoms::ONSTALLED
+    };
+    ("onstorage") => {
+        $crate::atom::static_atoms::ONSTORAGE
+    };
+    ("onsubmit") => {
+        $crate::atom::static_atoms::ONSUBMIT
+    };
+    ("onsuspend") => {
+        $crate::atom::static_atoms::ONSUSPEND
+    };
+    ("ontimeupdate") => {
+        $crate::atom::static_atoms::ONTIMEUPDATE
+    };
+    ("ontoggle") => {
+        $crate::atom::static_atoms::ONTOGGLE
+    };
+    ("onunload") => {
+        $crate::atom::static_atoms::ONUNLOAD
+    };
+    ("onvolumechange") => {
+        $crate::atom::static_atoms::ONVOLUMECHANGE
+    };
+    ("onwaiting") => {
+        $crate::atom::static_atoms::ONWAITING
+    };
+    ("onwheel") => {
+        $crate::atom::static_atoms::ONWHEEL
+    };
+    ("open") => {
+        $crate::atom::static_atoms::OPEN
+    };
+    ("optgroup") => {
+        $crate::atom::static_atoms::OPTGROUP
+    };
+    ("optimum") => {
+        $crate::atom::static_atoms::OPTIMUM
+    };
+    ("option") => {
+        $crate::atom::static_atoms::OPTION
+    };
+    ("output") => {
+        $crate::atom::static_atoms::OUTPUT
+    };
+    ("p") => {
+        $crate::atom::static_atoms::P
+    };
+    ("param") => {
+        $crate::atom::static_atoms::PARAM
+    };
+    ("path") => {
+        $crate::atom::static_atoms::PATH
+    };
+    ("pathLength") => {
+        $crate::atom::static_atoms::PATH_LENGTH
+    };
+    ("pathlength") => {
+        $crate::atom::static_atoms::PATHLENGTH
+    };
+    ("pattern") => {
+        $crate::atom::static_atoms::PATTERN
+    };
+    ("patternContentUnits") => {
+        $crate::atom::static_atoms::PATTERN_CONTENT_UNITS
+    };
+    ("patternTransform") => {
+        $crate::atom::static_atoms::PATTERN_TRANSFORM
+    };
+    ("patternUnits") => {
+        $crate::atom::static_atoms::PATTERN_UNITS
+    };
+    ("patterncontentunits") => {
+        $crate::atom::static_atoms::PATTERNCONTENTUNITS
+    };
+    ("patterntransform") => {
+        $crate::atom::static_atoms::PATTERNTRANSFORM
+    };
+    ("patternunits") => {
+        $crate::atom::static_atoms::PATTERNUNITS
+    };
+    ("picture") => {
+        $crate::atom::static_atoms::PICTURE
+    };
+    ("ping") => {
+        $crate::atom::static_atoms::PING
+    };
+    ("placeholder") => {
+        $crate::atom::static_atoms::PLACEHOLDER
+    };
+    ("plaintext") => {
+        $crate::atom::static_atoms::PLAINTEXT
+    };
+    ("playsinline") => {
+        $crate::atom::static_atoms::PLAYSINLINE
+    };
+    ("points") => {
+        $crate::atom::static_atoms::POINTS
+    };
+    ("pointsAtX") => {
+        $crate::atom::static_atoms::POINTS_AT_X
+    };
+    ("pointsAtY") => {
+        $crate::atom::static_atoms::POINTS_AT_Y
+    };
+    ("pointsAtZ") => {
+        $crate::atom::static_atoms::POINTS_AT_Z
+    };
+    ("pointsatx") => {
+        $crate::atom::static_atoms::POINTSATX
+    };
+    ("pointsaty") => {
+        $crate::atom::static_atoms::POINTSATY
+    };
+    ("pointsatz") => {
+        $crate::atom::static_atoms::POINTSATZ
+    };
+    ("polygon") => {
+        $crate::atom::static_atoms::POLYGON
+    };
+    ("polyline") => {
+        $crate::atom::static_atoms::POLYLINE
+    };
+    ("popover") => {
+        $crate::atom::static_atoms::POPOVER
+    };
+    ("popovertarget") => {
+        $crate::atom::static_atoms::POPOVERTARGET
+    };
+    ("popovertargetaction") => {
+        $crate::atom::static_atoms::POPOVERTARGETACTION
+    };
+    ("poster") => {
+        $crate::atom::static_atoms::POSTER
+    };
+    ("pre") => {
+        $crate::atom::static_atoms::PRE
+    };
+    ("preload") => {
+        $crate::atom::static_atoms::PRELOAD
+    };
+    ("preserveAlpha") => {
+        $crate::atom::static_atoms::PRESERVE_ALPHA
+    };
+    ("preserveAspectRatio") => {
+        $crate::atom::static_atoms::PRESERVE_ASPECT_RATIO
+    };
+    ("preservealpha") => {
+        $crate::atom::static_atoms::PRESERVEALPHA
+    };
+    ("preserveaspectratio") => {
+        $crate::atom::static_atoms::PRESERVEASPECTRATIO
+    };
+    ("primitiveUnits") => {
+        $crate::atom::static_atoms::PRIMITIVE_UNITS
+    };
+    ("primitiveunits") => {
+        $crate::atom::static_atoms::PRIMITIVEUNITS
+    };
+    ("profile") => {
+        $crate::atom::static_atoms::PROFILE
+    };
+    ("progress") => {
+        $crate::atom::static_atoms::PROGRESS
+    };
+    ("prompt") => {
+        $crate::atom::static_atoms::PROMPT
+    };
+    ("q") => {
+        $crate::atom::static_atoms::Q
+    };
+    ("r") => {
+        $crate::atom::static_atoms::R
+    };
+    ("radialGradient") => {
+        $crate::atom::static_atoms::RADIAL_GRADIENT
+    };
+    ("radialgradient") => {
+        $crate::atom::static_atoms::RADIALGRADIENT
+    };
+    ("rb") => {
+        $crate::atom::static_atoms::RB
+    };
+    ("readonly") => {
+        $crate::atom::static_atoms::READONLY
+    };
+    ("rect") => {
+        $crate::atom::static_atoms::RECT
+    };
+    ("refX") => {
+        $crate::atom::static_atoms::REF_X
+    };
+    ("refY") => {
+        $crate::atom::static_atoms::REF_Y
+    };
+    ("referrerpolicy") => {
+        $crate::atom::static_atoms::REFERRERPOLICY
+    };
+    ("refx") => {
+        $crate::atom::static_atoms::REFX
+    };
+    ("refy") => {
+        $crate::atom::static_atoms::REFY
+    };
+    ("rel") => {
+        $crate::atom::static_atoms::REL
+    };
+    ("repeatCount") => {
+        $crate::atom::static_atoms::REPEAT_COUNT
+    };
+    ("repeatDur") => {
+        $crate::atom::static_atoms::REPEAT_DUR
+    };
+    ("repeatcount") => {
+        $crate::atom::static_atoms::REPEATCOUNT
+    };
+    ("repeatdur") => {
+        $crate::atom::static_atoms::REPEATDUR
+    };
+    ("required") => {
+        $crate::atom::static_atoms::REQUIRED
+    };
+    ("requiredExtensions") => {
+        $crate::atom::static_atoms::REQUIRED_EXTENSIONS
+    };
+    ("requiredFeatures") => {
+        $crate::atom::static_atoms::REQUIRED_FEATURES
+    };
+    ("requiredextensions") => {
+        $crate::atom::static_atoms::REQUIREDEXTENSIONS
+    };
+    ("requiredfeatures") => {
+        $crate::atom::static_atoms::REQUIREDFEATURES
+    };
+    ("rev") => {
+        $crate::atom::static_atoms::REV
+    };
+    ("reversed") => {
+        $crate::atom::static_atoms::REVERSED
+    };
+    ("role") => {
+        $crate::atom::static_atoms::ROLE
+    };
+    ("rows") => {
+        $crate::atom::static_atoms::ROWS
+    };
+    ("rowspan") => {
+        $crate::atom::static_atoms::ROWSPAN
+    };
+    ("rp") => {
+        $crate::atom::static_atoms::RP
+    };
+    ("rt") => {
+        $crate::atom::static_atoms::RT
+    };
+    ("rtc") => {
+        $crate::atom::static_atoms::RTC
+    };
+    ("ruby") => {
+        $crate::atom::static_atoms::RUBY
+    };
+    ("rules") => {
+        $crate::atom::static_atoms::RULES
+    };
+    ("rx") => {
+        $crate::atom::static_atoms::RX
+    };
+    ("ry") => {
+        $crate::atom::static_atoms::RY
+    };
+    ("s") => {
+        $crate::atom::static_atoms::S
+    };
+    ("samp") => {
+        $crate::atom::static_atoms::SAMP
+    };
+    ("sandbox") => {
+        $crate::atom::static_atoms::SANDBOX
+    };
+    ("scheme") => {
+        $crate::atom::static_atoms::SCHEME
+    };
+    ("scope") => {
+        $crate::atom::static_atoms::SCOPE
+    };
+    ("script") => {
+        $crate::atom::static_atoms::SCRIPT
+    };
+    ("scrolling") => {
+        $crate::atom::static_atoms::SCROLLING
+    };
+    ("search") => {
+        $crate::atom::static_atoms::SEARCH
+    };
+    ("section") => {
+        $crate::atom::static_atoms::SECTION
+    };
+    ("select") => {
+        $crate::atom::static_atoms::SELECT
+    };
+    ("selected") => {
+        $crate::atom::static_atoms::SELECTED
+    };
+    ("semantics") => {
+        $crate::atom::static_atoms::SEMANTICS
+    };
+    ("set") => {
+        $crate::atom::static_atoms::SET
+    };
+    ("shadowrootclonable") => {
+        $crate::atom::static_atoms::SHADOWROOTCLONABLE
+    };
+    ("shadowrootdelegatesfocus") => {
+        $crate::atom::static_atoms::SHADOWROOTDELEGATESFOCUS
+    };
+    ("shadowrootmode") => {
+        $crate::atom::static_atoms::SHADOWROOTMODE
+    };
+    ("shape") => {
+        $crate::atom::static_atoms::SHAPE
+    };
+    ("show") => {
+        $crate::atom::static_atoms::SHOW
+    };
+    ("size") => {
+        $crate::atom::static_atoms::SIZE
+    };
+    ("sizes") => {
+        $crate::atom::static_atoms::SIZES
+    };
+    ("slot") => {
+        $crate::atom::static_atoms::SLOT
+    };
+    ("small") => {
+        $crate::atom::static_atoms::SMALL
+    };
+    ("source") => {
+        $crate::atom::static_atoms::SOURCE
+    };
+    ("space") => {
+        $crate::atom::static_atoms::SPACE
+    };
+    ("spacer") => {
+        $crate::atom::static_atoms::SPACER
+    };
+    ("span") => {
+        $crate::atom::static_atoms::SPAN
+    };
+    ("specularConstant") => {
+        $crate::atom::static_atoms::SPECULAR_CONSTANT
+    };
+    ("specularExponent") => {
+        $crate::atom::static_atoms::SPECULAR_EXPONENT
+    };
+    ("specularconstant") => {
+        $crate::atom::static_atoms::SPECULARCONSTANT
+    };
+    ("specularexponent") => {
+        $crate::atom::static_atoms::SPECULAREXPONENT
+    };
+    ("spellcheck") => {
+        $crate::atom::static_atoms::SPELLCHECK
+    };
+    ("spreadMethod") => {
+        $crate::atom::static_atoms::SPREAD_METHOD
+    };
+    ("spreadmethod") => {
+        $crate::atom::static_atoms::SPREADMETHOD
+    };
+    ("src") => {
+        $crate::atom::static_atoms::SRC
+    };
+    ("srcdoc") => {
+        $crate::atom::static_atoms::SRCDOC
+    };
+    ("srclang") => {
+        $crate::atom::static_atoms::SRCLANG
+    };
+    ("srcset") => {
+        $crate::atom::static_atoms::SRCSET
+    };
+    ("standby") => {
+        $crate::atom::static_atoms::STANDBY
+    };
+    ("start") => {
+        $crate::atom::static_atoms::START
+    };
+    ("startOffset") => {
+        $crate::atom::static_atoms::START_OFFSET
+    };
+    ("startoffset") => {
+        $crate::atom::static_atoms::STARTOFFSET
+    };
+    ("stdDeviation") => {
+        $crate::atom::static_atoms::STD_DEVIATION
+    };
+    ("stddeviation") => {
+        $crate::atom::static_atoms::STDDEVIATION
+    };
+    ("step") => {
+        $crate::atom::static_atoms::STEP
+    };
+    ("stitchTiles") => {
+        $crate::atom::static_atoms::STITCH_TILES
+    };
+    ("stitchtiles") => {
+        $crate::atom::static_atoms::STITCHTILES
+    };
+    ("stop") => {
+        $crate::atom::static_atoms::STOP
+    };
+    ("strike") => {
+        $crate::atom::static_atoms::STRIKE
+    };
+    ("stroke") => {
+        $crate::atom::static_atoms::STROKE
+    };
+    ("strong") => {
+        $crate::atom::static_atoms::STRONG
+    };
+    ("style") => {
+        $crate::atom::static_atoms::STYLE
+    };
+    ("sub") => {
+        $crate::atom::static_atoms::SUB
+    };
+    ("summary") => {
+        $crate::atom::static_atoms::SUMMARY
+    };
+    ("sup") => {
+        $crate::atom::static_atoms::SUP
+    };
+    ("surfaceScale") => {
+        $crate::atom::static_atoms::SURFACE_SCALE
+    };
+    ("surfacescale") => {
+        $crate::atom::static_atoms::SURFACESCALE
+    };
+    ("svg") => {
+        $crate::atom::static_atoms::SVG
+    };
+    ("switch") => {
+        $crate::atom::static_atoms::SWITCH
+    };
+    ("symbol") => {
+        $crate::atom::static_atoms::SYMBOL
+    };
+    ("systemLanguage") => {
+        $crate::atom::static_atoms::SYSTEM_LANGUAGE
+    };
+    ("systemlanguage") => {
+        $crate::atom::static_atoms::SYSTEMLANGUAGE
+    };
+    ("tabindex") => {
+        $crate::atom::static_atoms::TABINDEX
+    };
+    ("table") => {
+        $crate::atom::static_atoms::TABLE
+    };
+    ("tableValues") => {
+        $crate::atom::static_atoms::TABLE_VALUES
+    };
+    ("tablevalues") => {
+        $crate::atom::static_atoms::TABLEVALUES
+    };
+    ("target") => {
+        $crate::atom::static_atoms::TARGET
+    };
+    ("targetX") => {
+        $crate::atom::static_atoms::TARGET_X
+    };
+    ("targetY") => {
+        $crate::atom::static_atoms::TARGET_Y
+    };
+    ("targetx") => {
+        $crate::atom::static_atoms::TARGETX
+    };
+    ("targety") => {
+        $crate::atom::static_atoms::TARGETY
+    };
+    ("tbody") => {
+        $crate::atom::static_atoms::TBODY
+    };
+    ("td") => {
+        $crate::atom::static_atoms::TD
+    };
+    ("template") => {
+        $crate::atom::static_atoms::TEMPLATE
+    };
+    ("text") => {
+        $crate::atom::static_atoms::TEXT
+    };
+    ("textLength") => {
+        $crate::atom::static_atoms::TEXT_LENGTH
+    };
+    ("textPath") => {
+        $crate::atom::static_atoms::TEXT_PATH
+    };
+    ("textarea") => {
+        $crate::atom::static_atoms::TEXTAREA
+    };
+    ("textlength") => {
+        $crate::atom::static_atoms::TEXTLENGTH
+    };
+    ("textpath") => {
+        $crate::atom::static_atoms::TEXTPATH
+    };
+    ("tfoot") => {
+        $crate::atom::static_atoms::TFOOT
+    };
+    ("th") => {
+        $crate::atom::static_atoms::TH
+    };
+    ("thead") => {
+        $crate::atom::static_atoms::THEAD
+    };
+    ("time") => {
+        $crate::atom::static_atoms::TIME
+    };
+    ("title") => {
+        $crate::atom::static_atoms::TITLE
+    };
+    ("tr") => {
+        $crate::atom::static_atoms::TR
+    };
+    ("track") => {
+        $crate::atom::static_atoms::TRACK
+    };
+    ("transform") => {
+        $crate::atom::static_atoms::TRANSFORM
+    };
+    ("translate") => {
+        $crate::atom::static_atoms::TRANSLATE
+    };
+    ("tspan") => {
+        $crate::atom::static_atoms::TSPAN
+    };
+    ("tt") => {
+        $crate::atom::static_atoms::TT
+    };
+    ("type") => {
+        $crate::atom::static_atoms::TYPE
+    };
+    ("u") => {
+        $crate::atom::static_atoms::U
+    };
+    ("ul") => {
+        $crate::atom::static_atoms::UL
+    };
+    ("use") => {
+        $crate::atom::static_atoms::USE
+    };
+    ("usemap") => {
+        $crate::atom::static_atoms::USEMAP
+    };
+    ("valign") => {
+        $crate::atom::static_atoms::VALIGN
+    };
+    ("value") => {
+        $crate::atom::static_atoms::VALUE
+    };
+    ("valuetype") => {
+        $crate::atom::static_atoms::VALUETYPE
+    };
+    ("var") => {
+        $crate::atom::static_atoms::VAR
+    };
+    ("version") => {
+        $crate::atom::static_atoms::VERSION
+    };
+    ("video") => {
+        $crate::atom::static_atoms::VIDEO
+    };
+    ("view") => {
+        $crate::atom::static_atoms::VIEW
+    };
+    ("viewBox") => {
+        $crate::atom::static_atoms::VIEW_BOX
+    };
+    ("viewTarget") => {
+        $crate::atom::static_atoms::VIEW_TARGET
+    };
+    ("viewbox") => {
+        $crate::atom::static_atoms::VIEWBOX
+    };
+    ("viewtarget") => {
+        $crate::atom::static_atoms::VIEWTARGET
+    };
+    ("vlink") => {
+        $crate::atom::static_atoms::VLINK
+    };
+    ("vspace") => {
+        $crate::atom::static_atoms::VSPACE
+    };
+    ("wbr") => {
+        $crate::atom::static_atoms::WBR
+    };
+    ("width") => {
+        $crate::atom::static_atoms::WIDTH
+    };
+    ("wrap") => {
+        $crate::atom::static_atoms::WRAP
+    };
+    ("writingsuggestions") => {
+        $crate::atom::static_atoms::WRITINGSUGGESTIONS
+    };
+    ("x") => {
+        $crate::atom::static_atoms::X
+    };
+    ("x1") => {
+        $crate::atom::static_atoms::X1
+    };
+    ("x2") => {
+        $crate::atom::static_atoms::X2
+    };
+    ("xChannelSelector") => {
+        $crate::atom::static_atoms::X_CHANNEL_SELECTOR
+    };
+    ("xchannelselector") => {
+        $crate::atom::static_atoms::XCHANNELSELECTOR
+    };
+    ("xlink") => {
+        $crate::atom::static_atoms::XLINK
+    };
+    ("xlink:actuate") => {
+        $crate::atom::static_atoms::XLINK_ACTUATE
+    };
+    ("xlink:arcrole") => {
+        $crate::atom::static_atoms::XLINK_ARCROLE
+    };
+    ("xlink:href") => {
+        $crate::atom::static_atoms::XLINK_HREF
+    };
+    ("xlink:role") => {
+        $crate::atom::static_atoms::XLINK_ROLE
+    };
+    ("xlink:show") => {
+        $crate::atom::static_atoms::XLINK_SHOW
+    };
+    ("xlink:title") => {
+        $crate::atom::static_atoms::XLINK_TITLE
+    };
+    ("xlink:type") => {
+        $crate::atom::static_atoms::XLINK_TYPE
+    };
+    ("xml:lang") => {
+        $crate::atom::static_atoms::XML_LANG
+    };
+    ("xml:space") => {
+        $crate::atom::static_atoms::XML_SPACE
+    };
+    ("xmlns") => {
+        $crate::atom::static_atoms::XMLNS
+    };
+    ("xmlns:xlink") => {
+        $crate::atom::static_atoms::XMLNS_XLINK
+    };
+    ("xmp") => {
+        $crate::atom::static_atoms::XMP
+    };
+    ("y") => {
+        $crate::atom::static_atoms::Y
+    };
+    ("y1") => {
+        $crate::atom::static_atoms::Y1
+    };
+    ("y2") => {
+        $crate::atom::static_atoms::Y2
+    };
+    ("yChannelSelector") => {
+        $crate::atom::static_atoms::Y_CHANNEL_SELECTOR
+    };
+    ("ychannelselector") => {
+        $crate::atom::static_atoms::YCHANNELSELECTOR
+    };
+    ("zoomAndPan") => {
+        $crate::atom::static_atoms::ZOOM_AND_PAN
+    };
+    ("zoomandpan") => {
+        $crate::atom::static_atoms::ZOOMANDPAN
+    };
+}
@@ -0,0 +1,707 @@
+// A formatter on top of the parsed tree. Unlike the serialization, both functions change the whitespace of the document:
+// the pretty-printer puts every block on its own indented line, the minifier removes everything the parser would add back.
+use crate::atom;
+use crate::html::atom::Atom;
+use crate::html::tree_builder::serializing::{
+    escape_a_string, serialize_an_attribute_name, serialize_attributes, serializes_as_void,
+};
+use crate::html::tree_builder::tree::{Element, Namespace, Node, Tree};
+
+const INDENTATION: &str = "  ";
+
+// the elements whose content is written as it is, their whitespace is significant or they contain raw text
+const VERBATIM_ELEMENTS: [Atom; 11] = [
+    atom!("pre"),
+    atom!("textarea"),
+    atom!("listing"),
+    atom!("plaintext"),
+    atom!("script"),
+    atom!("style"),
+    atom!("xmp"),
+    atom!("iframe"),
+    atom!("noembed"),
+    atom!("noframes"),
+    atom!("noscript"),
+];
+
+// https://html.spec.whatwg.org/multipage/dom.html#phrasing-content-2
+// the elements that are laid out on a line together with the text around them
+const INLINE_ELEMENTS: [Atom; 34] = [
+    atom!("a"),
+    atom!("abbr"),
+    atom!("b"),
+    atom!("bdi"),
+    atom!("bdo"),
+    atom!("br"),
+    atom!("button"),
+    atom!("cite"),
+    atom!("code"),
+    atom!("data"),
+    atom!("del"),
+    atom!("dfn"),
+    atom!("em"),
+    atom!("i"),
+    atom!("img"),
+    atom!("input"),
+    atom!("ins"),
+    atom!("kbd"),
+    atom!("label"),
+    atom!("mark"),
+    atom!("meter"),
+    atom!("output"),
+    atom!("progress"),
+    atom!("q"),
+    atom!("s"),
+    atom!("samp"),
+    atom!("select"),
+    atom!("small"),
+    atom!("span"),
+    atom!("strong"),
+    atom!("sub"),
+    atom!("sup"),
+    atom!("time"),
+    atom!("u"),
+];
+
+// https://html.spec.whatwg.org/multipage/syntax.html#optional-tags
+// A p element's end tag may be omitted if the p element is immediately followed by one of these elements
+const ELEMENTS_CLOSING_A_P_ELEMENT: [Atom; 32] = [
+    atom!("address"),
+    atom!("article"),
+    atom!("aside"),
+    atom!("blockquote"),
+    atom!("details"),
+    atom!("dialog"),
+    atom!("div"),
+    atom!("dl"),
+    atom!("fieldset"),
+    atom!("figcaption"),
+    atom!("figure"),
+    atom!("footer"),
+    atom!("form"),
+    atom!("h1"),
+    atom!("h2"),
+    atom!("h3"),
+    atom!("h4"),
+    atom!("h5"),
+    atom!("h6"),
+    atom!("header"),
+    atom!("hgroup"),
+    atom!("hr"),
+    atom!("main"),
+    atom!("menu"),
+    atom!("nav"),
+    atom!("ol"),
+    atom!("p"),
+    atom!("pre"),
+    atom!("search"),
+    atom!("section"),
+    atom!("table"),
+    atom!("ul"),
+];
+
+// https://infra.spec.whatwg.org/#ascii-whitespace
+fn is_ascii_whitespace(c: char) -> bool {
+    matches!(c, '\t' | '\n' | '\u{000C}' | '\r' | ' ')
+}
+
+// replaces each run of whitespace with a single space
+fn collapse_whitespace(data: &str) -> String {
+    let mut collapsed = String::with_capacity(data.len());
+    let mut in_whitespace = false;
+    for c in data.chars() {
+        if is_ascii_whitespace(c) {
+            if !in_whitespace {
+                collapsed.push(' ');
+            }
+            in_whitespace = true;
+        } else {
+            collapsed.push(c);
+            in_whitespace = false;
+        }
+    }
+    collapsed
+}
+
+fn is_verbatim(element: &Element) -> bool {
+    element.is_html(&VERBATIM_ELEMENTS)
+}
+
+fn is_inline(element: &Element) -> bool {
+    element.is_html(&INLINE_ELEMENTS)
+}
+
+// the children of the document, or of the root of the tree
+fn top_level_nodes(tree: &Tree<Node>) -> Vec<usize> {
+    tree.get_root()
+        .map(|root| tree.get_node(root).children.clone())
+        .unwrap_or_default()
+}
+
+// the children of the node, the children of the template contents for a template element
+fn child_nodes(tree: &Tree<Node>, node: usize) -> &[usize] {
+    let node = tree
+        .get_element(node)
+        .and_then(|element| element.template_contents)
+        .unwrap_or(node);
+    &tree.get_node(node).children
+}
+
+// Pretty-prints the document: block elements and comments start on their own line and are indented by their depth,
+// text and inline elements are written on the line of their block with their whitespace collapsed, verbatim elements like pre are kept as they are.
+pub fn pretty_print(tree: &Tree<Node>) -> String {
+    let mut printer = PrettyPrinter {
+        tree,
+        output: String::new(),
+    };
+    printer.block(&top_level_nodes(tree), 0);
+    printer.output
+}
+
+struct PrettyPrinter<'a> {
+    tree: &'a Tree<Node>,
+    output: String,
+}
+
+impl PrettyPrinter<'_> {
+    // true if the node and its descendants fit on a single line
+    fn is_inline_content(&self, node: usize) -> bool {
+        match &self.tree.get_node(node).data {
+            Node::Text(_) => true,
+            Node::Element(element) if is_verbatim(element) => true,
+            Node::Element(element) => {
+                is_inline(element)
+                    && child_nodes(self.tree, node)
+                        .iter()
+                        .all(|child| self.is_inline_content(*child))
+            }
+            _ => false,
+        }
+    }
+
+    // the nodes as lines, consecutive inline content shares a line
+    fn block(&mut self, nodes: &[usize], depth: usize) {
+        let mut line = String::new();
+        for node in nodes {
+            let is_inline_element = matches!(
+                &self.tree.get_node(*node).data,
+                Node::Element(element) if is_inline(element)
+            );
+            if matches!(self.tree.get_node(*node).data, Node::Text(_))
+                || (is_inline_element && self.is_inline_content(*node))
+            {
+                self.inline(*node, &mut line);
+                continue;
+            }
+            self.line(&line, depth);
+            line.clear();
+            self.block_node(*node, depth);
+        }
+        self.line(&line, depth);
+    }
+
+    fn line(&mut self, line: &str, depth: usize) {
+        let line = line.trim_matches(' ');
+        if line.is_empty() {
+            return;
+        }
+        self.output.push_str(&INDENTATION.repeat(depth));
+        self.output.push_str(line);
+        self.output.push('\n');
+    }
+
+    fn block_node(&mut self, node: usize, depth: usize) {
+        let tree = self.tree;
+        match &tree.get_node(node).data {
+            Node::Element(element) => {
+                let children = child_nodes(tree, node);
+                // foreign elements without children are self-closing, like in the serialization
+                let is_self_closing = element.namespace != Namespace::Html && children.is_empty();
+                if is_verbatim(element) || serializes_as_void(element) || is_self_closing {
+                    self.line(&tree.outer_html(node), depth);
+                } else if children.iter().all(|child| self.is_inline_content(*child)) {
+                    // a block with only inline content is written on a single line
+                    let mut content = String::new();
+                    for child in children {
+                        self.inline(*child, &mut content);
+                    }
+                    let content = content.trim_matches(' ');
+                    let mut line = String::new();
+                    if element.namespace != Namespace::Html && content.is_empty() {
+                        // the whitespace is dropped, so the element is written like one without children
+                        line.push('<');
+                        line.push_str(&element.local_name);
+                        serialize_attributes(element, &mut line);
+                        line.push_str("/>");
+                    } else {
+                        start_tag(element, &mut line);
+                        line.push_str(content);
+                        end_tag(element, &mut line);
+                    }
+                    self.line(&line, depth);
+                } else {
+                    let mut line = String::new();
+                    start_tag(element, &mut line);
+                    self.line(&line, depth);
+                    self.block(children, depth + 1);
+                    line.clear();
+                    end_tag(element, &mut line);
+                    self.line(&line, depth);
+                }
+            }
+            Node::Comment(_) | Node::DocumentType(_) => self.line(&tree.outer_html(node), depth),
+            Node::Text(_) | Node::Document(_) | Node::DocumentFragment => {
+                unreachable!("text is inline content")
+            }
+        }
+    }
+
+    fn inline(&self, node: usize, line: &mut String) {
+        match &self.tree.get_node(node).data {
+            Node::Text(text) => {
+                let collapsed = collapse_whitespace(&text.data);
+                // a space at the start of a text is already at the end of the line
+                let collapsed = if line.is_empty() || line.ends_with(' ') {
+                    collapsed.trim_start_matches(' ')
+                } else {
+                    &collapsed
+                };
+                escape_a_string(collapsed, false, line);
+            }
+            Node::Element(element) if is_verbatim(element) => {
+                line.push_str(&self.tree.outer_html(node))
+            }
+            Node::Element(element) => {
+                start_tag(element, line);
+                for child in child_nodes(self.tree, node) {
+                    self.inline(*child, line);
+                }
+                end_tag(element, line);
+            }
+            _ => unreachable!("comments and doctypes are blocks"),
+        }
+    }
+}
+
+fn start_tag(element: &Element, s: &mut String) {
+    s.push('<');
+    s.push_str(&element.local_name);
+    serialize_attributes(element, s);
+    s.push('>');
+}
+
+fn end_tag(element: &Element, s: &mut String) {
+    if serializes_as_void(element) {
+        return;
+    }
+    s.push_str("</");
+    s.push_str(&element.local_name);
+    s.push('>');
+}
+
+// Minifies the document: collapses the whitespace outside of verbatim elements, drops the whitespace between blocks,
+// omits the optional start and end tags and writes the attribute values without quotes where that's safe.
+pub fn minify(tree: &Tree<Node>) -> String {
+    let minifier = Minifier { tree };
+    let mut output = String::new();
+    let root = tree.get_root();
+    let children = minifier.children(root);
+    for (index, child) in children.iter().enumerate() {
+        minifier.node(child, children.get(index + 1), root, &mut output);
+    }
+    output
+}
+
+// a node of the minified document, the text with its collapsed whitespace
+enum Minified {
+    Element(usize),
+    Text(String),
+    Comment(usize),
+    Doctype(usize),
+}
+
+struct Minifier<'a> {
+    tree: &'a Tree<Node>,
+}
+
+impl Minifier<'_> {
+    fn element(&self, node: &Minified) -> Option<&Element> {
+        match node {
+            Minified::Element(index) => self.tree.get_element(*index),
+            _ => None,
+        }
+    }
+
+    fn is_block(&self, node: &Minified) -> bool {
+        self.element(node)
+            .is_some_and(|element| !is_inline(element))
+    }
+
+    // the children without the whitespace between blocks, with the whitespace of the text collapsed
+    fn children(&self, node: Option<usize>) -> Vec<Minified> {
+        let Some(node) = node else {
+            return Vec::new();
+        };
+        let verbatim = self.tree.get_element(node).is_some_and(is_verbatim);
+        let mut children: Vec<Minified> = Vec::new();
+        for child in child_nodes(self.tree, node) {
+            match &self.tree.get_node(*child).data {
+                Node::Element(_) => children.push(Minified::Element(*child)),
+                Node::Comment(_) => children.push(Minified::Comment(*child)),
+                Node::DocumentType(_) => children.push(Minified::Doctype(*child)),
+                Node::Text(text) if verbatim => children.push(Minified::Text(text.data.clone())),
+                Node::Text(text) => children.push(Minified::Text(collapse_whitespace(&text.data))),
+                Node::Document(_) | Node::DocumentFragment => {
+                    unreachable!("the document is the root")
+                }
+            }
+        }
+        if verbatim {
+            return children;
+        }
+        // a space next to a block, or at the start or the end of a parent that is a block, doesn't render
+        // at the edges of an inline element the space is rendered on the line of its block
+        let parent_is_block = !self.tree.get_element(node).is_some_and(is_inline);
+        let mut index = 0;
+        while index < children.len() {
+            if let Minified::Text(data) = &children[index] {
+                let previous_is_block = match index.checked_sub(1) {
+                    Some(previous) => self.is_block(&children[previous]),
+                    None => parent_is_block,
+                };
+                let next_is_block = match children.get(index + 1) {
+                    Some(next) => self.is_block(next),
+                    None => parent_is_block,
+                };
+                let mut data = data.as_str();
+                if previous_is_block {
+                    data = data.trim_start_matches(' ');
+                }
+                if next_is_block {
+                    data = data.trim_end_matches(' ');
+                }
+                if data.is_empty() {
+                    children.remove(index);
+                    continue;
+                }
+                children[index] = Minified::Text(data.to_string());
+            }
+            index += 1;
+        }
+        children
+    }
+
+    fn node(
+        &self,
+        node: &Minified,
+        next_sibling: Option<&Minified>,
+        parent: Option<usize>,
+        output: &mut String,
+    ) {
+        match node {
+            Minified::Text(data) => escape_a_string(data, false, output),
+            Minified::Comment(index) | Minified::Doctype(index) => {
+                output.push_str(&self.tree.outer_html(*index))
+            }
+            Minified::Element(index) => {
+                let element = self.element(node).unwrap();
+                // the raw text and the leading line feed of a pre element are written like the serialization does
+                if is_verbatim(element) {
+                    self.start_tag(element, false, output);
+                    output.push_str(&self.tree.inner_html(*index));
+                    output.push_str("</");
+                    output.push_str(&element.local_name);
+                    output.push('>');
+                    return;
+                }
+                let children = self.children(Some(*index));
+                let is_self_closing = element.namespace != Namespace::Html && children.is_empty();
+                if !element.attributes.is_empty()
+                    || !self.start_tag_may_be_omitted(element, &children)
+                {
+                    self.start_tag(element, is_self_closing, output);
+                }
+                if is_self_closing || serializes_as_void(element) {
+                    return;
+                }
+                for (position, child) in children.iter().enumerate() {
+                    self.node(child, children.get(position + 1), Some(*index), output);
+                }
+                if !self.end_tag_may_be_omitted(element, next_sibling, parent) {
+                    output.push_str("</");
+                    output.push_str(&element.local_name);
+                    output.push('>');
+                }
+            }
+        }
+    }
+
+    // the attribute values without quotes if they have no characters that end an unquoted value
+    fn start_tag(&self, element: &Element, is_self_closing: bool, output: &mut String) {
+        output.push('<');
+        output.push_str(&element.local_name);
+        let mut last_value_is_unquoted = false;
+        for attribute in &element.attributes {
+            output.push(' ');
+            serialize_an_attribute_name(attribute, output);
+            last_value_is_unquoted = false;
+            if attribute.value.is_empty() {
+                continue;
+            }
+            output.push('=');
+            // https://html.spec.whatwg.org/multipage/syntax.html#unquoted
+            // the attribute value must not contain any literal ASCII whitespace, any U+0022 QUOTATION MARK characters ("), U+0027 APOSTROPHE characters ('), U+003D EQUALS SIGN characters (=), U+003C LESS-THAN SIGN characters (<), U+003E GREATER-THAN SIGN characters (>), or U+0060 GRAVE ACCENT characters (`), and must not be the empty string.
+            if attribute
+                .value
+                .chars()
+                .any(|c| is_ascii_whitespace(c) || matches!(c, '"' | '\'' | '=' | '<' | '>' | '`'))
+            {
+                output.push('"');
+                escape_a_string(&attribute.value, true, output);
+                output.push('"');
+            } else {
+                escape_a_string(&attribute.value, true, output);
+                last_value_is_unquoted = true;
+            }
+        }
+        if is_self_closing {
+            // the solidus would be part of an unquoted value
+            if last_value_is_unquoted {
+                output.push(' ');
+            }
+            output.push('/');
+        }
+        output.push('>');
+    }
+
+    // https://html.spec.whatwg.org/multipage/syntax.html#optional-tags
+    fn start_tag_may_be_omitted(&self, element: &Element, children: &[Minified]) -> bool {
+        if element.namespace != Namespace::Html {
+            return false;
+        }
+        let first_child = children.first();
+        match element.local_name {
+            // An html element's start tag may be omitted if the first thing inside the html element is not a comment.
+            atom!("html") => !matches!(first_child, Some(Minified::Comment(_))),
+            // A head element's start tag may be omitted if the element is empty, or if the first thing inside the head element is an element.
+            atom!("head") => matches!(first_child, None | Some(Minified::Element(_))),
+            // A body element's start tag may be omitted if the element is empty, or if the first thing inside the body element is not ASCII whitespace or a comment, except if the first thing inside the body element is a meta, noscript, link, script, style, or template element.
+            atom!("body") => match first_child {
+                None => true,
+                Some(Minified::Comment(_)) => false,
+                Some(Minified::Text(data)) => !data.starts_with(is_ascii_whitespace),
+                Some(child) => !self.element(child).is_some_and(|element| {
+                    element.is_html(&[
+                        atom!("meta"),
+                        atom!("noscript"),
+                        atom!("link"),
+                        atom!("script"),
+                        atom!("style"),
+                        atom!("template"),
+                    ])
+                }),
+            },
+            _ => false,
+        }
+    }
+
+    // https://html.spec.whatwg.org/multipage/syntax.html#optional-tags
+    // the end tags of the table elements are kept, the tree builder doesn't parse tables yet
+    fn end_tag_may_be_omitted(
+        &self,
+        element: &Element,
+        next_sibling: Option<&Minified>,
+        parent: Option<usize>,
+    ) -> bool {
+        if element.namespace != Namespace::Html {
+            return false;
+        }
+        let next_element = next_sibling.and_then(|sibling| self.element(sibling));
+        let next_is =
+            |local_names: &[Atom]| next_element.is_some_and(|next| next.is_html(local_names));
+        match element.local_name {
+            // An html element's end tag may be omitted if the html element is not immediately followed by a comment.
+            // A body element's end tag may be omitted if the body element is not immediately followed by a comment.
+            atom!("html") | atom!("body") => !matches!(next_sibling, Some(Minified::Comment(_))),
+            // A head element's end tag may be omitted if the head element is not immediately followed by ASCII whitespace or a comment.
+            atom!("head") => match next_sibling {
+                Some(Minified::Comment(_)) => false,
+                Some(Minified::Text(data)) => !data.starts_with(is_ascii_whitespace),
+                _ => true,
+            },
+            // An li element's end tag may be omitted if the li element is immediately followed by another li element or if there is no more content in the parent element.
+            atom!("li") => next_sibling.is_none() || next_is(&[atom!("li")]),
+            // A dt element's end tag may be omitted if the dt element is immediately followed by another dt element or a dd element.
+            atom!("dt") => next_is(&[atom!("dt"), atom!("dd")]),
+            // A dd element's end tag may be omitted if the dd element is immediately followed by another dd element or a dt element, or if there is no more content in the parent element.
+            atom!("dd") => next_sibling.is_none() || next_is(&[atom!("dd"), atom!("dt")]),
+            // A p element's end tag may be omitted if the p element is immediately followed by an address, article, aside, blockquote, details, dialog, div, dl, fieldset, figcaption, figure, footer, form, h1, h2, h3, h4, h5, h6, header, hgroup, hr, main, menu, nav, ol, p, pre, search, section, table, or ul element, or if there is no more content in the parent element and the parent element is an HTML element that is not an a, audio, del, ins, map, noscript, or video element, or an autonomous custom element.
+            atom!("p") => {
+                next_is(&ELEMENTS_CLOSING_A_P_ELEMENT)
+                    || (next_sibling.is_none()
+                        && parent
+                            .and_then(|parent| self.tree.get_element(parent))
+                            .is_some_and(|parent| {
+                                parent.namespace == Namespace::Html
+                                    && !parent.is_html(&[
+                                        atom!("a"),
+                                        atom!("audio"),
+                                        atom!("del"),
+                                        atom!("ins"),
+                                        atom!("map"),
+                                        atom!("noscript"),
+                                        atom!("video"),
+                                    ])
+                                    && !parent.local_name.contains('-')
+                            }))
+            }
+            // An option element's end tag may be omitted if the option element is immediately followed by another option element, if it is immediately followed by an optgroup element, if it is immediately followed by an hr element, or if there is no more content in the parent element.
+            atom!("option") => {
+                next_sibling.is_none()
+                    || next_is(&[atom!("option"), atom!("optgroup"), atom!("hr")])
+            }
+            // An optgroup element's end tag may be omitted if the optgroup element is immediately followed by another optgroup element, if it is immediately followed by an hr element, or if there is no more content in the parent element.
+            atom!("optgroup") => {
+                next_sibling.is_none() || next_is(&[atom!("optgroup"), atom!("hr")])
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs::File;
+
+    use crate::html::formatter::{minify, pretty_print};
+    use crate::html::tokenizer::Tokenizer;
+    use crate::html::tree_builder::tester::TestReader;
+    use crate::html::tree_builder::{ParserOptions, TreeBuilder};
+
+    fn parse(contents: &str) -> TreeBuilder {
+        let mut tree_builder = TreeBuilder::new(ParserOptions::default());
+        tree_builder.build(Tokenizer::new(contents));
+        tree_builder
+    }
+
+    const DOCUMENT: &str = "<html><head><title>A title</title></head>\n<body>\n  <div id=main class='a b'><p>Some <b>bold</b>\n   text</p><p>more</p>\n<ul><li>one</li>\n<li>two</li></ul><pre>\n  keep\n    this</pre><!-- note --></div>\n</body></html>";
+
+    #[test]
+    fn pretty_print_document() {
+        let tree_builder = parse(DOCUMENT);
+        assert_eq!(
+            pretty_print(tree_builder.get_tree()),
+            r#"<html>
+  <head>
+    <title>A title</title>
+  </head>
+  <body>
+    <div id="main" class="a b">
+      <p>Some <b>bold</b> text</p>
+      <p>more</p>
+      <ul>
+        <li>one</li>
+        <li>two</li>
+      </ul>
+      <pre>  keep
+    this</pre>
+      <!-- note -->
+    </div>
+  </body>
+</html>
+"#
+        );
+    }
+
+    #[test]
+    fn minify_document() {
+        let tree_builder = parse(DOCUMENT);
+        assert_eq!(
+            minify(tree_builder.get_tree()),
+            "<title>A title</title><div id=main class=\"a b\"><p>Some <b>bold</b> text<p>more<ul><li>one<li>two</ul><pre>  keep\n    this</pre><!-- note --></div>"
+        );
+    }
+
+    #[test]
+    fn minify_keeps_spaces_in_inline_elements() {
+        for (input, minified) in [
+            ("a<b> bold </b>c", "a<b> bold </b>c"),
+            (
+                "<p> a <b>b</b> </p><div> <b>c</b> d</div>",
+                "<p>a <b>b</b><div><b>c</b> d</div>",
+            ),
+            (
+                "<div>a</div> <span> b </span> <div>c</div>",
+                "<div>a</div><span> b </span><div>c</div>",
+            ),
+        ] {
+            assert_eq!(minify(parse(input).get_tree()), minified, "{input}");
+        }
+    }
+
+    #[test]
+    fn format_doctype_and_foreign_elements() {
+        let tree_builder =
+            parse("<!DOCTYPE html><p>a</p><svg><path d=M0></path><g><rect/></g></svg>");
+        let tree = tree_builder.get_tree();
+        assert_eq!(
+            minify(tree),
+            "<!DOCTYPE html><p>a</p><svg><path d=M0 /><g><rect/></g></svg>"
+        );
+        assert_eq!(
+            pretty_print(tree),
+            r#"<!DOCTYPE html>
+<html>
+  <head></head>
+  <body>
+    <p>a</p>
+    <svg>
+      <path d="M0"/>
+      <g>
+        <rect/>
+      </g>
+    </svg>
+  </body>
+</html>
+"#
+        );
+    }
+
+    #[test]
+    fn pretty_print_foreign_element_with_whitespace() {
+        let formatted = pretty_print(parse("<svg>\n</svg><math> <mi> </mi></math>").get_tree());
+        assert_eq!(
+            formatted,
+            "<html>\n  <head></head>\n  <body>\n    <svg/>\n    <math>\n      <mi/>\n    </math>\n  </body>\n</html>\n"
+        );
+        assert_eq!(pretty_print(parse(&formatted).get_tree()), formatted);
+    }
+
+    // formatting the result of parsing the formatted document gives the same output
+    #[test]
+    fn formatting_is_idempotent() {
+        for file_path in [
+            "html-tests/body.dat",
+            "html-tests/character_references.dat",
+            "html-tests/comments.dat",
+            "html-tests/doctype.dat",
+            "html-tests/foreign.dat",
+            "html-tests/head.dat",
+            "html-tests/test1.dat",
+            "html-tests/whitespace.dat",
+        ] {
+            let test_reader = TestReader::<File>::new_from_file(file_path).unwrap();
+            for test_case in test_reader.filter(|test_case| test_case.document_fragment.is_none()) {
+                let contents = test_case.data.join("\n");
+                for format in [pretty_print, minify] {
+                    let formatted = format(parse(&contents).get_tree());
+                    assert_eq!(
+                        format(parse(&formatted).get_tree()),
+                        formatted,
+                        "{file_path}: {contents:?}"
+                    );
+                }
+            }
+        }
+    }
+}
@@ -1,2 +1,4 @@
+pub mod atom;
+pub mod formatter;
 pub mod tokenizer;
 pub mod tree_builder;
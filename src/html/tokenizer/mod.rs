@@ -1,12 +1,36 @@
-use std::collections::HashMap;
+mod named_character_references;
+
+use std::{borrow::Cow, cell::Cell, collections::VecDeque};
+
+use crate::html::{atom::Atom, tree_builder::tree::Namespace};
+
+use self::named_character_references::{LONGEST_NAME, NAMED_CHARACTER_REFERENCES};
 
 // DOCTYPE tokens have a name, a public identifier, a system identifier, and a force-quirks flag. When a DOCTYPE token is created, its name, public identifier, and system identifier must be marked as missing (which is a distinct state from the empty string), and the force-quirks flag must be set to off (its other state is on).
 #[derive(PartialEq, Eq, Debug, Default, Clone)]
 pub struct DOCTYPE {
     name: Option<String>,
-    // public_id: Option<String>,
-    // system_id: Option<String>,
-    // force_quirks: bool,
+    public_id: Option<String>,
+    system_id: Option<String>,
+    force_quirks: bool,
+}
+
+impl DOCTYPE {
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn public_id(&self) -> Option<&str> {
+        self.public_id.as_deref()
+    }
+
+    pub fn system_id(&self) -> Option<&str> {
+        self.system_id.as_deref()
+    }
+
+    pub fn force_quirks(&self) -> bool {
+        self.force_quirks
+    }
 }
 
 #[derive(PartialEq, Eq, Clone, Debug)]
@@ -15,59 +39,199 @@ pub enum TagType {
     EndTag,
 }
 
+// The tokens borrow from the input: text runs, comments and attribute values are slices of it, unless a character reference or a replaced character changes the data.
+// The names are atoms, the known names are not allocated at all.
 #[derive(PartialEq, Eq, Debug, Default, Clone)]
-pub struct Tag {
-    pub name: String,
-    // self_closing: bool,
-    attributes: HashMap<String, String>,
+pub struct Tag<'a> {
+    pub name: Atom,
+    pub self_closing: bool,
+    pub attributes: Vec<Attribute<'a>>,
 }
 
-impl Tag {
+impl<'a> Tag<'a> {
     pub fn new() -> Self {
         Tag {
-            name: String::new(),
-            attributes: HashMap::new(),
+            name: Atom::default(),
+            self_closing: false,
+            attributes: Vec::new(),
+        }
+    }
+
+    pub fn new_with_name(name: &str) -> Self {
+        Tag {
+            name: Atom::from(name),
+            self_closing: false,
+            attributes: Vec::new(),
+        }
+    }
+
+    pub fn get_attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|attribute| attribute.name == name)
+            .map(|attribute| attribute.value.as_ref())
+    }
+
+    // a tag that no longer borrows from the input
+    pub fn into_owned(self) -> Tag<'static> {
+        Tag {
+            name: self.name,
+            self_closing: self.self_closing,
+            attributes: self
+                .attributes
+                .into_iter()
+                .map(Attribute::into_owned)
+                .collect(),
         }
     }
 }
 
 #[derive(PartialEq, Eq, Debug)]
-pub enum Token {
+pub enum Token<'a> {
     Doctype(DOCTYPE),
-    StartTag(Tag),
-    EndTag(Tag),
-    Comment(String),
+    StartTag(Tag<'a>),
+    EndTag(Tag<'a>),
+    Comment(Cow<'a, str>),
     Character(char),
+    // a run of character tokens, the text between markup is emitted as one token
+    Characters(Cow<'a, str>),
     EndOfFile,
 }
 
+impl Token<'_> {
+    // a token that no longer borrows from the input, e.g. to keep it after the input is gone
+    pub fn into_owned(self) -> Token<'static> {
+        match self {
+            Token::Doctype(doctype) => Token::Doctype(doctype),
+            Token::StartTag(tag) => Token::StartTag(tag.into_owned()),
+            Token::EndTag(tag) => Token::EndTag(tag.into_owned()),
+            Token::Comment(data) => Token::Comment(Cow::Owned(data.into_owned())),
+            Token::Character(char) => Token::Character(char),
+            Token::Characters(data) => Token::Characters(Cow::Owned(data.into_owned())),
+            Token::EndOfFile => Token::EndOfFile,
+        }
+    }
+}
+
 // Comment and character tokens have data.
 
+// Attributes start out without a namespace, the tree builder adjusts the name, prefix and namespace for foreign elements.
+// https://html.spec.whatwg.org/multipage/parsing.html#adjust-foreign-attributes
+#[derive(PartialEq, Eq, Debug, Default, Clone)]
+pub struct Attribute<'a> {
+    pub name: Atom,
+    pub value: Cow<'a, str>,
+    pub prefix: Option<String>,
+    pub namespace: Option<Namespace>,
+}
+
+impl Attribute<'_> {
+    pub fn new(name: &str, value: &str) -> Attribute<'static> {
+        Attribute {
+            name: Atom::from(name),
+            value: Cow::Owned(value.to_string()),
+            prefix: None,
+            namespace: None,
+        }
+    }
+
+    // an attribute that no longer borrows from the input, the elements of the tree own their attributes
+    pub fn into_owned(self) -> Attribute<'static> {
+        Attribute {
+            name: self.name,
+            value: Cow::Owned(self.value.into_owned()),
+            prefix: self.prefix,
+            namespace: self.namespace,
+        }
+    }
+}
+
+// The data of a comment or of an attribute value while it is consumed.
+// As long as the data is a slice of the input only its range is kept, a character that doesn't continue the slice turns it into a String.
 #[derive(Default)]
-struct Attribute {
-    name: String,
-    value: String,
+struct Data {
+    start: usize,
+    end: usize,
+    owned: Option<String>,
+}
+
+impl Data {
+    // empty data that starts at the index of the input
+    fn starting_at(index: usize) -> Self {
+        Data {
+            start: index,
+            end: index,
+            owned: None,
+        }
+    }
+
+    fn push(&mut self, input: &str, char: char) {
+        match &mut self.owned {
+            Some(owned) => owned.push(char),
+            // the data is still the same as the input
+            None if input[self.end..].starts_with(char) => self.end += char.len_utf8(),
+            None => {
+                let mut owned = input[self.start..self.end].to_string();
+                owned.push(char);
+                self.owned = Some(owned);
+            }
+        }
+    }
+
+    fn push_str(&mut self, input: &str, data: &str) {
+        for char in data.chars() {
+            self.push(input, char);
+        }
+    }
+
+    fn take<'a>(&mut self, input: &'a str) -> Cow<'a, str> {
+        let data = match self.owned.take() {
+            Some(owned) => Cow::Owned(owned),
+            None => Cow::Borrowed(&input[self.start..self.end]),
+        };
+        *self = Data::default();
+        data
+    }
 }
 
-pub struct Tokenizer {
+pub struct Tokenizer<'a> {
     eof_emitted: bool,
 
-    chars: Vec<char>,
+    input: &'a str,
+    // the byte index of the next input character, it moves one past the end when the EOF is consumed
     index: usize,
+    // the length of the last consumed character, to reconsume it
+    consumed_length: usize,
+    // the byte index of the first character of each line, for the positions of the parse errors
+    line_starts: Vec<usize>,
+    // the last computed position, the byte index and its column, the next position is counted from there
+    last_position: Cell<(usize, usize)>,
 
     current_state: State,
 
     current_doc_type: DOCTYPE,
-    current_tag: Tag,
+    current_tag: Tag<'a>,
     current_tag_type: TagType,
-    // current_attribute_name: String,
-    // current_attribute_value: String,
-    current_attribute: Attribute,
-    current_comment_data: String,
+    // the names are collected here and interned when the tag token is emitted, the buffers are reused for the next tag
+    current_tag_name: String,
+    current_attribute_name: String,
+    current_attribute_value: Data,
+    current_comment_data: Data,
+
+    // tokens that are emitted together (e.g. "]]") are queued and returned by the following calls
+    queued_tokens: VecDeque<Token<'a>>,
+    // set by the tree builder: there is an adjusted current node and it is not an element in the HTML namespace
+    cdata_allowed: bool,
+    temporary_buffer: String,
+    // the state the character reference states return to
+    return_state: State,
+    character_reference_code: u32,
+    // https://html.spec.whatwg.org/multipage/parsing.html#appropriate-end-tag-token
+    last_start_tag_name: Option<Atom>,
 }
 
-#[derive(Debug)]
-enum State {
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum State {
     DataState,
     RCDATAState,
     RAWTEXTState,
@@ -86,7 +250,10 @@ enum State {
     ScriptDataEndTagOpenState,
     ScriptDataEndTagNameState,
     ScriptDataEscapeSTartState,
+    ScriptDataEscapeStartDashState,
+    ScriptDataEscapedState,
     ScriptDataEscapedDasState,
+    ScriptDataEscapedDashDashState,
     ScriptDataEscapedLessTahSignState,
     ScriptDataEscapedEndTagOpenState,
     ScriptDataEscapedEndTagNameState,
@@ -147,6 +314,45 @@ enum State {
     NumericCharacterReferenceEndState,
 }
 
+// the public and the system identifier of a DOCTYPE token share the states that read them
+#[derive(Clone, Copy)]
+enum DoctypeIdentifier {
+    Public,
+    System,
+}
+
+impl DoctypeIdentifier {
+    fn of(self, doctype: &mut DOCTYPE) -> &mut Option<String> {
+        match self {
+            Self::Public => &mut doctype.public_id,
+            Self::System => &mut doctype.system_id,
+        }
+    }
+
+    fn before_state(self) -> State {
+        match self {
+            Self::Public => State::BeforeDOCTYPEpublicIdentifierState,
+            Self::System => State::BeforeDoctypeSystemIdentifierState,
+        }
+    }
+
+    fn quoted_state(self, quote: char) -> State {
+        match (self, quote) {
+            (Self::Public, '"') => State::DOCTYPEPublicIDentifierDoubleQuotedState,
+            (Self::Public, _) => State::DOCTYPEPublicIdentifierDingleQuotedState,
+            (Self::System, '"') => State::DOCTYPESystemIdentifierDoubleQuotedState,
+            (Self::System, _) => State::DOCTYPESystemIdentifierSingleQuotedState,
+        }
+    }
+
+    fn after_state(self) -> State {
+        match self {
+            Self::Public => State::AfterDOCTYPEPublicIdentifierState,
+            Self::System => State::AfterDOCTYPESystemIdentifierState,
+        }
+    }
+}
+
 macro_rules! new_state {
     ($self_ident:ident, $state_variant:expr) => {{
         $self_ident.current_state = $state_variant;
@@ -154,8 +360,8 @@ macro_rules! new_state {
     }};
 }
 
-impl Iterator for Tokenizer {
-    type Item = Token;
+impl<'a> Iterator for Tokenizer<'a> {
+    type Item = Token<'a>;
     fn next(&mut self) -> Option<Self::Item> {
         if self.eof_emitted {
             return None;
@@ -169,264 +375,302 @@ impl Iterator for Tokenizer {
     }
 }
 
-impl Tokenizer {
-    pub fn new(content: &str) -> Self {
-        let chars = content.chars().collect();
+impl<'a> Tokenizer<'a> {
+    pub fn new(content: &'a str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(content.match_indices('\n').map(|(index, _)| index + 1))
+            .collect();
         Tokenizer {
             eof_emitted: false,
             current_state: State::DataState,
-            chars,
+            input: content,
             index: 0,
+            consumed_length: 0,
+            line_starts,
+            last_position: Cell::new((0, 0)),
             current_doc_type: DOCTYPE::default(),
             current_tag: Tag::default(),
             current_tag_type: TagType::StartTag,
-            // current_attribute_name: String::new(),
-            // current_attribute_value: String::new(),
-            current_attribute: Attribute::default(),
-            current_comment_data: String::new(),
+            current_tag_name: String::new(),
+            current_attribute_name: String::new(),
+            current_attribute_value: Data::default(),
+            current_comment_data: Data::default(),
+            queued_tokens: VecDeque::new(),
+            cdata_allowed: false,
+            temporary_buffer: String::new(),
+            return_state: State::DataState,
+            character_reference_code: 0,
+            last_start_tag_name: None,
         }
     }
 
+    // the 1-based line and the column of the last consumed character, the column counts the consumed characters of the line
+    pub fn position(&self) -> (usize, usize) {
+        let index = self.index.min(self.input.len());
+        let line = self
+            .line_starts
+            .partition_point(|line_start| *line_start <= index);
+        let line_start = self.line_starts[line - 1];
+        // the positions are asked for token after token, so the characters are only counted from the last one on
+        let (last_index, last_column) = self.last_position.get();
+        let column = if line_start <= last_index && last_index <= index {
+            last_column + self.input[last_index..index].chars().count()
+        } else {
+            self.input[line_start..index].chars().count()
+        };
+        self.last_position.set((index, column));
+        (line, column)
+    }
+
+    // the tree builder switches the tokenizer state for RCDATA, raw text and script elements
+    pub(crate) fn switch_to(&mut self, state: State) {
+        self.current_state = state;
+    }
+
+    // https://html.spec.whatwg.org/multipage/parsing.html#markup-declaration-open-state
+    // the tokenizer can't look at the tree builder, so the tree builder tells it before each token
+    pub fn set_cdata_allowed(&mut self, cdata_allowed: bool) {
+        self.cdata_allowed = cdata_allowed;
+    }
+
     fn add_current_attribute_to_current_tag(&mut self) {
-        if self.current_attribute.name != String::new() {
+        if !self.current_attribute_name.is_empty() {
             /*  When the user agent leaves the attribute name state (and before emitting the tag token,
             if appropriate), the complete attribute's name must be compared to the other attributes on
             the same token; if there is already an attribute on the token with the exact same name,
             then this is a duplicate-attribute parse error and the new attribute must be removed
             from the token. */
             // todo emit duplicate-attribute parse error
+            let attribute = Attribute {
+                name: Atom::from(self.current_attribute_name.as_str()),
+                value: self.current_attribute_value.take(self.input),
+                prefix: None,
+                namespace: None,
+            };
+            self.current_attribute_name.clear();
             if !self
                 .current_tag
                 .attributes
-                .contains_key(&self.current_attribute.name)
+                .iter()
+                .any(|item| item.name == attribute.name)
             {
-                self.current_tag.attributes.insert(
-                    self.current_attribute.name.clone(),
-                    self.current_attribute.value.clone(),
-                );
+                self.current_tag.attributes.push(attribute);
             }
-            self.current_attribute = Attribute::default();
         }
     }
 
-    fn build_current_tag_token(&mut self) -> Token {
+    fn build_current_tag_token(&mut self) -> Token<'a> {
         self.add_current_attribute_to_current_tag();
+        self.current_tag.name = Atom::from(self.current_tag_name.as_str());
+        let tag = std::mem::take(&mut self.current_tag);
         if self.current_tag_type == TagType::StartTag {
-            Token::StartTag(self.current_tag.clone())
+            self.last_start_tag_name = Some(tag.name.clone());
+            Token::StartTag(tag)
         } else {
-            Token::EndTag(self.current_tag.clone())
+            Token::EndTag(tag)
         }
     }
 
+    // the input from the next input character on
+    fn remaining_input(&self) -> &'a str {
+        self.input.get(self.index..).unwrap_or_default()
+    }
+
     fn consume_next_input_character(&mut self) -> Option<char> {
-        if self.chars.len() > self.index {
-            let char = self.chars[self.index];
-            self.index += 1;
-            return Some(char);
-        } else {
-            return None;
-        }
+        let char = self.remaining_input().chars().next();
+        // the index also moves past the end of the input so that reconsuming an EOF works
+        self.consumed_length = char.map_or(1, char::len_utf8);
+        self.index += self.consumed_length;
+        char
+    }
+
+    fn next_characters_are(&self, value: &str, ascii_case_insensitive: bool) -> bool {
+        let mut chars = self.remaining_input().chars();
+        value.chars().all(|expected| match chars.next() {
+            Some(char) => {
+                char == expected || (ascii_case_insensitive && char.eq_ignore_ascii_case(&expected))
+            }
+            None => false,
+        })
     }
 
     fn consume_next_characters(&mut self, characters_number: usize) {
-        self.index += characters_number;
+        self.index += self
+            .remaining_input()
+            .chars()
+            .take(characters_number)
+            .map(char::len_utf8)
+            .sum::<usize>();
     }
 
     fn reconsume(&mut self) {
-        self.index -= 1;
+        self.index -= self.consumed_length;
+    }
+
+    // An appropriate end tag token is an end tag token whose tag name matches the tag name of the last start tag to have been emitted from this tokenizer, if any.
+    fn is_appropriate_end_tag_token(&self) -> bool {
+        self.current_tag_type == TagType::EndTag
+            && self
+                .last_start_tag_name
+                .as_ref()
+                .is_some_and(|name| name == self.current_tag_name.as_str())
+    }
+
+    fn emit_characters(&mut self, chars: impl Into<Cow<'a, str>>) -> Option<Token<'a>> {
+        Some(Token::Characters(chars.into()))
+    }
+
+    // emits the consumed character together with the following ones, up to the next character the state handles differently
+    // the run is a slice of the input
+    fn emit_character_run(&mut self, char: char, stop_characters: &[char]) -> Option<Token<'a>> {
+        let start = self.index - char.len_utf8();
+        let length = self
+            .remaining_input()
+            .find(stop_characters)
+            .unwrap_or(self.remaining_input().len());
+        self.index += length;
+        Some(Token::Characters(Cow::Borrowed(
+            &self.input[start..self.index],
+        )))
     }
 
-    pub fn next_token(&mut self) -> Token {
+    pub fn next_token(&mut self) -> Token<'a> {
+        if let Some(token) = self.queued_tokens.pop_front() {
+            return token;
+        }
         loop {
             if let Some(token) = match self.current_state {
                 State::DataState => self.data_state(),
-                State::RCDATAState => todo!("State Not implemented: {:?}", self.current_state),
-                State::RAWTEXTState => todo!("State Not implemented: {:?}", self.current_state),
-                State::ScriptDAtaState => todo!("State Not implemented: {:?}", self.current_state),
-                State::PLAINTEXState => todo!("State Not implemented: {:?}", self.current_state),
+                State::RCDATAState => self.rcdata_state(),
+                State::RAWTEXTState => self.rawtext_state(),
+                State::ScriptDAtaState => self.script_data_state(),
+                State::PLAINTEXState => self.plaintext_state(),
                 State::TagOpenState => self.tag_open_state(),
                 State::EndTagOpenState => self.end_tag_open_state(),
                 State::TagNameState => self.tag_name_state(),
-                State::RCDATALessTanSignState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
-                }
-                State::RCDATAEndTagOpenSTate => {
-                    todo!("State Not implemented: {:?}", self.current_state)
-                }
-                State::RCDATAEndTagNameSTate => {
-                    todo!("State Not implemented: {:?}", self.current_state)
-                }
-                State::RAWTEXTLessTanSignState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
-                }
-                State::RAWTEXTEndTagOpenState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
-                }
-                State::RAWTEXTEmdTagNameState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
-                }
-                State::ScriptDataLessTanSignState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
-                }
-                State::ScriptDataEndTagOpenState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
-                }
-                State::ScriptDataEndTagNameState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
-                }
-                State::ScriptDataEscapeSTartState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
-                }
-                State::ScriptDataEscapedDasState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
-                }
+                State::RCDATALessTanSignState => self.rcdata_less_than_sign_state(),
+                State::RCDATAEndTagOpenSTate => self.rcdata_end_tag_open_state(),
+                State::RCDATAEndTagNameSTate => self.rcdata_end_tag_name_state(),
+                State::RAWTEXTLessTanSignState => self.rawtext_less_than_sign_state(),
+                State::RAWTEXTEndTagOpenState => self.rawtext_end_tag_open_state(),
+                State::RAWTEXTEmdTagNameState => self.rawtext_end_tag_name_state(),
+                State::ScriptDataLessTanSignState => self.script_data_less_than_sign_state(),
+                State::ScriptDataEndTagOpenState => self.script_data_end_tag_open_state(),
+                State::ScriptDataEndTagNameState => self.script_data_end_tag_name_state(),
+                State::ScriptDataEscapeSTartState => self.script_data_escape_start_state(),
+                State::ScriptDataEscapeStartDashState => self.script_data_escape_start_dash_state(),
+                State::ScriptDataEscapedState => self.script_data_escaped_state(),
+                State::ScriptDataEscapedDasState => self.script_data_escaped_dash_state(),
+                State::ScriptDataEscapedDashDashState => self.script_data_escaped_dash_dash_state(),
                 State::ScriptDataEscapedLessTahSignState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
+                    self.script_data_escaped_less_than_sign_state()
                 }
                 State::ScriptDataEscapedEndTagOpenState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
+                    self.script_data_escaped_end_tag_open_state()
                 }
                 State::ScriptDataEscapedEndTagNameState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
+                    self.script_data_escaped_end_tag_name_state()
                 }
                 State::ScriptDataDoubleEscapeStartState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
-                }
-                State::ScriptDataDoubleEscapedState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
+                    self.script_data_double_escape_start_state()
                 }
+                State::ScriptDataDoubleEscapedState => self.script_data_double_escaped_state(),
                 State::ScriptDataDoubleEscapedDashState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
+                    self.script_data_double_escaped_dash_state()
                 }
                 State::ScriptDataDoubleEscapedDahsDashState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
+                    self.script_data_double_escaped_dash_dash_state()
                 }
                 State::ScriptDataDoubleEscapedLessTanSignState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
-                }
-                State::ScriptDataDoubleEscapeEndState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
+                    self.script_data_double_escaped_less_than_sign_state()
                 }
+                State::ScriptDataDoubleEscapeEndState => self.script_data_double_escape_end_state(),
                 State::BeforeAttributeNameState => self.before_attribute_name_state(),
                 State::AttributeNameState => self.attribute_name_state(),
-                State::AfterAttributeNameStat => {
-                    todo!("State Not implemented: {:?}", self.current_state)
-                }
+                State::AfterAttributeNameStat => self.after_attribute_name_state(),
                 State::BeforeAttributeValueState => self.before_attribute_value_state(),
                 State::AttributeValueDoubleQuotedState => {
                     self.attribute_value_double_quoted_state()
                 }
                 State::AttributeValueSingleQuotedState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
-                }
-                State::AttributeValueUnquotesState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
+                    self.attribute_value_single_quoted_state()
                 }
+                State::AttributeValueUnquotesState => self.attribute_value_unquoted_state(),
                 State::AfterAttributeValueQuotedState => self.after_attribute_value_quoted_state(),
-                State::SelfClosingStartTagState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
-                }
-                State::BogusCommentState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
-                }
+                State::SelfClosingStartTagState => self.self_closing_start_tag_state(),
+                State::BogusCommentState => self.bogus_comment_state(),
                 State::MarkupDeclarationOpenState => self.markup_declaration_open_state(),
                 State::CommentStartState => self.comment_start_state(),
                 State::CommentStartDashState => self.comment_start_dash_state(),
                 State::CommentState => self.comment_state(),
-                State::CommentLessThanSignState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
-                }
-                State::CommentLessThanSignBandState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
-                }
+                State::CommentLessThanSignState => self.comment_less_than_sign_state(),
+                State::CommentLessThanSignBandState => self.comment_less_than_sign_bang_state(),
                 State::CommentLessThanSignBangDashState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
+                    self.comment_less_than_sign_bang_dash_state()
                 }
                 State::CommentLessThanSignBangDashDashState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
+                    self.comment_less_than_sign_bang_dash_dash_state()
                 }
                 State::CommentEndDashState => self.comment_end_dash_state(),
                 State::CommentEndState => self.comment_end_state(),
-                State::CommentEndBandState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
-                }
+                State::CommentEndBandState => self.comment_end_bang_state(),
                 State::DOCTYPEState => self.doctype_state(),
                 State::BeforeDOCTYPENameState => self.before_doctype_name_state(),
                 State::DOCTYPENameState => self.doctype_name_state(),
-                State::AfterDOCTYPENameState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
-                }
+                State::AfterDOCTYPENameState => self.after_doctype_name_state(),
                 State::AfterDOCTYPEpublicKeywordState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
+                    self.after_doctype_keyword_state(DoctypeIdentifier::Public)
                 }
                 State::BeforeDOCTYPEpublicIdentifierState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
+                    self.before_doctype_identifier_state(DoctypeIdentifier::Public)
                 }
                 State::DOCTYPEPublicIDentifierDoubleQuotedState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
+                    self.doctype_identifier_quoted_state(DoctypeIdentifier::Public, '"')
                 }
                 State::DOCTYPEPublicIdentifierDingleQuotedState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
+                    self.doctype_identifier_quoted_state(DoctypeIdentifier::Public, '\'')
                 }
                 State::AfterDOCTYPEPublicIdentifierState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
+                    self.after_doctype_public_identifier_state()
                 }
                 State::BetweenDoctypePublicAndSystemIdentifiersState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
+                    self.between_doctype_public_and_system_identifiers_state()
                 }
                 State::AfterDoctypeSystemKeywordState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
+                    self.after_doctype_keyword_state(DoctypeIdentifier::System)
                 }
                 State::BeforeDoctypeSystemIdentifierState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
+                    self.before_doctype_identifier_state(DoctypeIdentifier::System)
                 }
                 State::DOCTYPESystemIdentifierDoubleQuotedState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
+                    self.doctype_identifier_quoted_state(DoctypeIdentifier::System, '"')
                 }
                 State::DOCTYPESystemIdentifierSingleQuotedState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
+                    self.doctype_identifier_quoted_state(DoctypeIdentifier::System, '\'')
                 }
                 State::AfterDOCTYPESystemIdentifierState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
-                }
-                State::BogusDoctypeState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
-                }
-                State::CDATASectionState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
-                }
-                State::CDATASectionBracketState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
-                }
-                State::CDATASectionEndState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
-                }
-                State::CharacterReferenceState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
-                }
-                State::NamedCharacterReferenceState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
-                }
-                State::AmbiguousAmpersandState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
-                }
-                State::NumericCharacterReferenceState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
-                }
+                    self.after_doctype_system_identifier_state()
+                }
+                State::BogusDoctypeState => self.bogus_doctype_state(),
+                State::CDATASectionState => self.cdata_section_state(),
+                State::CDATASectionBracketState => self.cdata_section_bracket_state(),
+                State::CDATASectionEndState => self.cdata_section_end_state(),
+                State::CharacterReferenceState => self.character_reference_state(),
+                State::NamedCharacterReferenceState => self.named_character_reference_state(),
+                State::AmbiguousAmpersandState => self.ambiguous_ampersand_state(),
+                State::NumericCharacterReferenceState => self.numeric_character_reference_state(),
                 State::HexadecimalCharacterReferenceStartState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
+                    self.hexadecimal_character_reference_start_state()
                 }
                 State::DecimalCharacterReferenceStartState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
+                    self.decimal_character_reference_start_state()
                 }
                 State::HexadecimalCharacterReferenceState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
-                }
-                State::DecimalCharacterReferenceState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
+                    self.hexadecimal_character_reference_state()
                 }
+                State::DecimalCharacterReferenceState => self.decimal_character_reference_state(),
                 State::NumericCharacterReferenceEndState => {
-                    todo!("State Not implemented: {:?}", self.current_state)
+                    self.numeric_character_reference_end_state()
                 }
             } {
                 return token;
@@ -436,356 +680,1783 @@ impl Tokenizer {
 
     // 13.2.5.1 Data state
     // https://html.spec.whatwg.org/multipage/parsing.html#data-state
-    fn data_state(&mut self) -> Option<Token> {
+    fn data_state(&mut self) -> Option<Token<'a>> {
         if let Some(char) = self.consume_next_input_character() {
             match char {
-                '&' => todo!(),
+                '&' => {
+                    self.return_state = State::DataState;
+                    new_state!(self, State::CharacterReferenceState)
+                }
                 '<' => new_state!(self, State::TagOpenState),
-                '\u{0000}' => todo!(),
-                _ => Some(Token::Character(char)),
+                '\u{0000}' => {
+                    // todo unexpected-null-character parse error
+                    // Emit the current input character as a character token.
+                    Some(Token::Character(char))
+                }
+                _ => self.emit_character_run(char, &['&', '<', '\u{0000}']),
             }
         } else {
             Some(Token::EndOfFile)
         }
     }
 
+    // 13.2.5.2 RCDATA state
+    // https://html.spec.whatwg.org/multipage/parsing.html#rcdata-state
+    fn rcdata_state(&mut self) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some('&') => {
+                self.return_state = State::RCDATAState;
+                new_state!(self, State::CharacterReferenceState)
+            }
+            Some('<') => new_state!(self, State::RCDATALessTanSignState),
+            Some('\u{0000}') => {
+                // todo unexpected-null-character parse error
+                Some(Token::Character('\u{FFFD}'))
+            }
+            Some(char) => self.emit_character_run(char, &['&', '<', '\u{0000}']),
+            None => Some(Token::EndOfFile),
+        }
+    }
+
+    // 13.2.5.3 RAWTEXT state
+    // https://html.spec.whatwg.org/multipage/parsing.html#rawtext-state
+    fn rawtext_state(&mut self) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some('<') => new_state!(self, State::RAWTEXTLessTanSignState),
+            Some('\u{0000}') => {
+                // todo unexpected-null-character parse error
+                Some(Token::Character('\u{FFFD}'))
+            }
+            Some(char) => self.emit_character_run(char, &['<', '\u{0000}']),
+            None => Some(Token::EndOfFile),
+        }
+    }
+
+    // 13.2.5.4 Script data state
+    // https://html.spec.whatwg.org/multipage/parsing.html#script-data-state
+    fn script_data_state(&mut self) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some('<') => new_state!(self, State::ScriptDataLessTanSignState),
+            Some('\u{0000}') => {
+                // todo unexpected-null-character parse error
+                Some(Token::Character('\u{FFFD}'))
+            }
+            Some(char) => self.emit_character_run(char, &['<', '\u{0000}']),
+            None => Some(Token::EndOfFile),
+        }
+    }
+
+    // 13.2.5.5 PLAINTEXT state
+    // https://html.spec.whatwg.org/multipage/parsing.html#plaintext-state
+    fn plaintext_state(&mut self) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some('\u{0000}') => {
+                // todo unexpected-null-character parse error
+                Some(Token::Character('\u{FFFD}'))
+            }
+            Some(char) => self.emit_character_run(char, &['\u{0000}']),
+            None => Some(Token::EndOfFile),
+        }
+    }
+
     // 13.2.5.6 Tag open state
     // https://html.spec.whatwg.org/multipage/parsing.html#tag-open-state
-    fn tag_open_state(&mut self) -> Option<Token> {
+    fn tag_open_state(&mut self) -> Option<Token<'a>> {
         if let Some(char) = self.consume_next_input_character() {
             match char {
                 '!' => new_state!(self, State::MarkupDeclarationOpenState),
                 '/' => new_state!(self, State::EndTagOpenState),
                 _ if char.is_ascii_alphabetic() => {
                     self.current_tag_type = TagType::StartTag;
-                    self.current_tag = Tag {
-                        name: "".to_string(),
-                        attributes: HashMap::new(),
-                    };
+                    self.current_tag = Tag::new();
+                    self.current_tag_name.clear();
                     self.reconsume();
                     new_state!(self, State::TagNameState)
                 }
-                '?' => todo!(),
-                _ => todo!(),
+                '?' => {
+                    // todo unexpected-question-mark-instead-of-tag-name parse error
+                    // Create a comment token whose data is the empty string. Reconsume in the bogus comment state.
+                    self.reconsume();
+                    self.current_comment_data = Data::starting_at(self.index);
+                    new_state!(self, State::BogusCommentState)
+                }
+                _ => {
+                    // todo invalid-first-character-of-tag-name parse error
+                    // Emit a U+003C LESS-THAN SIGN character token. Reconsume in the data state.
+                    self.reconsume();
+                    self.current_state = State::DataState;
+                    Some(Token::Character('<'))
+                }
             }
         } else {
-            todo!()
+            // todo eof-before-tag-name parse error
+            // Emit a U+003C LESS-THAN SIGN character token and an end-of-file token.
+            self.reconsume();
+            self.current_state = State::DataState;
+            Some(Token::Character('<'))
         }
     }
 
     // 13.2.5.7 End tag open state
     // https://html.spec.whatwg.org/multipage/parsing.html#end-tag-open-state
-    fn end_tag_open_state(&mut self) -> Option<Token> {
+    fn end_tag_open_state(&mut self) -> Option<Token<'a>> {
         if let Some(char) = self.consume_next_input_character() {
             match char {
                 _ if char.is_ascii_alphabetic() => {
                     self.current_tag_type = TagType::EndTag;
-                    self.current_tag = Tag {
-                        name: "".to_string(),
-                        attributes: HashMap::new(),
-                    };
+                    self.current_tag = Tag::new();
+                    self.current_tag_name.clear();
                     self.reconsume();
                     new_state!(self, State::TagNameState)
                 }
-                '>' => todo!(),
-                _ => todo!(),
+                '>' => {
+                    // todo missing-end-tag-name parse error
+                    new_state!(self, State::DataState)
+                }
+                _ => {
+                    // todo invalid-first-character-of-tag-name parse error
+                    // Create a comment token whose data is the empty string. Reconsume in the bogus comment state.
+                    self.reconsume();
+                    self.current_comment_data = Data::starting_at(self.index);
+                    new_state!(self, State::BogusCommentState)
+                }
             }
         } else {
-            todo!()
+            // todo eof-before-tag-name parse error
+            // Emit a U+003C LESS-THAN SIGN character token, a U+002F SOLIDUS character token and an end-of-file token.
+            self.reconsume();
+            self.current_state = State::DataState;
+            self.emit_characters("</")
         }
     }
 
     // 13.2.5.8 Tag name state
     // https://html.spec.whatwg.org/multipage/parsing.html#tag-name-state
-    fn tag_name_state(&mut self) -> Option<Token> {
+    fn tag_name_state(&mut self) -> Option<Token<'a>> {
         if let Some(char) = self.consume_next_input_character() {
             match char {
                 _ if is_one_of_tab_lf_ff_space(char) => {
                     new_state!(self, State::BeforeAttributeNameState)
                 }
-                '/' => todo!(),
+                '/' => new_state!(self, State::SelfClosingStartTagState),
                 '>' => {
                     self.current_state = State::DataState;
                     Some(self.build_current_tag_token())
                 }
                 _ if char.is_ascii_uppercase() => {
-                    self.current_tag.name.push(char.to_ascii_lowercase());
+                    self.current_tag_name.push(char.to_ascii_lowercase());
                     new_state!(self, State::TagNameState)
                 }
-                '\u{0000}' => todo!(),
+                '\u{0000}' => {
+                    // todo unexpected-null-character parse error
+                    self.current_tag_name.push('\u{FFFD}');
+                    None
+                }
                 _ => {
-                    self.current_tag.name.push(char);
+                    self.current_tag_name.push(char);
                     None
                 }
             }
         } else {
-            todo!()
+            // todo eof-in-tag parse error
+            Some(Token::EndOfFile)
         }
     }
 
-    // 13.2.5.32 Before attribute name state
-    // https://html.spec.whatwg.org/multipage/parsing.html#before-attribute-name-state
-    fn before_attribute_name_state(&mut self) -> Option<Token> {
-        if let Some(char) = self.consume_next_input_character() {
-            match char {
-                _ if is_one_of_tab_lf_ff_space(char) => {
-                    new_state!(self, State::BeforeAttributeNameState)
-                }
-                '/' => todo!(),
-                '>' => todo!(),
-                '=' => todo!(),
-                _ => {
-                    self.add_current_attribute_to_current_tag();
-                    self.reconsume();
-                    new_state!(self, State::AttributeNameState)
-                }
+    // 13.2.5.9 RCDATA less-than sign state
+    // https://html.spec.whatwg.org/multipage/parsing.html#rcdata-less-than-sign-state
+    fn rcdata_less_than_sign_state(&mut self) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some('/') => {
+                self.temporary_buffer.clear();
+                new_state!(self, State::RCDATAEndTagOpenSTate)
+            }
+            _ => {
+                self.reconsume();
+                self.current_state = State::RCDATAState;
+                Some(Token::Character('<'))
             }
-        } else {
-            todo!()
         }
     }
 
-    // 13.2.5.33 Attribute name state
-    // https://html.spec.whatwg.org/multipage/parsing.html#attribute-name-state
-    fn attribute_name_state(&mut self) -> Option<Token> {
-        if let Some(char) = self.consume_next_input_character() {
-            match char {
-                _ if is_one_of_tab_lf_ff_space(char) => todo!(),
-                '/' | '>' => todo!(),
-                '=' => new_state!(self, State::BeforeAttributeValueState),
-                _ if char.is_ascii_uppercase() => todo!(),
-                '\u{0000}' => todo!(),
-                '"' | '\'' | '<' => todo!(),
-                _ => {
-                    self.current_attribute.name.push(char);
-                    new_state!(self, State::AttributeNameState)
-                }
-            }
-        } else {
-            todo!()
-        }
+    // 13.2.5.10 RCDATA end tag open state
+    // https://html.spec.whatwg.org/multipage/parsing.html#rcdata-end-tag-open-state
+    fn rcdata_end_tag_open_state(&mut self) -> Option<Token<'a>> {
+        self.end_tag_open(State::RCDATAEndTagNameSTate, State::RCDATAState)
     }
 
-    // 13.2.5.35 Before attribute value state
-    // https://html.spec.whatwg.org/multipage/parsing.html#before-attribute-value-state
-    fn before_attribute_value_state(&mut self) -> Option<Token> {
-        if let Some(char) = self.consume_next_input_character() {
-            match char {
-                _ if is_one_of_tab_lf_ff_space(char) => {
-                    new_state!(self, State::BeforeAttributeValueState)
-                }
-                '"' => new_state!(self, State::AttributeValueDoubleQuotedState),
-                '\'' => todo!(),
-                '>' => todo!(),
-                _ => todo!(),
-            }
-        } else {
-            todo!()
-        }
+    // 13.2.5.11 RCDATA end tag name state
+    // https://html.spec.whatwg.org/multipage/parsing.html#rcdata-end-tag-name-state
+    fn rcdata_end_tag_name_state(&mut self) -> Option<Token<'a>> {
+        self.end_tag_name(State::RCDATAState)
     }
 
-    // 13.2.5.36 Attribute value (double-quoted) state
-    // https://html.spec.whatwg.org/multipage/parsing.html#attribute-value-(double-quoted)-state
-    fn attribute_value_double_quoted_state(&mut self) -> Option<Token> {
-        if let Some(char) = self.consume_next_input_character() {
-            match char {
-                '"' => new_state!(self, State::AfterAttributeValueQuotedState),
-                '&' => todo!(),
-                '\u{0000}' => todo!(),
-                _ => {
-                    self.current_attribute.value.push(char);
-                    new_state!(self, State::AttributeValueDoubleQuotedState)
-                }
+    // 13.2.5.12 RAWTEXT less-than sign state
+    // https://html.spec.whatwg.org/multipage/parsing.html#rawtext-less-than-sign-state
+    fn rawtext_less_than_sign_state(&mut self) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some('/') => {
+                self.temporary_buffer.clear();
+                new_state!(self, State::RAWTEXTEndTagOpenState)
+            }
+            _ => {
+                self.reconsume();
+                self.current_state = State::RAWTEXTState;
+                Some(Token::Character('<'))
             }
-        } else {
-            todo!()
         }
     }
 
-    // 13.2.5.39 After attribute value (quoted) state
-    // https://html.spec.whatwg.org/multipage/parsing.html#after-attribute-value-(quoted)-state
-    fn after_attribute_value_quoted_state(&mut self) -> Option<Token> {
-        if let Some(char) = self.consume_next_input_character() {
-            match char {
-                _ if is_one_of_tab_lf_ff_space(char) => {
-                    new_state!(self, State::BeforeAttributeNameState)
-                }
-                '/' => todo!(),
-                '>' => {
-                    self.current_state = State::DataState;
-                    Some(self.build_current_tag_token())
-                }
-                _ => todo!(),
+    // 13.2.5.13 RAWTEXT end tag open state
+    // https://html.spec.whatwg.org/multipage/parsing.html#rawtext-end-tag-open-state
+    fn rawtext_end_tag_open_state(&mut self) -> Option<Token<'a>> {
+        self.end_tag_open(State::RAWTEXTEmdTagNameState, State::RAWTEXTState)
+    }
+
+    // 13.2.5.14 RAWTEXT end tag name state
+    // https://html.spec.whatwg.org/multipage/parsing.html#rawtext-end-tag-name-state
+    fn rawtext_end_tag_name_state(&mut self) -> Option<Token<'a>> {
+        self.end_tag_name(State::RAWTEXTState)
+    }
+
+    // 13.2.5.15 Script data less-than sign state
+    // https://html.spec.whatwg.org/multipage/parsing.html#script-data-less-than-sign-state
+    fn script_data_less_than_sign_state(&mut self) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some('/') => {
+                self.temporary_buffer.clear();
+                new_state!(self, State::ScriptDataEndTagOpenState)
+            }
+            Some('!') => {
+                self.current_state = State::ScriptDataEscapeSTartState;
+                self.emit_characters("<!")
+            }
+            _ => {
+                self.reconsume();
+                self.current_state = State::ScriptDAtaState;
+                Some(Token::Character('<'))
             }
-        } else {
-            todo!()
         }
     }
 
-    // 13.2.5.42 Markup declaration open state
-    // https://html.spec.whatwg.org/multipage/parsing.html#markup-declaration-open-state
-    fn markup_declaration_open_state(&mut self) -> Option<Token> {
-        if String::from_iter(&self.chars[self.index..self.index + 2]) == "--" {
-            self.consume_next_characters(2);
-            self.current_comment_data = String::new();
-            new_state!(self, State::CommentStartState)
-        } else if lowercase_char_slice(&self.chars[self.index..self.index + 7]) == "doctype" {
-            self.consume_next_characters(7);
-            new_state!(self, State::DOCTYPEState)
-        } else {
-            todo!()
+    // 13.2.5.16 Script data end tag open state
+    // https://html.spec.whatwg.org/multipage/parsing.html#script-data-end-tag-open-state
+    fn script_data_end_tag_open_state(&mut self) -> Option<Token<'a>> {
+        self.end_tag_open(State::ScriptDataEndTagNameState, State::ScriptDAtaState)
+    }
+
+    // 13.2.5.17 Script data end tag name state
+    // https://html.spec.whatwg.org/multipage/parsing.html#script-data-end-tag-name-state
+    fn script_data_end_tag_name_state(&mut self) -> Option<Token<'a>> {
+        self.end_tag_name(State::ScriptDAtaState)
+    }
+
+    // 13.2.5.18 Script data escape start state
+    // https://html.spec.whatwg.org/multipage/parsing.html#script-data-escape-start-state
+    fn script_data_escape_start_state(&mut self) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some('-') => {
+                self.current_state = State::ScriptDataEscapeStartDashState;
+                Some(Token::Character('-'))
+            }
+            _ => {
+                self.reconsume();
+                new_state!(self, State::ScriptDAtaState)
+            }
         }
     }
 
-    // 13.2.5.43 Comment start state
-    // https://html.spec.whatwg.org/multipage/parsing.html#comment-start-state
-    fn comment_start_state(&mut self) -> Option<Token> {
-        if let Some(char) = self.consume_next_input_character() {
-            match char {
-                '-' => new_state!(self, State::CommentStartDashState),
-                '>' => todo!(),
-                _ => {
-                    self.reconsume();
-                    new_state!(self, State::CommentState)
-                }
+    // 13.2.5.19 Script data escape start dash state
+    // https://html.spec.whatwg.org/multipage/parsing.html#script-data-escape-start-dash-state
+    fn script_data_escape_start_dash_state(&mut self) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some('-') => {
+                self.current_state = State::ScriptDataEscapedDashDashState;
+                Some(Token::Character('-'))
+            }
+            _ => {
+                self.reconsume();
+                new_state!(self, State::ScriptDAtaState)
             }
-        } else {
-            todo!()
         }
     }
-    // 13.2.5.44 Comment start dash state
-    // https://html.spec.whatwg.org/multipage/parsing.html#comment-start-dash-state
-    fn comment_start_dash_state(&mut self) -> Option<Token> {
-        if let Some(char) = self.consume_next_input_character() {
-            match char {
-                _ => todo!(),
+
+    // 13.2.5.20 Script data escaped state
+    // https://html.spec.whatwg.org/multipage/parsing.html#script-data-escaped-state
+    fn script_data_escaped_state(&mut self) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some('-') => {
+                self.current_state = State::ScriptDataEscapedDasState;
+                Some(Token::Character('-'))
+            }
+            Some('<') => new_state!(self, State::ScriptDataEscapedLessTahSignState),
+            Some('\u{0000}') => {
+                // todo unexpected-null-character parse error
+                Some(Token::Character('\u{FFFD}'))
+            }
+            Some(char) => Some(Token::Character(char)),
+            None => {
+                // todo eof-in-script-html-comment-like-text parse error
+                Some(Token::EndOfFile)
             }
-        } else {
-            todo!()
         }
     }
 
-    // 13.2.5.45 Comment state
-    // https://html.spec.whatwg.org/multipage/parsing.html#comment-state
-    fn comment_state(&mut self) -> Option<Token> {
-        if let Some(char) = self.consume_next_input_character() {
-            match char {
-                '<' => todo!(),
-                '-' => new_state!(self, State::CommentEndDashState),
-                '\u{0000}' => todo!(),
-                _ => {
-                    self.current_comment_data.push(char);
-                    new_state!(self, State::CommentState)
-                }
+    // 13.2.5.21 Script data escaped dash state
+    // https://html.spec.whatwg.org/multipage/parsing.html#script-data-escaped-dash-state
+    fn script_data_escaped_dash_state(&mut self) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some('-') => {
+                self.current_state = State::ScriptDataEscapedDashDashState;
+                Some(Token::Character('-'))
+            }
+            Some('<') => new_state!(self, State::ScriptDataEscapedLessTahSignState),
+            Some('\u{0000}') => {
+                // todo unexpected-null-character parse error
+                self.current_state = State::ScriptDataEscapedState;
+                Some(Token::Character('\u{FFFD}'))
+            }
+            Some(char) => {
+                self.current_state = State::ScriptDataEscapedState;
+                Some(Token::Character(char))
+            }
+            None => {
+                // todo eof-in-script-html-comment-like-text parse error
+                Some(Token::EndOfFile)
             }
-        } else {
-            todo!()
         }
     }
 
-    // 13.2.5.50 Comment end dash state
-    // https://html.spec.whatwg.org/multipage/parsing.html#comment-end-dash-state
-    fn comment_end_dash_state(&mut self) -> Option<Token> {
+    // 13.2.5.22 Script data escaped dash dash state
+    // https://html.spec.whatwg.org/multipage/parsing.html#script-data-escaped-dash-dash-state
+    fn script_data_escaped_dash_dash_state(&mut self) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some('-') => Some(Token::Character('-')),
+            Some('<') => new_state!(self, State::ScriptDataEscapedLessTahSignState),
+            Some('>') => {
+                self.current_state = State::ScriptDAtaState;
+                Some(Token::Character('>'))
+            }
+            Some('\u{0000}') => {
+                // todo unexpected-null-character parse error
+                self.current_state = State::ScriptDataEscapedState;
+                Some(Token::Character('\u{FFFD}'))
+            }
+            Some(char) => {
+                self.current_state = State::ScriptDataEscapedState;
+                Some(Token::Character(char))
+            }
+            None => {
+                // todo eof-in-script-html-comment-like-text parse error
+                Some(Token::EndOfFile)
+            }
+        }
+    }
+
+    // 13.2.5.23 Script data escaped less-than sign state
+    // https://html.spec.whatwg.org/multipage/parsing.html#script-data-escaped-less-than-sign-state
+    fn script_data_escaped_less_than_sign_state(&mut self) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some('/') => {
+                self.temporary_buffer.clear();
+                new_state!(self, State::ScriptDataEscapedEndTagOpenState)
+            }
+            Some(char) if char.is_ascii_alphabetic() => {
+                self.temporary_buffer.clear();
+                self.reconsume();
+                self.current_state = State::ScriptDataDoubleEscapeStartState;
+                Some(Token::Character('<'))
+            }
+            _ => {
+                self.reconsume();
+                self.current_state = State::ScriptDataEscapedState;
+                Some(Token::Character('<'))
+            }
+        }
+    }
+
+    // 13.2.5.24 Script data escaped end tag open state
+    // https://html.spec.whatwg.org/multipage/parsing.html#script-data-escaped-end-tag-open-state
+    fn script_data_escaped_end_tag_open_state(&mut self) -> Option<Token<'a>> {
+        self.end_tag_open(
+            State::ScriptDataEscapedEndTagNameState,
+            State::ScriptDataEscapedState,
+        )
+    }
+
+    // 13.2.5.25 Script data escaped end tag name state
+    // https://html.spec.whatwg.org/multipage/parsing.html#script-data-escaped-end-tag-name-state
+    fn script_data_escaped_end_tag_name_state(&mut self) -> Option<Token<'a>> {
+        self.end_tag_name(State::ScriptDataEscapedState)
+    }
+
+    // 13.2.5.26 Script data double escape start state
+    // https://html.spec.whatwg.org/multipage/parsing.html#script-data-double-escape-start-state
+    fn script_data_double_escape_start_state(&mut self) -> Option<Token<'a>> {
+        self.double_escape_boundary(
+            State::ScriptDataDoubleEscapedState,
+            State::ScriptDataEscapedState,
+        )
+    }
+
+    // 13.2.5.27 Script data double escaped state
+    // https://html.spec.whatwg.org/multipage/parsing.html#script-data-double-escaped-state
+    fn script_data_double_escaped_state(&mut self) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some('-') => {
+                self.current_state = State::ScriptDataDoubleEscapedDashState;
+                Some(Token::Character('-'))
+            }
+            Some('<') => {
+                self.current_state = State::ScriptDataDoubleEscapedLessTanSignState;
+                Some(Token::Character('<'))
+            }
+            Some('\u{0000}') => {
+                // todo unexpected-null-character parse error
+                Some(Token::Character('\u{FFFD}'))
+            }
+            Some(char) => Some(Token::Character(char)),
+            None => {
+                // todo eof-in-script-html-comment-like-text parse error
+                Some(Token::EndOfFile)
+            }
+        }
+    }
+
+    // 13.2.5.28 Script data double escaped dash state
+    // https://html.spec.whatwg.org/multipage/parsing.html#script-data-double-escaped-dash-state
+    fn script_data_double_escaped_dash_state(&mut self) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some('-') => {
+                self.current_state = State::ScriptDataDoubleEscapedDahsDashState;
+                Some(Token::Character('-'))
+            }
+            Some('<') => {
+                self.current_state = State::ScriptDataDoubleEscapedLessTanSignState;
+                Some(Token::Character('<'))
+            }
+            Some('\u{0000}') => {
+                // todo unexpected-null-character parse error
+                self.current_state = State::ScriptDataDoubleEscapedState;
+                Some(Token::Character('\u{FFFD}'))
+            }
+            Some(char) => {
+                self.current_state = State::ScriptDataDoubleEscapedState;
+                Some(Token::Character(char))
+            }
+            None => {
+                // todo eof-in-script-html-comment-like-text parse error
+                Some(Token::EndOfFile)
+            }
+        }
+    }
+
+    // 13.2.5.29 Script data double escaped dash dash state
+    // https://html.spec.whatwg.org/multipage/parsing.html#script-data-double-escaped-dash-dash-state
+    fn script_data_double_escaped_dash_dash_state(&mut self) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some('-') => Some(Token::Character('-')),
+            Some('<') => {
+                self.current_state = State::ScriptDataDoubleEscapedLessTanSignState;
+                Some(Token::Character('<'))
+            }
+            Some('>') => {
+                self.current_state = State::ScriptDAtaState;
+                Some(Token::Character('>'))
+            }
+            Some('\u{0000}') => {
+                // todo unexpected-null-character parse error
+                self.current_state = State::ScriptDataDoubleEscapedState;
+                Some(Token::Character('\u{FFFD}'))
+            }
+            Some(char) => {
+                self.current_state = State::ScriptDataDoubleEscapedState;
+                Some(Token::Character(char))
+            }
+            None => {
+                // todo eof-in-script-html-comment-like-text parse error
+                Some(Token::EndOfFile)
+            }
+        }
+    }
+
+    // 13.2.5.30 Script data double escaped less-than sign state
+    // https://html.spec.whatwg.org/multipage/parsing.html#script-data-double-escaped-less-than-sign-state
+    fn script_data_double_escaped_less_than_sign_state(&mut self) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some('/') => {
+                self.temporary_buffer.clear();
+                self.current_state = State::ScriptDataDoubleEscapeEndState;
+                Some(Token::Character('/'))
+            }
+            _ => {
+                self.reconsume();
+                new_state!(self, State::ScriptDataDoubleEscapedState)
+            }
+        }
+    }
+
+    // 13.2.5.31 Script data double escape end state
+    // https://html.spec.whatwg.org/multipage/parsing.html#script-data-double-escape-end-state
+    fn script_data_double_escape_end_state(&mut self) -> Option<Token<'a>> {
+        self.double_escape_boundary(
+            State::ScriptDataEscapedState,
+            State::ScriptDataDoubleEscapedState,
+        )
+    }
+
+    // the RCDATA, RAWTEXT, script data and script data escaped end tag open states only differ in their states
+    fn end_tag_open(
+        &mut self,
+        end_tag_name_state: State,
+        return_state: State,
+    ) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            // ASCII alpha: Create a new end tag token, set its tag name to the empty string. Reconsume in the end tag name state.
+            Some(char) if char.is_ascii_alphabetic() => {
+                self.current_tag_type = TagType::EndTag;
+                self.current_tag = Tag::new();
+                self.current_tag_name.clear();
+                self.reconsume();
+                new_state!(self, end_tag_name_state)
+            }
+            // Anything else: Emit a U+003C LESS-THAN SIGN character token and a U+002F SOLIDUS character token. Reconsume in the return state.
+            _ => {
+                self.reconsume();
+                self.current_state = return_state;
+                self.emit_characters("</")
+            }
+        }
+    }
+
+    // the RCDATA, RAWTEXT, script data and script data escaped end tag name states only differ in their return state
+    fn end_tag_name(&mut self, return_state: State) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some(char)
+                if is_one_of_tab_lf_ff_space(char) && self.is_appropriate_end_tag_token() =>
+            {
+                new_state!(self, State::BeforeAttributeNameState)
+            }
+            Some('/') if self.is_appropriate_end_tag_token() => {
+                new_state!(self, State::SelfClosingStartTagState)
+            }
+            Some('>') if self.is_appropriate_end_tag_token() => {
+                self.current_state = State::DataState;
+                Some(self.build_current_tag_token())
+            }
+            Some(char) if char.is_ascii_alphabetic() => {
+                self.current_tag_name.push(char.to_ascii_lowercase());
+                self.temporary_buffer.push(char);
+                None
+            }
+            // Anything else: Emit a U+003C LESS-THAN SIGN character token, a U+002F SOLIDUS character token, and a character token for each of the characters in the temporary buffer (in the order they were added to the buffer). Reconsume in the return state.
+            _ => {
+                self.reconsume();
+                self.current_state = return_state;
+                let chars = format!("</{}", self.temporary_buffer);
+                self.emit_characters(chars)
+            }
+        }
+    }
+
+    // the script data double escape start and end states only differ in the states they switch to
+    fn double_escape_boundary(
+        &mut self,
+        script_state: State,
+        other_state: State,
+    ) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some(char) if is_one_of_tab_lf_ff_space(char) || char == '/' || char == '>' => {
+                // If the temporary buffer is the string "script", then switch to the first state. Otherwise, switch to the second state. Emit the current input character as a character token.
+                self.current_state = if self.temporary_buffer == "script" {
+                    script_state
+                } else {
+                    other_state
+                };
+                Some(Token::Character(char))
+            }
+            Some(char) if char.is_ascii_alphabetic() => {
+                self.temporary_buffer.push(char.to_ascii_lowercase());
+                Some(Token::Character(char))
+            }
+            _ => {
+                self.reconsume();
+                new_state!(self, other_state)
+            }
+        }
+    }
+
+    // 13.2.5.32 Before attribute name state
+    // https://html.spec.whatwg.org/multipage/parsing.html#before-attribute-name-state
+    fn before_attribute_name_state(&mut self) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some(char) if is_one_of_tab_lf_ff_space(char) => {
+                new_state!(self, State::BeforeAttributeNameState)
+            }
+            Some('/') | Some('>') | None => {
+                self.reconsume();
+                new_state!(self, State::AfterAttributeNameStat)
+            }
+            Some('=') => {
+                // todo unexpected-equals-sign-before-attribute-name parse error
+                self.add_current_attribute_to_current_tag();
+                self.current_attribute_name.push('=');
+                new_state!(self, State::AttributeNameState)
+            }
+            Some(_) => {
+                self.add_current_attribute_to_current_tag();
+                self.reconsume();
+                new_state!(self, State::AttributeNameState)
+            }
+        }
+    }
+
+    // 13.2.5.33 Attribute name state
+    // https://html.spec.whatwg.org/multipage/parsing.html#attribute-name-state
+    fn attribute_name_state(&mut self) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some(char) if is_one_of_tab_lf_ff_space(char) => {
+                self.reconsume();
+                new_state!(self, State::AfterAttributeNameStat)
+            }
+            Some('/') | Some('>') | None => {
+                self.reconsume();
+                new_state!(self, State::AfterAttributeNameStat)
+            }
+            Some('=') => new_state!(self, State::BeforeAttributeValueState),
+            Some(char) if char.is_ascii_uppercase() => {
+                self.current_attribute_name.push(char.to_ascii_lowercase());
+                new_state!(self, State::AttributeNameState)
+            }
+            Some('\u{0000}') => {
+                // todo unexpected-null-character parse error
+                self.current_attribute_name.push('\u{FFFD}');
+                new_state!(self, State::AttributeNameState)
+            }
+            Some(char) => {
+                // todo '"', '\'' and '<' are a unexpected-character-in-attribute-name parse error
+                self.current_attribute_name.push(char);
+                new_state!(self, State::AttributeNameState)
+            }
+        }
+    }
+
+    // 13.2.5.34 After attribute name state
+    // https://html.spec.whatwg.org/multipage/parsing.html#after-attribute-name-state
+    fn after_attribute_name_state(&mut self) -> Option<Token<'a>> {
         if let Some(char) = self.consume_next_input_character() {
             match char {
-                '-' => new_state!(self, State::CommentEndState),
+                _ if is_one_of_tab_lf_ff_space(char) => {
+                    new_state!(self, State::AfterAttributeNameStat)
+                }
+                '/' => new_state!(self, State::SelfClosingStartTagState),
+                '=' => new_state!(self, State::BeforeAttributeValueState),
+                '>' => {
+                    self.current_state = State::DataState;
+                    Some(self.build_current_tag_token())
+                }
                 _ => {
-                    self.current_comment_data.push('-');
+                    self.add_current_attribute_to_current_tag();
                     self.reconsume();
-                    new_state!(self, State::CommentState)
+                    new_state!(self, State::AttributeNameState)
                 }
             }
         } else {
-            todo!()
+            // todo eof-in-tag parse error
+            Some(Token::EndOfFile)
         }
     }
 
-    // 13.2.5.51 Comment end state
-    // https://html.spec.whatwg.org/multipage/parsing.html#comment-end-state
-    fn comment_end_state(&mut self) -> Option<Token> {
+    // 13.2.5.35 Before attribute value state
+    // https://html.spec.whatwg.org/multipage/parsing.html#before-attribute-value-state
+    fn before_attribute_value_state(&mut self) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some(char) if is_one_of_tab_lf_ff_space(char) => {
+                new_state!(self, State::BeforeAttributeValueState)
+            }
+            Some('"') => {
+                self.current_attribute_value = Data::starting_at(self.index);
+                new_state!(self, State::AttributeValueDoubleQuotedState)
+            }
+            Some('\'') => {
+                self.current_attribute_value = Data::starting_at(self.index);
+                new_state!(self, State::AttributeValueSingleQuotedState)
+            }
+            Some('>') => {
+                // todo missing-attribute-value parse error
+                self.current_state = State::DataState;
+                Some(self.build_current_tag_token())
+            }
+            _ => {
+                self.reconsume();
+                self.current_attribute_value = Data::starting_at(self.index);
+                new_state!(self, State::AttributeValueUnquotesState)
+            }
+        }
+    }
+
+    // 13.2.5.36 Attribute value (double-quoted) state
+    // https://html.spec.whatwg.org/multipage/parsing.html#attribute-value-(double-quoted)-state
+    fn attribute_value_double_quoted_state(&mut self) -> Option<Token<'a>> {
         if let Some(char) = self.consume_next_input_character() {
             match char {
-                '>' => {
-                    self.current_state = State::DataState;
-                    return Some(Token::Comment(self.current_comment_data.clone()));
+                '"' => new_state!(self, State::AfterAttributeValueQuotedState),
+                '&' => {
+                    self.return_state = State::AttributeValueDoubleQuotedState;
+                    new_state!(self, State::CharacterReferenceState)
+                }
+                '\u{0000}' => {
+                    // todo unexpected-null-character parse error
+                    self.current_attribute_value.push(self.input, '\u{FFFD}');
+                    None
+                }
+                _ => {
+                    self.current_attribute_value.push(self.input, char);
+                    new_state!(self, State::AttributeValueDoubleQuotedState)
                 }
-                _ => todo!(),
             }
         } else {
-            todo!()
+            // todo eof-in-tag parse error
+            Some(Token::EndOfFile)
         }
     }
 
-    // 13.2.5.53 DOCTYPE state
-    // https://html.spec.whatwg.org/multipage/parsing.html#doctype-state
-    fn doctype_state(&mut self) -> Option<Token> {
+    // 13.2.5.37 Attribute value (single-quoted) state
+    // https://html.spec.whatwg.org/multipage/parsing.html#attribute-value-(single-quoted)-state
+    fn attribute_value_single_quoted_state(&mut self) -> Option<Token<'a>> {
         if let Some(char) = self.consume_next_input_character() {
             match char {
-                _ if is_one_of_tab_lf_ff_space(char) => {
-                    new_state!(self, State::BeforeDOCTYPENameState)
+                '\'' => new_state!(self, State::AfterAttributeValueQuotedState),
+                '&' => {
+                    self.return_state = State::AttributeValueSingleQuotedState;
+                    new_state!(self, State::CharacterReferenceState)
+                }
+                '\u{0000}' => {
+                    // todo unexpected-null-character parse error
+                    self.current_attribute_value.push(self.input, '\u{FFFD}');
+                    None
+                }
+                _ => {
+                    self.current_attribute_value.push(self.input, char);
+                    None
                 }
-                _ => todo!(),
             }
         } else {
-            todo!()
+            // todo eof-in-tag parse error
+            Some(Token::EndOfFile)
         }
     }
 
-    // 13.2.5.54 Before DOCTYPE name state
-    // https://html.spec.whatwg.org/multipage/parsing.html#before-doctype-name-state
-    fn before_doctype_name_state(&mut self) -> Option<Token> {
+    // 13.2.5.38 Attribute value (unquoted) state
+    // https://html.spec.whatwg.org/multipage/parsing.html#attribute-value-(unquoted)-state
+    fn attribute_value_unquoted_state(&mut self) -> Option<Token<'a>> {
         if let Some(char) = self.consume_next_input_character() {
             match char {
                 _ if is_one_of_tab_lf_ff_space(char) => {
-                    new_state!(self, State::BeforeDOCTYPENameState)
+                    new_state!(self, State::BeforeAttributeNameState)
+                }
+                '&' => {
+                    self.return_state = State::AttributeValueUnquotesState;
+                    new_state!(self, State::CharacterReferenceState)
+                }
+                '>' => {
+                    self.current_state = State::DataState;
+                    Some(self.build_current_tag_token())
+                }
+                '\u{0000}' => {
+                    // todo unexpected-null-character parse error
+                    self.current_attribute_value.push(self.input, '\u{FFFD}');
+                    None
                 }
-                _ if char.is_ascii_uppercase() => todo!(),
-                '\u{0000}' => todo!(),
-                '>' => todo!(),
                 _ => {
-                    self.current_doc_type = DOCTYPE::default();
-                    self.current_doc_type.name = Some(String::from(char));
-                    new_state!(self, State::DOCTYPENameState)
+                    // todo '"', '\'', '<', '=' and '`' are a unexpected-character-in-unquoted-attribute-value parse error
+                    self.current_attribute_value.push(self.input, char);
+                    None
                 }
             }
         } else {
-            todo!()
+            // todo eof-in-tag parse error
+            Some(Token::EndOfFile)
         }
     }
 
-    // 13.2.5.55 DOCTYPE name state
-    // https://html.spec.whatwg.org/multipage/parsing.html#doctype-name-state
-    fn doctype_name_state(&mut self) -> Option<Token> {
+    // 13.2.5.39 After attribute value (quoted) state
+    // https://html.spec.whatwg.org/multipage/parsing.html#after-attribute-value-(quoted)-state
+    fn after_attribute_value_quoted_state(&mut self) -> Option<Token<'a>> {
         if let Some(char) = self.consume_next_input_character() {
             match char {
-                _ if is_one_of_tab_lf_ff_space(char) => todo!(),
-                _ if char.is_ascii_uppercase() => {
-                    todo!(); //Append the lowercase version of the current input character (add 0x0020 to the character's code point) to the current DOCTYPE token's name.
+                _ if is_one_of_tab_lf_ff_space(char) => {
+                    new_state!(self, State::BeforeAttributeNameState)
                 }
-                '\u{0000}' => todo!(),
+                '/' => new_state!(self, State::SelfClosingStartTagState),
                 '>' => {
                     self.current_state = State::DataState;
-                    Some(Token::Doctype(self.current_doc_type.clone()))
+                    Some(self.build_current_tag_token())
                 }
                 _ => {
-                    self.current_doc_type
-                        .name
-                        .as_mut()
-                        .expect("name should be a string")
-                        .push(char);
-                    new_state!(self, State::DOCTYPENameState)
-                } // todo Append the current input character to the current DOCTYPE token's name.
+                    // todo missing-whitespace-between-attributes parse error
+                    self.reconsume();
+                    new_state!(self, State::BeforeAttributeNameState)
+                }
+            }
+        } else {
+            // todo eof-in-tag parse error
+            Some(Token::EndOfFile)
+        }
+    }
+
+    // 13.2.5.40 Self-closing start tag state
+    // https://html.spec.whatwg.org/multipage/parsing.html#self-closing-start-tag-state
+    fn self_closing_start_tag_state(&mut self) -> Option<Token<'a>> {
+        if let Some(char) = self.consume_next_input_character() {
+            match char {
+                '>' => {
+                    self.current_tag.self_closing = true;
+                    self.current_state = State::DataState;
+                    Some(self.build_current_tag_token())
+                }
+                _ => {
+                    // todo unexpected-solidus-in-tag parse error
+                    self.reconsume();
+                    new_state!(self, State::BeforeAttributeNameState)
+                }
+            }
+        } else {
+            // todo eof-in-tag parse error
+            Some(Token::EndOfFile)
+        }
+    }
+
+    // 13.2.5.41 Bogus comment state
+    // https://html.spec.whatwg.org/multipage/parsing.html#bogus-comment-state
+    fn bogus_comment_state(&mut self) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some('>') => {
+                self.current_state = State::DataState;
+                Some(Token::Comment(self.current_comment_data.take(self.input)))
+            }
+            None => {
+                // Emit the comment. Emit an end-of-file token.
+                self.reconsume();
+                self.current_state = State::DataState;
+                Some(Token::Comment(self.current_comment_data.take(self.input)))
+            }
+            Some('\u{0000}') => {
+                // todo unexpected-null-character parse error
+                self.current_comment_data.push(self.input, '\u{FFFD}');
+                None
+            }
+            Some(char) => {
+                self.current_comment_data.push(self.input, char);
+                None
+            }
+        }
+    }
+
+    // 13.2.5.42 Markup declaration open state
+    // https://html.spec.whatwg.org/multipage/parsing.html#markup-declaration-open-state
+    fn markup_declaration_open_state(&mut self) -> Option<Token<'a>> {
+        if self.next_characters_are("--", false) {
+            self.consume_next_characters(2);
+            self.current_comment_data = Data::starting_at(self.index);
+            new_state!(self, State::CommentStartState)
+        } else if self.next_characters_are("doctype", true) {
+            self.consume_next_characters(7);
+            new_state!(self, State::DOCTYPEState)
+        } else if self.next_characters_are("[CDATA[", false) {
+            self.consume_next_characters(7);
+            // If there is an adjusted current node and it is not an element in the HTML namespace, then switch to the CDATA section state.
+            if self.cdata_allowed {
+                new_state!(self, State::CDATASectionState)
+            } else {
+                // Otherwise, this is a cdata-in-html-content parse error. Create a comment token whose data is the "[CDATA[" string. Switch to the bogus comment state.
+                self.current_comment_data = Data::starting_at(self.index - "[CDATA[".len());
+                self.current_comment_data.push_str(self.input, "[CDATA[");
+                new_state!(self, State::BogusCommentState)
             }
         } else {
-            todo!()
+            // todo incorrectly-opened-comment parse error
+            self.current_comment_data = Data::starting_at(self.index);
+            new_state!(self, State::BogusCommentState)
         }
     }
+
+    // 13.2.5.43 Comment start state
+    // https://html.spec.whatwg.org/multipage/parsing.html#comment-start-state
+    fn comment_start_state(&mut self) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some('-') => new_state!(self, State::CommentStartDashState),
+            Some('>') => {
+                // todo abrupt-closing-of-empty-comment parse error
+                self.current_state = State::DataState;
+                Some(Token::Comment(self.current_comment_data.take(self.input)))
+            }
+            _ => {
+                self.reconsume();
+                new_state!(self, State::CommentState)
+            }
+        }
+    }
+
+    // 13.2.5.44 Comment start dash state
+    // https://html.spec.whatwg.org/multipage/parsing.html#comment-start-dash-state
+    fn comment_start_dash_state(&mut self) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some('-') => new_state!(self, State::CommentEndState),
+            Some('>') => {
+                // todo abrupt-closing-of-empty-comment parse error
+                self.current_state = State::DataState;
+                Some(Token::Comment(self.current_comment_data.take(self.input)))
+            }
+            None => self.eof_in_comment(),
+            Some(_) => {
+                self.current_comment_data.push(self.input, '-');
+                self.reconsume();
+                new_state!(self, State::CommentState)
+            }
+        }
+    }
+
+    // 13.2.5.45 Comment state
+    // https://html.spec.whatwg.org/multipage/parsing.html#comment-state
+    fn comment_state(&mut self) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some('<') => {
+                self.current_comment_data.push(self.input, '<');
+                new_state!(self, State::CommentLessThanSignState)
+            }
+            Some('-') => new_state!(self, State::CommentEndDashState),
+            Some('\u{0000}') => {
+                // todo unexpected-null-character parse error
+                self.current_comment_data.push(self.input, '\u{FFFD}');
+                None
+            }
+            None => self.eof_in_comment(),
+            Some(char) => {
+                self.current_comment_data.push(self.input, char);
+                None
+            }
+        }
+    }
+
+    // 13.2.5.46 Comment less-than sign state
+    // https://html.spec.whatwg.org/multipage/parsing.html#comment-less-than-sign-state
+    fn comment_less_than_sign_state(&mut self) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some('!') => {
+                self.current_comment_data.push(self.input, '!');
+                new_state!(self, State::CommentLessThanSignBandState)
+            }
+            Some('<') => {
+                self.current_comment_data.push(self.input, '<');
+                None
+            }
+            _ => {
+                self.reconsume();
+                new_state!(self, State::CommentState)
+            }
+        }
+    }
+
+    // 13.2.5.47 Comment less-than sign bang state
+    // https://html.spec.whatwg.org/multipage/parsing.html#comment-less-than-sign-bang-state
+    fn comment_less_than_sign_bang_state(&mut self) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some('-') => new_state!(self, State::CommentLessThanSignBangDashState),
+            _ => {
+                self.reconsume();
+                new_state!(self, State::CommentState)
+            }
+        }
+    }
+
+    // 13.2.5.48 Comment less-than sign bang dash state
+    // https://html.spec.whatwg.org/multipage/parsing.html#comment-less-than-sign-bang-dash-state
+    fn comment_less_than_sign_bang_dash_state(&mut self) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some('-') => new_state!(self, State::CommentLessThanSignBangDashDashState),
+            _ => {
+                self.reconsume();
+                new_state!(self, State::CommentEndDashState)
+            }
+        }
+    }
+
+    // 13.2.5.49 Comment less-than sign bang dash dash state
+    // https://html.spec.whatwg.org/multipage/parsing.html#comment-less-than-sign-bang-dash-dash-state
+    fn comment_less_than_sign_bang_dash_dash_state(&mut self) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some('>') | None => {
+                self.reconsume();
+                new_state!(self, State::CommentEndState)
+            }
+            Some(_) => {
+                // todo nested-comment parse error
+                self.reconsume();
+                new_state!(self, State::CommentEndState)
+            }
+        }
+    }
+
+    // 13.2.5.50 Comment end dash state
+    // https://html.spec.whatwg.org/multipage/parsing.html#comment-end-dash-state
+    fn comment_end_dash_state(&mut self) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some('-') => new_state!(self, State::CommentEndState),
+            None => self.eof_in_comment(),
+            Some(_) => {
+                self.current_comment_data.push(self.input, '-');
+                self.reconsume();
+                new_state!(self, State::CommentState)
+            }
+        }
+    }
+
+    // 13.2.5.51 Comment end state
+    // https://html.spec.whatwg.org/multipage/parsing.html#comment-end-state
+    fn comment_end_state(&mut self) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some('>') => {
+                self.current_state = State::DataState;
+                Some(Token::Comment(self.current_comment_data.take(self.input)))
+            }
+            Some('!') => new_state!(self, State::CommentEndBandState),
+            Some('-') => {
+                self.current_comment_data.push(self.input, '-');
+                None
+            }
+            None => self.eof_in_comment(),
+            Some(_) => {
+                self.current_comment_data.push_str(self.input, "--");
+                self.reconsume();
+                new_state!(self, State::CommentState)
+            }
+        }
+    }
+
+    // 13.2.5.52 Comment end bang state
+    // https://html.spec.whatwg.org/multipage/parsing.html#comment-end-bang-state
+    fn comment_end_bang_state(&mut self) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some('-') => {
+                self.current_comment_data.push_str(self.input, "--!");
+                new_state!(self, State::CommentEndDashState)
+            }
+            Some('>') => {
+                // todo incorrectly-closed-comment parse error
+                self.current_state = State::DataState;
+                Some(Token::Comment(self.current_comment_data.take(self.input)))
+            }
+            None => self.eof_in_comment(),
+            Some(_) => {
+                self.current_comment_data.push_str(self.input, "--!");
+                self.reconsume();
+                new_state!(self, State::CommentState)
+            }
+        }
+    }
+
+    // EOF in the comment states: This is an eof-in-comment parse error. Emit the current comment token. Emit an end-of-file token.
+    fn eof_in_comment(&mut self) -> Option<Token<'a>> {
+        // todo eof-in-comment parse error
+        self.reconsume();
+        self.current_state = State::DataState;
+        Some(Token::Comment(self.current_comment_data.take(self.input)))
+    }
+
+    // Switch to the data state. Emit the current DOCTYPE token.
+    fn emit_current_doctype(&mut self) -> Option<Token<'a>> {
+        self.current_state = State::DataState;
+        Some(Token::Doctype(std::mem::take(&mut self.current_doc_type)))
+    }
+
+    // EOF in the DOCTYPE states: This is an eof-in-doctype parse error. Set the current DOCTYPE token's force-quirks flag to on. Emit the current DOCTYPE token. Emit an end-of-file token.
+    fn eof_in_doctype(&mut self) -> Option<Token<'a>> {
+        // todo eof-in-doctype parse error
+        self.current_doc_type.force_quirks = true;
+        self.reconsume();
+        self.emit_current_doctype()
+    }
+
+    // Set the current DOCTYPE token's force-quirks flag to on. Reconsume in the bogus DOCTYPE state.
+    fn bogus_doctype(&mut self) -> Option<Token<'a>> {
+        self.current_doc_type.force_quirks = true;
+        self.reconsume();
+        new_state!(self, State::BogusDoctypeState)
+    }
+
+    // 13.2.5.53 DOCTYPE state
+    // https://html.spec.whatwg.org/multipage/parsing.html#doctype-state
+    fn doctype_state(&mut self) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some(char) if is_one_of_tab_lf_ff_space(char) => {
+                new_state!(self, State::BeforeDOCTYPENameState)
+            }
+            Some('>') => {
+                self.reconsume();
+                new_state!(self, State::BeforeDOCTYPENameState)
+            }
+            None => {
+                // Create a new DOCTYPE token.
+                self.current_doc_type = DOCTYPE::default();
+                self.eof_in_doctype()
+            }
+            Some(_) => {
+                // todo missing-whitespace-before-doctype-name parse error
+                self.reconsume();
+                new_state!(self, State::BeforeDOCTYPENameState)
+            }
+        }
+    }
+
+    // 13.2.5.54 Before DOCTYPE name state
+    // https://html.spec.whatwg.org/multipage/parsing.html#before-doctype-name-state
+    fn before_doctype_name_state(&mut self) -> Option<Token<'a>> {
+        let char = self.consume_next_input_character();
+        if char.is_some_and(is_one_of_tab_lf_ff_space) {
+            // Ignore the character.
+            return None;
+        }
+        self.current_doc_type = DOCTYPE::default();
+        match char {
+            Some('\u{0000}') => {
+                // todo unexpected-null-character parse error
+                self.current_doc_type.name = Some(String::from('\u{FFFD}'));
+                new_state!(self, State::DOCTYPENameState)
+            }
+            Some('>') => {
+                // todo missing-doctype-name parse error
+                self.current_doc_type.force_quirks = true;
+                self.emit_current_doctype()
+            }
+            None => self.eof_in_doctype(),
+            // ASCII upper alpha: Set the token's name to the lowercase version of the current input character.
+            Some(char) => {
+                self.current_doc_type.name = Some(String::from(char.to_ascii_lowercase()));
+                new_state!(self, State::DOCTYPENameState)
+            }
+        }
+    }
+
+    // 13.2.5.55 DOCTYPE name state
+    // https://html.spec.whatwg.org/multipage/parsing.html#doctype-name-state
+    fn doctype_name_state(&mut self) -> Option<Token<'a>> {
+        let char = self.consume_next_input_character();
+        let name = self.current_doc_type.name.get_or_insert_default();
+        match char {
+            Some(char) if is_one_of_tab_lf_ff_space(char) => {
+                new_state!(self, State::AfterDOCTYPENameState)
+            }
+            Some('>') => self.emit_current_doctype(),
+            Some('\u{0000}') => {
+                // todo unexpected-null-character parse error
+                name.push('\u{FFFD}');
+                None
+            }
+            None => self.eof_in_doctype(),
+            // ASCII upper alpha: Append the lowercase version of the current input character to the current DOCTYPE token's name.
+            Some(char) => {
+                name.push(char.to_ascii_lowercase());
+                None
+            }
+        }
+    }
+
+    // 13.2.5.56 After DOCTYPE name state
+    // https://html.spec.whatwg.org/multipage/parsing.html#after-doctype-name-state
+    fn after_doctype_name_state(&mut self) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some(char) if is_one_of_tab_lf_ff_space(char) => None,
+            Some('>') => self.emit_current_doctype(),
+            None => self.eof_in_doctype(),
+            Some(_) => {
+                // the six characters starting from the current input character
+                self.reconsume();
+                // If the six characters starting from the current input character are an ASCII case-insensitive match for the word "PUBLIC", then consume those characters and switch to the after DOCTYPE public keyword state.
+                if self.next_characters_are("PUBLIC", true) {
+                    self.consume_next_characters(6);
+                    new_state!(self, State::AfterDOCTYPEpublicKeywordState)
+                }
+                // Otherwise, if the six characters starting from the current input character are an ASCII case-insensitive match for the word "SYSTEM", then consume those characters and switch to the after DOCTYPE system keyword state.
+                else if self.next_characters_are("SYSTEM", true) {
+                    self.consume_next_characters(6);
+                    new_state!(self, State::AfterDoctypeSystemKeywordState)
+                } else {
+                    // todo invalid-character-sequence-after-doctype-name parse error
+                    // the current input character is already reconsumed
+                    self.current_doc_type.force_quirks = true;
+                    new_state!(self, State::BogusDoctypeState)
+                }
+            }
+        }
+    }
+
+    // 13.2.5.57 After DOCTYPE public keyword state
+    // https://html.spec.whatwg.org/multipage/parsing.html#after-doctype-public-keyword-state
+    // 13.2.5.63 After DOCTYPE system keyword state
+    // https://html.spec.whatwg.org/multipage/parsing.html#after-doctype-system-keyword-state
+    fn after_doctype_keyword_state(&mut self, identifier: DoctypeIdentifier) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some(char) if is_one_of_tab_lf_ff_space(char) => {
+                new_state!(self, identifier.before_state())
+            }
+            Some(quote @ ('"' | '\'')) => {
+                // todo missing-whitespace-after-doctype-public-keyword or missing-whitespace-after-doctype-system-keyword parse error
+                self.start_doctype_identifier(identifier, quote)
+            }
+            Some('>') => {
+                // todo missing-doctype-public-identifier or missing-doctype-system-identifier parse error
+                self.current_doc_type.force_quirks = true;
+                self.emit_current_doctype()
+            }
+            None => self.eof_in_doctype(),
+            Some(_) => {
+                // todo missing-quote-before-doctype-public-identifier or missing-quote-before-doctype-system-identifier parse error
+                self.bogus_doctype()
+            }
+        }
+    }
+
+    // 13.2.5.58 Before DOCTYPE public identifier state
+    // https://html.spec.whatwg.org/multipage/parsing.html#before-doctype-public-identifier-state
+    // 13.2.5.64 Before DOCTYPE system identifier state
+    // https://html.spec.whatwg.org/multipage/parsing.html#before-doctype-system-identifier-state
+    fn before_doctype_identifier_state(
+        &mut self,
+        identifier: DoctypeIdentifier,
+    ) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some(char) if is_one_of_tab_lf_ff_space(char) => None,
+            Some(quote @ ('"' | '\'')) => self.start_doctype_identifier(identifier, quote),
+            Some('>') => {
+                // todo missing-doctype-public-identifier or missing-doctype-system-identifier parse error
+                self.current_doc_type.force_quirks = true;
+                self.emit_current_doctype()
+            }
+            None => self.eof_in_doctype(),
+            Some(_) => {
+                // todo missing-quote-before-doctype-public-identifier or missing-quote-before-doctype-system-identifier parse error
+                self.bogus_doctype()
+            }
+        }
+    }
+
+    // Set the current DOCTYPE token's public or system identifier to the empty (not missing) string, switch to the quoted state of the identifier.
+    fn start_doctype_identifier(
+        &mut self,
+        identifier: DoctypeIdentifier,
+        quote: char,
+    ) -> Option<Token<'a>> {
+        *identifier.of(&mut self.current_doc_type) = Some(String::new());
+        new_state!(self, identifier.quoted_state(quote))
+    }
+
+    // 13.2.5.59 DOCTYPE public identifier (double-quoted) state
+    // https://html.spec.whatwg.org/multipage/parsing.html#doctype-public-identifier-(double-quoted)-state
+    // 13.2.5.60 DOCTYPE public identifier (single-quoted) state, 13.2.5.65 and 13.2.5.66 the system identifier states
+    fn doctype_identifier_quoted_state(
+        &mut self,
+        identifier: DoctypeIdentifier,
+        quote: char,
+    ) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some(char) if char == quote => new_state!(self, identifier.after_state()),
+            Some('\u{0000}') => {
+                // todo unexpected-null-character parse error
+                identifier
+                    .of(&mut self.current_doc_type)
+                    .get_or_insert_default()
+                    .push('\u{FFFD}');
+                None
+            }
+            Some('>') => {
+                // todo abrupt-doctype-public-identifier or abrupt-doctype-system-identifier parse error
+                self.current_doc_type.force_quirks = true;
+                self.emit_current_doctype()
+            }
+            None => self.eof_in_doctype(),
+            Some(char) => {
+                identifier
+                    .of(&mut self.current_doc_type)
+                    .get_or_insert_default()
+                    .push(char);
+                None
+            }
+        }
+    }
+
+    // 13.2.5.61 After DOCTYPE public identifier state
+    // https://html.spec.whatwg.org/multipage/parsing.html#after-doctype-public-identifier-state
+    fn after_doctype_public_identifier_state(&mut self) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some(char) if is_one_of_tab_lf_ff_space(char) => {
+                new_state!(self, State::BetweenDoctypePublicAndSystemIdentifiersState)
+            }
+            Some('>') => self.emit_current_doctype(),
+            Some(quote @ ('"' | '\'')) => {
+                // todo missing-whitespace-between-doctype-public-and-system-identifiers parse error
+                self.start_doctype_identifier(DoctypeIdentifier::System, quote)
+            }
+            None => self.eof_in_doctype(),
+            Some(_) => {
+                // todo missing-quote-before-doctype-system-identifier parse error
+                self.bogus_doctype()
+            }
+        }
+    }
+
+    // 13.2.5.62 Between DOCTYPE public and system identifiers state
+    // https://html.spec.whatwg.org/multipage/parsing.html#between-doctype-public-and-system-identifiers-state
+    fn between_doctype_public_and_system_identifiers_state(&mut self) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some(char) if is_one_of_tab_lf_ff_space(char) => None,
+            Some('>') => self.emit_current_doctype(),
+            Some(quote @ ('"' | '\'')) => {
+                self.start_doctype_identifier(DoctypeIdentifier::System, quote)
+            }
+            None => self.eof_in_doctype(),
+            Some(_) => {
+                // todo missing-quote-before-doctype-system-identifier parse error
+                self.bogus_doctype()
+            }
+        }
+    }
+
+    // 13.2.5.67 After DOCTYPE system identifier state
+    // https://html.spec.whatwg.org/multipage/parsing.html#after-doctype-system-identifier-state
+    fn after_doctype_system_identifier_state(&mut self) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some(char) if is_one_of_tab_lf_ff_space(char) => None,
+            Some('>') => self.emit_current_doctype(),
+            None => self.eof_in_doctype(),
+            Some(_) => {
+                // todo unexpected-character-after-doctype-system-identifier parse error
+                // (This does not set the current DOCTYPE token's force-quirks flag to on.)
+                self.reconsume();
+                new_state!(self, State::BogusDoctypeState)
+            }
+        }
+    }
+
+    // 13.2.5.68 Bogus DOCTYPE state
+    // https://html.spec.whatwg.org/multipage/parsing.html#bogus-doctype-state
+    fn bogus_doctype_state(&mut self) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some('>') => self.emit_current_doctype(),
+            None => {
+                // Emit the DOCTYPE token. Emit an end-of-file token.
+                self.reconsume();
+                self.emit_current_doctype()
+            }
+            // todo unexpected-null-character parse error for U+0000
+            // Ignore the character.
+            Some(_) => None,
+        }
+    }
+
+    // 13.2.5.69 CDATA section state
+    // https://html.spec.whatwg.org/multipage/parsing.html#cdata-section-state
+    fn cdata_section_state(&mut self) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some(']') => new_state!(self, State::CDATASectionBracketState),
+            Some(char) => Some(Token::Character(char)),
+            None => {
+                // todo eof-in-cdata parse error
+                Some(Token::EndOfFile)
+            }
+        }
+    }
+
+    // 13.2.5.70 CDATA section bracket state
+    // https://html.spec.whatwg.org/multipage/parsing.html#cdata-section-bracket-state
+    fn cdata_section_bracket_state(&mut self) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some(']') => new_state!(self, State::CDATASectionEndState),
+            _ => {
+                self.reconsume();
+                self.current_state = State::CDATASectionState;
+                Some(Token::Character(']'))
+            }
+        }
+    }
+
+    // 13.2.5.71 CDATA section end state
+    // https://html.spec.whatwg.org/multipage/parsing.html#cdata-section-end-state
+    fn cdata_section_end_state(&mut self) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            Some(']') => Some(Token::Character(']')),
+            Some('>') => new_state!(self, State::DataState),
+            _ => {
+                self.reconsume();
+                self.current_state = State::CDATASectionState;
+                self.queued_tokens.push_back(Token::Character(']'));
+                Some(Token::Character(']'))
+            }
+        }
+    }
+
+    // https://html.spec.whatwg.org/multipage/parsing.html#charref-in-attribute
+    // A character reference is said to be consumed as part of an attribute if the return state is either attribute value (double-quoted) state, attribute value (single-quoted) state, or attribute value (unquoted) state.
+    fn consumed_as_part_of_an_attribute(&self) -> bool {
+        matches!(
+            self.return_state,
+            State::AttributeValueDoubleQuotedState
+                | State::AttributeValueSingleQuotedState
+                | State::AttributeValueUnquotesState
+        )
+    }
+
+    // https://html.spec.whatwg.org/multipage/parsing.html#flush-code-points-consumed-as-a-character-reference
+    // When a state says to flush code points consumed as a character reference, it means that for each code point in the temporary buffer (in the order they were added to the buffer) user agent must append the code point from the buffer to the current attribute's value if the character reference was consumed as part of an attribute, or emit the code point as a character token otherwise.
+    fn flush_code_points_consumed_as_a_character_reference(&mut self) -> Option<Token<'a>> {
+        let temporary_buffer = std::mem::take(&mut self.temporary_buffer);
+        if self.consumed_as_part_of_an_attribute() {
+            self.current_attribute_value
+                .push_str(self.input, &temporary_buffer);
+            None
+        } else {
+            self.emit_characters(temporary_buffer)
+        }
+    }
+
+    // 13.2.5.72 Character reference state
+    // https://html.spec.whatwg.org/multipage/parsing.html#character-reference-state
+    fn character_reference_state(&mut self) -> Option<Token<'a>> {
+        // Set the temporary buffer to the empty string. Append a U+0026 AMPERSAND (&) character to the temporary buffer.
+        self.temporary_buffer = String::from('&');
+        match self.consume_next_input_character() {
+            // ASCII alphanumeric: Reconsume in the named character reference state.
+            Some(char) if char.is_ascii_alphanumeric() => {
+                self.reconsume();
+                new_state!(self, State::NamedCharacterReferenceState)
+            }
+            // U+0023 NUMBER SIGN (#): Append the current input character to the temporary buffer. Switch to the numeric character reference state.
+            Some('#') => {
+                self.temporary_buffer.push('#');
+                new_state!(self, State::NumericCharacterReferenceState)
+            }
+            // Anything else: Flush code points consumed as a character reference. Reconsume in the return state.
+            _ => {
+                self.reconsume();
+                self.current_state = self.return_state;
+                self.flush_code_points_consumed_as_a_character_reference()
+            }
+        }
+    }
+
+    // 13.2.5.73 Named character reference state
+    // https://html.spec.whatwg.org/multipage/parsing.html#named-character-reference-state
+    fn named_character_reference_state(&mut self) -> Option<Token<'a>> {
+        // Consume the maximum number of characters possible, where the consumed characters are one of the identifiers in the first column of the named character references table. Append each character to the temporary buffer when it's consumed.
+        let candidate: String = self.remaining_input().chars().take(LONGEST_NAME).collect();
+        let reference = (1..=candidate.len())
+            .rev()
+            .filter(|length| candidate.is_char_boundary(*length))
+            .find_map(|length| {
+                let name = &candidate[..length];
+                NAMED_CHARACTER_REFERENCES
+                    .binary_search_by(|(item, _)| (*item).cmp(name))
+                    .ok()
+                    .map(|position| NAMED_CHARACTER_REFERENCES[position])
+            });
+        // If there is a match
+        if let Some((name, characters)) = reference {
+            self.consume_next_characters(name.chars().count());
+            self.temporary_buffer.push_str(name);
+            // If the character reference was consumed as part of an attribute, and the last character matched is not a U+003B SEMICOLON character (;), and the next input character is either a U+003D EQUALS SIGN character (=) or an ASCII alphanumeric, then, for historical reasons, flush code points consumed as a character reference and switch to the return state.
+            let next_input_character = self.remaining_input().chars().next();
+            if self.consumed_as_part_of_an_attribute()
+                && !name.ends_with(';')
+                && next_input_character
+                    .is_some_and(|char| char == '=' || char.is_ascii_alphanumeric())
+            {
+                self.current_state = self.return_state;
+                return self.flush_code_points_consumed_as_a_character_reference();
+            }
+            // Otherwise:
+            // 1. If the last character matched is not a U+003B SEMICOLON character (;), then this is a missing-semicolon-after-character-reference parse error.
+            // todo missing-semicolon-after-character-reference parse error
+            // 2. Set the temporary buffer to the empty string. Append one or two characters corresponding to the character reference name (as given by the second column of the named character references table) to the temporary buffer.
+            self.temporary_buffer = characters.to_string();
+            // 3. Flush code points consumed as a character reference. Switch to the return state.
+            self.current_state = self.return_state;
+            self.flush_code_points_consumed_as_a_character_reference()
+        } else {
+            // Otherwise: Flush code points consumed as a character reference. Switch to the ambiguous ampersand state.
+            self.current_state = State::AmbiguousAmpersandState;
+            self.flush_code_points_consumed_as_a_character_reference()
+        }
+    }
+
+    // 13.2.5.74 Ambiguous ampersand state
+    // https://html.spec.whatwg.org/multipage/parsing.html#ambiguous-ampersand-state
+    fn ambiguous_ampersand_state(&mut self) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            // ASCII alphanumeric: If the character reference was consumed as part of an attribute, then append the current input character to the current attribute's value. Otherwise, emit the current input character as a character token.
+            Some(char) if char.is_ascii_alphanumeric() => {
+                if self.consumed_as_part_of_an_attribute() {
+                    self.current_attribute_value.push(self.input, char);
+                    None
+                } else {
+                    Some(Token::Character(char))
+                }
+            }
+            // U+003B SEMICOLON (;): This is an unknown-named-character-reference parse error. Reconsume in the return state.
+            // todo unknown-named-character-reference parse error
+            // Anything else: Reconsume in the return state.
+            _ => {
+                self.reconsume();
+                new_state!(self, self.return_state)
+            }
+        }
+    }
+
+    // 13.2.5.75 Numeric character reference state
+    // https://html.spec.whatwg.org/multipage/parsing.html#numeric-character-reference-state
+    fn numeric_character_reference_state(&mut self) -> Option<Token<'a>> {
+        // Set the character reference code to zero (0).
+        self.character_reference_code = 0;
+        match self.consume_next_input_character() {
+            // U+0078 LATIN SMALL LETTER X, U+0058 LATIN CAPITAL LETTER X: Append the current input character to the temporary buffer. Switch to the hexadecimal character reference start state.
+            Some(char @ ('x' | 'X')) => {
+                self.temporary_buffer.push(char);
+                new_state!(self, State::HexadecimalCharacterReferenceStartState)
+            }
+            // Anything else: Reconsume in the decimal character reference start state.
+            _ => {
+                self.reconsume();
+                new_state!(self, State::DecimalCharacterReferenceStartState)
+            }
+        }
+    }
+
+    // 13.2.5.76 Hexadecimal character reference start state
+    // https://html.spec.whatwg.org/multipage/parsing.html#hexadecimal-character-reference-start-state
+    fn hexadecimal_character_reference_start_state(&mut self) -> Option<Token<'a>> {
+        self.numeric_character_reference_start(
+            |char| char.is_ascii_hexdigit(),
+            State::HexadecimalCharacterReferenceState,
+        )
+    }
+
+    // 13.2.5.77 Decimal character reference start state
+    // https://html.spec.whatwg.org/multipage/parsing.html#decimal-character-reference-start-state
+    fn decimal_character_reference_start_state(&mut self) -> Option<Token<'a>> {
+        self.numeric_character_reference_start(
+            |char| char.is_ascii_digit(),
+            State::DecimalCharacterReferenceState,
+        )
+    }
+
+    fn numeric_character_reference_start(
+        &mut self,
+        is_digit: fn(char) -> bool,
+        digits_state: State,
+    ) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            // ASCII (hex) digit: Reconsume in the hexadecimal (decimal) character reference state.
+            Some(char) if is_digit(char) => {
+                self.reconsume();
+                new_state!(self, digits_state)
+            }
+            // Anything else: This is an absence-of-digits-in-numeric-character-reference parse error. Flush code points consumed as a character reference. Reconsume in the return state.
+            _ => {
+                // todo absence-of-digits-in-numeric-character-reference parse error
+                self.reconsume();
+                self.current_state = self.return_state;
+                self.flush_code_points_consumed_as_a_character_reference()
+            }
+        }
+    }
+
+    // 13.2.5.78 Hexadecimal character reference state
+    // https://html.spec.whatwg.org/multipage/parsing.html#hexadecimal-character-reference-state
+    fn hexadecimal_character_reference_state(&mut self) -> Option<Token<'a>> {
+        self.numeric_character_reference_digits(16)
+    }
+
+    // 13.2.5.79 Decimal character reference state
+    // https://html.spec.whatwg.org/multipage/parsing.html#decimal-character-reference-state
+    fn decimal_character_reference_state(&mut self) -> Option<Token<'a>> {
+        self.numeric_character_reference_digits(10)
+    }
+
+    fn numeric_character_reference_digits(&mut self, radix: u32) -> Option<Token<'a>> {
+        match self.consume_next_input_character() {
+            // ASCII digit: Multiply the character reference code by the radix. Add a numeric version of the current input character to the character reference code.
+            // the code saturates above the largest code point, it is out of range anyway
+            Some(char) if char.is_digit(radix) => {
+                let digit = char.to_digit(radix).unwrap();
+                self.character_reference_code =
+                    (self.character_reference_code * radix + digit).min(0x110000);
+                None
+            }
+            // U+003B SEMICOLON: Switch to the numeric character reference end state.
+            Some(';') => new_state!(self, State::NumericCharacterReferenceEndState),
+            // Anything else: This is a missing-semicolon-after-character-reference parse error. Reconsume in the numeric character reference end state.
+            _ => {
+                // todo missing-semicolon-after-character-reference parse error
+                self.reconsume();
+                new_state!(self, State::NumericCharacterReferenceEndState)
+            }
+        }
+    }
+
+    // 13.2.5.80 Numeric character reference end state
+    // https://html.spec.whatwg.org/multipage/parsing.html#numeric-character-reference-end-state
+    fn numeric_character_reference_end_state(&mut self) -> Option<Token<'a>> {
+        // Check the character reference code:
+        let code = match self.character_reference_code {
+            // If the number is 0x00, then this is a null-character-reference parse error. Set the character reference code to 0xFFFD.
+            // If the number is greater than 0x10FFFF, then this is a character-reference-outside-unicode-range parse error. Set the character reference code to 0xFFFD.
+            // If the number is a surrogate, then this is a surrogate-character-reference parse error. Set the character reference code to 0xFFFD.
+            // todo null-character-reference, character-reference-outside-unicode-range and surrogate-character-reference parse errors
+            0x00 | 0x110000.. | 0xD800..=0xDFFF => 0xFFFD,
+            // If the number is a noncharacter, then this is a noncharacter-character-reference parse error.
+            // If the number is 0x0D, or a control that's not ASCII whitespace, then this is a control-character-reference parse error. If the number is one of the numbers in the first column of the following table, then find the row with that number in the first column, and set the character reference code to the number in the second column of that row.
+            // todo noncharacter-character-reference and control-character-reference parse errors
+            code @ 0x80..=0x9F => match code {
+                0x80 => 0x20AC,
+                0x82 => 0x201A,
+                0x83 => 0x0192,
+                0x84 => 0x201E,
+                0x85 => 0x2026,
+                0x86 => 0x2020,
+                0x87 => 0x2021,
+                0x88 => 0x02C6,
+                0x89 => 0x2030,
+                0x8A => 0x0160,
+                0x8B => 0x2039,
+                0x8C => 0x0152,
+                0x8E => 0x017D,
+                0x91 => 0x2018,
+                0x92 => 0x2019,
+                0x93 => 0x201C,
+                0x94 => 0x201D,
+                0x95 => 0x2022,
+                0x96 => 0x2013,
+                0x97 => 0x2014,
+                0x98 => 0x02DC,
+                0x99 => 0x2122,
+                0x9A => 0x0161,
+                0x9B => 0x203A,
+                0x9C => 0x0153,
+                0x9E => 0x017E,
+                0x9F => 0x0178,
+                _ => code,
+            },
+            code => code,
+        };
+        // Set the temporary buffer to the empty string. Append a code point equal to the character reference code to the temporary buffer. Flush code points consumed as a character reference. Switch to the return state.
+        self.temporary_buffer = String::from(
+            char::from_u32(code).expect("surrogates and out of range codes are replaced"),
+        );
+        self.current_state = self.return_state;
+        self.flush_code_points_consumed_as_a_character_reference()
+    }
 }
 
 fn is_one_of_tab_lf_ff_space(char: char) -> bool {
     char == '\u{0009}' || char == '\u{000A}' || char == '\u{000C}' || char == '\u{0020}'
 }
 
-fn lowercase_char_slice(slice: &[char]) -> String {
-    slice.iter().flat_map(|&c| c.to_lowercase()).collect()
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::atom;
+
+    // whether the data is a slice of the input
+    fn borrows_from(input: &str, data: &str) -> bool {
+        input.as_bytes().as_ptr_range().contains(&data.as_ptr())
+    }
+
+    #[test]
+    fn tokens_borrow_from_the_input() {
+        let input = r#"<a href="/x" title=t>text</a><!-- comment -->"#;
+        let tokens: Vec<Token> = Tokenizer::new(input).collect();
+        let Token::StartTag(tag) = &tokens[0] else {
+            panic!("expected a start tag, got {:?}", tokens[0]);
+        };
+        assert_eq!(tag.name, atom!("a"));
+        assert!(
+            tag.attributes
+                .iter()
+                .all(|attribute| borrows_from(input, &attribute.value))
+        );
+        assert_eq!(tag.get_attribute("title"), Some("t"));
+        assert!(
+            matches!(&tokens[1], Token::Characters(data) if data == "text" && borrows_from(input, data))
+        );
+        assert!(
+            matches!(&tokens[3], Token::Comment(data) if data == " comment " && borrows_from(input, data))
+        );
+    }
+
+    #[test]
+    fn character_references_allocate() {
+        let input = "<p title='a&amp;b' class='&foo'>&lt;!--x-->";
+        let tokens: Vec<Token> = Tokenizer::new(input).collect();
+        let Token::StartTag(tag) = &tokens[0] else {
+            panic!("expected a start tag, got {:?}", tokens[0]);
+        };
+        assert_eq!(tag.attributes[0].value, "a&b");
+        assert!(!borrows_from(input, &tag.attributes[0].value));
+        // an unknown reference stays as it is in the input
+        assert_eq!(tag.attributes[1].value, "&foo");
+        assert!(borrows_from(input, &tag.attributes[1].value));
+        assert_eq!(tokens[1], Token::Characters("<".into()));
+        assert!(
+            matches!(&tokens[2], Token::Characters(data) if data == "!--x-->" && borrows_from(input, data))
+        );
+    }
+
+    #[test]
+    fn positions_count_characters() {
+        let mut tokenizer = Tokenizer::new("ä€<p>\n<b>");
+        assert_eq!(tokenizer.next_token(), Token::Characters("ä€".into()));
+        assert_eq!(tokenizer.position(), (1, 2));
+        tokenizer.next_token();
+        assert_eq!(tokenizer.position(), (1, 5));
+        tokenizer.next_token();
+        tokenizer.next_token();
+        assert_eq!(tokenizer.position(), (2, 3));
+    }
+
+    #[test]
+    fn invalid_tags() {
+        let tokens: Vec<Token> = Tokenizer::new("a < b</>c</ d><?e>\0<f\0>").collect();
+        assert_eq!(
+            tokens,
+            [
+                Token::Characters("a ".into()),
+                Token::Character('<'),
+                Token::Characters(" b".into()),
+                Token::Characters("c".into()),
+                Token::Comment(" d".into()),
+                Token::Comment("?e".into()),
+                Token::Character('\0'),
+                Token::StartTag(Tag::new_with_name("f\u{FFFD}")),
+                Token::EndOfFile,
+            ]
+        );
+        let tokens: Vec<Token> = Tokenizer::new("a<").collect();
+        assert_eq!(tokens[1..], [Token::Character('<'), Token::EndOfFile]);
+        let tokens: Vec<Token> = Tokenizer::new("a</").collect();
+        assert_eq!(
+            tokens[1..],
+            [Token::Characters("</".into()), Token::EndOfFile]
+        );
+    }
+
+    #[test]
+    fn owned_tokens_outlive_the_input() {
+        let token = {
+            let input = String::from("<p class=x>");
+            Tokenizer::new(&input).next_token().into_owned()
+        };
+        let Token::StartTag(tag) = token else {
+            panic!("expected a start tag, got {token:?}");
+        };
+        assert_eq!(tag.get_attribute("class"), Some("x"));
+    }
 }
@@ -0,0 +1,262 @@
+use crate::atom;
+use crate::html::{
+    atom::Atom,
+    tokenizer::Tag,
+    tree_builder::tree::{Element, Namespace},
+};
+
+// https://html.spec.whatwg.org/multipage/parsing.html#parsing-main-inforeign
+const SVG_TAG_NAME_ADJUSTMENTS: [(Atom, Atom); 37] = [
+    (atom!("altglyph"), atom!("altGlyph")),
+    (atom!("altglyphdef"), atom!("altGlyphDef")),
+    (atom!("altglyphitem"), atom!("altGlyphItem")),
+    (atom!("animatecolor"), atom!("animateColor")),
+    (atom!("animatemotion"), atom!("animateMotion")),
+    (atom!("animatetransform"), atom!("animateTransform")),
+    (atom!("clippath"), atom!("clipPath")),
+    (atom!("feblend"), atom!("feBlend")),
+    (atom!("fecolormatrix"), atom!("feColorMatrix")),
+    (atom!("fecomponenttransfer"), atom!("feComponentTransfer")),
+    (atom!("fecomposite"), atom!("feComposite")),
+    (atom!("feconvolvematrix"), atom!("feConvolveMatrix")),
+    (atom!("fediffuselighting"), atom!("feDiffuseLighting")),
+    (atom!("fedisplacementmap"), atom!("feDisplacementMap")),
+    (atom!("fedistantlight"), atom!("feDistantLight")),
+    (atom!("fedropshadow"), atom!("feDropShadow")),
+    (atom!("feflood"), atom!("feFlood")),
+    (atom!("fefunca"), atom!("feFuncA")),
+    (atom!("fefuncb"), atom!("feFuncB")),
+    (atom!("fefuncg"), atom!("feFuncG")),
+    (atom!("fefuncr"), atom!("feFuncR")),
+    (atom!("fegaussianblur"), atom!("feGaussianBlur")),
+    (atom!("feimage"), atom!("feImage")),
+    (atom!("femerge"), atom!("feMerge")),
+    (atom!("femergenode"), atom!("feMergeNode")),
+    (atom!("femorphology"), atom!("feMorphology")),
+    (atom!("feoffset"), atom!("feOffset")),
+    (atom!("fepointlight"), atom!("fePointLight")),
+    (atom!("fespecularlighting"), atom!("feSpecularLighting")),
+    (atom!("fespotlight"), atom!("feSpotLight")),
+    (atom!("fetile"), atom!("feTile")),
+    (atom!("feturbulence"), atom!("feTurbulence")),
+    (atom!("foreignobject"), atom!("foreignObject")),
+    (atom!("glyphref"), atom!("glyphRef")),
+    (atom!("lineargradient"), atom!("linearGradient")),
+    (atom!("radialgradient"), atom!("radialGradient")),
+    (atom!("textpath"), atom!("textPath")),
+];
+
+// https://html.spec.whatwg.org/multipage/parsing.html#adjust-svg-attributes
+const SVG_ATTRIBUTE_ADJUSTMENTS: [(Atom, Atom); 58] = [
+    (atom!("attributename"), atom!("attributeName")),
+    (atom!("attributetype"), atom!("attributeType")),
+    (atom!("basefrequency"), atom!("baseFrequency")),
+    (atom!("baseprofile"), atom!("baseProfile")),
+    (atom!("calcmode"), atom!("calcMode")),
+    (atom!("clippathunits"), atom!("clipPathUnits")),
+    (atom!("diffuseconstant"), atom!("diffuseConstant")),
+    (atom!("edgemode"), atom!("edgeMode")),
+    (atom!("filterunits"), atom!("filterUnits")),
+    (atom!("glyphref"), atom!("glyphRef")),
+    (atom!("gradienttransform"), atom!("gradientTransform")),
+    (atom!("gradientunits"), atom!("gradientUnits")),
+    (atom!("kernelmatrix"), atom!("kernelMatrix")),
+    (atom!("kernelunitlength"), atom!("kernelUnitLength")),
+    (atom!("keypoints"), atom!("keyPoints")),
+    (atom!("keysplines"), atom!("keySplines")),
+    (atom!("keytimes"), atom!("keyTimes")),
+    (atom!("lengthadjust"), atom!("lengthAdjust")),
+    (atom!("limitingconeangle"), atom!("limitingConeAngle")),
+    (atom!("markerheight"), atom!("markerHeight")),
+    (atom!("markerunits"), atom!("markerUnits")),
+    (atom!("markerwidth"), atom!("markerWidth")),
+    (atom!("maskcontentunits"), atom!("maskContentUnits")),
+    (atom!("maskunits"), atom!("maskUnits")),
+    (atom!("numoctaves"), atom!("numOctaves")),
+    (atom!("pathlength"), atom!("pathLength")),
+    (atom!("patterncontentunits"), atom!("patternContentUnits")),
+    (atom!("patterntransform"), atom!("patternTransform")),
+    (atom!("patternunits"), atom!("patternUnits")),
+    (atom!("pointsatx"), atom!("pointsAtX")),
+    (atom!("pointsaty"), atom!("pointsAtY")),
+    (atom!("pointsatz"), atom!("pointsAtZ")),
+    (atom!("preservealpha"), atom!("preserveAlpha")),
+    (atom!("preserveaspectratio"), atom!("preserveAspectRatio")),
+    (atom!("primitiveunits"), atom!("primitiveUnits")),
+    (atom!("refx"), atom!("refX")),
+    (atom!("refy"), atom!("refY")),
+    (atom!("repeatcount"), atom!("repeatCount")),
+    (atom!("repeatdur"), atom!("repeatDur")),
+    (atom!("requiredextensions"), atom!("requiredExtensions")),
+    (atom!("requiredfeatures"), atom!("requiredFeatures")),
+    (atom!("specularconstant"), atom!("specularConstant")),
+    (atom!("specularexponent"), atom!("specularExponent")),
+    (atom!("spreadmethod"), atom!("spreadMethod")),
+    (atom!("startoffset"), atom!("startOffset")),
+    (atom!("stddeviation"), atom!("stdDeviation")),
+    (atom!("stitchtiles"), atom!("stitchTiles")),
+    (atom!("surfacescale"), atom!("surfaceScale")),
+    (atom!("systemlanguage"), atom!("systemLanguage")),
+    (atom!("tablevalues"), atom!("tableValues")),
+    (atom!("targetx"), atom!("targetX")),
+    (atom!("targety"), atom!("targetY")),
+    (atom!("textlength"), atom!("textLength")),
+    (atom!("viewbox"), atom!("viewBox")),
+    (atom!("viewtarget"), atom!("viewTarget")),
+    (atom!("xchannelselector"), atom!("xChannelSelector")),
+    (atom!("ychannelselector"), atom!("yChannelSelector")),
+    (atom!("zoomandpan"), atom!("zoomAndPan")),
+];
+
+// https://html.spec.whatwg.org/multipage/parsing.html#adjust-foreign-attributes
+const FOREIGN_ATTRIBUTE_ADJUSTMENTS: [(Atom, Option<&str>, Atom, Namespace); 11] = [
+    (
+        atom!("xlink:actuate"),
+        Some("xlink"),
+        atom!("actuate"),
+        Namespace::XLink,
+    ),
+    (
+        atom!("xlink:arcrole"),
+        Some("xlink"),
+        atom!("arcrole"),
+        Namespace::XLink,
+    ),
+    (
+        atom!("xlink:href"),
+        Some("xlink"),
+        atom!("href"),
+        Namespace::XLink,
+    ),
+    (
+        atom!("xlink:role"),
+        Some("xlink"),
+        atom!("role"),
+        Namespace::XLink,
+    ),
+    (
+        atom!("xlink:show"),
+        Some("xlink"),
+        atom!("show"),
+        Namespace::XLink,
+    ),
+    (
+        atom!("xlink:title"),
+        Some("xlink"),
+        atom!("title"),
+        Namespace::XLink,
+    ),
+    (
+        atom!("xlink:type"),
+        Some("xlink"),
+        atom!("type"),
+        Namespace::XLink,
+    ),
+    (
+        atom!("xml:lang"),
+        Some("xml"),
+        atom!("lang"),
+        Namespace::Xml,
+    ),
+    (
+        atom!("xml:space"),
+        Some("xml"),
+        atom!("space"),
+        Namespace::Xml,
+    ),
+    (atom!("xmlns"), None, atom!("xmlns"), Namespace::Xmlns),
+    (
+        atom!("xmlns:xlink"),
+        Some("xmlns"),
+        atom!("xlink"),
+        Namespace::Xmlns,
+    ),
+];
+
+// If the adjusted current node is an element in the SVG namespace, and the token's tag name is one of the ones in the first column of the following table,
+// change the tag name to the name given in the corresponding cell in the second column. (This fixes the case of SVG elements that are not all lowercase.)
+pub fn adjust_svg_tag_name(tag: &mut Tag) {
+    if let Some((_, adjusted)) = SVG_TAG_NAME_ADJUSTMENTS
+        .iter()
+        .find(|(name, _)| *name == tag.name)
+    {
+        tag.name = adjusted.clone();
+    }
+}
+
+// https://html.spec.whatwg.org/multipage/parsing.html#adjust-mathml-attributes
+pub fn adjust_mathml_attributes(tag: &mut Tag) {
+    // When the steps below require the user agent to adjust MathML attributes for a token, then, if the token has an attribute named definitionurl, change its name to definitionURL (note the case difference).
+    for attribute in tag.attributes.iter_mut() {
+        if attribute.name == atom!("definitionurl") {
+            attribute.name = atom!("definitionURL");
+        }
+    }
+}
+
+// https://html.spec.whatwg.org/multipage/parsing.html#adjust-svg-attributes
+pub fn adjust_svg_attributes(tag: &mut Tag) {
+    // When the steps below require the user agent to adjust SVG attributes for a token, then, for each attribute on the token whose attribute name is one of the ones in the first column of the following table,
+    // change the attribute's name to the name given in the corresponding cell in the second column. (This fixes the case of SVG attributes that are not all lowercase.)
+    for attribute in tag.attributes.iter_mut() {
+        if let Some((_, adjusted)) = SVG_ATTRIBUTE_ADJUSTMENTS
+            .iter()
+            .find(|(name, _)| *name == attribute.name)
+        {
+            attribute.name = adjusted.clone();
+        }
+    }
+}
+
+// https://html.spec.whatwg.org/multipage/parsing.html#adjust-foreign-attributes
+pub fn adjust_foreign_attributes(tag: &mut Tag) {
+    // When the steps below require the user agent to adjust foreign attributes for a token, then, if any of the attributes on the token match the strings given in the first column of the following table,
+    // let the attribute be a namespaced attribute, with the prefix being the string given in the corresponding cell in the second column, the local name being the string given in the corresponding cell in the third column,
+    // and the namespace being the namespace given in the corresponding cell in the fourth column. (This fixes the use of namespaced attributes, in particular lang attributes in the XML namespace.)
+    for attribute in tag.attributes.iter_mut() {
+        if let Some((_, prefix, local_name, namespace)) = FOREIGN_ATTRIBUTE_ADJUSTMENTS
+            .iter()
+            .find(|(name, ..)| *name == attribute.name)
+        {
+            attribute.prefix = prefix.map(str::to_string);
+            attribute.name = local_name.clone();
+            attribute.namespace = Some(*namespace);
+        }
+    }
+}
+
+// https://html.spec.whatwg.org/multipage/parsing.html#mathml-text-integration-point
+pub fn is_mathml_text_integration_point(element: &Element) -> bool {
+    // A node is a MathML text integration point if it is one of the following elements:
+    // A MathML mi element
+    // A MathML mo element
+    // A MathML mn element
+    // A MathML ms element
+    // A MathML mtext element
+    element.namespace == Namespace::MathMl
+        && matches!(
+            element.local_name,
+            atom!("mi") | atom!("mo") | atom!("mn") | atom!("ms") | atom!("mtext")
+        )
+}
+
+// https://html.spec.whatwg.org/multipage/parsing.html#html-integration-point
+pub fn is_html_integration_point(element: &Element) -> bool {
+    // A node is an HTML integration point if it is one of the following elements:
+    // A MathML annotation-xml element whose start tag token had an attribute with the name "encoding" whose value was an ASCII case-insensitive match for the string "text/html"
+    // A MathML annotation-xml element whose start tag token had an attribute with the name "encoding" whose value was an ASCII case-insensitive match for the string "application/xhtml+xml"
+    // An SVG foreignObject element
+    // An SVG desc element
+    // An SVG title element
+    if element.is(Namespace::MathMl, atom!("annotation-xml")) {
+        return element.get_attribute("encoding").is_some_and(|encoding| {
+            encoding.eq_ignore_ascii_case("text/html")
+                || encoding.eq_ignore_ascii_case("application/xhtml+xml")
+        });
+    }
+    element.namespace == Namespace::Svg
+        && matches!(
+            element.local_name,
+            atom!("foreignObject") | atom!("desc") | atom!("title")
+        )
+}
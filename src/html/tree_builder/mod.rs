@@ -1,46 +1,46 @@
+mod foreign_content;
+pub mod observer;
+mod query;
+mod quirks;
+pub(crate) mod serializing;
+pub mod sink;
+pub mod streaming;
+#[cfg(any(test, feature = "tester"))]
 pub mod tester;
 pub mod tree;
 
-use std::{cell::RefCell, rc::Rc};
+use std::borrow::Cow;
+use std::slice;
 
+use crate::atom;
 use crate::html::{
-    tokenizer::{Tag, Token, Tokenizer},
-    tree_builder::tree::{Document, Element, Node, Text, Tree, debug_print_tree},
+    atom::Atom,
+    tokenizer::{Attribute, State, Tag, Token, Tokenizer},
+    tree_builder::{
+        foreign_content::{
+            adjust_foreign_attributes, adjust_mathml_attributes, adjust_svg_attributes,
+            adjust_svg_tag_name, is_html_integration_point, is_mathml_text_integration_point,
+        },
+        observer::ParseObserver,
+        quirks::doctype_quirks_mode,
+        sink::TreeSink,
+        tree::{Element, Namespace, Node, QuirksMode, Tree, debug_print_tree},
+    },
 };
 
-// pub struct Node {
-//     children: Vec<Node>,
-// }
-
-// pub struct Document {
-//     element: Option<Rc<RefCell<Element>>>,
-// }
-
-// #[derive(Debug)]
-// pub struct Element {
-//     name: String,
-// }
-
-// // https://dom.spec.whatwg.org/#interface-documenttype
-// pub struct DocumentType {
-//     node: Node,
-//     name: String,
-//     publicId: String,
-//     systemId: String,
-// }
-
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 enum InsertionMode {
     Initial,
     BeforeHtml,
     BeforeHead,
     InHead,
-    BeforeInHead,
-    BeforeInHeadNoscript,
+    InHeadNoscript,
     AfterHead,
     InBody,
     Text,
     InTable,
+    // todo the in table text insertion mode, the in table insertion mode switches to it
+    #[allow(dead_code)]
     InTableText,
     InCaption,
     InColumnGroup,
@@ -57,442 +57,2482 @@ enum InsertionMode {
     AfterAfterFrameset,
 }
 
-pub struct TreeBuilder {
-    tree: Tree<Node>,
+impl InsertionMode {
+    // the name of the insertion mode in the spec
+    fn name(&self) -> &'static str {
+        match self {
+            InsertionMode::Initial => "initial",
+            InsertionMode::BeforeHtml => "before html",
+            InsertionMode::BeforeHead => "before head",
+            InsertionMode::InHead => "in head",
+            InsertionMode::InHeadNoscript => "in head noscript",
+            InsertionMode::AfterHead => "after head",
+            InsertionMode::InBody => "in body",
+            InsertionMode::Text => "text",
+            InsertionMode::InTable => "in table",
+            InsertionMode::InTableText => "in table text",
+            InsertionMode::InCaption => "in caption",
+            InsertionMode::InColumnGroup => "in column group",
+            InsertionMode::InTableBody => "in table body",
+            InsertionMode::InRow => "in row",
+            InsertionMode::InCell => "in cell",
+            InsertionMode::InSelect => "in select",
+            InsertionMode::InSelectInTable => "in select in table",
+            InsertionMode::InTemplate => "in template",
+            InsertionMode::AfterBody => "after body",
+            InsertionMode::InFrameset => "in frameset",
+            InsertionMode::AfterFrameset => "after frameset",
+            InsertionMode::AfterAfterBody => "after after body",
+            InsertionMode::AfterAfterFrameset => "after after frameset",
+        }
+    }
+}
+
+// https://html.spec.whatwg.org/multipage/parsing.html#parse-errors
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub code: String,
+    // the position of the input after the token that caused the error, like the (line,column) of the html5lib tests
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ParserOptions {
+    // https://html.spec.whatwg.org/multipage/parsing.html#scripting-flag
+    // The scripting flag is set to "enabled" if scripting was enabled for the Document with which the parser is associated when the parser was created, and "disabled" otherwise.
+    pub scripting: bool,
+}
+
+pub struct TreeBuilder<S: TreeSink = Tree<Node>, O: ParseObserver = ()> {
+    options: ParserOptions,
+    // builds the nodes, the arena tree by default
+    sink: S,
     current_insertion_mode: InsertionMode, // tokenizer: Tokenizer,
     stack_of_open_elements: Vec<usize>,
     head_element_pointer: Option<usize>,
     document: usize,
+    frameset_ok: bool,
+    stopped: bool,
+    parse_errors: Vec<ParseError>,
+    // the position of the input after the current token
+    position: (usize, usize),
+    original_insertion_mode: Option<InsertionMode>,
+    stack_of_template_insertion_modes: Vec<InsertionMode>,
+    // the tokenizer state the tree builder switched to, applied before the next token is read
+    tokenizer_state: Option<State>,
+    // https://html.spec.whatwg.org/multipage/parsing.html#concept-frag-parse-context
+    context_element: Option<usize>,
+    // the line feed right after a pre, listing or textarea start tag is ignored
+    skip_next_line_feed_token: bool,
+    // notified about the tree construction, nothing is observed by default
+    observer: O,
 }
 
 impl TreeBuilder {
-    pub fn new() -> Self {
-        let mut tree = Tree::new();
-        let document = tree.create_node(Node::Document(Document::new()));
-        tree.set_root(document);
+    pub fn new(options: ParserOptions) -> Self {
+        Self::with_observer(options, ())
+    }
+}
+
+impl<O: ParseObserver> TreeBuilder<Tree<Node>, O> {
+    pub fn with_observer(options: ParserOptions, observer: O) -> Self {
+        Self::with_sink_and_observer(options, Tree::new(), observer)
+    }
+
+    pub fn get_tree(&self) -> &Tree<Node> {
+        &self.sink
+    }
+
+    pub fn debug_print(&self) {
+        debug_print_tree(&self.sink);
+    }
+
+    // https://html.spec.whatwg.org/multipage/parsing.html#parsing-html-fragments
+    // returns the nodes of the fragment, the children of the root element the fragment was parsed into
+    pub fn parse_fragment(&mut self, context_element: Element, input: &str) -> Vec<usize> {
+        let root = self.parse_fragment_into_root(context_element, input);
+        self.sink.get_node(root).children.clone()
+    }
+}
+
+impl<S: TreeSink> TreeBuilder<S> {
+    pub fn with_sink(options: ParserOptions, sink: S) -> Self {
+        Self::with_sink_and_observer(options, sink, ())
+    }
+}
+
+impl<S: TreeSink, O: ParseObserver> TreeBuilder<S, O> {
+    pub fn with_sink_and_observer(options: ParserOptions, mut sink: S, observer: O) -> Self {
+        let document = sink.create_document();
         Self {
-            tree,
+            options,
+            sink,
             current_insertion_mode: InsertionMode::Initial,
             stack_of_open_elements: vec![],
             head_element_pointer: None,
             document,
+            frameset_ok: true,
+            stopped: false,
+            parse_errors: Vec::new(),
+            position: (1, 0),
+            original_insertion_mode: None,
+            stack_of_template_insertion_modes: Vec::new(),
+            tokenizer_state: None,
+            context_element: None,
+            skip_next_line_feed_token: false,
+            observer,
         }
     }
 
-    pub fn get_tree(&self) -> &Tree<Node> {
-        &self.tree
+    pub fn observer(&self) -> &O {
+        &self.observer
     }
 
-    pub fn debug_print(&self) {
-        debug_print_tree(&self.tree);
+    pub fn into_observer(self) -> O {
+        self.observer
+    }
+
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    pub fn into_parts(self) -> (S, Vec<ParseError>) {
+        (self.sink, self.parse_errors)
+    }
+
+    pub fn get_parse_errors(&self) -> &[ParseError] {
+        &self.parse_errors
     }
 
     pub fn build(&mut self, tokenizer: Tokenizer) {
-        self.current_insertion_mode = InsertionMode::Initial;
-        let mut tokenizer_iter = tokenizer.into_iter();
-        let mut reprocess_token: Option<Token> = None;
+        self.switch_the_insertion_mode_to(InsertionMode::Initial);
+        self.run(tokenizer);
+    }
 
-        let mut token: Token = Token::EndOfFile;
-        loop {
-            if let Some(c_token) = reprocess_token {
-                token = c_token;
-                reprocess_token = None;
-            } else if let Some(c_token) = tokenizer_iter.next() {
-                token = c_token
-            } else {
+    // https://html.spec.whatwg.org/multipage/parsing.html#parsing-html-fragments
+    // returns the root element, the nodes of the fragment are its children
+    pub fn parse_fragment_into_root(&mut self, context_element: Element, input: &str) -> usize {
+        // 1. Let document be a Document node whose type is "html".
+        // 2. If context's node document is in quirks mode, then set document's mode to "quirks".
+        // 3. Otherwise, if context's node document is in limited-quirks mode, then set document's mode to "limited-quirks".
+        // todo
+        // 4. If allowDeclarativeShadowRoots is true, then set document's allow declarative shadow roots to true.
+        // todo
+        // 5. Create a new HTML parser, and associate it with document.
+        let mut tokenizer = Tokenizer::new(input);
+        // 6. Set the state of the HTML parser's tokenization stage as follows, switching on the context element:
+        if context_element.namespace == Namespace::Html {
+            match context_element.local_name {
+                // title, textarea: Switch the tokenizer to the RCDATA state.
+                atom!("title") | atom!("textarea") => tokenizer.switch_to(State::RCDATAState),
+                // style, xmp, iframe, noembed, noframes: Switch the tokenizer to the RAWTEXT state.
+                atom!("style")
+                | atom!("xmp")
+                | atom!("iframe")
+                | atom!("noembed")
+                | atom!("noframes") => tokenizer.switch_to(State::RAWTEXTState),
+                // script: Switch the tokenizer to the script data state.
+                atom!("script") => tokenizer.switch_to(State::ScriptDAtaState),
+                // noscript: If the scripting flag is enabled, switch the tokenizer to the RAWTEXT state. Otherwise, leave the tokenizer in the data state.
+                atom!("noscript") if self.options.scripting => {
+                    tokenizer.switch_to(State::RAWTEXTState)
+                }
+                // plaintext: Switch the tokenizer to the PLAINTEXT state.
+                atom!("plaintext") => tokenizer.switch_to(State::PLAINTEXState),
+                // Any other element: Leave the tokenizer in the data state.
+                _ => {}
+            }
+        }
+        // the context element is not part of the document, it only lives in the arena
+        let is_template = context_element.is(Namespace::Html, atom!("template"));
+        self.context_element = Some(self.sink.create_element(context_element));
+        // 7. Let root be the result of creating an element given document, "html", the HTML namespace, null, null, false, and context's custom element registry.
+        let root = create_an_element(
+            self.document,
+            atom!("html"),
+            Namespace::Html,
+            None,
+            None,
+            false,
+        );
+        let root = self.sink.create_element(root);
+        // 8. Append root to document.
+        self.sink.append(self.document, root);
+        // 9. Set up the HTML parser's stack of open elements so that it contains just the single element root.
+        self.stack_of_open_elements = vec![];
+        self.push_onto_the_stack_of_open_elements(root);
+        // 10. If context is a template element, then push "in template" onto the stack of template insertion modes so that it is the new current template insertion mode.
+        if is_template {
+            self.stack_of_template_insertion_modes
+                .push(InsertionMode::InTemplate);
+        }
+        // 11. Create a start tag token whose name is the local name of context and whose attributes are the attributes of context.
+        // Let this start tag token be the start tag token of context; e.g. for the purposes of determining if it is an HTML integration point.
+        // the context element keeps its attributes, so it is used directly
+        // 12. Reset the parser's insertion mode appropriately.
+        self.reset_the_insertion_mode_appropriately();
+        // 13. Set the HTML parser's form element pointer to the nearest node to context that is a form element (going straight up the ancestor chain, and including the element itself, if it is a form element), if any.
+        // todo
+        // 14. Place the input into the input stream for the HTML parser just created. The encoding confidence is irrelevant.
+        // 15. Start the HTML parser and let it run until it has consumed all the characters just inserted into the input stream.
+        self.run(tokenizer);
+        // 16. Return root's children, in tree order.
+        root
+    }
+
+    fn run(&mut self, mut tokenizer: Tokenizer) {
+        while !self.stopped {
+            if let Some(state) = self.tokenizer_state.take() {
+                tokenizer.switch_to(state);
+            }
+            tokenizer.set_cdata_allowed(
+                self.get_adjusted_current_node()
+                    .is_some_and(|node| self.get_element(node).namespace != Namespace::Html),
+            );
+            let Some(token) = tokenizer.next() else {
                 break;
+            };
+            self.observer.token_emitted(&token);
+            self.position = tokenizer.position();
+            // the line feed right after a pre, listing or textarea start tag is ignored
+            let skip_line_feed = std::mem::take(&mut self.skip_next_line_feed_token);
+            let token = match token {
+                Token::Character('\n') if skip_line_feed => continue,
+                Token::Characters(data) if skip_line_feed && data.starts_with('\n') => {
+                    if data.len() == 1 {
+                        continue;
+                    }
+                    Token::Characters(rest_of_run(data, 1))
+                }
+                token => token,
+            };
+            self.process_token(token);
+        }
+    }
+
+    // https://html.spec.whatwg.org/multipage/parsing.html#tree-construction-dispatcher
+    fn process_token(&mut self, token: Token) {
+        if let Token::Characters(data) = token {
+            self.process_characters(data);
+            return;
+        }
+        if self.use_current_insertion_mode(&token) {
+            // Process the token according to the rules given in the section corresponding to the current insertion mode in HTML content.
+            self.process_token_using_the_rules_for(self.current_insertion_mode, token);
+        } else {
+            // Process the token according to the rules given in the section for parsing tokens in foreign content.
+            self.process_token_in_foreign_content(token);
+        }
+    }
+
+    // A run of characters is processed one character token at a time, until it reaches "in body", "text" or foreign content, which get the rest of the run as a whole.
+    fn process_characters(&mut self, data: Cow<str>) {
+        let mut index = 0;
+        while let Some(c) = data[index..].chars().next() {
+            let token = Token::Character(c);
+            let use_current_insertion_mode = self.use_current_insertion_mode(&token);
+            if !use_current_insertion_mode
+                || matches!(
+                    self.current_insertion_mode,
+                    InsertionMode::InBody | InsertionMode::Text
+                )
+            {
+                let rest = rest_of_run(data, index);
+                if use_current_insertion_mode {
+                    self.process_token_using_the_rules_for(
+                        self.current_insertion_mode,
+                        Token::Characters(rest),
+                    );
+                } else {
+                    self.process_token_in_foreign_content(Token::Characters(rest));
+                }
+                return;
             }
-            println!("token emit: {:?}", token);
-
-            match self.current_insertion_mode {
-                // https://html.spec.whatwg.org/multipage/parsing.html#the-initial-insertion-mode
-                InsertionMode::Initial => match token {
-                    Token::Character(c)
-                        if c == '\u{0009}'
-                            || c == '\u{000A}'
-                            || c == '\u{000C}'
-                            || c == '\u{000D}'
-                            || c == ' ' =>
-                    {
-                        todo!()
-                    }
-                    Token::Comment(c) => todo!(),
-                    Token::Doctype(doctype) => {
-                        // todo not implemented at all
-                        self.current_insertion_mode = InsertionMode::BeforeHtml;
-                    }
-                    _ => {
-                        // todo
-                        // If the document is not an iframe srcdoc document, then this is a parse error; if the parser cannot change the mode flag is false, set the Document to quirks mode.
-                        self.current_insertion_mode = InsertionMode::BeforeHtml;
-                        reprocess_token = Some(token);
-                    }
-                },
-                // https://html.spec.whatwg.org/multipage/parsing.html#the-before-html-insertion-mode
-                InsertionMode::BeforeHtml => match token {
-                    Token::Doctype(doctype) => todo!(),
-                    Token::Comment(comment) => todo!(),
-                    Token::Character(c)
-                        if c == '\u{0009}'
-                            || c == '\u{000A}'
-                            || c == '\u{000C}'
-                            || c == '\u{000D}'
-                            || c == ' ' =>
-                    {
-                        ()
-                    }
-                    Token::StartTag(tag) if tag.name == "html" => {
-                        // create an element for the token in the HTML namespace, with the Document as the intended parent.
-                        let element = self.create_element_for_token(tag, "namespace", ()); // todo document is the intended_parent;                         
-                        let el_node = self.tree.create_node(element);
-                        // Append it to the Document object.
-                        self.tree.add_child(self.document, el_node);
-                        // Pull this element in the stack of the open elements
-                        self.stack_of_open_elements.push(el_node);
-                        // Switch the insertion mode to before head
-                        self.current_insertion_mode = InsertionMode::BeforeHead;
-                    }
-                    Token::EndTag(tag)
-                        if tag.name != "head"
-                            && tag.name != "body"
-                            && tag.name != "html"
-                            && tag.name != "br" =>
+            self.process_token_using_the_rules_for(self.current_insertion_mode, token);
+            index += c.len_utf8();
+        }
+    }
+
+    fn use_current_insertion_mode(&self, token: &Token) -> bool {
+        // As each token is emitted from the tokenizer, the user agent must follow the appropriate steps from the following list, known as the tree construction dispatcher:
+        match self.get_adjusted_current_node() {
+            // If the stack of open elements is empty
+            None => true,
+            Some(node) => {
+                let element = self.get_element(node);
+                // If the adjusted current node is an element in the HTML namespace
+                element.namespace == Namespace::Html
+                    // If the adjusted current node is a MathML text integration point and the token is a start tag whose tag name is neither "mglyph" nor "malignmark"
+                    || (is_mathml_text_integration_point(element)
+                        && matches!(&token, Token::StartTag(tag) if tag.name != atom!("mglyph") && tag.name != atom!("malignmark")))
+                    // If the adjusted current node is a MathML text integration point and the token is a character token
+                    || (is_mathml_text_integration_point(element)
+                        && matches!(token, Token::Character(_)))
+                    // If the adjusted current node is a MathML annotation-xml element and the token is a start tag whose tag name is "svg"
+                    || (element.is(Namespace::MathMl, atom!("annotation-xml"))
+                        && matches!(&token, Token::StartTag(tag) if tag.name == atom!("svg")))
+                    // If the adjusted current node is an HTML integration point and the token is a start tag
+                    // If the adjusted current node is an HTML integration point and the token is a character token
+                    || (is_html_integration_point(element)
+                        && matches!(token, Token::StartTag(_) | Token::Character(_)))
+                    // If the token is an end-of-file token
+                    || *token == Token::EndOfFile
+            }
+        }
+    }
+
+    // https://html.spec.whatwg.org/multipage/parsing.html#using-the-rules-for
+    fn process_token_using_the_rules_for(&mut self, insertion_mode: InsertionMode, token: Token) {
+        match insertion_mode {
+            // https://html.spec.whatwg.org/multipage/parsing.html#the-initial-insertion-mode
+            InsertionMode::Initial => match token {
+                Token::Character(c)
+                    if c == '\u{0009}'
+                        || c == '\u{000A}'
+                        || c == '\u{000C}'
+                        || c == '\u{000D}'
+                        || c == ' ' =>
+                {
+                    // Ignore the token.
+                }
+                Token::Comment(data) => {
+                    // Insert a comment as the last child of the Document object.
+                    self.insert_a_comment(data, Some(self.document));
+                }
+                Token::Doctype(doctype) => {
+                    // If the DOCTYPE token's name is not "html", or the token's public identifier is not missing, or the token's system identifier is neither missing nor "about:legacy-compat", then there is a parse error.
+                    if doctype.name() != Some("html")
+                        || doctype.public_id().is_some()
+                        || doctype
+                            .system_id()
+                            .is_some_and(|system_id| system_id != "about:legacy-compat")
                     {
-                        // todo parse error
-                        // ignore the token
+                        self.parse_error("unknown-doctype");
                     }
-                    _ => {
-                        let mut tag = Tag::new();
-                        tag.name = "html".into();
-                        // Create an html element whose node document is the Document object.
-                        let element = self.create_element_for_token(tag, "namespace", ());
-                        let el_node = self.tree.create_node(element);
-                        //  Append it to the Document object.
-                        self.tree.add_child(self.document, el_node);
-                        //  Put this element in the stack of open elements.
-                        self.stack_of_open_elements.push(el_node);
-                        // Switch the insertion mode to "before head", then reprocess the token.
-                        self.current_insertion_mode = InsertionMode::BeforeHead;
-                        reprocess_token = Some(token);
-                    }
-                },
-                // https://html.spec.whatwg.org/multipage/parsing.html#the-before-head-insertion-mode
-                InsertionMode::BeforeHead => match token {
-                    Token::Character(c)
-                        if c == '\u{0009}'
-                            || c == '\u{000A}'
-                            || c == '\u{000C}'
-                            || c == '\u{000D}'
-                            || c == ' ' =>
-                    {
-                        ()
-                    }
-                    Token::Comment(_) => (), // todo don't ignore comments
-                    Token::Doctype(doctype) => todo!(),
-                    Token::StartTag(tag) if tag.name == "html" => todo!(),
-                    Token::StartTag(tag) if tag.name == "head" => {
-                        let element = self.insert_an_html_element(tag);
-                        self.head_element_pointer = Some(element);
-                        self.current_insertion_mode = InsertionMode::InHead;
-                    }
-                    Token::EndTag(tag)
-                        if tag.name != "head"
-                            && tag.name != "body"
-                            && tag.name != "html"
-                            && tag.name != "br" =>
-                    {
-                        // todo parse error
-                        // ignore the token
+                    // Append a DocumentType node to the Document node, with its name set to the name given in the DOCTYPE token, or the empty string if the name was missing; its public ID set to the public identifier given in the DOCTYPE token, or the empty string if the public identifier was missing; and its system ID set to the system identifier given in the DOCTYPE token, or the empty string if the system identifier was missing.
+                    self.sink.append_doctype(
+                        doctype.name().unwrap_or_default(),
+                        doctype.public_id().unwrap_or_default(),
+                        doctype.system_id().unwrap_or_default(),
+                    );
+                    // Then, if the document is not an iframe srcdoc document, and the parser cannot change the mode flag is false, and the DOCTYPE token matches one of the conditions in the following list, then set the Document to quirks mode:
+                    // Otherwise, ... set the Document to limited-quirks mode:
+                    let mode = doctype_quirks_mode(&doctype);
+                    if mode != QuirksMode::NoQuirks {
+                        self.sink.set_quirks_mode(mode);
                     }
-                    _ => {
-                        // Insert an HTML element for a "head" start tag token with no attributes.
-                        let mut tag = Tag::new();
-                        tag.name = "head".into();
-                        let element = self.insert_an_html_element(tag);
-                        // Set the head element pointer to the newly created head element.
-                        self.head_element_pointer = Some(element);
-                        // Switch the insertion mode to "in head".
-                        self.current_insertion_mode = InsertionMode::InHead;
-                        // Reprocess the current token.
-                        reprocess_token = Some(token)
-                    }
-                },
-                // https://html.spec.whatwg.org/multipage/parsing.html#parsing-main-inhead
-                InsertionMode::InHead => match token {
-                    Token::Character(c)
-                        if c == '\u{0009}'
-                            || c == '\u{000A}'
-                            || c == '\u{000C}'
-                            || c == '\u{000D}'
-                            || c == ' ' =>
-                    // todo insert characters
-                    {
-                        ()
-                    }
-                    Token::Comment(comment) => todo!(),
-                    Token::StartTag(tag) if tag.name == "html" => todo!(),
-                    Token::StartTag(tag)
-                        if tag.name == "base"
-                            || tag.name == "basefont"
-                            || tag.name == "bgsound"
-                            || tag.name == "link" =>
+                    // Then, switch the insertion mode to "before html".
+                    self.switch_the_insertion_mode_to(InsertionMode::BeforeHtml);
+                }
+                _ => {
+                    // If the document is not an iframe srcdoc document, then this is a parse error; if the parser cannot change the mode flag is false, set the Document to quirks mode.
+                    self.parse_error(match token {
+                        Token::StartTag(_) => "expected-doctype-but-got-start-tag",
+                        Token::EndTag(_) => "expected-doctype-but-got-end-tag",
+                        Token::EndOfFile => "expected-doctype-but-got-eof",
+                        _ => "expected-doctype-but-got-chars",
+                    });
+                    self.sink.set_quirks_mode(QuirksMode::Quirks);
+                    self.switch_the_insertion_mode_to(InsertionMode::BeforeHtml);
+                    self.process_token(token);
+                }
+            },
+            // https://html.spec.whatwg.org/multipage/parsing.html#the-before-html-insertion-mode
+            InsertionMode::BeforeHtml => match token {
+                Token::Doctype(_) => {
+                    // Parse error. Ignore the token.
+                    self.parse_error("before-html-unexpected-doctype");
+                }
+                Token::Comment(data) => {
+                    // Insert a comment as the last child of the Document object.
+                    self.insert_a_comment(data, Some(self.document));
+                }
+                Token::Character(c)
+                    if c == '\u{0009}'
+                        || c == '\u{000A}'
+                        || c == '\u{000C}'
+                        || c == '\u{000D}'
+                        || c == ' ' => {}
+                Token::StartTag(tag) if tag.name == atom!("html") => {
+                    // create an element for the token in the HTML namespace, with the Document as the intended parent.
+                    let element =
+                        self.create_element_for_token(tag, Namespace::Html, self.document);
+                    let el_node = self.sink.create_element(element);
+                    // Append it to the Document object.
+                    self.sink.append(self.document, el_node);
+                    // Pull this element in the stack of the open elements
+                    self.push_onto_the_stack_of_open_elements(el_node);
+                    // Switch the insertion mode to before head
+                    self.switch_the_insertion_mode_to(InsertionMode::BeforeHead);
+                }
+                Token::EndTag(tag)
+                    if tag.name != atom!("head")
+                        && tag.name != atom!("body")
+                        && tag.name != atom!("html")
+                        && tag.name != atom!("br") =>
+                {
+                    // Parse error. Ignore the token.
+                    self.parse_error("unexpected-end-tag-before-html");
+                }
+                _ => {
+                    let mut tag = Tag::new();
+                    tag.name = "html".into();
+                    // Create an html element whose node document is the Document object.
+                    let element =
+                        self.create_element_for_token(tag, Namespace::Html, self.document);
+                    let el_node = self.sink.create_element(element);
+                    //  Append it to the Document object.
+                    self.sink.append(self.document, el_node);
+                    //  Put this element in the stack of open elements.
+                    self.push_onto_the_stack_of_open_elements(el_node);
+                    // Switch the insertion mode to "before head", then reprocess the token.
+                    self.switch_the_insertion_mode_to(InsertionMode::BeforeHead);
+                    self.process_token(token);
+                }
+            },
+            // https://html.spec.whatwg.org/multipage/parsing.html#the-before-head-insertion-mode
+            InsertionMode::BeforeHead => match token {
+                Token::Character(c)
+                    if c == '\u{0009}'
+                        || c == '\u{000A}'
+                        || c == '\u{000C}'
+                        || c == '\u{000D}'
+                        || c == ' ' => {}
+                Token::Comment(data) => {
+                    // Insert a comment.
+                    self.insert_a_comment(data, None);
+                }
+                Token::Doctype(_) => {
+                    // Parse error. Ignore the token.
+                    self.parse_error("before-head-unexpected-doctype");
+                }
+                Token::StartTag(ref tag) if tag.name == atom!("html") => {
+                    // Process the token using the rules for the "in body" insertion mode.
+                    self.process_token_using_the_rules_for(InsertionMode::InBody, token);
+                }
+                Token::StartTag(tag) if tag.name == atom!("head") => {
+                    let element = self.insert_an_html_element(tag);
+                    self.head_element_pointer = Some(element);
+                    self.switch_the_insertion_mode_to(InsertionMode::InHead);
+                }
+                Token::EndTag(tag)
+                    if tag.name != atom!("head")
+                        && tag.name != atom!("body")
+                        && tag.name != atom!("html")
+                        && tag.name != atom!("br") =>
+                {
+                    // Parse error. Ignore the token.
+                    self.parse_error("unexpected-end-tag-before-head");
+                }
+                _ => {
+                    // Insert an HTML element for a "head" start tag token with no attributes.
+                    let mut tag = Tag::new();
+                    tag.name = "head".into();
+                    let element = self.insert_an_html_element(tag);
+                    // Set the head element pointer to the newly created head element.
+                    self.head_element_pointer = Some(element);
+                    // Switch the insertion mode to "in head".
+                    self.switch_the_insertion_mode_to(InsertionMode::InHead);
+                    // Reprocess the current token.
+                    self.process_token(token)
+                }
+            },
+            // https://html.spec.whatwg.org/multipage/parsing.html#parsing-main-inhead
+            InsertionMode::InHead => match token {
+                Token::Character(c)
+                    if c == '\u{0009}'
+                        || c == '\u{000A}'
+                        || c == '\u{000C}'
+                        || c == '\u{000D}'
+                        || c == ' ' =>
+                {
+                    // Insert the character.
+                    self.insert_a_character(c);
+                }
+                Token::Comment(data) => {
+                    // Insert a comment.
+                    self.insert_a_comment(data, None);
+                }
+                Token::Doctype(_) => {
+                    // Parse error. Ignore the token.
+                    self.parse_error("in-head-unexpected-doctype");
+                }
+                Token::StartTag(ref tag) if tag.name == atom!("html") => {
+                    // Process the token using the rules for the "in body" insertion mode.
+                    self.process_token_using_the_rules_for(InsertionMode::InBody, token);
+                }
+                Token::StartTag(tag)
+                    if matches!(
+                        tag.name,
+                        atom!("base") | atom!("basefont") | atom!("bgsound") | atom!("link")
+                    ) =>
+                {
+                    // Insert an HTML element for the token. Immediately pop the current node off the stack of open elements.
+                    self.insert_an_html_element(tag);
+                    self.pop_the_current_node();
+                    // Acknowledge the token's self-closing flag, if it is set.
+                }
+                Token::StartTag(tag) if tag.name == atom!("meta") => {
+                    // Insert an HTML element for the token. Immediately pop the current node off the stack of open elements.
+                    self.insert_an_html_element(tag);
+                    self.pop_the_current_node();
+                    // Acknowledge the token's self-closing flag, if it is set.
+                    // todo change the encoding if the element has a charset or http-equiv attribute
+                }
+                Token::StartTag(tag) if tag.name == atom!("title") => {
+                    // Follow the generic RCDATA element parsing algorithm.
+                    self.generic_rcdata_element_parsing_algorithm(tag);
+                }
+                Token::StartTag(tag)
+                    if (tag.name == atom!("noscript") && self.options.scripting)
+                        || tag.name == atom!("noframes")
+                        || tag.name == atom!("style") =>
+                {
+                    // Follow the generic raw text element parsing algorithm.
+                    self.generic_raw_text_element_parsing_algorithm(tag);
+                }
+                Token::StartTag(tag)
+                    if tag.name == atom!("noscript") && !self.options.scripting =>
+                {
+                    // Insert an HTML element for the token.
+                    self.insert_an_html_element(tag);
+                    // Switch the insertion mode to "in head noscript".
+                    self.switch_the_insertion_mode_to(InsertionMode::InHeadNoscript);
+                }
+                Token::StartTag(tag) if tag.name == atom!("script") => {
+                    // 1. Let the adjusted insertion location be the appropriate place for inserting a node.
+                    let adjusted_insertion_location = self.appropriate_place_for_inserting_a_node();
+                    // 2. Create an element for the token in the HTML namespace, with the intended parent being the element in which the adjusted insertion location finds itself.
+                    let element = self.create_element_for_token(
+                        tag,
+                        Namespace::Html,
+                        adjusted_insertion_location,
+                    );
+                    // 3. Set the element's parser document to the Document, and set the element's force async to false.
+                    // todo
+                    // 4. If the parser was created as part of the HTML fragment parsing algorithm, then set the script element's already started to true. (fragment case)
+                    // todo
+                    // 5. If the parser was invoked via the document.write() or document.writeln() methods, then optionally set the script element's already started to true.
+                    // todo
+                    // 6. Insert the newly created element at the adjusted insertion location.
+                    let element = self.sink.create_element(element);
+                    self.sink.append(adjusted_insertion_location, element);
+                    // 7. Push the element onto the stack of open elements so that it is the new current node.
+                    self.push_onto_the_stack_of_open_elements(element);
+                    // 8. Switch the tokenizer to the script data state.
+                    self.tokenizer_state = Some(State::ScriptDAtaState);
+                    // 9. Set the original insertion mode to the current insertion mode.
+                    self.original_insertion_mode = Some(self.current_insertion_mode);
+                    // 10. Switch the insertion mode to "text".
+                    self.switch_the_insertion_mode_to(InsertionMode::Text);
+                }
+                Token::EndTag(tag) if tag.name == atom!("head") => {
+                    // Pop the current node (which will be the head element) off the stack of open elements.
+                    self.pop_the_current_node();
+                    // Switch the insertion mode to "after head".
+                    self.switch_the_insertion_mode_to(InsertionMode::AfterHead);
+                }
+                Token::StartTag(tag) if tag.name == atom!("template") => {
+                    // 1. Let templateStartTag be the start tag.
+                    // 2. Insert a marker at the end of the list of active formatting elements.
+                    // todo
+                    // 3. Set the frameset-ok flag to "not ok".
+                    self.frameset_ok = false;
+                    // 4. Switch the insertion mode to "in template".
+                    self.switch_the_insertion_mode_to(InsertionMode::InTemplate);
+                    // 5. Push "in template" onto the stack of template insertion modes so that it is the new current template insertion mode.
+                    self.stack_of_template_insertion_modes
+                        .push(InsertionMode::InTemplate);
+                    // 6. - 9. todo declarative shadow roots
+                    // then insert an HTML element for the token.
+                    self.insert_an_html_element(tag);
+                }
+                Token::EndTag(tag) if tag.name == atom!("template") => {
+                    // If there is no template element on the stack of open elements, then this is a parse error; ignore the token.
+                    if !self.has_a_template_element_on_the_stack_of_open_elements() {
+                        self.parse_error("in-head-unexpected-end-template-tag");
+                        return;
+                    }
+                    // Otherwise, run these steps:
+                    // 1. Generate all implied end tags thoroughly.
+                    self.generate_all_implied_end_tags_thoroughly();
+                    // 2. If the current node is not a template element, then this is a parse error.
+                    if !self.current_node_is_html(&[atom!("template")]) {
+                        self.parse_error("in-head-expected-template-as-current-node");
+                    }
+                    // 3. Pop elements from the stack of open elements until a template element has been popped from the stack.
+                    self.pop_until_an_html_element_has_been_popped(&[atom!("template")]);
+                    // 4. Clear the list of active formatting elements up to the last marker.
+                    // todo
+                    // 5. Pop the current template insertion mode off the stack of template insertion modes.
+                    self.stack_of_template_insertion_modes.pop();
+                    // 6. Reset the insertion mode appropriately.
+                    self.reset_the_insertion_mode_appropriately();
+                }
+                Token::EndTag(ref tag)
+                    if !matches!(tag.name, atom!("body") | atom!("html") | atom!("br")) =>
+                {
+                    // Parse error. Ignore the token.
+                    self.parse_error("in-head-unexpected-end-tag");
+                }
+                Token::StartTag(tag) if tag.name == atom!("head") => {
+                    // Parse error. Ignore the token.
+                    self.parse_error("in-head-unexpected-head-start-tag");
+                }
+                // An end tag whose tag name is one of: "body", "html", "br": Act as described in the "anything else" entry below.
+                _ => {
+                    // Pop the current node (which will be the head element) off the stack of open elements.
+                    self.pop_the_current_node();
+                    // Switch the insertion mode to "after head".
+                    self.switch_the_insertion_mode_to(InsertionMode::AfterHead);
+                    // Reprocess the token.
+                    self.process_token(token);
+                }
+            },
+            // https://html.spec.whatwg.org/multipage/parsing.html#parsing-main-inheadnoscript
+            InsertionMode::InHeadNoscript => match token {
+                Token::Doctype(_) => {
+                    // Parse error. Ignore the token.
+                    self.parse_error("in-head-noscript-unexpected-doctype");
+                }
+                Token::StartTag(ref tag) if tag.name == atom!("html") => {
+                    // Process the token using the rules for the "in body" insertion mode.
+                    self.process_token_using_the_rules_for(InsertionMode::InBody, token);
+                }
+                Token::EndTag(ref tag) if tag.name == atom!("noscript") => {
+                    // Pop the current node (which will be a noscript element) from the stack of open elements; the new current node will be a head element.
+                    self.pop_the_current_node();
+                    // Switch the insertion mode to "in head".
+                    self.switch_the_insertion_mode_to(InsertionMode::InHead);
+                }
+                Token::Character('\u{0009}' | '\u{000A}' | '\u{000C}' | '\u{000D}' | ' ')
+                | Token::Comment(_) => {
+                    // Process the token using the rules for the "in head" insertion mode.
+                    self.process_token_using_the_rules_for(InsertionMode::InHead, token);
+                }
+                Token::StartTag(ref tag)
+                    if matches!(
+                        tag.name,
+                        atom!("basefont")
+                            | atom!("bgsound")
+                            | atom!("link")
+                            | atom!("meta")
+                            | atom!("noframes")
+                            | atom!("style")
+                    ) =>
+                {
+                    // Process the token using the rules for the "in head" insertion mode.
+                    self.process_token_using_the_rules_for(InsertionMode::InHead, token);
+                }
+                Token::StartTag(ref tag)
+                    if tag.name == atom!("head") || tag.name == atom!("noscript") =>
+                {
+                    // Parse error. Ignore the token.
+                    self.parse_error("in-head-noscript-unexpected-start-tag");
+                }
+                Token::EndTag(ref tag) if tag.name != atom!("br") => {
+                    // Parse error. Ignore the token.
+                    self.parse_error("in-head-noscript-unexpected-end-tag");
+                }
+                // An end tag whose tag name is "br": Act as described in the "anything else" entry below.
+                _ => {
+                    // Parse error.
+                    self.parse_error("in-head-noscript-unexpected-token");
+                    // Pop the current node (which will be a noscript element) from the stack of open elements; the new current node will be a head element.
+                    self.pop_the_current_node();
+                    // Switch the insertion mode to "in head".
+                    self.switch_the_insertion_mode_to(InsertionMode::InHead);
+                    // Reprocess the token.
+                    self.process_token(token);
+                }
+            },
+            // https://html.spec.whatwg.org/multipage/parsing.html#the-after-head-insertion-mode
+            InsertionMode::AfterHead => match token {
+                Token::Character(c)
+                    if c == '\u{0009}'
+                        || c == '\u{000A}'
+                        || c == '\u{000C}'
+                        || c == '\u{000D}'
+                        || c == ' ' =>
+                {
+                    // Insert the character.
+                    self.insert_a_character(c);
+                }
+                Token::Comment(data) => {
+                    // Insert a comment.
+                    self.insert_a_comment(data, None);
+                }
+                Token::Doctype(_) => {
+                    // Parse error. Ignore the token.
+                    self.parse_error("after-head-unexpected-doctype");
+                }
+                Token::StartTag(ref tag) if tag.name == atom!("html") => {
+                    // Process the token using the rules for the "in body" insertion mode.
+                    self.process_token_using_the_rules_for(InsertionMode::InBody, token);
+                }
+                Token::StartTag(tag) if tag.name == atom!("body") => {
+                    // Insert an HTML element for the token.
+                    self.insert_an_html_element(tag);
+                    // Set the frameset-ok flag to "not ok".
+                    self.frameset_ok = false;
+                    // Switch the insertion mode to "in body".
+                    self.switch_the_insertion_mode_to(InsertionMode::InBody);
+                }
+                Token::StartTag(tag) if tag.name == atom!("frameset") => {
+                    // Insert an HTML element for the token.
+                    self.insert_an_html_element(tag);
+                    // Switch the insertion mode to "in frameset".
+                    self.switch_the_insertion_mode_to(InsertionMode::InFrameset);
+                }
+                Token::StartTag(tag)
+                    if matches!(
+                        tag.name,
+                        atom!("base")
+                            | atom!("basefont")
+                            | atom!("bgsound")
+                            | atom!("link")
+                            | atom!("meta")
+                            | atom!("noframes")
+                            | atom!("script")
+                            | atom!("style")
+                            | atom!("template")
+                            | atom!("title")
+                    ) =>
+                {
+                    // Parse error.
+                    self.parse_error("unexpected-start-tag-after-head");
+                    // Push the node pointed to by the head element pointer onto the stack of open elements.
+                    let head = self
+                        .head_element_pointer
+                        .expect("the head element pointer is set after head");
+                    self.push_onto_the_stack_of_open_elements(head);
+                    // Process the token using the rules for the "in head" insertion mode.
+                    self.process_token_using_the_rules_for(
+                        InsertionMode::InHead,
+                        Token::StartTag(tag),
+                    );
+                    // Remove the node pointed to by the head element pointer from the stack of open elements. (It might not be the current node at this point.)
+                    self.remove_from_the_stack_of_open_elements(head);
+                }
+                Token::EndTag(ref tag) if tag.name == atom!("template") => {
+                    // Process the token using the rules for the "in head" insertion mode.
+                    self.process_token_using_the_rules_for(InsertionMode::InHead, token);
+                }
+                Token::StartTag(ref tag) if tag.name == atom!("head") => {
+                    // Parse error. Ignore the token.
+                    self.parse_error("after-head-unexpected-head-start-tag");
+                }
+                Token::EndTag(ref tag)
+                    if !matches!(tag.name, atom!("body") | atom!("html") | atom!("br")) =>
+                {
+                    // Parse error. Ignore the token.
+                    self.parse_error("after-head-unexpected-end-tag");
+                }
+                // An end tag whose tag name is one of: "body", "html", "br": Act as described in the "anything else" entry below.
+                _ => {
+                    let mut tag = Tag::new();
+                    tag.name = "body".into();
+                    // Insert an HTML element for a "body" start tag token with no attributes.
+                    self.insert_an_html_element(tag);
+                    // Switch the insertion mode to "in body".
+                    self.switch_the_insertion_mode_to(InsertionMode::InBody);
+                    // Reprocess the current token.
+                    self.process_token(token);
+                }
+            },
+            // 13.2.6.4.7 The "in body" insertion mode
+            // https://html.spec.whatwg.org/multipage/parsing.html#parsing-main-inbody
+            InsertionMode::InBody => match token {
+                Token::Characters(data) => {
+                    // Reconstruct the active formatting elements, if any.
+                    // todo
+                    // Insert the token's characters.
+                    self.insert_characters(&data);
+                    // Set the frameset-ok flag to "not ok", unless all of them are whitespace.
+                    if !data.chars().all(is_whitespace) {
+                        self.frameset_ok = false;
+                    }
+                }
+                Token::Character('\u{0000}') => {
+                    // Parse error. Ignore the token.
+                    self.parse_error("in-body-unexpected-null-character");
+                }
+                Token::Character(c)
+                    if c == '\u{0009}'
+                        || c == '\u{000A}'
+                        || c == '\u{000C}'
+                        || c == '\u{000D}'
+                        || c == ' ' =>
+                {
+                    // Reconstruct the active formatting elements, if any.
+                    // todo
+                    // Insert the token's character.
+                    self.insert_a_character(c);
+                }
+                Token::Character(c) => {
+                    // Reconstruct the active formatting elements, if any.
+                    // todo
+                    // Insert the token's character.
+                    self.insert_a_character(c);
+                    // Set the frameset-ok flag to "not ok".
+                    self.frameset_ok = false;
+                }
+                Token::Comment(data) => {
+                    // Insert a comment.
+                    self.insert_a_comment(data, None);
+                }
+                Token::Doctype(_) => {
+                    // Parse error. Ignore the token.
+                    self.parse_error("in-body-unexpected-doctype");
+                }
+                Token::StartTag(tag) if tag.name == atom!("html") => {
+                    // Parse error.
+                    self.parse_error("in-body-unexpected-html-start-tag");
+                    // If there is a template element on the stack of open elements, then ignore the token.
+                    if self.has_a_template_element_on_the_stack_of_open_elements() {
+                        return;
+                    }
+                    // Otherwise, for each attribute on the token, check to see if the attribute is already present on the top element of the stack of open elements. If it is not, add the attribute and its corresponding value to that element.
+                    let html = self.stack_of_open_elements[0];
+                    self.sink.add_attributes_if_missing(
+                        html,
+                        tag.attributes
+                            .into_iter()
+                            .map(Attribute::into_owned)
+                            .collect(),
+                    );
+                }
+                Token::StartTag(tag)
+                    if matches!(
+                        tag.name,
+                        atom!("base")
+                            | atom!("basefont")
+                            | atom!("bgsound")
+                            | atom!("link")
+                            | atom!("meta")
+                            | atom!("noframes")
+                            | atom!("script")
+                            | atom!("style")
+                            | atom!("template")
+                            | atom!("title")
+                    ) =>
+                {
+                    // Process the token using the rules for the "in head" insertion mode.
+                    self.process_token_using_the_rules_for(
+                        InsertionMode::InHead,
+                        Token::StartTag(tag),
+                    );
+                }
+                Token::EndTag(ref tag) if tag.name == atom!("template") => {
+                    // Process the token using the rules for the "in head" insertion mode.
+                    self.process_token_using_the_rules_for(InsertionMode::InHead, token);
+                }
+                Token::StartTag(tag) if tag.name == atom!("body") => {
+                    // Parse error.
+                    self.parse_error("in-body-unexpected-body-start-tag");
+                    // If the stack of open elements has only one node on it, if the second element on the stack of open elements is not a body element, or if there is a template element on the stack of open elements, then ignore the token. (fragment case or there is a template element on the stack)
+                    let Some(&body) = self.stack_of_open_elements.get(1) else {
+                        return;
+                    };
+                    if !self.get_element(body).is(Namespace::Html, atom!("body"))
+                        || self.has_a_template_element_on_the_stack_of_open_elements()
                     {
-                        todo!()
+                        return;
+                    }
+                    // Otherwise, set the frameset-ok flag to "not ok"; then, for each attribute on the token, check to see if the attribute is already present on the body element (the second element) on the stack of open elements, and if it is not, add the attribute and its corresponding value to that element.
+                    self.frameset_ok = false;
+                    self.sink.add_attributes_if_missing(
+                        body,
+                        tag.attributes
+                            .into_iter()
+                            .map(Attribute::into_owned)
+                            .collect(),
+                    );
+                }
+                Token::StartTag(tag) if tag.name == atom!("frameset") => {
+                    // Parse error.
+                    self.parse_error("in-body-unexpected-frameset-start-tag");
+                    // If the stack of open elements has only one node on it, or if the second element on the stack of open elements is not a body element, then ignore the token. (fragment case or there is a template element on the stack)
+                    let Some(&body) = self.stack_of_open_elements.get(1) else {
+                        return;
+                    };
+                    if !self.get_element(body).is(Namespace::Html, atom!("body")) {
+                        return;
+                    }
+                    // If the frameset-ok flag is set to "not ok", ignore the token.
+                    if !self.frameset_ok {
+                        return;
+                    }
+                    // Otherwise, run the following steps:
+                    // 1. Remove the second element on the stack of open elements from its parent node, if it has one.
+                    self.sink.remove_from_parent(body);
+                    // 2. Pop all the nodes from the bottom of the stack of open elements, from the current node up to, but not including, the root html element.
+                    self.pop_elements_above(1);
+                    // 3. Insert an HTML element for the token.
+                    self.insert_an_html_element(tag);
+                    // 4. Switch the insertion mode to "in frameset".
+                    self.switch_the_insertion_mode_to(InsertionMode::InFrameset);
+                }
+                Token::EndOfFile => {
+                    // If the stack of template insertion modes is not empty, then process the token using the rules for the "in template" insertion mode.
+                    if !self.stack_of_template_insertion_modes.is_empty() {
+                        self.process_token_using_the_rules_for(InsertionMode::InTemplate, token);
+                        return;
+                    }
+                    // Otherwise, follow these steps:
+                    // If there is a node in the stack of open elements that is not either a dd element, a dt element, an li element, an optgroup element, an option element, a p element, an rb element, an rp element, an rt element, an rtc element, a tbody element, a td element, a tfoot element, a th element, a thead element, a tr element, the body element, or the html element, then this is a parse error.
+                    if self.has_a_node_that_is_not_allowed_at_the_end_of_body() {
+                        self.parse_error("in-body-eof-with-open-elements");
+                    }
+                    // Stop parsing.
+                    self.stop_parsing();
+                }
+                Token::EndTag(ref tag) if tag.name == atom!("body") => {
+                    // If the stack of open elements does not have a body element in scope, this is a parse error; ignore the token.
+                    if !self.has_an_element_in_scope(&[atom!("body")]) {
+                        self.parse_error("in-body-unexpected-end-body-tag");
+                        return;
                     }
-                    Token::StartTag(tag) if tag.name == "meta" => {
+                    // Otherwise, if there is a node in the stack of open elements that is not either a dd element, a dt element, an li element, an optgroup element, an option element, a p element, an rb element, an rp element, an rt element, an rtc element, a tbody element, a td element, a tfoot element, a th element, a thead element, a tr element, the body element, or the html element, then this is a parse error.
+                    if self.has_a_node_that_is_not_allowed_at_the_end_of_body() {
+                        self.parse_error("in-body-end-body-tag-with-open-elements");
+                    }
+                    // Switch the insertion mode to "after body".
+                    self.switch_the_insertion_mode_to(InsertionMode::AfterBody);
+                }
+                Token::EndTag(ref tag) if tag.name == atom!("html") => {
+                    // If the stack of open elements does not have a body element in scope, this is a parse error; ignore the token.
+                    if !self.has_an_element_in_scope(&[atom!("body")]) {
+                        self.parse_error("in-body-unexpected-end-html-tag");
+                        return;
+                    }
+                    // Otherwise, if there is a node in the stack of open elements that is not either a dd element, a dt element, an li element, an optgroup element, an option element, a p element, an rb element, an rp element, an rt element, an rtc element, a tbody element, a td element, a tfoot element, a th element, a thead element, a tr element, the body element, or the html element, then this is a parse error.
+                    if self.has_a_node_that_is_not_allowed_at_the_end_of_body() {
+                        self.parse_error("in-body-end-html-tag-with-open-elements");
+                    }
+                    // Switch the insertion mode to "after body".
+                    self.switch_the_insertion_mode_to(InsertionMode::AfterBody);
+                    // Reprocess the token.
+                    self.process_token(token);
+                }
+                Token::StartTag(tag)
+                    if matches!(
+                        tag.name,
+                        atom!("address")
+                            | atom!("article")
+                            | atom!("aside")
+                            | atom!("blockquote")
+                            | atom!("center")
+                            | atom!("details")
+                            | atom!("dialog")
+                            | atom!("dir")
+                            | atom!("div")
+                            | atom!("dl")
+                            | atom!("fieldset")
+                            | atom!("figcaption")
+                            | atom!("figure")
+                            | atom!("footer")
+                            | atom!("header")
+                            | atom!("hgroup")
+                            | atom!("main")
+                            | atom!("menu")
+                            | atom!("nav")
+                            | atom!("ol")
+                            | atom!("p")
+                            | atom!("search")
+                            | atom!("section")
+                            | atom!("summary")
+                            | atom!("ul")
+                    ) =>
+                {
+                    // If the stack of open elements has a p element in button scope, then close a p element.
+                    if self.has_an_element_in_button_scope(&[atom!("p")]) {
+                        self.close_a_p_element();
+                    }
+                    // Insert an HTML element for the token.
+                    self.insert_an_html_element(tag);
+                }
+                Token::StartTag(tag)
+                    if matches!(
+                        tag.name,
+                        atom!("h1")
+                            | atom!("h2")
+                            | atom!("h3")
+                            | atom!("h4")
+                            | atom!("h5")
+                            | atom!("h6")
+                    ) =>
+                {
+                    // If the stack of open elements has a p element in button scope, then close a p element.
+                    if self.has_an_element_in_button_scope(&[atom!("p")]) {
+                        self.close_a_p_element();
+                    }
+                    // If the current node is an HTML element whose tag name is one of "h1", "h2", "h3", "h4", "h5", or "h6", then this is a parse error; pop the current node off the stack of open elements.
+                    if self.current_node_is_html(&[
+                        atom!("h1"),
+                        atom!("h2"),
+                        atom!("h3"),
+                        atom!("h4"),
+                        atom!("h5"),
+                        atom!("h6"),
+                    ]) {
+                        self.parse_error("in-body-nested-heading");
+                        self.pop_the_current_node();
+                    }
+                    // Insert an HTML element for the token.
+                    self.insert_an_html_element(tag);
+                }
+                Token::StartTag(tag)
+                    if tag.name == atom!("pre") || tag.name == atom!("listing") =>
+                {
+                    // If the stack of open elements has a p element in button scope, then close a p element.
+                    if self.has_an_element_in_button_scope(&[atom!("p")]) {
+                        self.close_a_p_element();
+                    }
+                    // Insert an HTML element for the token.
+                    self.insert_an_html_element(tag);
+                    // If the next token is a U+000A LINE FEED (LF) character token, then ignore that token and move on to the next one. (Newlines at the start of pre blocks are ignored as an authoring convenience.)
+                    self.skip_next_line_feed_token = true;
+                    // Set the frameset-ok flag to "not ok".
+                    self.frameset_ok = false;
+                }
+                Token::StartTag(tag) if tag.name == atom!("li") => {
+                    // 1. Set the frameset-ok flag to "not ok".
+                    self.frameset_ok = false;
+                    // 2. Initialize node to be the current node (the bottommost node of the stack).
+                    // 3. Loop: If node is an li element, then run these substeps:
+                    // 4. If node is in the special category, but is not an address, div, or p element, then jump to the step labeled done below.
+                    // 5. Otherwise, set node to the previous entry in the stack of open elements and return to the step labeled loop.
+                    self.close_list_item_elements(&[atom!("li")]);
+                    // 6. Done: If the stack of open elements has a p element in button scope, then close a p element.
+                    if self.has_an_element_in_button_scope(&[atom!("p")]) {
+                        self.close_a_p_element();
+                    }
+                    // 7. Finally, insert an HTML element for the token.
+                    self.insert_an_html_element(tag);
+                }
+                Token::StartTag(tag) if tag.name == atom!("dd") || tag.name == atom!("dt") => {
+                    // 1. Set the frameset-ok flag to "not ok".
+                    self.frameset_ok = false;
+                    // 2. Initialize node to be the current node (the bottommost node of the stack).
+                    // 3. Loop: If node is a dd or dt element, then run these substeps:
+                    // 4. If node is in the special category, but is not an address, div, or p element, then jump to the step labeled done below.
+                    // 5. Otherwise, set node to the previous entry in the stack of open elements and return to the step labeled loop.
+                    self.close_list_item_elements(&[atom!("dd"), atom!("dt")]);
+                    // 6. Done: If the stack of open elements has a p element in button scope, then close a p element.
+                    if self.has_an_element_in_button_scope(&[atom!("p")]) {
+                        self.close_a_p_element();
+                    }
+                    // 7. Finally, insert an HTML element for the token.
+                    self.insert_an_html_element(tag);
+                }
+                Token::EndTag(tag)
+                    if matches!(
+                        tag.name,
+                        atom!("address")
+                            | atom!("article")
+                            | atom!("aside")
+                            | atom!("blockquote")
+                            | atom!("button")
+                            | atom!("center")
+                            | atom!("details")
+                            | atom!("dialog")
+                            | atom!("dir")
+                            | atom!("div")
+                            | atom!("dl")
+                            | atom!("fieldset")
+                            | atom!("figcaption")
+                            | atom!("figure")
+                            | atom!("footer")
+                            | atom!("header")
+                            | atom!("hgroup")
+                            | atom!("listing")
+                            | atom!("main")
+                            | atom!("menu")
+                            | atom!("nav")
+                            | atom!("ol")
+                            | atom!("pre")
+                            | atom!("search")
+                            | atom!("section")
+                            | atom!("summary")
+                            | atom!("ul")
+                    ) =>
+                {
+                    // If the stack of open elements does not have an element in scope that is an HTML element with the same tag name as that of the token, then this is a parse error; ignore the token.
+                    if !self.has_an_element_in_scope(slice::from_ref(&tag.name)) {
+                        self.parse_error("in-body-unexpected-end-tag");
+                        return;
+                    }
+                    // Otherwise, run these steps:
+                    // 1. Generate implied end tags.
+                    self.generate_implied_end_tags(None);
+                    // 2. If the current node is not an HTML element with the same tag name as that of the token, then this is a parse error.
+                    if !self.current_node_is_html(slice::from_ref(&tag.name)) {
+                        self.parse_error("in-body-end-tag-not-current-node");
+                    }
+                    // 3. Pop elements from the stack of open elements until an HTML element with the same tag name as the token has been popped from the stack.
+                    self.pop_until_an_html_element_has_been_popped(slice::from_ref(&tag.name));
+                }
+                Token::EndTag(tag) if tag.name == atom!("p") => {
+                    // If the stack of open elements does not have a p element in button scope, then this is a parse error; insert an HTML element for a "p" start tag token with no attributes.
+                    if !self.has_an_element_in_button_scope(&[atom!("p")]) {
+                        self.parse_error("in-body-unexpected-end-p-tag");
+                        let mut tag = Tag::new();
+                        tag.name = "p".into();
                         self.insert_an_html_element(tag);
-                        self.stack_of_open_elements.pop();
-                    }
-                    Token::StartTag(tag) if tag.name == "title" => todo!(),
-                    Token::StartTag(tag) if tag.name == "noscript" /* todo && scripting_flag.is_enabled() */ || tag.name == "noframes" || tag.name == "style" => {
-                        todo!()
-                    }
-                    Token::StartTag(tag) if tag.name == "noscript" /* && scripting_flag.is_disabled() */ => todo!(),
-                    Token::StartTag(tag) if tag.name == "script" => todo!(),
-                    Token::EndTag(tag) if tag.name == "head" => {
-                        self.stack_of_open_elements.pop();
-                        self.current_insertion_mode = InsertionMode::AfterHead;
-                    }
-                    // todo:  An end tag whose tag name is one of: "body", "html", "br" Act as described in the "anything else" entry below.
-                    Token::StartTag(tag) if tag.name == "template" => todo!(),
-                    Token::EndTag(tag) if tag.name == "template" => todo!(),
-                    Token::StartTag(tag) if tag.name == "head" => todo!(),
-                    Token::EndTag(tag) => todo!(),
-                    _ => {
-                        //Pop the current node (which will be the head element) off the stack of open elements.
-                        self.stack_of_open_elements.pop();
-                        //Switch the insertion mode to "after head".
-                        self.current_insertion_mode = InsertionMode::AfterHead;
-                        //Reprocess the token.
-                        reprocess_token = Some(token);
-                    },
-                },
-                // https://html.spec.whatwg.org/multipage/parsing.html#the-after-head-insertion-mode
-                InsertionMode::AfterHead => match token {
-                    Token::Character(c)
-                        if c == '\u{0009}'
-                            || c == '\u{000A}'
-                            || c == '\u{000C}'
-                            || c == '\u{000D}'
-                            || c == ' ' =>
-                    // todo insert characters
+                    }
+                    // Close a p element.
+                    self.close_a_p_element();
+                }
+                Token::EndTag(tag) if tag.name == atom!("li") => {
+                    // If the stack of open elements does not have an li element in list item scope, then this is a parse error; ignore the token.
+                    if !self.has_an_element_in_list_item_scope(&[atom!("li")]) {
+                        self.parse_error("in-body-unexpected-end-li-tag");
+                        return;
+                    }
+                    // Otherwise, run these steps:
+                    // 1. Generate implied end tags, except for li elements.
+                    self.generate_implied_end_tags(Some(atom!("li")));
+                    // 2. If the current node is not an li element, then this is a parse error.
+                    if !self.current_node_is_html(&[atom!("li")]) {
+                        self.parse_error("in-body-end-li-tag-not-current-node");
+                    }
+                    // 3. Pop elements from the stack of open elements until an li element has been popped from the stack.
+                    self.pop_until_an_html_element_has_been_popped(&[atom!("li")]);
+                }
+                Token::EndTag(tag) if tag.name == atom!("dd") || tag.name == atom!("dt") => {
+                    // If the stack of open elements does not have an element in scope that is an HTML element with the same tag name as that of the token, then this is a parse error; ignore the token.
+                    if !self.has_an_element_in_scope(slice::from_ref(&tag.name)) {
+                        self.parse_error("in-body-unexpected-end-tag");
+                        return;
+                    }
+                    // Otherwise, run these steps:
+                    // 1. Generate implied end tags, except for HTML elements with the same tag name as the token.
+                    self.generate_implied_end_tags(Some(tag.name.clone()));
+                    // 2. If the current node is not an HTML element with the same tag name as that of the token, then this is a parse error.
+                    if !self.current_node_is_html(slice::from_ref(&tag.name)) {
+                        self.parse_error("in-body-end-tag-not-current-node");
+                    }
+                    // 3. Pop elements from the stack of open elements until an HTML element with the same tag name as the token has been popped from the stack.
+                    self.pop_until_an_html_element_has_been_popped(slice::from_ref(&tag.name));
+                }
+                Token::EndTag(tag)
+                    if matches!(
+                        tag.name,
+                        atom!("h1")
+                            | atom!("h2")
+                            | atom!("h3")
+                            | atom!("h4")
+                            | atom!("h5")
+                            | atom!("h6")
+                    ) =>
+                {
+                    const HEADINGS: &[Atom] = &[
+                        atom!("h1"),
+                        atom!("h2"),
+                        atom!("h3"),
+                        atom!("h4"),
+                        atom!("h5"),
+                        atom!("h6"),
+                    ];
+                    // If the stack of open elements does not have an element in scope that is an HTML element and whose tag name is one of "h1", "h2", "h3", "h4", "h5", or "h6", then this is a parse error; ignore the token.
+                    if !self.has_an_element_in_scope(HEADINGS) {
+                        self.parse_error("in-body-unexpected-end-heading-tag");
+                        return;
+                    }
+                    // Otherwise, run these steps:
+                    // 1. Generate implied end tags.
+                    self.generate_implied_end_tags(None);
+                    // 2. If the current node is not an HTML element with the same tag name as that of the token, then this is a parse error.
+                    if !self.current_node_is_html(slice::from_ref(&tag.name)) {
+                        self.parse_error("in-body-end-tag-not-current-node");
+                    }
+                    // 3. Pop elements from the stack of open elements until an HTML element whose tag name is one of "h1", "h2", "h3", "h4", "h5", or "h6" has been popped from the stack.
+                    self.pop_until_an_html_element_has_been_popped(HEADINGS);
+                }
+                Token::EndTag(tag) if tag.name == atom!("br") => {
+                    // Parse error. Drop the attributes from the token, and act as described in the next entry; i.e. act as if this was a "br" start tag token with no attributes, rather than the end tag token that it actually is.
+                    self.parse_error("in-body-unexpected-end-br-tag");
+                    self.process_token(Token::StartTag(Tag::new_with_name("br")));
+                }
+                Token::StartTag(tag)
+                    if matches!(
+                        tag.name,
+                        atom!("area")
+                            | atom!("br")
+                            | atom!("embed")
+                            | atom!("img")
+                            | atom!("keygen")
+                            | atom!("wbr")
+                    ) =>
+                {
+                    // Reconstruct the active formatting elements, if any.
+                    // todo
+                    // Insert an HTML element for the token. Immediately pop the current node off the stack of open elements.
+                    self.insert_an_html_element(tag);
+                    self.pop_the_current_node();
+                    // Acknowledge the token's self-closing flag, if it is set.
+                    // Set the frameset-ok flag to "not ok".
+                    self.frameset_ok = false;
+                }
+                Token::StartTag(tag) if tag.name == atom!("input") => {
+                    // Reconstruct the active formatting elements, if any.
+                    // todo
+                    // If the token does not have an attribute with the name "type", or if it does, but that attribute's value is not an ASCII case-insensitive match for the string "hidden", then: set the frameset-ok flag to "not ok".
+                    let is_hidden = tag.attributes.iter().any(|attribute| {
+                        attribute.name == atom!("type")
+                            && attribute.value.eq_ignore_ascii_case("hidden")
+                    });
+                    // Insert an HTML element for the token. Immediately pop the current node off the stack of open elements.
+                    self.insert_an_html_element(tag);
+                    self.pop_the_current_node();
+                    // Acknowledge the token's self-closing flag, if it is set.
+                    if !is_hidden {
+                        self.frameset_ok = false;
+                    }
+                }
+                Token::StartTag(tag)
+                    if matches!(tag.name, atom!("param") | atom!("source") | atom!("track")) =>
+                {
+                    // Insert an HTML element for the token. Immediately pop the current node off the stack of open elements.
+                    self.insert_an_html_element(tag);
+                    self.pop_the_current_node();
+                    // Acknowledge the token's self-closing flag, if it is set.
+                }
+                Token::StartTag(tag) if tag.name == atom!("hr") => {
+                    // If the stack of open elements has a p element in button scope, then close a p element.
+                    if self.has_an_element_in_button_scope(&[atom!("p")]) {
+                        self.close_a_p_element();
+                    }
+                    // Insert an HTML element for the token. Immediately pop the current node off the stack of open elements.
+                    self.insert_an_html_element(tag);
+                    self.pop_the_current_node();
+                    // Acknowledge the token's self-closing flag, if it is set.
+                    // Set the frameset-ok flag to "not ok".
+                    self.frameset_ok = false;
+                }
+                Token::StartTag(mut tag) if tag.name == atom!("image") => {
+                    // Parse error. Change the token's tag name to "img" and reprocess it. (Don't ask.)
+                    self.parse_error("in-body-unexpected-image-start-tag");
+                    tag.name = atom!("img");
+                    self.process_token(Token::StartTag(tag));
+                }
+                Token::StartTag(tag) if tag.name == atom!("textarea") => {
+                    // 1. Insert an HTML element for the token.
+                    self.insert_an_html_element(tag);
+                    // 2. If the next token is a U+000A LINE FEED (LF) character token, then ignore that token and move on to the next one. (Newlines at the start of textarea elements are ignored as an authoring convenience.)
+                    self.skip_next_line_feed_token = true;
+                    // 3. Switch the tokenizer to the RCDATA state.
+                    self.tokenizer_state = Some(State::RCDATAState);
+                    // 4. Set the original insertion mode to the current insertion mode.
+                    self.original_insertion_mode = Some(self.current_insertion_mode);
+                    // 5. Set the frameset-ok flag to "not ok".
+                    self.frameset_ok = false;
+                    // 6. Switch the insertion mode to "text".
+                    self.switch_the_insertion_mode_to(InsertionMode::Text);
+                }
+                Token::StartTag(tag) if tag.name == atom!("xmp") => {
+                    // If the stack of open elements has a p element in button scope, then close a p element.
+                    if self.has_an_element_in_button_scope(&[atom!("p")]) {
+                        self.close_a_p_element();
+                    }
+                    // Reconstruct the active formatting elements, if any.
+                    // todo
+                    // Set the frameset-ok flag to "not ok".
+                    self.frameset_ok = false;
+                    // Follow the generic raw text element parsing algorithm.
+                    self.generic_raw_text_element_parsing_algorithm(tag);
+                }
+                Token::StartTag(tag) if tag.name == atom!("iframe") => {
+                    // Set the frameset-ok flag to "not ok".
+                    self.frameset_ok = false;
+                    // Follow the generic raw text element parsing algorithm.
+                    self.generic_raw_text_element_parsing_algorithm(tag);
+                }
+                Token::StartTag(mut tag) if tag.name == atom!("math") => {
+                    // Reconstruct the active formatting elements, if any.
+                    // todo
+                    // Adjust MathML attributes for the token. (This fixes the case of MathML attributes that are not all lowercase.)
+                    adjust_mathml_attributes(&mut tag);
+                    // Adjust foreign attributes for the token. (This fixes the use of namespaced attributes, in particular XLink.)
+                    adjust_foreign_attributes(&mut tag);
+                    // Insert a foreign element for the token, with MathML namespace and false.
+                    let self_closing = tag.self_closing;
+                    self.insert_a_foreign_element(tag, Namespace::MathMl, false);
+                    // If the token has its self-closing flag set, pop the current node off the stack of open elements and acknowledge the token's self-closing flag.
+                    if self_closing {
+                        self.pop_the_current_node();
+                    }
+                }
+                Token::StartTag(mut tag) if tag.name == atom!("svg") => {
+                    // Reconstruct the active formatting elements, if any.
+                    // todo
+                    // Adjust SVG attributes for the token. (This fixes the case of SVG attributes that are not all lowercase.)
+                    adjust_svg_attributes(&mut tag);
+                    // Adjust foreign attributes for the token. (This fixes the use of namespaced attributes, in particular XLink in SVG.)
+                    adjust_foreign_attributes(&mut tag);
+                    // Insert a foreign element for the token, with SVG namespace and false.
+                    let self_closing = tag.self_closing;
+                    self.insert_a_foreign_element(tag, Namespace::Svg, false);
+                    // If the token has its self-closing flag set, pop the current node off the stack of open elements and acknowledge the token's self-closing flag.
+                    if self_closing {
+                        self.pop_the_current_node();
+                    }
+                }
+                Token::StartTag(tag)
+                    if tag.name == atom!("noembed")
+                        || tag.name == atom!("noscript") && self.options.scripting =>
+                {
+                    // Follow the generic raw text element parsing algorithm.
+                    self.generic_raw_text_element_parsing_algorithm(tag);
+                }
+                Token::StartTag(ref tag)
+                    if matches!(
+                        tag.name,
+                        atom!("caption")
+                            | atom!("col")
+                            | atom!("colgroup")
+                            | atom!("frame")
+                            | atom!("head")
+                            | atom!("tbody")
+                            | atom!("td")
+                            | atom!("tfoot")
+                            | atom!("th")
+                            | atom!("thead")
+                            | atom!("tr")
+                    ) =>
+                {
+                    // Parse error. Ignore the token.
+                    self.parse_error("in-body-unexpected-start-tag-ignored");
+                }
+                Token::StartTag(tag) => {
+                    // Reconstruct the active formatting elements, if any.
+                    // todo
+                    // Insert an HTML element for the token.
+                    self.insert_an_html_element(tag);
+                    // Note: This element will be an ordinary element. With one exception: if the scripting flag is disabled, it can also be a noscript element.
+                }
+                Token::EndTag(tag) => {
+                    // Run these steps:
+                    // 1. Initialize node to be the current node (the bottommost node of the stack).
+                    for index in (0..self.stack_of_open_elements.len()).rev() {
+                        let node = self.stack_of_open_elements[index];
+                        let element = self.get_element(node);
+                        // 2. Loop: If node is an HTML element with the same tag name as the token, then:
+                        if element.is(Namespace::Html, tag.name.clone()) {
+                            // 1. Generate implied end tags, except for HTML elements with the same tag name as the token.
+                            self.generate_implied_end_tags(Some(tag.name));
+                            // 2. If node is not the current node, then this is a parse error.
+                            if self.get_current_node() != Some(node) {
+                                self.parse_error("in-body-end-tag-not-current-node");
+                            }
+                            // 3. Pop all the nodes from the current node up to node, including node, then stop these steps.
+                            self.pop_elements_above(index);
+                            return;
+                        }
+                        // 3. Otherwise, if node is in the special category, then this is a parse error; ignore the token, and return.
+                        if is_special(element) {
+                            self.parse_error("in-body-unexpected-end-tag");
+                            return;
+                        }
+                        // 4. Set node to the previous entry in the stack of open elements.
+                        // 5. Return to the step labeled loop.
+                    }
+                }
+            },
+            // https://html.spec.whatwg.org/multipage/parsing.html#parsing-main-incdata
+            InsertionMode::Text => match token {
+                Token::Character(c) => {
+                    // Insert the token's character.
+                    self.insert_a_character(c);
+                }
+                Token::Characters(data) => {
+                    // Insert the token's characters.
+                    self.insert_characters(&data);
+                }
+                Token::EndOfFile => {
+                    // Parse error.
+                    self.parse_error("text-unexpected-eof");
+                    // If the current node is a script element, then set its already started to true.
+                    // todo
+                    // Pop the current node off the stack of open elements.
+                    self.pop_the_current_node();
+                    // Switch the insertion mode to the original insertion mode and reprocess the token.
+                    self.switch_to_the_original_insertion_mode();
+                    self.process_token(token);
+                }
+                Token::EndTag(tag) if tag.name == atom!("script") => {
+                    // If the active speculative HTML parser is null and the JavaScript execution context stack is empty, then perform a microtask checkpoint.
+                    // todo
+                    // Let script be the current node (which will be a script element).
+                    // Pop the current node off the stack of open elements.
+                    self.pop_the_current_node();
+                    // Switch the insertion mode to the original insertion mode.
+                    self.switch_to_the_original_insertion_mode();
+                    // todo prepare and execute the script element
+                }
+                Token::EndTag(_) => {
+                    // Pop the current node off the stack of open elements.
+                    self.pop_the_current_node();
+                    // Switch the insertion mode to the original insertion mode.
+                    self.switch_to_the_original_insertion_mode();
+                }
+                Token::Doctype(_) | Token::StartTag(_) | Token::Comment(_) => {
+                    unreachable!("the tokenizer only emits characters and end tags in text")
+                }
+            },
+            // 13.2.6.4.19 The "after body" insertion mode
+            // https://html.spec.whatwg.org/multipage/parsing.html#parsing-main-afterbody
+            InsertionMode::AfterBody => match token {
+                Token::Character(c)
+                    if c == '\u{0009}'
+                        || c == '\u{000A}'
+                        || c == '\u{000C}'
+                        || c == '\u{000D}'
+                        || c == ' ' =>
+                {
+                    // Process the token using the rules for the "in body" insertion mode.
+                    self.process_token_using_the_rules_for(InsertionMode::InBody, token);
+                }
+                Token::Comment(data) => {
+                    // Insert a comment as the last child of the first element in the stack of open elements (the html element).
+                    let html = self.stack_of_open_elements[0];
+                    self.insert_a_comment(data, Some(html));
+                }
+                Token::Doctype(_) => {
+                    // Parse error. Ignore the token.
+                    self.parse_error("after-body-unexpected-doctype");
+                }
+                Token::StartTag(ref tag) if tag.name == atom!("html") => {
+                    // Process the token using the rules for the "in body" insertion mode.
+                    self.process_token_using_the_rules_for(InsertionMode::InBody, token);
+                }
+                Token::EndTag(tag) if tag.name == atom!("html") => {
+                    // If the parser was created as part of the HTML fragment parsing algorithm, this is a parse error; ignore the token. (fragment case)
+                    if self.context_element.is_some() {
+                        self.parse_error("after-body-unexpected-end-html-tag");
+                        return;
+                    }
+                    // Otherwise, switch the insertion mode to "after after body".
+                    self.switch_the_insertion_mode_to(InsertionMode::AfterAfterBody);
+                }
+                Token::EndOfFile => self.stop_parsing(),
+                _ => {
+                    // Parse error. Switch the insertion mode to "in body" and reprocess the token.
+                    self.parse_error("after-body-unexpected-token");
+                    self.switch_the_insertion_mode_to(InsertionMode::InBody);
+                    self.process_token(token);
+                }
+            },
+            // 13.2.6.4.22 The "after after body" insertion mode
+            // https://html.spec.whatwg.org/multipage/parsing.html#the-after-after-body-insertion-mode
+            InsertionMode::AfterAfterBody => match token {
+                Token::Character(c)
+                    if c == '\u{0009}'
+                        || c == '\u{000A}'
+                        || c == '\u{000C}'
+                        || c == '\u{000D}'
+                        || c == ' ' =>
+                {
+                    // Process the token using the rules for the "in body" insertion mode.
+                    self.process_token_using_the_rules_for(InsertionMode::InBody, token);
+                }
+                Token::Comment(data) => {
+                    // Insert a comment as the last child of the Document object.
+                    self.insert_a_comment(data, Some(self.document));
+                }
+                Token::Doctype(_) => {
+                    // Process the token using the rules for the "in body" insertion mode.
+                    self.process_token_using_the_rules_for(InsertionMode::InBody, token);
+                }
+                Token::StartTag(ref tag) if tag.name == atom!("html") => {
+                    // Process the token using the rules for the "in body" insertion mode.
+                    self.process_token_using_the_rules_for(InsertionMode::InBody, token);
+                }
+                Token::EndOfFile => self.stop_parsing(),
+                _ => {
+                    // Parse error. Switch the insertion mode to "in body" and reprocess the token.
+                    self.parse_error("after-after-body-unexpected-token");
+                    self.switch_the_insertion_mode_to(InsertionMode::InBody);
+                    self.process_token(token);
+                }
+            },
+
+            // 13.2.6.4.18 The "in template" insertion mode
+            // https://html.spec.whatwg.org/multipage/parsing.html#parsing-main-intemplate
+            InsertionMode::InTemplate => match token {
+                Token::Character(_)
+                | Token::Characters(_)
+                | Token::Comment(_)
+                | Token::Doctype(_) => {
+                    // Process the token using the rules for the "in body" insertion mode.
+                    self.process_token_using_the_rules_for(InsertionMode::InBody, token);
+                }
+                Token::StartTag(ref tag)
+                    if matches!(
+                        tag.name,
+                        atom!("base")
+                            | atom!("basefont")
+                            | atom!("bgsound")
+                            | atom!("link")
+                            | atom!("meta")
+                            | atom!("noframes")
+                            | atom!("script")
+                            | atom!("style")
+                            | atom!("template")
+                            | atom!("title")
+                    ) =>
+                {
+                    // Process the token using the rules for the "in head" insertion mode.
+                    self.process_token_using_the_rules_for(InsertionMode::InHead, token);
+                }
+                Token::EndTag(ref tag) if tag.name == atom!("template") => {
+                    // Process the token using the rules for the "in head" insertion mode.
+                    self.process_token_using_the_rules_for(InsertionMode::InHead, token);
+                }
+                Token::StartTag(ref tag)
+                    if matches!(
+                        tag.name,
+                        atom!("caption")
+                            | atom!("colgroup")
+                            | atom!("tbody")
+                            | atom!("tfoot")
+                            | atom!("thead")
+                    ) =>
+                {
+                    // Pop the current template insertion mode off the stack of template insertion modes. Push "in table" onto the stack of template insertion modes so that it is the new current template insertion mode. Switch the insertion mode to "in table", and reprocess the token.
+                    self.replace_the_current_template_insertion_mode(InsertionMode::InTable, token);
+                }
+                Token::StartTag(ref tag) if tag.name == atom!("col") => {
+                    // Pop the current template insertion mode off the stack of template insertion modes. Push "in column group" onto the stack of template insertion modes so that it is the new current template insertion mode. Switch the insertion mode to "in column group", and reprocess the token.
+                    self.replace_the_current_template_insertion_mode(
+                        InsertionMode::InColumnGroup,
+                        token,
+                    );
+                }
+                Token::StartTag(ref tag) if tag.name == atom!("tr") => {
+                    // Pop the current template insertion mode off the stack of template insertion modes. Push "in table body" onto the stack of template insertion modes so that it is the new current template insertion mode. Switch the insertion mode to "in table body", and reprocess the token.
+                    self.replace_the_current_template_insertion_mode(
+                        InsertionMode::InTableBody,
+                        token,
+                    );
+                }
+                Token::StartTag(ref tag) if matches!(tag.name, atom!("td") | atom!("th")) => {
+                    // Pop the current template insertion mode off the stack of template insertion modes. Push "in row" onto the stack of template insertion modes so that it is the new current template insertion mode. Switch the insertion mode to "in row", and reprocess the token.
+                    self.replace_the_current_template_insertion_mode(InsertionMode::InRow, token);
+                }
+                Token::StartTag(_) => {
+                    // Pop the current template insertion mode off the stack of template insertion modes. Push "in body" onto the stack of template insertion modes so that it is the new current template insertion mode. Switch the insertion mode to "in body", and reprocess the token.
+                    self.replace_the_current_template_insertion_mode(InsertionMode::InBody, token);
+                }
+                Token::EndTag(_) => {
+                    // Parse error. Ignore the token.
+                    self.parse_error("in-template-unexpected-end-tag");
+                }
+                Token::EndOfFile => {
+                    // If there is no template element on the stack of open elements, then stop parsing. (fragment case)
+                    if !self.has_a_template_element_on_the_stack_of_open_elements() {
+                        self.stop_parsing();
+                        return;
+                    }
+                    // Otherwise, this is a parse error.
+                    self.parse_error("in-template-eof");
+                    // Pop elements from the stack of open elements until a template element has been popped from the stack.
+                    self.pop_until_an_html_element_has_been_popped(&[atom!("template")]);
+                    // Clear the list of active formatting elements up to the last marker.
+                    // todo
+                    // Pop the current template insertion mode off the stack of template insertion modes.
+                    self.stack_of_template_insertion_modes.pop();
+                    // Reset the insertion mode appropriately.
+                    self.reset_the_insertion_mode_appropriately();
+                    // Reprocess the token.
+                    self.process_token(token);
+                }
+            },
+            // 13.2.6.4.20 The "in frameset" insertion mode
+            // https://html.spec.whatwg.org/multipage/parsing.html#parsing-main-inframeset
+            InsertionMode::InFrameset => match token {
+                Token::Character(c) if is_whitespace(c) => {
+                    // Insert the character.
+                    self.insert_a_character(c);
+                }
+                Token::Comment(data) => {
+                    // Insert a comment.
+                    self.insert_a_comment(data, None);
+                }
+                Token::Doctype(_) => {
+                    // Parse error. Ignore the token.
+                    self.parse_error("in-frameset-unexpected-doctype");
+                }
+                Token::StartTag(ref tag) if tag.name == atom!("html") => {
+                    // Process the token using the rules for the "in body" insertion mode.
+                    self.process_token_using_the_rules_for(InsertionMode::InBody, token);
+                }
+                Token::StartTag(tag) if tag.name == atom!("frameset") => {
+                    // Insert an HTML element for the token.
+                    self.insert_an_html_element(tag);
+                }
+                Token::EndTag(tag) if tag.name == atom!("frameset") => {
+                    // If the current node is the root html element, then this is a parse error; ignore the token. (fragment case)
+                    if self.stack_of_open_elements.len() == 1 {
+                        self.parse_error("in-frameset-unexpected-end-frameset-tag");
+                        return;
+                    }
+                    // Otherwise, pop the current node from the stack of open elements.
+                    self.pop_the_current_node();
+                    // If the parser was not created as part of the HTML fragment parsing algorithm (fragment case), and the current node is no longer a frameset element, then switch the insertion mode to "after frameset".
+                    if self.context_element.is_none()
+                        && !self.current_node_is_html(&[atom!("frameset")])
                     {
-                        ()
+                        self.switch_the_insertion_mode_to(InsertionMode::AfterFrameset);
                     }
-                    Token::Comment(comment) => todo!(),
-                    Token::Doctype(doctype) => todo!(),
-                    Token::StartTag(tag) if tag.name == "html" => todo!(),
-                    Token::StartTag(tag) if tag.name == "body" => {
-                        // self.stack_of_open_elements.push(Rc::clone(&element));
+                }
+                Token::StartTag(tag) if tag.name == atom!("frame") => {
+                    // Insert an HTML element for the token. Immediately pop the current node off the stack of open elements.
+                    self.insert_an_html_element(tag);
+                    self.pop_the_current_node();
+                    // Acknowledge the token's self-closing flag, if it is set.
+                }
+                Token::StartTag(ref tag) if tag.name == atom!("noframes") => {
+                    // Process the token using the rules for the "in head" insertion mode.
+                    self.process_token_using_the_rules_for(InsertionMode::InHead, token);
+                }
+                Token::EndOfFile => {
+                    // If the current node is not the root html element, then this is a parse error.
+                    if self.stack_of_open_elements.len() != 1 {
+                        self.parse_error("in-frameset-eof");
+                    }
+                    // Note: The current node can only be the root html element in the fragment case.
+                    // Stop parsing.
+                    self.stop_parsing();
+                }
+                _ => {
+                    // Parse error. Ignore the token.
+                    self.parse_error("in-frameset-unexpected-token");
+                }
+            },
+            // 13.2.6.4.21 The "after frameset" insertion mode
+            // https://html.spec.whatwg.org/multipage/parsing.html#parsing-main-afterframeset
+            InsertionMode::AfterFrameset => match token {
+                Token::Character(c) if is_whitespace(c) => {
+                    // Insert the character.
+                    self.insert_a_character(c);
+                }
+                Token::Comment(data) => {
+                    // Insert a comment.
+                    self.insert_a_comment(data, None);
+                }
+                Token::Doctype(_) => {
+                    // Parse error. Ignore the token.
+                    self.parse_error("after-frameset-unexpected-doctype");
+                }
+                Token::StartTag(ref tag) if tag.name == atom!("html") => {
+                    // Process the token using the rules for the "in body" insertion mode.
+                    self.process_token_using_the_rules_for(InsertionMode::InBody, token);
+                }
+                Token::EndTag(ref tag) if tag.name == atom!("html") => {
+                    // Switch the insertion mode to "after after frameset".
+                    self.switch_the_insertion_mode_to(InsertionMode::AfterAfterFrameset);
+                }
+                Token::StartTag(ref tag) if tag.name == atom!("noframes") => {
+                    // Process the token using the rules for the "in head" insertion mode.
+                    self.process_token_using_the_rules_for(InsertionMode::InHead, token);
+                }
+                Token::EndOfFile => self.stop_parsing(),
+                _ => {
+                    // Parse error. Ignore the token.
+                    self.parse_error("after-frameset-unexpected-token");
+                }
+            },
+            // 13.2.6.4.23 The "after after frameset" insertion mode
+            // https://html.spec.whatwg.org/multipage/parsing.html#the-after-after-frameset-insertion-mode
+            InsertionMode::AfterAfterFrameset => match token {
+                Token::Comment(data) => {
+                    // Insert a comment as the last child of the Document object.
+                    self.insert_a_comment(data, Some(self.document));
+                }
+                Token::Doctype(_) => {
+                    // Process the token using the rules for the "in body" insertion mode.
+                    self.process_token_using_the_rules_for(InsertionMode::InBody, token);
+                }
+                Token::Character(c) if is_whitespace(c) => {
+                    // Process the token using the rules for the "in body" insertion mode.
+                    self.process_token_using_the_rules_for(InsertionMode::InBody, token);
+                }
+                Token::StartTag(ref tag) if tag.name == atom!("html") => {
+                    // Process the token using the rules for the "in body" insertion mode.
+                    self.process_token_using_the_rules_for(InsertionMode::InBody, token);
+                }
+                Token::EndOfFile => self.stop_parsing(),
+                Token::StartTag(ref tag) if tag.name == atom!("noframes") => {
+                    // Process the token using the rules for the "in head" insertion mode.
+                    self.process_token_using_the_rules_for(InsertionMode::InHead, token);
+                }
+                _ => {
+                    // Parse error. Ignore the token.
+                    self.parse_error("after-after-frameset-unexpected-token");
+                }
+            },
 
-                        // self.insert_an_html_element(tag);
-                        // todo Set the frameset-ok flag to "not ok".
+            _ => todo!("unsupported insertion mode \"{}\"", insertion_mode.name()),
+        }
+    }
 
-                        // let element = Rc::new(RefCell::new(element));
-                        self.insert_an_html_element(tag);
-                        self.current_insertion_mode = InsertionMode::InBody;
-                    }
-                    Token::StartTag(tag) if tag.name == "frameset" => todo!(),
-                    Token::StartTag(tag)
-                        if !matches!(
-                            tag.name.as_str(),
-                            "base"
-                                | "basefont"
-                                | "bgsound"
-                                | "link"
-                                | "meta"
-                                | "noframes"
-                                | "script"
-                                | "style"
-                                | "template"
-                                | "title"
-                        ) =>
-                    {
-                        todo!()
+    // https://html.spec.whatwg.org/multipage/parsing.html#parsing-main-inforeign
+    fn process_token_in_foreign_content(&mut self, token: Token) {
+        match token {
+            Token::Character('\u{0000}') => {
+                // Parse error. Insert a U+FFFD REPLACEMENT CHARACTER character.
+                self.parse_error("foreign-content-unexpected-null-character");
+                self.insert_a_character('\u{FFFD}');
+            }
+            Token::Character(c)
+                if c == '\u{0009}'
+                    || c == '\u{000A}'
+                    || c == '\u{000C}'
+                    || c == '\u{000D}'
+                    || c == ' ' =>
+            {
+                // Insert the token's character.
+                self.insert_a_character(c);
+            }
+            Token::Character(c) => {
+                // Insert the token's character.
+                self.insert_a_character(c);
+                // Set the frameset-ok flag to "not ok".
+                self.frameset_ok = false;
+            }
+            Token::Characters(data) => {
+                // Insert the token's characters.
+                self.insert_characters(&data);
+                // Set the frameset-ok flag to "not ok", unless all of them are whitespace.
+                if !data.chars().all(is_whitespace) {
+                    self.frameset_ok = false;
+                }
+            }
+            Token::Comment(data) => {
+                // Insert a comment.
+                self.insert_a_comment(data, None);
+            }
+            Token::Doctype(_) => {
+                // Parse error. Ignore the token.
+                self.parse_error("foreign-content-unexpected-doctype");
+            }
+            Token::StartTag(ref tag)
+                if matches!(
+                    tag.name,
+                    atom!("b")
+                        | atom!("big")
+                        | atom!("blockquote")
+                        | atom!("body")
+                        | atom!("br")
+                        | atom!("center")
+                        | atom!("code")
+                        | atom!("dd")
+                        | atom!("div")
+                        | atom!("dl")
+                        | atom!("dt")
+                        | atom!("em")
+                        | atom!("embed")
+                        | atom!("h1")
+                        | atom!("h2")
+                        | atom!("h3")
+                        | atom!("h4")
+                        | atom!("h5")
+                        | atom!("h6")
+                        | atom!("head")
+                        | atom!("hr")
+                        | atom!("i")
+                        | atom!("img")
+                        | atom!("li")
+                        | atom!("listing")
+                        | atom!("menu")
+                        | atom!("meta")
+                        | atom!("nobr")
+                        | atom!("ol")
+                        | atom!("p")
+                        | atom!("pre")
+                        | atom!("ruby")
+                        | atom!("s")
+                        | atom!("small")
+                        | atom!("span")
+                        | atom!("strong")
+                        | atom!("strike")
+                        | atom!("sub")
+                        | atom!("sup")
+                        | atom!("table")
+                        | atom!("tt")
+                        | atom!("u")
+                        | atom!("ul")
+                        | atom!("var")
+                ) || (tag.name == atom!("font")
+                    && tag.attributes.iter().any(|attribute| {
+                        matches!(
+                            attribute.name,
+                            atom!("color") | atom!("face") | atom!("size")
+                        )
+                    })) =>
+            {
+                self.break_out_of_foreign_content(token);
+            }
+            Token::EndTag(ref tag) if tag.name == atom!("br") || tag.name == atom!("p") => {
+                self.break_out_of_foreign_content(token);
+            }
+            Token::StartTag(mut tag) => {
+                let adjusted_current_node = self
+                    .get_adjusted_current_node()
+                    .expect("foreign content requires an adjusted current node");
+                let namespace = self.get_element(adjusted_current_node).namespace;
+                // If the adjusted current node is an element in the MathML namespace, adjust MathML attributes for the token. (This fixes the case of MathML attributes that are not all lowercase.)
+                if namespace == Namespace::MathMl {
+                    adjust_mathml_attributes(&mut tag);
+                }
+                if namespace == Namespace::Svg {
+                    // If the adjusted current node is an element in the SVG namespace, and the token's tag name is one of the ones in the first column of the following table,
+                    // change the tag name to the name given in the corresponding cell in the second column. (This fixes the case of SVG elements that are not all lowercase.)
+                    adjust_svg_tag_name(&mut tag);
+                    // If the adjusted current node is an element in the SVG namespace, adjust SVG attributes for the token. (This fixes the case of SVG attributes that are not all lowercase.)
+                    adjust_svg_attributes(&mut tag);
+                }
+                // Adjust foreign attributes for the token. (This fixes the use of namespaced attributes, in particular XLink in SVG.)
+                adjust_foreign_attributes(&mut tag);
+                // Insert a foreign element for the token, with adjusted current node's namespace and false.
+                let self_closing = tag.self_closing;
+                self.insert_a_foreign_element(tag, namespace, false);
+                // If the token has its self-closing flag set, then run the appropriate steps from the following list:
+                if self_closing {
+                    // If the token's tag name is "script", and the new current node is in the SVG namespace
+                    // Acknowledge the token's self-closing flag, and then act as described in the steps for a "script" end tag below.
+                    // todo script processing, for now this is the same as popping the current node.
+                    // Otherwise
+                    // Pop the current node off the stack of open elements and acknowledge the token's self-closing flag.
+                    self.pop_the_current_node();
+                }
+            }
+            Token::EndTag(ref tag)
+                if tag.name == atom!("script")
+                    && self.get_current_node().is_some_and(|node| {
+                        self.get_element(node).is(Namespace::Svg, atom!("script"))
+                    }) =>
+            {
+                // Pop the current node off the stack of open elements.
+                self.pop_the_current_node();
+                // todo script processing
+            }
+            Token::EndTag(tag) => {
+                // Run these steps:
+                // 1. Initialize node to be the current node (the bottommost node of the stack).
+                let mut index = self.stack_of_open_elements.len() - 1;
+                // 2. If node's tag name, converted to ASCII lowercase, is not the same as the tag name of the token, then this is a parse error.
+                if !self
+                    .get_element(self.stack_of_open_elements[index])
+                    .local_name
+                    .eq_ignore_ascii_case(&tag.name)
+                {
+                    self.parse_error("foreign-content-unexpected-end-tag");
+                }
+                loop {
+                    // 3. Loop: If node is the topmost element in the stack of open elements, then return. (fragment case)
+                    if index == 0 {
+                        return;
                     }
-                    Token::EndTag(tag) if tag.name == "template" => todo!(),
-                    // todo An end tag whose tag name is one of: "body", "html", "br" => Act as described in the "anything else" entry below.
-                    Token::StartTag(tag) if tag.name == "head" => todo!(),
-                    Token::EndTag(tag) => todo!(),
-                    _ => {
-                        let mut tag = Tag::new();
-                        tag.name = "body".into();
-                        // Insert an HTML element for a "body" start tag token with no attributes.
-                        self.insert_an_html_element(tag);
-                        // Switch the insertion mode to "in body".
-                        self.current_insertion_mode = InsertionMode::InBody;
-                        // Reprocess the current token.
-                        reprocess_token = Some(token);
-                    }
-                },
-                // 13.2.6.4.7 The "in body" insertion mode
-                // https://html.spec.whatwg.org/multipage/parsing.html#parsing-main-inbody
-                InsertionMode::InBody => match token {
-                    Token::Character(c) if c == '\u{0000}' => todo!(),
-                    Token::Character(c)
-                        if c == '\u{0009}'
-                            || c == '\u{000A}'
-                            || c == '\u{000C}'
-                            || c == '\u{000D}'
-                            || c == ' ' =>
-                    // todo insert characters
-                    {
-                        ()
-                    }
-                    Token::Character(c) => {
-                        // Reconstruct the active formatting elements, if any.
-                        // todo
-                        // Insert the token's character.
-                        self.insert_a_character(c);
-                        // Set the frameset-ok flag to "not ok".
-                        // todo
-                    }
-                    Token::Comment(commend) => todo!(),
-                    Token::Doctype(doctype) => todo!(),
-                    Token::StartTag(tag) if tag.name == "html" => todo!(),
-                    Token::StartTag(tag)
-                        if !matches!(
-                            tag.name.as_str(),
-                            "base"
-                                | "basefont"
-                                | "bgsound"
-                                | "link"
-                                | "meta"
-                                | "noframes"
-                                | "script"
-                                | "style"
-                                | "template"
-                                | "title"
-                        ) =>
-                    {
-                        todo!()
-                    }
-                    Token::EndTag(tag) if tag.name == "tempalte" => todo!(),
-                    Token::EndTag(tag) if tag.name == "body" => {
-                        // todo
-                        self.current_insertion_mode = InsertionMode::AfterBody;
-                    }
-                    Token::StartTag(tag) if tag.name == "frameset" => todo!(),
-                    Token::EndOfFile => {
-                        // If the stack of template insertion modes is not empty, then process the token using the rules for the "in template" insertion mode.
-                        // todo
-                        // Otherwise, follow these steps:
-                        // todo
-                        // If there is a node in the stack of open elements that is not either a dd element, a dt element, an li element, an optgroup element, an option element, a p element, an rb element, an rp element, an rt element, an rtc element, a tbody element, a td element, a tfoot element, a th element, a thead element, a tr element, the body element, or the html element, then this is a parse error.
-                        // todo
-                        // Stop parsing.
+                    // 4. If node's tag name, converted to ASCII lowercase, is the same as the tag name of the token, pop elements from the stack of open elements until node has been popped from the stack, and then return.
+                    let element = self.get_element(self.stack_of_open_elements[index]);
+                    if element.local_name.eq_ignore_ascii_case(&tag.name) {
+                        self.pop_elements_above(index);
                         return;
                     }
-                    Token::EndTag(tag) if tag.name == "body" => todo!(),
-                    _ => todo!(),
-                },
-                // 13.2.6.4.19 The "after body" insertion mode
-                // https://html.spec.whatwg.org/multipage/parsing.html#parsing-main-afterbody
-                InsertionMode::AfterBody => match token {
-                    Token::Character(c)
-                        if c == '\u{0009}'
-                            || c == '\u{000A}'
-                            || c == '\u{000C}'
-                            || c == '\u{000D}'
-                            || c == ' ' =>
-                    // todo Process the token using the rules for the "in body" insertion mode.
-                    {
-                        ()
-                    }
-                    Token::EndTag(tag) if tag.name == "html" => {
-                        // todo
-                        self.current_insertion_mode = InsertionMode::AfterAfterBody;
-                    }
-                    _ => todo!(),
-                },
-                // 13.2.6.4.22 The "after after body" insertion mode
-                // https://html.spec.whatwg.org/multipage/parsing.html#the-after-after-body-insertion-mode
-                InsertionMode::AfterAfterBody => match token {
-                    Token::Character(c)
-                        if c == '\u{0009}'
-                            || c == '\u{000A}'
-                            || c == '\u{000C}'
-                            || c == '\u{000D}'
-                            || c == ' ' =>
-                    // todo Process the token using the rules for the "in body" insertion mode.
+                    // 5. Set node to the previous entry in the stack of open elements.
+                    index -= 1;
+                    // 6. If node is not an element in the HTML namespace, return to the step labeled loop.
+                    if self
+                        .get_element(self.stack_of_open_elements[index])
+                        .namespace
+                        != Namespace::Html
                     {
-                        ()
+                        continue;
                     }
-                    Token::EndOfFile => return,
-                    _ => todo!(),
-                },
+                    // 7. Otherwise, process the token according to the rules given in the section corresponding to the current insertion mode in HTML content.
+                    self.process_token_using_the_rules_for(
+                        self.current_insertion_mode,
+                        Token::EndTag(tag),
+                    );
+                    return;
+                }
+            }
+            Token::EndOfFile => unreachable!("end-of-file tokens are processed in HTML content"),
+        }
+    }
 
-                _ => todo!(
-                    "Insertion mode not implemented {:?}",
-                    self.current_insertion_mode
-                ),
+    // The breakout start tags, font with color/face/size and </br>, </p> in foreign content.
+    fn break_out_of_foreign_content(&mut self, token: Token) {
+        // Parse error.
+        self.parse_error("foreign-content-unexpected-html-element");
+        // While the current node is not a MathML text integration point, an HTML integration point, or an element in the HTML namespace, pop elements from the stack of open elements.
+        while let Some(current) = self.get_current_node() {
+            let element = self.get_element(current);
+            if is_mathml_text_integration_point(element)
+                || is_html_integration_point(element)
+                || element.namespace == Namespace::Html
+            {
+                break;
             }
+            self.pop_the_current_node();
+        }
+        // Reprocess the token according to the rules given in the section corresponding to the current insertion mode in HTML content.
+        self.process_token_using_the_rules_for(self.current_insertion_mode, token);
+    }
+
+    // https://html.spec.whatwg.org/multipage/parsing.html#generic-rcdata-element-parsing-algorithm
+    fn generic_rcdata_element_parsing_algorithm(&mut self, tag: Tag) {
+        self.generic_text_element_parsing_algorithm(tag, State::RCDATAState);
+    }
+
+    // https://html.spec.whatwg.org/multipage/parsing.html#generic-raw-text-element-parsing-algorithm
+    fn generic_raw_text_element_parsing_algorithm(&mut self, tag: Tag) {
+        self.generic_text_element_parsing_algorithm(tag, State::RAWTEXTState);
+    }
+
+    // The generic raw text element parsing algorithm and the generic RCDATA element parsing algorithm consist of the following steps.
+    fn generic_text_element_parsing_algorithm(&mut self, tag: Tag, tokenizer_state: State) {
+        // 1. Insert an HTML element for the token.
+        self.insert_an_html_element(tag);
+        // 2. If the algorithm that was invoked is the generic raw text element parsing algorithm, switch the tokenizer to the RAWTEXT state; otherwise the algorithm invoked was the generic RCDATA element parsing algorithm, switch the tokenizer to the RCDATA state.
+        self.tokenizer_state = Some(tokenizer_state);
+        // 3. Set the original insertion mode to the current insertion mode.
+        self.original_insertion_mode = Some(self.current_insertion_mode);
+        // 4. Then, switch the insertion mode to "text".
+        self.switch_the_insertion_mode_to(InsertionMode::Text);
+    }
+
+    // https://html.spec.whatwg.org/multipage/parsing.html#generate-all-implied-end-tags-thoroughly
+    fn generate_all_implied_end_tags_thoroughly(&mut self) {
+        // while the current node is a caption element, a colgroup element, a dd element, a dt element, an li element, an optgroup element, an option element, a p element, an rb element, an rp element, an rt element, an rtc element, a tbody element, a td element, a tfoot element, a th element, a thead element, or a tr element, the UA must pop the current node off the stack of open elements.
+        while self.current_node_is_html(&[
+            atom!("caption"),
+            atom!("colgroup"),
+            atom!("dd"),
+            atom!("dt"),
+            atom!("li"),
+            atom!("optgroup"),
+            atom!("option"),
+            atom!("p"),
+            atom!("rb"),
+            atom!("rp"),
+            atom!("rt"),
+            atom!("rtc"),
+            atom!("tbody"),
+            atom!("td"),
+            atom!("tfoot"),
+            atom!("th"),
+            atom!("thead"),
+            atom!("tr"),
+        ]) {
+            self.pop_the_current_node();
         }
     }
 
+    fn current_node_is_html(&self, local_names: &[Atom]) -> bool {
+        self.get_current_node()
+            .is_some_and(|node| self.get_element(node).is_html(local_names))
+    }
+
+    fn push_onto_the_stack_of_open_elements(&mut self, node: usize) {
+        self.stack_of_open_elements.push(node);
+        let depth = self.stack_of_open_elements.len();
+        let element = self.sink.element(node);
+        self.observer.element_pushed(element, depth);
+    }
+
+    fn pop_the_current_node(&mut self) -> Option<usize> {
+        let depth = self.stack_of_open_elements.len();
+        let node = self.stack_of_open_elements.pop()?;
+        let element = self.sink.element(node);
+        self.observer.element_popped(element, depth);
+        self.sink.pop(node);
+        Some(node)
+    }
+
+    // pop elements from the stack of open elements until it only contains the elements below index
+    fn pop_elements_above(&mut self, index: usize) {
+        while self.stack_of_open_elements.len() > index {
+            self.pop_the_current_node();
+        }
+    }
+
+    fn has_a_template_element_on_the_stack_of_open_elements(&self) -> bool {
+        self.stack_of_open_elements.iter().any(|node| {
+            self.get_element(*node)
+                .is(Namespace::Html, atom!("template"))
+        })
+    }
+
+    // the steps of the "in template" insertion mode that replace the current template insertion mode and reprocess the token in it
+    fn replace_the_current_template_insertion_mode(
+        &mut self,
+        insertion_mode: InsertionMode,
+        token: Token,
+    ) {
+        self.stack_of_template_insertion_modes.pop();
+        self.stack_of_template_insertion_modes.push(insertion_mode);
+        self.switch_the_insertion_mode_to(insertion_mode);
+        self.process_token(token);
+    }
+
+    fn remove_from_the_stack_of_open_elements(&mut self, node: usize) {
+        let Some(index) = self.stack_of_open_elements.iter().position(|n| *n == node) else {
+            return;
+        };
+        let depth = self.stack_of_open_elements.len();
+        self.stack_of_open_elements.remove(index);
+        let element = self.sink.element(node);
+        self.observer.element_popped(element, depth);
+        self.sink.pop(node);
+    }
+
+    fn switch_the_insertion_mode_to(&mut self, insertion_mode: InsertionMode) {
+        if self.current_insertion_mode != insertion_mode {
+            self.observer
+                .insertion_mode_changed(self.current_insertion_mode.name(), insertion_mode.name());
+        }
+        self.current_insertion_mode = insertion_mode;
+    }
+
+    fn switch_to_the_original_insertion_mode(&mut self) {
+        let insertion_mode = self
+            .original_insertion_mode
+            .take()
+            .expect("the original insertion mode is set before switching to text");
+        self.switch_the_insertion_mode_to(insertion_mode);
+    }
+
+    // pop elements from the stack of open elements until an HTML element with one of the local names has been popped from the stack
+    fn pop_until_an_html_element_has_been_popped(&mut self, local_names: &[Atom]) {
+        while let Some(node) = self.pop_the_current_node() {
+            if self.get_element(node).is_html(local_names) {
+                break;
+            }
+        }
+    }
+
+    // https://html.spec.whatwg.org/multipage/parsing.html#generate-implied-end-tags
+    fn generate_implied_end_tags(&mut self, except: Option<Atom>) {
+        // When the steps below require the UA to generate implied end tags, then, while the current node is a dd element, a dt element, an li element, an optgroup element,
+        // an option element, a p element, an rb element, an rp element, an rt element, or an rtc element, the UA must pop the current node off the stack of open elements.
+        // If a step requires the UA to generate implied end tags but lists an element to exclude from the process, then the UA must perform the above steps as if that element was not in the above list.
+        while let Some(node) = self.get_current_node() {
+            let element = self.get_element(node);
+            if except
+                .as_ref()
+                .is_some_and(|except| element.is(Namespace::Html, except.clone()))
+                || !element.is_html(&[
+                    atom!("dd"),
+                    atom!("dt"),
+                    atom!("li"),
+                    atom!("optgroup"),
+                    atom!("option"),
+                    atom!("p"),
+                    atom!("rb"),
+                    atom!("rp"),
+                    atom!("rt"),
+                    atom!("rtc"),
+                ])
+            {
+                return;
+            }
+            self.pop_the_current_node();
+        }
+    }
+
+    // https://html.spec.whatwg.org/multipage/parsing.html#has-an-element-in-the-specific-scope
+    fn has_an_element_in_the_specific_scope(&self, local_names: &[Atom], scope: Scope) -> bool {
+        // 1. Initialize node to be the current node (the bottommost node of the stack).
+        for node in self.stack_of_open_elements.iter().rev() {
+            let element = self.get_element(*node);
+            // 2. If node is the target node, terminate in a match state.
+            if element.is_html(local_names) {
+                return true;
+            }
+            // 3. Otherwise, if node is one of the element types in list, terminate in a failure state.
+            if scope.contains(element) {
+                return false;
+            }
+            // 4. Otherwise, set node to the previous entry in the stack of open elements and return to step 2. (This will never fail, since the loop will always terminate in the previous step if the top of the stack — an html element — is reached.)
+        }
+        false
+    }
+
+    // https://html.spec.whatwg.org/multipage/parsing.html#has-an-element-in-scope
+    fn has_an_element_in_scope(&self, local_names: &[Atom]) -> bool {
+        self.has_an_element_in_the_specific_scope(local_names, Scope::Default)
+    }
+
+    // https://html.spec.whatwg.org/multipage/parsing.html#has-an-element-in-list-item-scope
+    fn has_an_element_in_list_item_scope(&self, local_names: &[Atom]) -> bool {
+        self.has_an_element_in_the_specific_scope(local_names, Scope::ListItem)
+    }
+
+    // https://html.spec.whatwg.org/multipage/parsing.html#has-an-element-in-button-scope
+    fn has_an_element_in_button_scope(&self, local_names: &[Atom]) -> bool {
+        self.has_an_element_in_the_specific_scope(local_names, Scope::Button)
+    }
+
+    // https://html.spec.whatwg.org/multipage/parsing.html#has-an-element-in-table-scope
+    // todo the table insertion modes, the scope is only tested for now
+    #[allow(dead_code)]
+    fn has_an_element_in_table_scope(&self, local_names: &[Atom]) -> bool {
+        self.has_an_element_in_the_specific_scope(local_names, Scope::Table)
+    }
+
+    // https://html.spec.whatwg.org/multipage/parsing.html#has-an-element-in-select-scope
+    // todo the select insertion modes, the scope is only tested for now
+    #[allow(dead_code)]
+    fn has_an_element_in_select_scope(&self, local_names: &[Atom]) -> bool {
+        self.has_an_element_in_the_specific_scope(local_names, Scope::Select)
+    }
+
+    // https://html.spec.whatwg.org/multipage/parsing.html#close-a-p-element
+    fn close_a_p_element(&mut self) {
+        // Generate implied end tags, except for p elements.
+        self.generate_implied_end_tags(Some(atom!("p")));
+        // If the current node is not a p element, then this is a parse error.
+        if !self.current_node_is_html(&[atom!("p")]) {
+            self.parse_error("close-p-element-not-current-node");
+        }
+        // Pop elements from the stack of open elements until a p element has been popped from the stack.
+        self.pop_until_an_html_element_has_been_popped(&[atom!("p")]);
+    }
+
+    // the loop of the "li" and "dd"/"dt" start tags in body
+    fn close_list_item_elements(&mut self, local_names: &[Atom]) {
+        for node in self.stack_of_open_elements.clone().into_iter().rev() {
+            let element = self.get_element(node);
+            // Loop: If node is one of the list item elements, then run these substeps:
+            if element.is_html(local_names) {
+                let local_name = element.local_name.clone();
+                // 1. Generate implied end tags, except for elements with the same tag name as node.
+                self.generate_implied_end_tags(Some(local_name.clone()));
+                // 2. If the current node is not an element with the same tag name as node, then this is a parse error.
+                if !self.current_node_is_html(slice::from_ref(&local_name)) {
+                    self.parse_error("in-body-list-item-not-current-node");
+                }
+                // 3. Pop elements from the stack of open elements until an element with the same tag name as node has been popped from the stack.
+                self.pop_until_an_html_element_has_been_popped(slice::from_ref(&local_name));
+                // 4. Jump to the step labeled done below.
+                return;
+            }
+            // If node is in the special category, but is not an address, div, or p element, then jump to the step labeled done below.
+            if is_special(element)
+                && !element.is_html(&[atom!("address"), atom!("div"), atom!("p")])
+            {
+                return;
+            }
+            // Otherwise, set node to the previous entry in the stack of open elements and return to the step labeled loop.
+        }
+    }
+
+    // whether there is a node in the stack of open elements that is not either a dd element, a dt element, an li element, an optgroup element, an option element, a p element, an rb element, an rp element, an rt element, an rtc element, a tbody element, a td element, a tfoot element, a th element, a thead element, a tr element, the body element, or the html element
+    fn has_a_node_that_is_not_allowed_at_the_end_of_body(&self) -> bool {
+        self.stack_of_open_elements.iter().any(|node| {
+            !self.get_element(*node).is_html(&[
+                atom!("dd"),
+                atom!("dt"),
+                atom!("li"),
+                atom!("optgroup"),
+                atom!("option"),
+                atom!("p"),
+                atom!("rb"),
+                atom!("rp"),
+                atom!("rt"),
+                atom!("rtc"),
+                atom!("tbody"),
+                atom!("td"),
+                atom!("tfoot"),
+                atom!("th"),
+                atom!("thead"),
+                atom!("tr"),
+                atom!("body"),
+                atom!("html"),
+            ])
+        })
+    }
+
+    // https://html.spec.whatwg.org/multipage/parsing.html#reset-the-insertion-mode-appropriately
+    fn reset_the_insertion_mode_appropriately(&mut self) {
+        // 1. Let last be false.
+        let mut last = false;
+        // 2. Let node be the last node in the stack of open elements.
+        let mut index = self.stack_of_open_elements.len() - 1;
+        loop {
+            let mut node = self.stack_of_open_elements[index];
+            // 3. Loop: If node is the first node in the stack of open elements, then set last to true, and, if the parser was created as part of the HTML fragment parsing algorithm (fragment case), set node to the context element passed to that algorithm.
+            if index == 0 {
+                last = true;
+                if let Some(context_element) = self.context_element {
+                    node = context_element;
+                }
+            }
+            let element = self.get_element(node);
+            if element.namespace == Namespace::Html {
+                match element.local_name {
+                    // 4. If node is a select element, run these substeps:
+                    atom!("select") => {
+                        // 1. If last is true, jump to the step below labeled done.
+                        if !last {
+                            // 2. Let ancestor be node.
+                            // 3. Loop: If ancestor is the first node in the stack of open elements, jump to the step below labeled done.
+                            for ancestor in self.stack_of_open_elements[..index].iter().rev() {
+                                // 4. Let ancestor be the node before ancestor in the stack of open elements.
+                                let ancestor = self.get_element(*ancestor);
+                                // 5. If ancestor is a template node, jump to the step below labeled done.
+                                if ancestor.is(Namespace::Html, atom!("template")) {
+                                    break;
+                                }
+                                // 6. If ancestor is a table node, switch the insertion mode to "in select in table" and return.
+                                if ancestor.is(Namespace::Html, atom!("table")) {
+                                    self.switch_the_insertion_mode_to(
+                                        InsertionMode::InSelectInTable,
+                                    );
+                                    return;
+                                }
+                                // 7. Jump back to the step labeled loop.
+                            }
+                        }
+                        // 8. Done: Switch the insertion mode to "in select" and return.
+                        self.switch_the_insertion_mode_to(InsertionMode::InSelect);
+                        return;
+                    }
+                    // 5. If node is a td or th element and last is false, then switch the insertion mode to "in cell" and return.
+                    atom!("td") | atom!("th") if !last => {
+                        self.switch_the_insertion_mode_to(InsertionMode::InCell);
+                        return;
+                    }
+                    // 6. If node is a tr element, then switch the insertion mode to "in row" and return.
+                    atom!("tr") => {
+                        self.switch_the_insertion_mode_to(InsertionMode::InRow);
+                        return;
+                    }
+                    // 7. If node is a tbody, thead, or tfoot element, then switch the insertion mode to "in table body" and return.
+                    atom!("tbody") | atom!("thead") | atom!("tfoot") => {
+                        self.switch_the_insertion_mode_to(InsertionMode::InTableBody);
+                        return;
+                    }
+                    // 8. If node is a caption element, then switch the insertion mode to "in caption" and return.
+                    atom!("caption") => {
+                        self.switch_the_insertion_mode_to(InsertionMode::InCaption);
+                        return;
+                    }
+                    // 9. If node is a colgroup element, then switch the insertion mode to "in column group" and return.
+                    atom!("colgroup") => {
+                        self.switch_the_insertion_mode_to(InsertionMode::InColumnGroup);
+                        return;
+                    }
+                    // 10. If node is a table element, then switch the insertion mode to "in table" and return.
+                    atom!("table") => {
+                        self.switch_the_insertion_mode_to(InsertionMode::InTable);
+                        return;
+                    }
+                    // 11. If node is a template element, then switch the insertion mode to the current template insertion mode and return.
+                    atom!("template") => {
+                        self.switch_the_insertion_mode_to(
+                            *self
+                                .stack_of_template_insertion_modes
+                                .last()
+                                .expect("a template element has a template insertion mode"),
+                        );
+                        return;
+                    }
+                    // 12. If node is a head element and last is false, then switch the insertion mode to "in head" and return.
+                    atom!("head") if !last => {
+                        self.switch_the_insertion_mode_to(InsertionMode::InHead);
+                        return;
+                    }
+                    // 13. If node is a body element, then switch the insertion mode to "in body" and return.
+                    atom!("body") => {
+                        self.switch_the_insertion_mode_to(InsertionMode::InBody);
+                        return;
+                    }
+                    // 14. If node is a frameset element, then switch the insertion mode to "in frameset" and return. (fragment case)
+                    atom!("frameset") => {
+                        self.switch_the_insertion_mode_to(InsertionMode::InFrameset);
+                        return;
+                    }
+                    // 15. If node is an html element, run these substeps:
+                    atom!("html") => {
+                        // 1. If the head element pointer is null, switch the insertion mode to "before head" and return. (fragment case)
+                        // 2. Otherwise, the head element pointer is not null, switch the insertion mode to "after head" and return.
+                        self.switch_the_insertion_mode_to(if self.head_element_pointer.is_none() {
+                            InsertionMode::BeforeHead
+                        } else {
+                            InsertionMode::AfterHead
+                        });
+                        return;
+                    }
+                    _ => {}
+                }
+            }
+            // 16. If last is true, then switch the insertion mode to "in body" and return. (fragment case)
+            if last {
+                self.switch_the_insertion_mode_to(InsertionMode::InBody);
+                return;
+            }
+            // 17. Let node now be the node before node in the stack of open elements.
+            index -= 1;
+            // 18. Return to the step labeled loop.
+        }
+    }
+
+    // https://html.spec.whatwg.org/multipage/parsing.html#stop-parsing
+    fn stop_parsing(&mut self) {
+        // 1. If the active speculative HTML parser is not null, then stop the speculative HTML parser and return.
+        // 2. Set the insertion point to undefined.
+        // 3. Update the current document readiness to "interactive".
+        // todo
+        // 4. Pop all the nodes off the stack of open elements.
+        self.pop_elements_above(0);
+        // todo the rest of the end
+        self.stopped = true;
+    }
+
+    fn parse_error(&mut self, code: &str) {
+        let (line, column) = self.position;
+        let error = ParseError {
+            code: code.to_string(),
+            line,
+            column,
+        };
+        self.observer.parse_error(&error);
+        self.sink.parse_error(&error);
+        self.parse_errors.push(error);
+    }
+
+    // https://html.spec.whatwg.org/multipage/parsing.html#insert-an-html-element
     fn insert_an_html_element(&mut self, tag: Tag) -> usize {
-        let element = self.create_element_for_token(tag, "namespace", ());
-        let el = self.tree.create_node(element);
+        // To insert an HTML element given a token token: insert a foreign element given token, the HTML namespace, and false.
+        self.insert_a_foreign_element(tag, Namespace::Html, false)
+    }
+
+    // https://html.spec.whatwg.org/multipage/parsing.html#insert-a-foreign-element
+    fn insert_a_foreign_element(
+        &mut self,
+        tag: Tag,
+        namespace: Namespace,
+        only_add_to_element_stack: bool,
+    ) -> usize {
+        // 1. Let the adjustedInsertionLocation be the appropriate place for inserting a node.
+        let adjusted_insertion_location = self.appropriate_place_for_inserting_a_node();
+        // 2. Let element be the result of creating an element for the token given token, namespace, and the element in which the adjustedInsertionLocation finds itself.
+        let element = self.create_element_for_token(tag, namespace, adjusted_insertion_location);
+        let el = self.sink.create_element(element);
+        // 3. If onlyAddToElementStack is false, then run insert an element at the adjusted insertion location with element.
+        if !only_add_to_element_stack {
+            // todo custom element reactions
+            self.sink.append(adjusted_insertion_location, el);
+        }
+        // 4. Push element onto the stack of open elements so that it is the new current node.
+        self.push_onto_the_stack_of_open_elements(el);
+        // 5. Return element.
+        el
+    }
 
-        if let Some(current) = self.get_current_node() {
-            self.tree.add_child(*current, el);
+    // https://html.spec.whatwg.org/multipage/parsing.html#appropriate-place-for-inserting-a-node
+    fn appropriate_place_for_inserting_a_node(&self) -> usize {
+        // 1. If there was an override target specified, then let target be the override target. Otherwise, let target be the current node.
+        let target = self.get_current_node().unwrap_or(self.document);
+        // 2. Determine the adjusted insertion location using the first matching steps from the following list:
+        // todo foster parenting
+        // Otherwise: Let adjusted insertion location be inside target, after its last child (if any).
+        // 3. If the adjusted insertion location is inside a template element, let it instead be inside the template element's template contents, after its last child (if any).
+        if target != self.document
+            && self
+                .get_element(target)
+                .is(Namespace::Html, atom!("template"))
+        {
+            return self.sink.get_template_contents(target);
         }
-        self.stack_of_open_elements.push(el);
-        return el;
+        // 4. Return the adjusted insertion location.
+        target
+    }
+
+    // https://html.spec.whatwg.org/multipage/parsing.html#insert-a-comment
+    fn insert_a_comment(&mut self, data: Cow<str>, position: Option<usize>) {
+        // 1. Let data be the data given in the comment token being processed.
+        // 2. If position was specified, then let the adjusted insertion location be position. Otherwise, let adjusted insertion location be the appropriate place for inserting a node.
+        let adjusted_insertion_location =
+            position.unwrap_or_else(|| self.appropriate_place_for_inserting_a_node());
+        // 3. Create a Comment node whose data attribute is set to data and whose node document is the same as that of the node in which the adjusted insertion location finds itself.
+        let comment = self.sink.create_comment(data.into_owned());
+        // 4. Insert the newly created node at the adjusted insertion location.
+        self.sink.append(adjusted_insertion_location, comment);
     }
 
     // https://html.spec.whatwg.org/multipage/parsing.html#insert-a-character
     fn insert_a_character(&mut self, c: char) {
-        // todo this seems to hacky
-        if let Some(cur_index) = self.get_current_node2() {
-            let node = self.tree.get_node(cur_index);
-            if let Some(last_node) = node.children.top() {
-                let node = self.tree.get_node(*last_node);
-                // If there is a Text node immediately before the adjusted insertion location, then append data to that Text node's data.
-                if let Node::Text(txt) = &node.data {
-                    let mut txt = txt.data.clone();
-                    txt.push(c);
-                    let node = Node::Text(Text::new(txt));
-                    self.tree.replace_data_at_index(*last_node, node);
-                    return;
-                }
-            }
+        self.insert_characters(c.encode_utf8(&mut [0; 4]));
+    }
 
-            let node = Node::Text(Text::new(c.to_string()));
-            let text_node = self.tree.create_node(node);
-            self.tree.add_child(cur_index, text_node);
+    // inserts a run of characters, the data is appended to the Text node in place
+    fn insert_characters(&mut self, data: &str) {
+        // 1. Let data be the characters passed to the algorithm, or, if no characters were explicitly specified, the character of the character token being processed.
+        // 2. Let the adjusted insertion location be the appropriate place for inserting a node.
+        let adjusted_insertion_location = self.appropriate_place_for_inserting_a_node();
+        // 3. If the adjusted insertion location is inside a Document node, then ignore the token.
+        if adjusted_insertion_location == self.document {
+            return;
         }
+        // 4. If there is a Text node immediately before the adjusted insertion location, then append data to that Text node's data.
+        // Otherwise, create a new Text node whose data is data and whose node document is the same as that of the element in which the adjusted insertion location finds itself, and insert the newly created node at the adjusted insertion location.
+        self.sink.append_text(adjusted_insertion_location, data);
     }
 
-    fn get_current_node(&self) -> Option<&usize> {
-        return self.stack_of_open_elements.top();
+    // https://html.spec.whatwg.org/multipage/parsing.html#current-node
+    // The current node is the bottommost node in this stack of open elements.
+    fn get_current_node(&self) -> Option<usize> {
+        self.stack_of_open_elements.last().copied()
     }
 
-    fn get_current_node2(&self) -> Option<usize> {
-        let len = self.stack_of_open_elements.len();
-        if len == 0 {
-            return None;
-        } else {
-            return Some(self.stack_of_open_elements[len - 1]);
+    // https://html.spec.whatwg.org/multipage/parsing.html#adjusted-current-node
+    fn get_adjusted_current_node(&self) -> Option<usize> {
+        // The adjusted current node is the context element if the parser was created as part of the HTML fragment parsing algorithm and the stack of open elements has only one element in it (fragment case);
+        // otherwise, the adjusted current node is the current node.
+        match self.context_element {
+            Some(context_element) if self.stack_of_open_elements.len() == 1 => {
+                Some(context_element)
+            }
+            _ => self.get_current_node(),
         }
     }
 
+    fn get_element(&self, index: usize) -> &Element {
+        self.sink.element(index)
+    }
+
     // https://html.spec.whatwg.org/multipage/parsing.html#create-an-element-for-the-token
     // todo what is a token token?? --> i use Tag
-    fn create_element_for_token(&self, tag: Tag, namespace: &str, intended_parent: ()) -> Node {
+    fn create_element_for_token(
+        &self,
+        tag: Tag,
+        namespace: Namespace,
+        _intended_parent: usize,
+    ) -> Element {
         // 1. If the active speculative HTML parser is not null, then return the result of creating a speculative mock element given namespace, token's tag name, and token's attributes.
 
         // 2. Otherwise, optionally create a speculative mock element given namespace, token's tag name, and token's attributes.
         // Note: The result is not used. This step allows for a speculative fetch to be initiated from non-speculative parsing. The fetch is still speculative at this point, because, for example, by the time the element is inserted, intended parent might have been removed from the document.
 
         // 3. Let document be intendedParent's node document.
-        let document = {}; // todo
+        let document = self.document; // todo we only have one document
         // 4. Let localName be token's tag name.
         let local_name = tag.name;
         // 5. Let is be the value of the "is" attribute in token, if such an attribute exists; otherwise null.
-        let is = {};
+        let is = tag
+            .attributes
+            .iter()
+            .find(|attribute| attribute.name == atom!("is"))
+            .map(|attribute| attribute.value.to_string());
         // 6. Let registry be the result of looking up a custom element registry given intendedParent.
-        let registry = {}; // todo
+        // todo
         // 7. Let definition be the result of looking up a custom element definition given registry, namespace, localName, and is.
 
         // 8. Let willExecuteScript be true if definition is non-null and the parser was not created as part of the HTML fragment parsing algorithm; otherwise false.
@@ -506,17 +2546,22 @@ impl TreeBuilder {
         // 10. Let element be the result of creating an element given document, localName, namespace, null, is, willExecuteScript, and registry.
         // Note: This will cause custom element constructors to run, if willExecuteScript is true. However, since we incremented the throw-on-dynamic-markup-insertion counter,
         //       this cannot cause new characters to be inserted into the tokenizer, or the document to be blown away.
-        let element = self.create_an_element(
+        let mut element = create_an_element(
             document,
             local_name,
             namespace,
             None,
             is,
             will_execute_script,
-            registry,
         );
 
         // 11. Append each attribute in the given token to element.
+        // the element owns its attributes, the token borrows them from the input
+        element.attributes = tag
+            .attributes
+            .into_iter()
+            .map(Attribute::into_owned)
+            .collect();
         // Note: This can enqueue a custom element callback reaction for the attributeChangedCallback, which might run immediately (in the next step).
         // Note: Even though the is attribute governs the creation of a customized built-in element, it is not present during the execution of the relevant custom element constructor; it is appended in this step, along with all other attributes.
 
@@ -533,33 +2578,514 @@ impl TreeBuilder {
         // 15. If element is a form-associated element and not a form-associated custom element, the form element pointer is not null, there is no template element on the stack of open elements, element is either not listed or doesn't have a form attribute, and the intendedParent is in the same tree as the element pointed to by the form element pointer, then associate element with the form element pointed to by the form element pointer and set element's parser inserted flag.
 
         // 16. Return element.
-        return element;
+        element
     }
+}
 
-    fn create_an_element(
-        &self,
-        document: (),
-        local_name: String,
-        namespace: &str,
-        none: Option<()>,
-        is: (),
-        will_execute_script: bool,
-        registry: (),
-    ) -> Node {
-        // todo
-        return Node::Element(Element::new(local_name));
+// the characters of a run from the byte index on, a run borrowed from the input stays borrowed
+fn rest_of_run(data: Cow<str>, index: usize) -> Cow<str> {
+    match data {
+        Cow::Borrowed(data) => Cow::Borrowed(&data[index..]),
+        Cow::Owned(mut data) => {
+            data.drain(..index);
+            Cow::Owned(data)
+        }
     }
 }
 
-trait Stack {
-    fn top(&self) -> Option<&usize>;
+// https://dom.spec.whatwg.org/#concept-create-element
+fn create_an_element(
+    _document: usize,
+    local_name: Atom,
+    namespace: Namespace,
+    _prefix: Option<String>,
+    _is: Option<String>,
+    _synchronous_custom_elements: bool,
+) -> Element {
+    // todo
+    Element::new_with_namespace(local_name, namespace)
+}
+
+// https://html.spec.whatwg.org/multipage/parsing.html#has-an-element-in-the-specific-scope
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Scope {
+    Default,
+    ListItem,
+    Button,
+    Table,
+    Select,
 }
 
-impl Stack for Vec<usize> {
-    fn top(&self) -> Option<&usize> {
-        match &self[..] {
-            [] => None,
-            [.., n] => Some(n),
+impl Scope {
+    // whether the element is one of the element types in the list of the scope
+    fn contains(&self, element: &Element) -> bool {
+        match self {
+            // The stack of open elements is said to have a particular element in scope when it has that element in the specific scope consisting of the following element types:
+            // applet, caption, html, table, td, th, marquee, object, template, MathML mi, MathML mo, MathML mn, MathML ms, MathML mtext, MathML annotation-xml, SVG foreignObject, SVG desc, SVG title
+            Scope::Default => {
+                element.is_html(&[
+                    atom!("applet"),
+                    atom!("caption"),
+                    atom!("html"),
+                    atom!("table"),
+                    atom!("td"),
+                    atom!("th"),
+                    atom!("marquee"),
+                    atom!("object"),
+                    atom!("template"),
+                ]) || (element.namespace == Namespace::MathMl
+                    && matches!(
+                        element.local_name,
+                        atom!("mi")
+                            | atom!("mo")
+                            | atom!("mn")
+                            | atom!("ms")
+                            | atom!("mtext")
+                            | atom!("annotation-xml")
+                    ))
+                    || (element.namespace == Namespace::Svg
+                        && matches!(
+                            element.local_name,
+                            atom!("foreignObject") | atom!("desc") | atom!("title")
+                        ))
+            }
+            // ... consisting of the following element types: All the element types listed above for the has an element in scope algorithm. ol in the HTML namespace, ul in the HTML namespace
+            Scope::ListItem => {
+                element.is_html(&[atom!("ol"), atom!("ul")]) || Scope::Default.contains(element)
+            }
+            // ... consisting of the following element types: All the element types listed above for the has an element in scope algorithm. button in the HTML namespace
+            Scope::Button => {
+                element.is_html(&[atom!("button")]) || Scope::Default.contains(element)
+            }
+            // ... consisting of the following element types: html in the HTML namespace, table in the HTML namespace, template in the HTML namespace
+            Scope::Table => element.is_html(&[atom!("html"), atom!("table"), atom!("template")]),
+            // ... consisting of all element types except the following: optgroup in the HTML namespace, option in the HTML namespace
+            Scope::Select => !element.is_html(&[atom!("optgroup"), atom!("option")]),
+        }
+    }
+}
+
+// https://html.spec.whatwg.org/multipage/parsing.html#special
+fn is_special(element: &Element) -> bool {
+    match element.namespace {
+        Namespace::Html => matches!(
+            element.local_name,
+            atom!("address")
+                | atom!("applet")
+                | atom!("area")
+                | atom!("article")
+                | atom!("aside")
+                | atom!("base")
+                | atom!("basefont")
+                | atom!("bgsound")
+                | atom!("blockquote")
+                | atom!("body")
+                | atom!("br")
+                | atom!("button")
+                | atom!("caption")
+                | atom!("center")
+                | atom!("col")
+                | atom!("colgroup")
+                | atom!("dd")
+                | atom!("details")
+                | atom!("dir")
+                | atom!("div")
+                | atom!("dl")
+                | atom!("dt")
+                | atom!("embed")
+                | atom!("fieldset")
+                | atom!("figcaption")
+                | atom!("figure")
+                | atom!("footer")
+                | atom!("form")
+                | atom!("frame")
+                | atom!("frameset")
+                | atom!("h1")
+                | atom!("h2")
+                | atom!("h3")
+                | atom!("h4")
+                | atom!("h5")
+                | atom!("h6")
+                | atom!("head")
+                | atom!("header")
+                | atom!("hgroup")
+                | atom!("hr")
+                | atom!("html")
+                | atom!("iframe")
+                | atom!("img")
+                | atom!("input")
+                | atom!("keygen")
+                | atom!("li")
+                | atom!("link")
+                | atom!("listing")
+                | atom!("main")
+                | atom!("marquee")
+                | atom!("menu")
+                | atom!("meta")
+                | atom!("nav")
+                | atom!("noembed")
+                | atom!("noframes")
+                | atom!("noscript")
+                | atom!("object")
+                | atom!("ol")
+                | atom!("p")
+                | atom!("param")
+                | atom!("plaintext")
+                | atom!("pre")
+                | atom!("script")
+                | atom!("search")
+                | atom!("section")
+                | atom!("select")
+                | atom!("source")
+                | atom!("style")
+                | atom!("summary")
+                | atom!("table")
+                | atom!("tbody")
+                | atom!("td")
+                | atom!("template")
+                | atom!("textarea")
+                | atom!("tfoot")
+                | atom!("th")
+                | atom!("thead")
+                | atom!("title")
+                | atom!("tr")
+                | atom!("track")
+                | atom!("ul")
+                | atom!("wbr")
+                | atom!("xmp")
+        ),
+        Namespace::MathMl => matches!(
+            element.local_name,
+            atom!("mi")
+                | atom!("mo")
+                | atom!("mn")
+                | atom!("ms")
+                | atom!("mtext")
+                | atom!("annotation-xml")
+        ),
+        Namespace::Svg => matches!(
+            element.local_name,
+            atom!("foreignObject") | atom!("desc") | atom!("title")
+        ),
+        _ => false,
+    }
+}
+
+// U+0009 CHARACTER TABULATION, U+000A LINE FEED (LF), U+000C FORM FEED (FF), U+000D CARRIAGE RETURN (CR), or U+0020 SPACE
+fn is_whitespace(c: char) -> bool {
+    matches!(c, '\u{0009}' | '\u{000A}' | '\u{000C}' | '\u{000D}' | ' ')
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs::File;
+
+    use crate::atom;
+    use crate::html::tokenizer::Tokenizer;
+    use crate::html::tree_builder::tester::TestReader;
+    use crate::html::tree_builder::tree::{Element, Namespace, Node};
+    use crate::html::tree_builder::{InsertionMode, ParserOptions, TreeBuilder};
+
+    fn run_test_file(file_path: &str) {
+        let test_reader = TestReader::<File>::new_from_file(file_path).unwrap();
+        for test_case in test_reader {
+            let contents = test_case.data.join("\n");
+            for scripting in test_case.scripting_settings() {
+                let mut tree_builder = TreeBuilder::new(ParserOptions { scripting });
+                if let Some(context_element) = test_case.context_element() {
+                    let nodes = tree_builder.parse_fragment(context_element, &contents);
+                    TestReader::<File>::assert_eq_nodes(
+                        &test_case,
+                        tree_builder.get_tree(),
+                        &nodes,
+                    );
+                } else {
+                    let tokenizer = Tokenizer::new(&contents);
+                    tree_builder.build(tokenizer);
+                    TestReader::<File>::assert_eq(&test_case, tree_builder.get_tree());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn foreign_content() {
+        run_test_file("html-tests/foreign.dat");
+    }
+
+    #[test]
+    fn doctype() {
+        run_test_file("html-tests/doctype.dat");
+    }
+
+    #[test]
+    fn head() {
+        run_test_file("html-tests/head.dat");
+    }
+
+    #[test]
+    fn scripting() {
+        run_test_file("html-tests/scripting.dat");
+    }
+
+    #[test]
+    fn fragment() {
+        run_test_file("html-tests/fragment.dat");
+    }
+
+    #[test]
+    fn body() {
+        run_test_file("html-tests/body.dat");
+    }
+
+    #[test]
+    fn comments() {
+        run_test_file("html-tests/comments.dat");
+    }
+
+    #[test]
+    fn whitespace() {
+        run_test_file("html-tests/whitespace.dat");
+    }
+
+    #[test]
+    fn character_references() {
+        run_test_file("html-tests/character_references.dat");
+    }
+
+    #[test]
+    fn lists() {
+        run_test_file("html-tests/test1.dat");
+    }
+
+    // a tree builder whose stack of open elements holds the given elements, each a child of the previous one
+    fn with_stack_of_open_elements(elements: &[(Namespace, &str)]) -> TreeBuilder {
+        let mut tree_builder = TreeBuilder::new(ParserOptions::default());
+        let mut parent = tree_builder.document;
+        for (namespace, local_name) in elements {
+            let element = Element::new_with_namespace(local_name.to_string(), *namespace);
+            let node = tree_builder.sink.create_node(Node::Element(element));
+            tree_builder.sink.add_child(parent, node);
+            tree_builder.stack_of_open_elements.push(node);
+            parent = node;
+        }
+        tree_builder
+    }
+
+    fn html(local_names: &[&'static str]) -> Vec<(Namespace, &'static str)> {
+        local_names
+            .iter()
+            .map(|local_name| (Namespace::Html, *local_name))
+            .collect()
+    }
+
+    fn stack_local_names(tree_builder: &TreeBuilder) -> Vec<&str> {
+        tree_builder
+            .stack_of_open_elements
+            .iter()
+            .map(|node| tree_builder.get_element(*node).local_name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn has_an_element_in_scope() {
+        let tree_builder = with_stack_of_open_elements(&html(&["html", "body", "p", "span"]));
+        assert!(tree_builder.has_an_element_in_scope(&[atom!("p")]));
+        assert!(tree_builder.has_an_element_in_scope(&[atom!("body")]));
+        assert!(!tree_builder.has_an_element_in_scope(&[atom!("div")]));
+
+        let tree_builder =
+            with_stack_of_open_elements(&html(&["html", "body", "p", "table", "td", "span"]));
+        assert!(!tree_builder.has_an_element_in_scope(&[atom!("p")]));
+        assert!(tree_builder.has_an_element_in_scope(&[atom!("td")]));
+
+        let mut elements = html(&["html", "body", "p"]);
+        elements.push((Namespace::Svg, "svg"));
+        elements.push((Namespace::Svg, "foreignObject"));
+        let tree_builder = with_stack_of_open_elements(&elements);
+        assert!(!tree_builder.has_an_element_in_scope(&[atom!("p")]));
+
+        // a MathML mi only bounds the scope in the MathML namespace
+        let tree_builder = with_stack_of_open_elements(&html(&["html", "body", "p", "mi"]));
+        assert!(tree_builder.has_an_element_in_scope(&[atom!("p")]));
+    }
+
+    #[test]
+    fn has_an_element_in_list_item_scope() {
+        let tree_builder = with_stack_of_open_elements(&html(&["html", "body", "li", "ul", "div"]));
+        assert!(tree_builder.has_an_element_in_scope(&[atom!("li")]));
+        assert!(!tree_builder.has_an_element_in_list_item_scope(&[atom!("li")]));
+
+        let tree_builder = with_stack_of_open_elements(&html(&["html", "body", "ul", "li", "div"]));
+        assert!(tree_builder.has_an_element_in_list_item_scope(&[atom!("li")]));
+    }
+
+    #[test]
+    fn has_an_element_in_button_scope() {
+        let tree_builder = with_stack_of_open_elements(&html(&["html", "body", "p", "button"]));
+        assert!(tree_builder.has_an_element_in_scope(&[atom!("p")]));
+        assert!(!tree_builder.has_an_element_in_button_scope(&[atom!("p")]));
+        assert!(tree_builder.has_an_element_in_button_scope(&[atom!("button")]));
+    }
+
+    #[test]
+    fn has_an_element_in_table_scope() {
+        let tree_builder =
+            with_stack_of_open_elements(&html(&["html", "body", "table", "tbody", "tr", "td"]));
+        assert!(tree_builder.has_an_element_in_table_scope(&[atom!("tr")]));
+        assert!(tree_builder.has_an_element_in_table_scope(&[atom!("table")]));
+
+        // the cell does not bound the table scope, but the nested table does
+        let tree_builder =
+            with_stack_of_open_elements(&html(&["html", "body", "table", "tr", "td", "table"]));
+        assert!(!tree_builder.has_an_element_in_table_scope(&[atom!("tr")]));
+        assert!(!tree_builder.has_an_element_in_scope(&[atom!("tr")]));
+    }
+
+    #[test]
+    fn has_an_element_in_select_scope() {
+        let tree_builder =
+            with_stack_of_open_elements(&html(&["html", "body", "select", "optgroup", "option"]));
+        assert!(tree_builder.has_an_element_in_select_scope(&[atom!("select")]));
+
+        let tree_builder = with_stack_of_open_elements(&html(&["html", "body", "select", "div"]));
+        assert!(!tree_builder.has_an_element_in_select_scope(&[atom!("select")]));
+    }
+
+    #[test]
+    fn generate_implied_end_tags() {
+        let mut tree_builder =
+            with_stack_of_open_elements(&html(&["html", "body", "div", "p", "li", "dd", "rt"]));
+        tree_builder.generate_implied_end_tags(Some(atom!("li")));
+        assert_eq!(
+            stack_local_names(&tree_builder),
+            ["html", "body", "div", "p", "li"]
+        );
+        tree_builder.generate_implied_end_tags(None);
+        assert_eq!(stack_local_names(&tree_builder), ["html", "body", "div"]);
+
+        // only HTML elements are implied
+        let mut elements = html(&["html", "body"]);
+        elements.push((Namespace::MathMl, "p"));
+        let mut tree_builder = with_stack_of_open_elements(&elements);
+        tree_builder.generate_implied_end_tags(None);
+        assert_eq!(stack_local_names(&tree_builder), ["html", "body", "p"]);
+    }
+
+    #[test]
+    fn generate_all_implied_end_tags_thoroughly() {
+        let mut tree_builder = with_stack_of_open_elements(&html(&[
+            "html", "body", "table", "tbody", "tr", "td", "p",
+        ]));
+        tree_builder.generate_all_implied_end_tags_thoroughly();
+        assert_eq!(stack_local_names(&tree_builder), ["html", "body", "table"]);
+    }
+
+    #[test]
+    fn close_a_p_element() {
+        let mut tree_builder =
+            with_stack_of_open_elements(&html(&["html", "body", "p", "dt", "option"]));
+        tree_builder.close_a_p_element();
+        assert_eq!(stack_local_names(&tree_builder), ["html", "body"]);
+        assert!(tree_builder.get_parse_errors().is_empty());
+
+        let mut tree_builder =
+            with_stack_of_open_elements(&html(&["html", "body", "p", "span", "li"]));
+        tree_builder.close_a_p_element();
+        assert_eq!(stack_local_names(&tree_builder), ["html", "body"]);
+        assert_eq!(tree_builder.get_parse_errors().len(), 1);
+    }
+
+    #[test]
+    fn parse_error_positions() {
+        let mut tree_builder = TreeBuilder::new(ParserOptions::default());
+        tree_builder.build(Tokenizer::new("<p>a\n</span></p>\n</body><i>x"));
+        let errors: Vec<(usize, usize, &str)> = tree_builder
+            .get_parse_errors()
+            .iter()
+            .map(|error| (error.line, error.column, error.code.as_str()))
+            .collect();
+        assert_eq!(
+            errors,
+            [
+                (1, 3, "expected-doctype-but-got-start-tag"),
+                (2, 7, "in-body-unexpected-end-tag"),
+                (3, 10, "after-body-unexpected-token"),
+                (3, 11, "in-body-eof-with-open-elements")
+            ]
+        );
+    }
+
+    #[test]
+    fn reset_the_insertion_mode_appropriately() {
+        let cases = [
+            (vec!["html", "body", "div"], InsertionMode::InBody),
+            (
+                vec!["html", "body", "table", "tbody", "tr", "td", "span"],
+                InsertionMode::InCell,
+            ),
+            (
+                vec!["html", "body", "table", "tbody", "tr"],
+                InsertionMode::InRow,
+            ),
+            (
+                vec!["html", "body", "table", "tbody"],
+                InsertionMode::InTableBody,
+            ),
+            (
+                vec!["html", "body", "table", "caption"],
+                InsertionMode::InCaption,
+            ),
+            (
+                vec!["html", "body", "table", "colgroup"],
+                InsertionMode::InColumnGroup,
+            ),
+            (vec!["html", "body", "table"], InsertionMode::InTable),
+            (vec!["html", "body", "select"], InsertionMode::InSelect),
+            (
+                vec!["html", "body", "table", "tr", "td", "select"],
+                InsertionMode::InSelectInTable,
+            ),
+            (vec!["html", "head"], InsertionMode::InHead),
+            (vec!["html", "frameset"], InsertionMode::InFrameset),
+            (vec!["html"], InsertionMode::BeforeHead),
+        ];
+        for (local_names, insertion_mode) in cases {
+            let mut tree_builder = with_stack_of_open_elements(&html(&local_names));
+            tree_builder.reset_the_insertion_mode_appropriately();
+            assert_eq!(
+                tree_builder.current_insertion_mode, insertion_mode,
+                "{local_names:?}"
+            );
+        }
+
+        // the head element pointer is set once the head element was inserted
+        let mut tree_builder = with_stack_of_open_elements(&html(&["html"]));
+        tree_builder.head_element_pointer = Some(tree_builder.stack_of_open_elements[0]);
+        tree_builder.reset_the_insertion_mode_appropriately();
+        assert_eq!(
+            tree_builder.current_insertion_mode,
+            InsertionMode::AfterHead
+        );
+    }
+
+    #[test]
+    fn reset_the_insertion_mode_appropriately_in_the_fragment_case() {
+        for (context_element, insertion_mode) in [
+            // a cell only switches to "in cell" when it is not the last node
+            ("td", InsertionMode::InBody),
+            ("tr", InsertionMode::InRow),
+            ("select", InsertionMode::InSelect),
+            ("head", InsertionMode::InBody),
+            ("title", InsertionMode::InBody),
+        ] {
+            let mut tree_builder = with_stack_of_open_elements(&html(&["html"]));
+            let context_element = tree_builder
+                .sink
+                .create_node(Node::Element(Element::new(context_element.to_string())));
+            tree_builder.context_element = Some(context_element);
+            tree_builder.reset_the_insertion_mode_appropriately();
+            assert_eq!(tree_builder.current_insertion_mode, insertion_mode);
         }
     }
 }
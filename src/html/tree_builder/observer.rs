@@ -0,0 +1,186 @@
+use std::io::Write;
+
+use crate::html::{
+    tokenizer::Token,
+    tree_builder::{
+        ParseError,
+        tree::{Element, Namespace},
+    },
+};
+
+// Callbacks into the tree construction, e.g. to trace how a document is parsed.
+// Every method does nothing by default, so an observer only implements what it is interested in.
+pub trait ParseObserver {
+    // a token was emitted by the tokenizer, before the tree builder processes it
+    fn token_emitted(&mut self, _token: &Token) {}
+
+    // the insertion mode was switched, the modes are named like in the spec, e.g. "in body"
+    fn insertion_mode_changed(&mut self, _from: &str, _to: &str) {}
+
+    // an element was pushed onto the stack of open elements, depth is the size of the stack afterwards
+    fn element_pushed(&mut self, _element: &Element, _depth: usize) {}
+
+    // an element was popped off the stack of open elements, depth is the size of the stack before
+    fn element_popped(&mut self, _element: &Element, _depth: usize) {}
+
+    fn parse_error(&mut self, _error: &ParseError) {}
+}
+
+// the default observer of the tree builder, which observes nothing
+impl ParseObserver for () {}
+
+// Writes a readable line per callback, the pushed and popped elements are indented by the depth of the stack of open elements:
+// token  StartTag(Tag { name: "p", self_closing: false, attributes: [] })
+// mode   "in body" -> "text"
+// push     <p>
+// error  (1,3): unexpected-null-character
+pub struct ParseTrace<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> ParseTrace<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    // a trace is for debugging, so a failing writer does not stop the parser
+    fn write_line(&mut self, kind: &str, line: std::fmt::Arguments) {
+        let _ = writeln!(self.writer, "{kind:<6} {line}");
+    }
+}
+
+fn element_name(element: &Element) -> String {
+    match element.namespace {
+        Namespace::Html => format!("<{}>", element.local_name),
+        Namespace::Svg => format!("<svg {}>", element.local_name),
+        Namespace::MathMl => format!("<math {}>", element.local_name),
+        _ => format!("<{:?} {}>", element.namespace, element.local_name),
+    }
+}
+
+impl<W: Write> ParseObserver for ParseTrace<W> {
+    fn token_emitted(&mut self, token: &Token) {
+        self.write_line("token", format_args!("{token:?}"));
+    }
+
+    fn insertion_mode_changed(&mut self, from: &str, to: &str) {
+        self.write_line("mode", format_args!("{from:?} -> {to:?}"));
+    }
+
+    fn element_pushed(&mut self, element: &Element, depth: usize) {
+        let indent = "  ".repeat(depth - 1);
+        self.write_line("push", format_args!("{indent}{}", element_name(element)));
+    }
+
+    fn element_popped(&mut self, element: &Element, depth: usize) {
+        let indent = "  ".repeat(depth - 1);
+        self.write_line("pop", format_args!("{indent}{}", element_name(element)));
+    }
+
+    fn parse_error(&mut self, error: &ParseError) {
+        self.write_line(
+            "error",
+            format_args!("({},{}): {}", error.line, error.column, error.code),
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::html::{
+        tokenizer::Tokenizer,
+        tree_builder::{ParserOptions, TreeBuilder},
+    };
+
+    #[test]
+    fn parse_trace() {
+        let mut tree_builder =
+            TreeBuilder::with_observer(ParserOptions::default(), ParseTrace::new(Vec::new()));
+        tree_builder.build(Tokenizer::new("<p>a</br>"));
+        let trace = String::from_utf8(tree_builder.observer().get_ref().clone()).unwrap();
+        let lines: Vec<&str> = trace.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                r#"token  StartTag(Tag { name: "p", self_closing: false, attributes: [] })"#,
+                "error  (1,3): expected-doctype-but-got-start-tag",
+                r#"mode   "initial" -> "before html""#,
+                "push   <html>",
+                r#"mode   "before html" -> "before head""#,
+                "push     <head>",
+                r#"mode   "before head" -> "in head""#,
+                "pop      <head>",
+                r#"mode   "in head" -> "after head""#,
+                "push     <body>",
+                r#"mode   "after head" -> "in body""#,
+                "push       <p>",
+                r#"token  Characters("a")"#,
+                r#"token  EndTag(Tag { name: "br", self_closing: false, attributes: [] })"#,
+                "error  (1,9): in-body-unexpected-end-br-tag",
+                "push         <br>",
+                "pop          <br>",
+                "token  EndOfFile",
+                // the end pops all the nodes off the stack of open elements
+                "pop        <p>",
+                "pop      <body>",
+                "pop    <html>",
+            ]
+        );
+    }
+
+    #[derive(Default)]
+    struct Counter {
+        tokens: usize,
+        pushed: usize,
+        popped: usize,
+        errors: Vec<String>,
+    }
+
+    impl ParseObserver for Counter {
+        fn token_emitted(&mut self, _token: &Token) {
+            self.tokens += 1;
+        }
+
+        fn element_pushed(&mut self, _element: &Element, _depth: usize) {
+            self.pushed += 1;
+        }
+
+        fn element_popped(&mut self, _element: &Element, _depth: usize) {
+            self.popped += 1;
+        }
+
+        fn parse_error(&mut self, error: &ParseError) {
+            self.errors.push(error.code.clone());
+        }
+    }
+
+    #[test]
+    fn custom_observer() {
+        let mut tree_builder =
+            TreeBuilder::with_observer(ParserOptions::default(), Counter::default());
+        tree_builder.build(Tokenizer::new("<!DOCTYPE html><div><span>a</span></div>"));
+        let counter = tree_builder.observer();
+        assert_eq!(counter.tokens, 7);
+        // html, head, body, div, span
+        assert_eq!(counter.pushed, 5);
+        // head, span, div, and body and html at the end
+        assert_eq!(counter.popped, 5);
+        assert_eq!(
+            counter.errors,
+            tree_builder
+                .get_parse_errors()
+                .iter()
+                .map(|e| e.code.clone())
+                .collect::<Vec<_>>()
+        );
+    }
+}
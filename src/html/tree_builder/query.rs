@@ -0,0 +1,222 @@
+use crate::html::tree_builder::tree::{Element, Namespace, Node, Tree};
+
+impl Tree<Node> {
+    pub fn get_element(&self, index: usize) -> Option<&Element> {
+        match &self.get_node(index).data {
+            Node::Element(element) => Some(element),
+            _ => None,
+        }
+    }
+
+    // the elements of the document in tree order
+    fn elements(&self) -> impl Iterator<Item = (usize, &Element)> {
+        self.get_root()
+            .into_iter()
+            .flat_map(|root| self.descendants(root))
+            .filter_map(|(index, _)| Some((index, self.get_element(index)?)))
+    }
+
+    // https://dom.spec.whatwg.org/#dom-nonelementparentnode-getelementbyid
+    // The getElementById(elementId) method steps are to return the first element, in tree order, within this's descendants, whose ID is elementId; otherwise, if there is no such element, null.
+    pub fn get_element_by_id(&self, element_id: &str) -> Option<usize> {
+        self.elements()
+            .find(|(_, element)| element.id() == Some(element_id))
+            .map(|(index, _)| index)
+    }
+
+    // https://dom.spec.whatwg.org/#concept-getelementsbytagname
+    pub fn get_elements_by_tag_name(&self, qualified_name: &str) -> Vec<usize> {
+        let lowercase_name = qualified_name.to_ascii_lowercase();
+        self.elements()
+            .filter(|(_, element)| {
+                // 1. If qualifiedName is "*" (U+002A), return a HTMLCollection rooted at root, whose filter matches only descendant elements.
+                qualified_name == "*"
+                    // 2. Otherwise, if root's node document is an HTML document, return a HTMLCollection rooted at root, whose filter matches the following descendant elements:
+                    // Whose namespace is the HTML namespace and whose qualified name is qualifiedName, in ASCII lowercase.
+                    || (element.namespace == Namespace::Html && element.local_name == lowercase_name)
+                    // Whose namespace is not the HTML namespace and whose qualified name is qualifiedName.
+                    || (element.namespace != Namespace::Html && element.local_name == qualified_name)
+            })
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    // https://dom.spec.whatwg.org/#concept-getelementsbyclassname
+    pub fn get_elements_by_class_name(&self, class_names: &str) -> Vec<usize> {
+        // 1. Let classes be the result of running the ordered set parser on classNames.
+        let classes: Vec<&str> = class_names.split_ascii_whitespace().collect();
+        // 2. If classes is the empty set, return an empty HTMLCollection.
+        if classes.is_empty() {
+            return Vec::new();
+        }
+        // 3. Return a HTMLCollection rooted at root, whose filter matches descendant elements that have all their classes in classes.
+        // todo compare ASCII case-insensitively in quirks mode
+        self.elements()
+            .filter(|(_, element)| {
+                classes
+                    .iter()
+                    .all(|class| element.class_list().any(|item| item == *class))
+            })
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    // https://dom.spec.whatwg.org/#dom-parentnode-children
+    // the children of the node that are elements
+    pub fn children_elements(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        self.get_node(index)
+            .children
+            .iter()
+            .copied()
+            .filter(|child| self.get_element(*child).is_some())
+    }
+
+    // https://dom.spec.whatwg.org/#dom-node-textcontent
+    pub fn text_content(&self, index: usize) -> Option<String> {
+        match &self.get_node(index).data {
+            // Document, DocumentType: Null.
+            Node::Document(_) | Node::DocumentType(_) => None,
+            // DocumentFragment, Element: The descendant text content of this.
+            Node::DocumentFragment | Node::Element(_) => Some(
+                self.descendants(index)
+                    .filter_map(|(descendant, _)| match &self.get_node(descendant).data {
+                        Node::Text(text) => Some(text.data.as_str()),
+                        _ => None,
+                    })
+                    .collect(),
+            ),
+            // CharacterData: This's data.
+            Node::Text(text) => Some(text.data.clone()),
+            Node::Comment(comment) => Some(comment.data.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::html::tokenizer::Tokenizer;
+    use crate::html::tree_builder::tree::{Node, Tree};
+    use crate::html::tree_builder::{ParserOptions, TreeBuilder};
+
+    fn parse(contents: &str) -> TreeBuilder {
+        let mut tree_builder = TreeBuilder::new(ParserOptions::default());
+        tree_builder.build(Tokenizer::new(contents));
+        tree_builder
+    }
+
+    fn local_names(tree: &Tree<Node>, nodes: impl IntoIterator<Item = usize>) -> Vec<String> {
+        nodes
+            .into_iter()
+            .map(|node| tree.get_element(node).unwrap().local_name.to_string())
+            .collect()
+    }
+
+    const CONTENTS: &str = "<div id=main class='a b'><p id=first class=b>one<span>two</span></p><!--x--><P class=a>three</P><svg><foreignobject/><path/></svg></div><p id=main>";
+
+    #[test]
+    fn get_element_by_id() {
+        let tree_builder = parse(CONTENTS);
+        let tree = tree_builder.get_tree();
+        let main = tree.get_element_by_id("main").unwrap();
+        assert_eq!(local_names(tree, [main]), ["div"]);
+        assert_eq!(local_names(tree, tree.get_element_by_id("first")), ["p"]);
+        assert_eq!(tree.get_element_by_id("missing"), None);
+        assert_eq!(tree.get_element_by_id(""), None);
+    }
+
+    #[test]
+    fn get_elements_by_tag_name() {
+        let tree_builder = parse(CONTENTS);
+        let tree = tree_builder.get_tree();
+        assert_eq!(tree.get_elements_by_tag_name("p").len(), 3);
+        assert_eq!(tree.get_elements_by_tag_name("P").len(), 3);
+        // the tag name of foreign elements is case-sensitive
+        assert_eq!(tree.get_elements_by_tag_name("foreignObject").len(), 1);
+        assert_eq!(tree.get_elements_by_tag_name("foreignobject").len(), 0);
+        assert_eq!(
+            local_names(tree, tree.get_elements_by_tag_name("*")),
+            [
+                "html",
+                "head",
+                "body",
+                "div",
+                "p",
+                "span",
+                "p",
+                "svg",
+                "foreignObject",
+                "path",
+                "p"
+            ]
+        );
+    }
+
+    #[test]
+    fn get_elements_by_class_name() {
+        let tree_builder = parse(CONTENTS);
+        let tree = tree_builder.get_tree();
+        assert_eq!(
+            local_names(tree, tree.get_elements_by_class_name("a")),
+            ["div", "p"]
+        );
+        assert_eq!(
+            local_names(tree, tree.get_elements_by_class_name("b")),
+            ["div", "p"]
+        );
+        assert_eq!(
+            local_names(tree, tree.get_elements_by_class_name(" b  a ")),
+            ["div"]
+        );
+        assert!(tree.get_elements_by_class_name(" ").is_empty());
+    }
+
+    #[test]
+    fn traversal() {
+        let tree_builder = parse(CONTENTS);
+        let tree = tree_builder.get_tree();
+        let main = tree.get_element_by_id("main").unwrap();
+        assert_eq!(
+            local_names(tree, tree.children_elements(main)),
+            ["p", "p", "svg"]
+        );
+        assert_eq!(
+            local_names(
+                tree,
+                tree.ancestors(main)
+                    .filter(|node| tree.get_element(*node).is_some())
+            ),
+            ["body", "html"]
+        );
+        let descendants: Vec<(String, usize)> = tree
+            .descendants(main)
+            .map(|(node, depth)| (format!("{:?}", tree.get_node(node).data), depth))
+            .collect();
+        let expected = [
+            ("<p>", 1),
+            ("\"one\"", 2),
+            ("<span>", 2),
+            ("\"two\"", 3),
+            ("<!-- x -->", 1),
+            ("<p>", 1),
+            ("\"three\"", 2),
+            ("<svg svg>", 1),
+            ("<svg foreignObject>", 2),
+            ("<svg path>", 2),
+        ];
+        assert_eq!(
+            descendants,
+            expected.map(|(node, depth)| (node.to_string(), depth))
+        );
+    }
+
+    #[test]
+    fn text_content() {
+        let tree_builder = parse(CONTENTS);
+        let tree = tree_builder.get_tree();
+        let main = tree.get_element_by_id("main").unwrap();
+        assert_eq!(tree.text_content(main).as_deref(), Some("onetwothree"));
+        assert_eq!(tree.text_content(tree.get_root().unwrap()), None);
+        let comment = tree.get_node(main).children[1];
+        assert_eq!(tree.text_content(comment).as_deref(), Some("x"));
+    }
+}
@@ -0,0 +1,336 @@
+use crate::atom;
+use crate::html::atom::Atom;
+use crate::html::tree_builder::tree::{Attribute, Element, Namespace, Node, Tree};
+
+// https://html.spec.whatwg.org/multipage/parsing.html#serializes-as-void
+// the elements that have no end tag and no children in the serialization
+const VOID_ELEMENTS: [Atom; 18] = [
+    atom!("area"),
+    atom!("base"),
+    atom!("basefont"),
+    atom!("bgsound"),
+    atom!("br"),
+    atom!("col"),
+    atom!("embed"),
+    atom!("frame"),
+    atom!("hr"),
+    atom!("img"),
+    atom!("input"),
+    atom!("keygen"),
+    atom!("link"),
+    atom!("meta"),
+    atom!("param"),
+    atom!("source"),
+    atom!("track"),
+    atom!("wbr"),
+];
+
+impl Tree<Node> {
+    // https://html.spec.whatwg.org/multipage/dynamic-markup-insertion.html#dom-element-outerhtml
+    // the node itself and its descendants
+    pub fn outer_html(&self, node: usize) -> String {
+        let mut s = String::new();
+        self.serialize_node(node, &mut s);
+        s
+    }
+
+    // https://html.spec.whatwg.org/multipage/dynamic-markup-insertion.html#dom-element-innerhtml
+    // the descendants of the node
+    pub fn inner_html(&self, node: usize) -> String {
+        let mut s = String::new();
+        self.serialize_children(node, &mut s);
+        s
+    }
+
+    // the whole document
+    pub fn to_html(&self) -> String {
+        self.get_root()
+            .map(|root| self.inner_html(root))
+            .unwrap_or_default()
+    }
+
+    // https://html.spec.whatwg.org/multipage/parsing.html#html-fragment-serialisation-algorithm
+    fn serialize_children(&self, node: usize, s: &mut String) {
+        // 1. If the node serializes as void, then return the empty string.
+        if let Some(element) = self.get_element(node)
+            && serializes_as_void(element)
+        {
+            return;
+        }
+        // 2. Let s be a string, and initialize it to the empty string.
+        // 3. If the node is a template element, then let the node instead be the template element's template contents (a DocumentFragment node).
+        let node = self
+            .get_element(node)
+            .and_then(|element| element.template_contents)
+            .unwrap_or(node);
+        // 4. If current node is a pre, textarea, or listing element, and the first child node of the element, if any, is a Text node whose character data has as its first character a U+000A LINE FEED (LF) character, then append a U+000A LINE FEED (LF) character.
+        // this step was removed from the spec, without it the line feed would be lost when the markup is parsed again
+        if let Some(element) = self.get_element(node)
+            && element.is_html(&[atom!("pre"), atom!("textarea"), atom!("listing")])
+            && let Some(first_child) = self.get_node(node).children.first()
+            && let Node::Text(text) = &self.get_node(*first_child).data
+            && text.data.starts_with('\n')
+        {
+            s.push('\n');
+        }
+        // 5. For each child node of the node, in tree order, run the following steps:
+        for child in &self.get_node(node).children {
+            self.serialize_node(*child, s);
+        }
+        // 6. Return s.
+    }
+
+    // the steps for each child node of the html fragment serialisation algorithm
+    fn serialize_node(&self, current_node: usize, s: &mut String) {
+        // 1. Let current node be the child node being processed.
+        // 2. Append the appropriate string from the following list to s:
+        match &self.get_node(current_node).data {
+            // If current node is an Element
+            Node::Element(element) => {
+                // If current node is an element in the HTML namespace, the MathML namespace, or the SVG namespace, then let tagname be current node's local name. Otherwise, let tagname be current node's qualified name.
+                let tagname = &element.local_name;
+                // Append a U+003C LESS-THAN SIGN character (<), followed by tagname.
+                s.push('<');
+                s.push_str(tagname);
+                serialize_attributes(element, s);
+                // Foreign elements without children are written with the self-closing flag, which the parser acknowledges in foreign content.
+                if element.namespace != Namespace::Html
+                    && self.get_node(current_node).children.is_empty()
+                {
+                    s.push_str("/>");
+                    return;
+                }
+                // Append a U+003E GREATER-THAN SIGN character (>).
+                s.push('>');
+                // If current node serializes as void, then continue on to the next child node at this point.
+                if serializes_as_void(element) {
+                    return;
+                }
+                // Append the value of running the HTML fragment serialization algorithm on the current node element (thus recursing into this algorithm for that element), followed by a U+003C LESS-THAN SIGN character (<), a U+002F SOLIDUS character (/), tagname again, and finally a U+003E GREATER-THAN SIGN character (>).
+                self.serialize_children(current_node, s);
+                s.push_str("</");
+                s.push_str(tagname);
+                s.push('>');
+            }
+            // If current node is a Text node
+            Node::Text(text) => {
+                // If the parent of current node is a style, script, xmp, iframe, noembed, noframes, or plaintext element, or if the parent of current node is a noscript element and scripting is enabled for the node, then append the value of current node's data IDL attribute literally.
+                // todo the tree doesn't know whether scripting is enabled, noscript is serialized like scripting is disabled
+                let is_raw_text = self
+                    .parent(current_node)
+                    .and_then(|parent| self.get_element(parent))
+                    .is_some_and(|parent| {
+                        parent.is_html(&[
+                            atom!("style"),
+                            atom!("script"),
+                            atom!("xmp"),
+                            atom!("iframe"),
+                            atom!("noembed"),
+                            atom!("noframes"),
+                            atom!("plaintext"),
+                        ])
+                    });
+                if is_raw_text {
+                    s.push_str(&text.data);
+                } else {
+                    // Otherwise, append the value of current node's data IDL attribute, escaped as described below.
+                    escape_a_string(&text.data, false, s);
+                }
+            }
+            // If current node is a Comment
+            Node::Comment(comment) => {
+                // Append the literal string "<!--" (U+003C LESS-THAN SIGN, U+0021 EXCLAMATION MARK, U+002D HYPHEN-MINUS, U+002D HYPHEN-MINUS), followed by the value of current node's data IDL attribute, followed by the literal string "-->" (U+002D HYPHEN-MINUS, U+002D HYPHEN-MINUS, U+003E GREATER-THAN SIGN).
+                s.push_str("<!--");
+                s.push_str(&comment.data);
+                s.push_str("-->");
+            }
+            // If current node is a DocumentType
+            Node::DocumentType(doctype) => {
+                // Append the literal string "<!DOCTYPE" (U+003C LESS-THAN SIGN, U+0021 EXCLAMATION MARK, U+0044 LATIN CAPITAL LETTER D, U+004F LATIN CAPITAL LETTER O, U+0043 LATIN CAPITAL LETTER C, U+0054 LATIN CAPITAL LETTER T, U+0059 LATIN CAPITAL LETTER Y, U+0050 LATIN CAPITAL LETTER P, U+0045 LATIN CAPITAL LETTER E), followed by a space (U+0020 SPACE), followed by the value of current node's name IDL attribute, followed by the literal string ">" (U+003E GREATER-THAN SIGN).
+                s.push_str("<!DOCTYPE ");
+                s.push_str(&doctype.name);
+                s.push('>');
+            }
+            // the document and the template contents are only serialized through their children
+            Node::Document(_) | Node::DocumentFragment => self.serialize_children(current_node, s),
+        }
+    }
+}
+
+// the attributes of the start tag, each with a leading space
+pub(crate) fn serialize_attributes(element: &Element, s: &mut String) {
+    // For each attribute that the element has, append a U+0020 SPACE character, the attribute's serialized name as described below, a U+003D EQUALS SIGN character (=), a U+0022 QUOTATION MARK character ("), the attribute's value, escaped as described below in attribute mode, and a second U+0022 QUOTATION MARK character (").
+    for attribute in &element.attributes {
+        s.push(' ');
+        serialize_an_attribute_name(attribute, s);
+        s.push_str("=\"");
+        escape_a_string(&attribute.value, true, s);
+        s.push('"');
+    }
+}
+
+// https://html.spec.whatwg.org/multipage/parsing.html#attribute's-serialised-name
+pub(crate) fn serialize_an_attribute_name(attribute: &Attribute, s: &mut String) {
+    // An attribute's serialized name for the purposes of the previous paragraph must be determined as follows:
+    match attribute.namespace {
+        // If the attribute has no namespace: The attribute's serialized name is the attribute's local name.
+        None => {}
+        // If the attribute is in the XML namespace: The attribute's serialized name is the string "xml:" followed by the attribute's local name.
+        Some(Namespace::Xml) => s.push_str("xml:"),
+        // If the attribute is in the XMLNS namespace and the attribute's local name is xmlns: The attribute's serialized name is the string "xmlns".
+        Some(Namespace::Xmlns) if attribute.name == atom!("xmlns") => {}
+        // If the attribute is in the XMLNS namespace and the attribute's local name is not xmlns: The attribute's serialized name is the string "xmlns:" followed by the attribute's local name.
+        Some(Namespace::Xmlns) => s.push_str("xmlns:"),
+        // If the attribute is in the XLink namespace: The attribute's serialized name is the string "xlink:" followed by the attribute's local name.
+        Some(Namespace::XLink) => s.push_str("xlink:"),
+        // If the attribute is in some other namespace: The attribute's serialized name is the attribute's qualified name.
+        Some(_) => attribute.prefix.iter().for_each(|prefix| {
+            s.push_str(prefix);
+            s.push(':');
+        }),
+    }
+    s.push_str(&attribute.name);
+}
+
+// https://html.spec.whatwg.org/multipage/parsing.html#serializes-as-void
+// For the purposes of the following algorithm, an element serializes as void if its element type is one of the void elements, or is basefont, bgsound, frame, keygen, or param.
+pub(crate) fn serializes_as_void(element: &Element) -> bool {
+    element.is_html(&VOID_ELEMENTS)
+}
+
+// https://html.spec.whatwg.org/multipage/parsing.html#escapingString
+pub(crate) fn escape_a_string(string: &str, attribute_mode: bool, s: &mut String) {
+    for c in string.chars() {
+        match c {
+            // 1. Replace any occurrence of the "&" character by the string "&amp;".
+            '&' => s.push_str("&amp;"),
+            // 2. Replace any occurrences of the U+00A0 NO-BREAK SPACE character by the string "&nbsp;".
+            '\u{00A0}' => s.push_str("&nbsp;"),
+            // 3. Replace any occurrences of the "<" character by the string "&lt;".
+            '<' => s.push_str("&lt;"),
+            // 4. Replace any occurrences of the ">" character by the string "&gt;".
+            '>' => s.push_str("&gt;"),
+            // 5. If the algorithm was invoked in the attribute mode, then replace any occurrences of the """ character by the string "&quot;".
+            '"' if attribute_mode => s.push_str("&quot;"),
+            _ => s.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs::File;
+
+    use crate::html::tokenizer::Tokenizer;
+    use crate::html::tree_builder::tester::TestReader;
+    use crate::html::tree_builder::{ParserOptions, TreeBuilder};
+
+    fn parse(contents: &str) -> TreeBuilder {
+        let mut tree_builder = TreeBuilder::new(ParserOptions::default());
+        tree_builder.build(Tokenizer::new(contents));
+        tree_builder
+    }
+
+    #[test]
+    fn serialize() {
+        let tree_builder = parse(
+            "<p class='a\"&amp;'>x &lt; y&nbsp;<br><img src=a><!--c--></p><script>if (a < b) {}</script><pre>\n\ntext</pre><svg><path d=M0></path><g><rect/></g></svg>",
+        );
+        let tree = tree_builder.get_tree();
+        assert_eq!(
+            tree.to_html(),
+            "<html><head></head><body><p class=\"a&quot;&amp;\">x &lt; y&nbsp;<br><img src=\"a\"><!--c--></p><script>if (a < b) {}</script><pre>\n\ntext</pre><svg><path d=\"M0\"/><g><rect/></g></svg></body></html>"
+        );
+        let p = tree.get_elements_by_tag_name("p")[0];
+        assert_eq!(
+            tree.inner_html(p),
+            "x &lt; y&nbsp;<br><img src=\"a\"><!--c-->"
+        );
+        let br = tree.get_elements_by_tag_name("br")[0];
+        assert_eq!(tree.outer_html(br), "<br>");
+        assert_eq!(tree.inner_html(br), "");
+    }
+
+    #[test]
+    fn serialize_text_elements() {
+        // the leading line feed of pre and textarea survives, the contents of xmp and iframe are not escaped
+        let input =
+            "<textarea>\n\nx</textarea><pre>\n\ny</pre><xmp><p></</xmp><iframe><b>&amp;</iframe>";
+        let html = parse(input).get_tree().to_html();
+        assert_eq!(
+            html,
+            "<html><head></head><body><textarea>\n\nx</textarea><pre>\n\ny</pre><xmp><p></</xmp><iframe><b>&amp;</iframe></body></html>"
+        );
+        assert_eq!(parse(&html).get_tree().to_html(), html);
+    }
+
+    #[test]
+    fn serialize_doctype_and_template_contents() {
+        let tree_builder = parse("<!DOCTYPE html><template><p>a</template>");
+        let tree = tree_builder.get_tree();
+        assert_eq!(
+            tree.to_html(),
+            "<!DOCTYPE html><html><head><template><p>a</p></template></head><body></body></html>"
+        );
+        // the children are inserted into the template contents, not into the template element
+        let template = tree.get_elements_by_tag_name("template")[0];
+        assert!(tree.get_node(template).children.is_empty());
+        assert_eq!(tree.inner_html(template), "<p>a</p>");
+    }
+
+    #[test]
+    fn serialize_foreign_attributes() {
+        let tree_builder = parse(
+            "<svg xmlns:xlink=x xlink:href=y xml:lang=z><foreignObject><p>a</p></foreignObject></svg>",
+        );
+        let tree = tree_builder.get_tree();
+        let svg = tree.get_elements_by_tag_name("svg")[0];
+        assert_eq!(
+            tree.outer_html(svg),
+            "<svg xmlns:xlink=\"x\" xlink:href=\"y\" xml:lang=\"z\"><foreignObject><p>a</p></foreignObject></svg>"
+        );
+    }
+
+    // parsing the serialization of a tree gives the same tree, for the inputs of the tree construction tests
+    #[test]
+    fn round_trip() {
+        for file_path in [
+            "html-tests/body.dat",
+            "html-tests/character_references.dat",
+            "html-tests/comments.dat",
+            "html-tests/doctype.dat",
+            "html-tests/foreign.dat",
+            "html-tests/fragment.dat",
+            "html-tests/head.dat",
+            "html-tests/scripting.dat",
+            "html-tests/test1.dat",
+            "html-tests/whitespace.dat",
+        ] {
+            let test_reader = TestReader::<File>::new_from_file(file_path).unwrap();
+            for test_case in test_reader {
+                let contents = test_case.data.join("\n");
+                // the serialization of noscript depends on the scripting flag
+                let serialize = |contents: &str| {
+                    let mut tree_builder = TreeBuilder::new(ParserOptions::default());
+                    match test_case.context_element() {
+                        Some(context_element) => {
+                            let nodes = tree_builder.parse_fragment(context_element, contents);
+                            let tree = tree_builder.get_tree();
+                            nodes.iter().map(|node| tree.outer_html(*node)).collect()
+                        }
+                        None => {
+                            tree_builder.build(Tokenizer::new(contents));
+                            tree_builder.get_tree().to_html()
+                        }
+                    }
+                };
+                let serialized = serialize(&contents);
+                assert_eq!(
+                    serialize(&serialized),
+                    serialized,
+                    "{file_path}: {contents:?}"
+                );
+            }
+        }
+    }
+}
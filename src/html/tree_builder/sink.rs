@@ -0,0 +1,295 @@
+use crate::atom;
+use crate::html::tokenizer::Attribute;
+use crate::html::tree_builder::{
+    ParseError,
+    tree::{Comment, Document, DocumentType, Element, Namespace, Node, QuirksMode, Text, Tree},
+};
+
+// The tree construction builds its nodes through a sink, so it can target any document model, or no tree at all.
+// The sink hands out the ids of the nodes it creates, the tree builder only passes them back.
+pub trait TreeSink {
+    // creates the Document node, called once when the tree builder is created
+    fn create_document(&mut self) -> usize;
+
+    // creates an element that is not inserted yet
+    fn create_element(&mut self, element: Element) -> usize;
+
+    // creates a Comment node that is not inserted yet
+    fn create_comment(&mut self, data: String) -> usize;
+
+    // The element of a node created by create_element.
+    // The tree builder looks at the context element and the elements on the stack of open elements, so a sink keeps at least those.
+    fn element(&self, node: usize) -> &Element;
+
+    // appends the child as the last child of the parent
+    fn append(&mut self, parent: usize, child: usize);
+
+    // inserts characters as the last child of the parent, they are appended to the data of the last child if it is a Text node
+    // https://html.spec.whatwg.org/multipage/parsing.html#insert-a-character
+    fn append_text(&mut self, parent: usize, data: &str);
+
+    // inserts the child right before the reference child, which is a child of the parent
+    fn insert_before(&mut self, parent: usize, child: usize, reference_child: usize);
+
+    // moves all children of from, in order, to the end of the children of to
+    fn reparent_children(&mut self, from: usize, to: usize);
+
+    // removes the node from its parent, if it has one
+    fn remove_from_parent(&mut self, node: usize);
+
+    // the element was popped off the stack of open elements
+    // the tree builder may still come back to it through the head element pointer, but not to its children
+    fn pop(&mut self, _node: usize) {}
+
+    // adds the attributes the element doesn't have yet, for an html or body start tag in body
+    // https://html.spec.whatwg.org/multipage/parsing.html#parsing-main-inbody
+    fn add_attributes_if_missing(&mut self, _node: usize, _attributes: Vec<Attribute<'static>>) {}
+
+    // appends a DocumentType node to the document
+    // https://html.spec.whatwg.org/multipage/parsing.html#the-initial-insertion-mode
+    fn append_doctype(&mut self, _name: &str, _public_id: &str, _system_id: &str) {}
+
+    // the node the children of a template element are inserted into
+    // https://html.spec.whatwg.org/multipage/scripting.html#template-contents
+    fn get_template_contents(&self, template: usize) -> usize;
+
+    // https://dom.spec.whatwg.org/#concept-document-mode
+    fn set_quirks_mode(&mut self, _mode: QuirksMode) {}
+
+    // the tree builder records the parse errors itself, a sink only gets them if it is interested
+    fn parse_error(&mut self, _error: &ParseError) {}
+}
+
+// the arena tree the tree builder builds by default
+impl TreeSink for Tree<Node> {
+    fn create_document(&mut self) -> usize {
+        let document = self.create_node(Node::Document(Document::new()));
+        self.set_root(document);
+        document
+    }
+
+    fn create_element(&mut self, mut element: Element) -> usize {
+        // https://html.spec.whatwg.org/multipage/scripting.html#template-contents
+        // When a template element is created, the user agent must run the following steps to establish the template contents:
+        // Let doc be the template element's node document's appropriate template contents owner document.
+        // Create a DocumentFragment object whose node document is doc and host is the template element.
+        // Set the template element's template contents to the newly created DocumentFragment object.
+        if element.is(Namespace::Html, atom!("template")) {
+            element.template_contents = Some(self.create_node(Node::DocumentFragment));
+        }
+        self.create_node(Node::Element(element))
+    }
+
+    fn create_comment(&mut self, data: String) -> usize {
+        self.create_node(Node::Comment(Comment::new(data)))
+    }
+
+    fn element(&self, node: usize) -> &Element {
+        match &self.get_node(node).data {
+            Node::Element(element) => element,
+            node => panic!("expected an element, got {:?}", node),
+        }
+    }
+
+    fn append(&mut self, parent: usize, child: usize) {
+        self.add_child(parent, child);
+    }
+
+    fn append_text(&mut self, parent: usize, data: &str) {
+        if let Some(&last_child) = self.get_node(parent).children.last()
+            && let Node::Text(text) = &mut self.get_node_mut(last_child).data
+        {
+            text.data.push_str(data);
+            return;
+        }
+        let text = self.create_node(Node::Text(Text::new(data.to_string())));
+        self.add_child(parent, text);
+    }
+
+    fn insert_before(&mut self, parent: usize, child: usize, reference_child: usize) {
+        Tree::insert_before(self, parent, child, Some(reference_child));
+    }
+
+    fn reparent_children(&mut self, from: usize, to: usize) {
+        self.move_children(from, to);
+    }
+
+    fn remove_from_parent(&mut self, node: usize) {
+        self.detach(node);
+    }
+
+    fn add_attributes_if_missing(&mut self, node: usize, attributes: Vec<Attribute<'static>>) {
+        let Node::Element(element) = &mut self.get_node_mut(node).data else {
+            panic!("expected an element");
+        };
+        for attribute in attributes {
+            if element.get_attribute(&attribute.name).is_none() {
+                element.attributes.push(attribute);
+            }
+        }
+    }
+
+    fn append_doctype(&mut self, name: &str, public_id: &str, system_id: &str) {
+        if let Some(root) = self.get_root() {
+            let doctype = self.create_node(Node::DocumentType(DocumentType::new(
+                name.to_string(),
+                public_id.to_string(),
+                system_id.to_string(),
+            )));
+            self.add_child(root, doctype);
+        }
+    }
+
+    fn get_template_contents(&self, template: usize) -> usize {
+        self.element(template)
+            .template_contents
+            .expect("a template element created by the sink has template contents")
+    }
+
+    fn set_quirks_mode(&mut self, mode: QuirksMode) {
+        if let Some(root) = self.get_root()
+            && let Node::Document(document) = &mut self.get_node_mut(root).data
+        {
+            document.mode = mode;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::html::{
+        tokenizer::Tokenizer,
+        tree_builder::{ParserOptions, TreeBuilder},
+    };
+
+    // a sink without a tree, it only logs what is built
+    #[derive(Default)]
+    struct Log {
+        elements: Vec<Option<Element>>,
+        lines: Vec<String>,
+    }
+
+    impl Log {
+        fn name(&self, node: usize) -> &str {
+            match &self.elements[node] {
+                Some(element) => &element.local_name,
+                None => "#node",
+            }
+        }
+
+        fn create(&mut self, element: Option<Element>) -> usize {
+            self.elements.push(element);
+            self.elements.len() - 1
+        }
+    }
+
+    impl TreeSink for Log {
+        fn create_document(&mut self) -> usize {
+            self.create(None)
+        }
+
+        fn create_element(&mut self, element: Element) -> usize {
+            self.create(Some(element))
+        }
+
+        fn create_comment(&mut self, data: String) -> usize {
+            self.lines.push(format!("comment {data}"));
+            self.create(None)
+        }
+
+        fn element(&self, node: usize) -> &Element {
+            self.elements[node].as_ref().unwrap()
+        }
+
+        fn append(&mut self, parent: usize, child: usize) {
+            let line = format!("append {} {}", self.name(parent), self.name(child));
+            self.lines.push(line);
+        }
+
+        fn append_text(&mut self, parent: usize, data: &str) {
+            let line = format!("text {} {data:?}", self.name(parent));
+            self.lines.push(line);
+        }
+
+        fn insert_before(&mut self, _parent: usize, _child: usize, _reference_child: usize) {}
+
+        fn reparent_children(&mut self, _from: usize, _to: usize) {}
+
+        fn remove_from_parent(&mut self, _node: usize) {}
+
+        fn get_template_contents(&self, template: usize) -> usize {
+            template
+        }
+
+        fn set_quirks_mode(&mut self, mode: QuirksMode) {
+            self.lines.push(format!("mode {mode:?}"));
+        }
+    }
+
+    #[test]
+    fn custom_sink() {
+        let mut tree_builder = TreeBuilder::with_sink(ParserOptions::default(), Log::default());
+        tree_builder.build(Tokenizer::new("<title>a</title><p>b<!--c-->d"));
+        assert_eq!(
+            tree_builder.sink().lines,
+            [
+                "mode Quirks",
+                "append #node html",
+                "append html head",
+                "append head title",
+                r#"text title "a""#,
+                "append html body",
+                "append body p",
+                r#"text p "b""#,
+                "comment c",
+                "append p #node",
+                r#"text p "d""#,
+            ]
+        );
+    }
+
+    #[test]
+    fn quirks_mode() {
+        for (input, mode) in [
+            ("<!DOCTYPE html><p>", QuirksMode::NoQuirks),
+            ("<!DOCTYPE svg><p>", QuirksMode::Quirks),
+            (
+                "<!DOCTYPE html SYSTEM 'about:legacy-compat'>",
+                QuirksMode::NoQuirks,
+            ),
+            (
+                "<!DOCTYPE html PUBLIC \"-//W3C//DTD HTML 4.01//EN\" \"http://www.w3.org/TR/html4/strict.dtd\">",
+                QuirksMode::NoQuirks,
+            ),
+            (
+                "<!DOCTYPE html PUBLIC \"-//W3C//DTD HTML 4.01 Transitional//EN\">",
+                QuirksMode::Quirks,
+            ),
+            (
+                "<!DOCTYPE html PUBLIC \"-//W3C//DTD HTML 4.01 Transitional//EN\" \"\">",
+                QuirksMode::LimitedQuirks,
+            ),
+            (
+                "<!doctype html public '-//w3c//dtd xhtml 1.0 frameset//en'>",
+                QuirksMode::LimitedQuirks,
+            ),
+            (
+                "<!DOCTYPE html PUBLIC \"-//IETF//DTD HTML//EN\">",
+                QuirksMode::Quirks,
+            ),
+            ("<!DOCTYPE html PUBLIC \"HTML\">", QuirksMode::Quirks),
+            ("<!DOCTYPE html SYSTEM>", QuirksMode::Quirks),
+            ("<!DOCTYPE html", QuirksMode::Quirks),
+            ("<p>", QuirksMode::Quirks),
+        ] {
+            let mut tree_builder = TreeBuilder::new(ParserOptions::default());
+            tree_builder.build(Tokenizer::new(input));
+            let tree = tree_builder.get_tree();
+            let Node::Document(document) = &tree.get_node(tree.get_root().unwrap()).data else {
+                panic!("expected the document node");
+            };
+            assert_eq!(document.mode, mode, "{input}");
+        }
+    }
+}
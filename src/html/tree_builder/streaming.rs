@@ -0,0 +1,298 @@
+use std::collections::HashMap;
+
+use crate::atom;
+use crate::html::{
+    tokenizer::Tokenizer,
+    tree_builder::{
+        ParseError, ParserOptions, TreeBuilder,
+        sink::TreeSink,
+        tree::{Element, Namespace},
+    },
+};
+
+// What the streaming parser reports, in the order the nodes are inserted into the document.
+// The depth is the number of elements around the node, the html element is at depth 0.
+#[derive(Debug, PartialEq)]
+pub enum Event<'a> {
+    Doctype {
+        name: &'a str,
+        public_id: &'a str,
+        system_id: &'a str,
+    },
+    StartElement {
+        element: &'a Element,
+        depth: usize,
+    },
+    EndElement {
+        element: &'a Element,
+        depth: usize,
+    },
+    // the text of an element can come in several events, e.g. around a character reference
+    Text {
+        text: &'a str,
+        depth: usize,
+    },
+    Comment {
+        data: &'a str,
+        depth: usize,
+    },
+}
+
+// Runs the tree construction without building a tree, so implied and misnested tags are still fixed up.
+// An element is released after it is popped off the stack of open elements, text and comments are never kept.
+// Nodes are reported when they are inserted, a node inserted into an element that was already closed (e.g. a meta after </head>) comes after the end of the element.
+pub struct Parser {
+    options: ParserOptions,
+}
+
+impl Parser {
+    pub fn new(options: ParserOptions) -> Self {
+        Self { options }
+    }
+
+    // parses the whole input and returns the parse errors
+    pub fn parse<F: FnMut(Event)>(&self, input: &str, callback: F) -> Vec<ParseError> {
+        let sink = EventSink {
+            nodes: HashMap::new(),
+            next_id: 0,
+            popped: Vec::new(),
+            callback,
+        };
+        let mut tree_builder = TreeBuilder::with_sink(self.options, sink);
+        tree_builder.build(Tokenizer::new(input));
+        let (_, parse_errors) = tree_builder.into_parts();
+        parse_errors
+    }
+}
+
+enum StreamNode {
+    Document,
+    // the depth is set once the element is inserted
+    Element {
+        element: Element,
+        depth: Option<usize>,
+        closed: bool,
+    },
+    // a comment waits for its parent
+    Comment(String),
+}
+
+// the sink of the streaming parser, it only keeps the nodes the tree builder can still come back to
+struct EventSink<F: FnMut(Event)> {
+    nodes: HashMap<usize, StreamNode>,
+    next_id: usize,
+    // the tree builder still looks at the elements it just popped, they are released when the next node is created
+    popped: Vec<usize>,
+    callback: F,
+}
+
+impl<F: FnMut(Event)> EventSink<F> {
+    fn create(&mut self, node: StreamNode) -> usize {
+        for popped in self.popped.drain(..) {
+            self.nodes.remove(&popped);
+        }
+        let id = self.next_id;
+        self.next_id += 1;
+        self.nodes.insert(id, node);
+        id
+    }
+
+    // the depth of the children of the parent, none if the parent is not in the document
+    fn depth_of_children(&self, parent: usize) -> Option<usize> {
+        match self.nodes.get(&parent)? {
+            StreamNode::Document => Some(0),
+            StreamNode::Element { depth, .. } => depth.map(|depth| depth + 1),
+            StreamNode::Comment(_) => None,
+        }
+    }
+
+    fn insert(&mut self, parent: usize, child: usize) {
+        let Some(child_depth) = self.depth_of_children(parent) else {
+            return;
+        };
+        match self.nodes.get_mut(&child) {
+            Some(StreamNode::Element { element, depth, .. }) if depth.is_none() => {
+                *depth = Some(child_depth);
+                (self.callback)(Event::StartElement {
+                    element,
+                    depth: child_depth,
+                });
+            }
+            Some(StreamNode::Comment(_)) => {
+                let Some(StreamNode::Comment(data)) = self.nodes.remove(&child) else {
+                    unreachable!()
+                };
+                (self.callback)(Event::Comment {
+                    data: &data,
+                    depth: child_depth,
+                });
+            }
+            _ => {}
+        }
+    }
+}
+
+impl<F: FnMut(Event)> TreeSink for EventSink<F> {
+    fn create_document(&mut self) -> usize {
+        self.create(StreamNode::Document)
+    }
+
+    fn create_element(&mut self, element: Element) -> usize {
+        self.create(StreamNode::Element {
+            element,
+            depth: None,
+            closed: false,
+        })
+    }
+
+    fn create_comment(&mut self, data: String) -> usize {
+        self.create(StreamNode::Comment(data))
+    }
+
+    fn element(&self, node: usize) -> &Element {
+        match self.nodes.get(&node) {
+            Some(StreamNode::Element { element, .. }) => element,
+            _ => panic!("expected an element that is not released, got node {node}"),
+        }
+    }
+
+    fn append(&mut self, parent: usize, child: usize) {
+        self.insert(parent, child);
+    }
+
+    fn append_text(&mut self, parent: usize, data: &str) {
+        if let Some(depth) = self.depth_of_children(parent) {
+            (self.callback)(Event::Text { text: data, depth });
+        }
+    }
+
+    fn insert_before(&mut self, parent: usize, child: usize, _reference_child: usize) {
+        self.insert(parent, child);
+    }
+
+    // the children were already reported where they were inserted
+    fn reparent_children(&mut self, _from: usize, _to: usize) {}
+
+    fn remove_from_parent(&mut self, _node: usize) {}
+
+    // a stream has no document fragments, the contents of a template element are reported as its children
+    fn get_template_contents(&self, template: usize) -> usize {
+        template
+    }
+
+    fn pop(&mut self, node: usize) {
+        let Some(StreamNode::Element {
+            element,
+            depth,
+            closed,
+        }) = self.nodes.get_mut(&node)
+        else {
+            return;
+        };
+        if !*closed && let Some(depth) = *depth {
+            *closed = true;
+            (self.callback)(Event::EndElement { element, depth });
+        }
+        // the tree builder keeps the head element pointer, every other element is done
+        if !element.is(Namespace::Html, atom!("head")) {
+            self.popped.push(node);
+        }
+    }
+
+    fn append_doctype(&mut self, name: &str, public_id: &str, system_id: &str) {
+        (self.callback)(Event::Doctype {
+            name,
+            public_id,
+            system_id,
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn events(input: &str) -> Vec<String> {
+        let mut events = Vec::new();
+        Parser::new(ParserOptions::default()).parse(input, |event| {
+            events.push(match event {
+                Event::Doctype { name, .. } => format!("doctype {name}"),
+                Event::StartElement { element, depth } => {
+                    format!("{}<{}>", "  ".repeat(depth), element.local_name)
+                }
+                Event::EndElement { element, depth } => {
+                    format!("{}</{}>", "  ".repeat(depth), element.local_name)
+                }
+                Event::Text { text, depth } => format!("{}{text:?}", "  ".repeat(depth)),
+                Event::Comment { data, depth } => format!("{}<!--{data}-->", "  ".repeat(depth)),
+            })
+        });
+        events
+    }
+
+    #[test]
+    fn streaming_events() {
+        assert_eq!(
+            events("<!DOCTYPE html><!--c--><title>t</title><p>a<p>b<br></p>"),
+            [
+                "doctype html",
+                "<!--c-->",
+                "<html>",
+                "  <head>",
+                "    <title>",
+                r#"      "t""#,
+                "    </title>",
+                "  </head>",
+                "  <body>",
+                "    <p>",
+                r#"      "a""#,
+                "    </p>",
+                "    <p>",
+                r#"      "b""#,
+                "      <br>",
+                "      </br>",
+                "    </p>",
+                "  </body>",
+                "</html>",
+            ]
+        );
+    }
+
+    #[test]
+    fn extract_links() {
+        let mut links = Vec::new();
+        let mut open = 0;
+        let errors = Parser::new(ParserOptions::default()).parse(
+            "<ul><li><a href=/a>a</a></i><li><a href=/b>b</a></ul>",
+            |event| match event {
+                Event::StartElement { element, .. } => {
+                    open += 1;
+                    if let Some(href) = element.get_attribute("href") {
+                        links.push(href.to_string());
+                    }
+                }
+                Event::EndElement { .. } => open -= 1,
+                _ => {}
+            },
+        );
+        assert_eq!(links, ["/a", "/b"]);
+        assert_eq!(open, 0);
+        // the missing doctype and the </i>
+        assert_eq!(errors.len(), 2);
+    }
+
+    #[test]
+    fn elements_are_released() {
+        let sink = EventSink {
+            nodes: HashMap::new(),
+            next_id: 0,
+            popped: Vec::new(),
+            callback: |_: Event| {},
+        };
+        let mut tree_builder = TreeBuilder::with_sink(ParserOptions::default(), sink);
+        tree_builder.build(Tokenizer::new("<title>t</title><div><p>a<!--b--></div>"));
+        // only the document, the head element and the elements popped at the end are left
+        let sink = tree_builder.sink();
+        assert_eq!(sink.nodes.len() - sink.popped.len(), 2);
+    }
+}
@@ -1,17 +1,15 @@
-use std::error::Error;
 use std::fs::File;
-use std::io::{self, BufRead, BufReader, Cursor, ErrorKind, Read};
+use std::io::{self, BufRead, BufReader, Read};
 use std::path::Path;
-use std::thread::current;
 
-use crate::html::tree_builder::tree::{Node, Tree};
+use crate::html::tree_builder::tree::{Attribute, Element, Namespace, Node, Tree};
 
 pub struct TestReader<R>
 where
     R: Read,
 {
     iter: io::Lines<io::BufReader<R>>,
-    last_error: Option<String>,
+    in_test_case: bool,
 }
 
 impl<R> TestReader<R>
@@ -21,7 +19,7 @@ where
     fn from_reader(reader: BufReader<R>) -> Self {
         TestReader {
             iter: reader.lines(),
-            last_error: None,
+            in_test_case: false,
         }
     }
 
@@ -41,82 +39,189 @@ where
     }
 
     fn format_indentation(depth: usize) -> String {
-        let indentation = if (depth == 0) {
+        if depth == 0 {
             "".to_owned()
         } else {
             let mut str = "|".to_owned();
             str.push_str(&" ".repeat(depth * 2 - 1));
             str
-        };
-        indentation
+        }
+    }
+
+    fn format_attribute(attribute: &Attribute) -> String {
+        match attribute.namespace {
+            Some(Namespace::XLink) => format!("xlink {}=\"{}\"", attribute.name, attribute.value),
+            Some(Namespace::Xml) => format!("xml {}=\"{}\"", attribute.name, attribute.value),
+            Some(Namespace::Xmlns) => format!("xmlns {}=\"{}\"", attribute.name, attribute.value),
+            _ => format!("{}=\"{}\"", attribute.name, attribute.value),
+        }
+    }
+
+    // The lines of the html5lib tree format, the attributes of an element are listed one level deeper, sorted by their name.
+    // The template contents are listed under a content line, after the attributes.
+    fn format_nodes(tree: &Tree<Node>, nodes: &[usize], depth: usize, lines: &mut Vec<String>) {
+        for node in nodes {
+            let node = tree.get_node(*node);
+            lines.push(format!(
+                "{}{:?}",
+                Self::format_indentation(depth),
+                node.data
+            ));
+            if let Node::Element(element) = &node.data {
+                let mut attributes: Vec<String> = element
+                    .attributes
+                    .iter()
+                    .map(Self::format_attribute)
+                    .collect();
+                attributes.sort();
+                for attribute in attributes {
+                    lines.push(format!(
+                        "{}{}",
+                        Self::format_indentation(depth + 1),
+                        attribute
+                    ));
+                }
+                if let Some(contents) = element.template_contents {
+                    lines.push(format!("{}content", Self::format_indentation(depth + 1)));
+                    let children = &tree.get_node(contents).children;
+                    Self::format_nodes(tree, children, depth + 2, lines);
+                }
+            }
+            Self::format_nodes(tree, &node.children, depth + 1, lines);
+        }
     }
 
     pub fn assert_eq(test_case: &TestCase, tree: &Tree<Node>) -> bool {
-        let mut tree_iter = tree.into_iter();
-        let node = tree_iter.next(); // #document
+        // the #document node is not part of the test case
+        let nodes = match tree.get_root() {
+            Some(root) => tree.get_node(root).children.clone(),
+            None => Vec::new(),
+        };
+        Self::assert_eq_nodes(test_case, tree, &nodes)
+    }
+
+    // compares the nodes, e.g. the result of parsing a fragment, with the test case
+    pub fn assert_eq_nodes(test_case: &TestCase, tree: &Tree<Node>, nodes: &[usize]) -> bool {
+        if let Err(message) = Self::compare_nodes(test_case, tree, nodes) {
+            panic!("{message}");
+        }
+        true
+    }
+
+    // the html5lib tree format of the nodes, one line for each node and attribute
+    pub fn format_tree(tree: &Tree<Node>, nodes: &[usize]) -> Vec<String> {
+        let mut lines = Vec::new();
+        Self::format_nodes(tree, nodes, 1, &mut lines);
+        lines
+    }
+
+    // like assert_eq_nodes, the error describes the first line that differs
+    pub fn compare_nodes(
+        test_case: &TestCase,
+        tree: &Tree<Node>,
+        nodes: &[usize],
+    ) -> Result<(), String> {
+        let mut tree_lines = Self::format_tree(tree, nodes).into_iter();
         for (line_number, line) in test_case.document.iter().enumerate() {
-            if let Some(node) = tree_iter.next() {
-                let indentation = Self::format_indentation(tree_iter.get_current_depth());
-                if format!("{}{:?}", indentation, node) == *line {
+            if let Some(tree_line) = tree_lines.next() {
+                if tree_line == *line {
                     continue;
                 };
-                panic!(
+                return Err(format!(
                     "assertion `tree == test_case` failed in line {} \n tree:      {:?}\n test_case: {:?}",
-                    line_number,
-                    format!("{}{:?}", indentation, node),
-                    line
-                );
+                    line_number, tree_line, line
+                ));
             }
-            panic!(
+            return Err(format!(
                 "assertion `tree == test_case` failed in line {} \n tree:      {:?}\n test_case: {:?}",
                 line_number, None::<Node>, line
-            );
+            ));
         }
-        if let Some(node) = tree_iter.next() {
-            let indentation = Self::format_indentation(tree_iter.get_current_depth());
-            panic!(
+        if let Some(tree_line) = tree_lines.next() {
+            return Err(format!(
                 "assertion `tree == test_case` failed \n tree:      {:?}\n test_case: {:?}",
-                format!("{}{:?}", indentation, node),
-                None::<String>
-            );
+                tree_line, None::<String>
+            ));
         }
+        Ok(())
+    }
 
-        return true;
+    pub fn get(mut self) -> TestCase {
+        self.next().unwrap_or_default()
     }
+}
 
-    pub fn get(self) -> TestCase {
+// Test cases are separated by the "#data" line of the next case.
+impl<R> Iterator for TestReader<R>
+where
+    R: Read,
+{
+    type Item = TestCase;
+
+    fn next(&mut self) -> Option<Self::Item> {
         let mut test_case = TestCase::new();
         let mut current_state = TestState::Data;
-        for line in self.iter.map_while(Result::ok) {
-            if line == "" {
-                break;
-            }
+        let mut started = self.in_test_case;
+        for line in self.iter.by_ref().map_while(Result::ok) {
             match line.as_str() {
-                "#data" => current_state = TestState::Data,
+                "#data" if started => {
+                    self.in_test_case = true;
+                    test_case.finish();
+                    return Some(test_case);
+                }
+                "#data" => {
+                    started = true;
+                    current_state = TestState::Data;
+                }
                 "#errors" => current_state = TestState::Error,
                 "#document" => current_state = TestState::Document,
-                "#new-errors" | "#document-fragment" | "#script-off" | "#script-on" => todo!(),
+                "#script-on" => test_case.scripting = Some(true),
+                "#script-off" => test_case.scripting = Some(false),
+                "#document-fragment" => current_state = TestState::DocumentFragment,
+                "#new-errors" => current_state = TestState::NewError,
                 _ => match current_state {
                     TestState::Data => test_case.data.push(line),
                     TestState::Error => test_case.error.push(line),
-                    TestState::Document => test_case.document.push(line),
+                    TestState::NewError => test_case.new_error.push(line),
+                    TestState::DocumentFragment => test_case.document_fragment = Some(line),
+                    // Text nodes can span multiple lines, only lines starting with "|" start a new node.
+                    TestState::Document => match test_case.document.last_mut() {
+                        Some(last) if !line.starts_with('|') => {
+                            last.push('\n');
+                            last.push_str(&line);
+                        }
+                        _ => test_case.document.push(line),
+                    },
                 },
             }
         }
-        test_case
+        self.in_test_case = false;
+        if !started {
+            return None;
+        }
+        test_case.finish();
+        Some(test_case)
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct TestCase {
     pub data: Vec<String>,
     pub error: Vec<String>,
+    // the errors of the #new-errors section, the codes of the spec for errors that the old html5lib codes don't cover
+    pub new_error: Vec<String>,
     pub document: Vec<String>,
+    // None if the test case should pass with the scripting flag enabled and disabled
+    pub scripting: Option<bool>,
+    // the context element of a fragment test case, e.g. "td" or "svg path"
+    pub document_fragment: Option<String>,
 }
 
 enum TestState {
     Data,
     Error,
+    NewError,
+    DocumentFragment,
     Document,
 }
 
@@ -125,18 +230,49 @@ impl TestCase {
         TestCase {
             data: Vec::new(),
             error: Vec::new(),
+            new_error: Vec::new(),
             document: Vec::new(),
+            scripting: None,
+            document_fragment: None,
+        }
+    }
+
+    pub fn context_element(&self) -> Option<Element> {
+        let document_fragment = self.document_fragment.as_ref()?;
+        let element = match document_fragment.split_once(' ') {
+            Some(("svg", local_name)) => {
+                Element::new_with_namespace(local_name.to_string(), Namespace::Svg)
+            }
+            Some(("math", local_name)) => {
+                Element::new_with_namespace(local_name.to_string(), Namespace::MathMl)
+            }
+            _ => Element::new(document_fragment.to_string()),
+        };
+        Some(element)
+    }
+
+    pub fn scripting_settings(&self) -> Vec<bool> {
+        match self.scripting {
+            Some(scripting) => vec![scripting],
+            None => vec![true, false],
+        }
+    }
+
+    // The empty line separating two test cases is not part of the document.
+    fn finish(&mut self) {
+        if let Some(last) = self.document.last_mut() {
+            let len = last.trim_end_matches('\n').len();
+            last.truncate(len);
         }
     }
 }
 
 #[cfg(test)]
 mod test {
-    use std::fs::File;
-    use std::io::{self, BufRead, BufReader, Cursor, Lines, Read};
+    use std::io;
 
     use crate::html::tree_builder::tester::TestReader;
-    use crate::html::tree_builder::tree::{Document, Element, Node, Text, Tree, debug_print_tree};
+    use crate::html::tree_builder::tree::{Document, Element, Node, Text, Tree};
 
     fn create_element_node(tag_name: &str) -> Node {
         Node::Element(Element::new(tag_name.to_string()))
@@ -158,7 +294,7 @@ mod test {
 |     "Test"
 "#;
 
-        let mut test_reader = TestReader::<io::Cursor<String>>::new_from_string(data.to_owned());
+        let test_reader = TestReader::<io::Cursor<String>>::new_from_string(data.to_owned());
         let test_case = test_reader.get();
 
         let mut tree = Tree::new();
@@ -188,7 +324,7 @@ mod test {
 |     "Test"
 "#;
 
-        let mut test_reader = TestReader::<io::Cursor<String>>::new_from_string(data.to_owned());
+        let test_reader = TestReader::<io::Cursor<String>>::new_from_string(data.to_owned());
         let test_case = test_reader.get();
 
         let mut tree = Tree::new();
@@ -220,7 +356,7 @@ mod test {
 |     "Test"
 "#;
 
-        let mut test_reader = TestReader::<io::Cursor<String>>::new_from_string(data.to_owned());
+        let test_reader = TestReader::<io::Cursor<String>>::new_from_string(data.to_owned());
         let test_case = test_reader.get();
 
         let mut tree = Tree::new();
@@ -234,4 +370,35 @@ mod test {
         // missing Test Node
         TestReader::<io::Cursor<String>>::assert_eq(&test_case, &tree);
     }
+
+    #[test]
+    fn new_errors() {
+        let data = r#"#data
+<p>\0
+#errors
+(1,3): expected-doctype-but-got-start-tag
+#new-errors
+(1:4) unexpected-null-character
+#document
+| <html>
+|   <head>
+|   <body>
+|     <p>
+
+#data
+x
+#errors
+#document
+| <html>
+|   <head>
+|   <body>
+|     "x"
+"#;
+        let test_cases: Vec<_> =
+            TestReader::<io::Cursor<String>>::new_from_string(data.to_owned()).collect();
+        assert_eq!(test_cases.len(), 2);
+        assert_eq!(test_cases[0].new_error, ["(1:4) unexpected-null-character"]);
+        assert_eq!(test_cases[0].document.len(), 4);
+        assert!(test_cases[1].new_error.is_empty());
+    }
 }
@@ -1,12 +1,30 @@
 use std::fmt;
 
+use crate::html::atom::Atom;
+pub use crate::html::tokenizer::Attribute;
+
 pub struct Tree<T> {
-    data: Vec<TreeNode<T>>,
+    data: Vec<Option<TreeNode<T>>>,
     root: Option<usize>,
+    // the slots of removed nodes, reused by create_node
+    free: Vec<usize>,
 }
 
-pub struct TreeNode<T> {
-    index: usize,
+impl<T> Default for Tree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// A parsed tree is shared across threads, so the nodes are plain data: no Rc, no Cell or RefCell.
+// This fails to compile as soon as a node type stops being Send or Sync.
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Tree<Node>>();
+};
+
+pub(crate) struct TreeNode<T> {
+    parent: Option<usize>,
     pub children: Vec<usize>,
     pub data: T,
 }
@@ -16,6 +34,7 @@ impl<T> Tree<T> {
         Tree {
             data: Vec::new(),
             root: None,
+            free: Vec::new(),
         }
     }
 
@@ -23,41 +42,159 @@ impl<T> Tree<T> {
         self.root = Some(root);
     }
 
+    pub fn get_root(&self) -> Option<usize> {
+        self.root
+    }
+
+    // creates a node without a parent, the slot of a removed node is reused
     pub fn create_node(&mut self, data: T) -> usize {
-        let index = self.data.len();
+        let index = self.free.pop().unwrap_or(self.data.len());
         let node = TreeNode {
-            index,
+            parent: None,
             children: Vec::new(),
             data,
         };
-        self.data.push(node);
+        if index == self.data.len() {
+            self.data.push(Some(node));
+        } else {
+            self.data[index] = Some(node);
+        }
         index
     }
 
     pub fn add_children(&mut self, parent: usize, children: Vec<usize>) {
-        let parent_element = &mut self.data[parent];
-        parent_element.children.extend(children.into_iter());
+        for child in children {
+            self.add_child(parent, child);
+        }
     }
 
     pub fn add_child(&mut self, parent: usize, child: usize) {
-        let parent_element = &mut self.data[parent];
-        parent_element.children.push(child);
+        self.insert_before(parent, child, None);
     }
 
-    pub fn get_node(&self, index: usize) -> &TreeNode<T> {
-        &self.data[index]
+    // https://dom.spec.whatwg.org/#concept-node-insert
+    // inserts the child into the parent before the reference child, or as the last child without one, the child is detached from its old parent first
+    pub fn insert_before(&mut self, parent: usize, child: usize, reference_child: Option<usize>) {
+        assert!(
+            parent != child && !self.ancestors(parent).any(|ancestor| ancestor == child),
+            "a node can't be inserted into itself"
+        );
+        self.detach(child);
+        let position = match reference_child {
+            Some(reference_child) => self
+                .position_in_parent(reference_child)
+                .filter(|_| self.parent(reference_child) == Some(parent))
+                .expect("the reference child is a child of the parent"),
+            None => self.get_node(parent).children.len(),
+        };
+        self.get_node_mut(parent).children.insert(position, child);
+        self.get_node_mut(child).parent = Some(parent);
     }
 
-    pub fn replace_data_at_index(&mut self, index: usize, data: T) {
-        self.data[index] = TreeNode {
-            index,
-            children: Vec::new(),
-            data,
-        };
+    // removes the node from its parent, the node and its children stay in the tree
+    pub fn detach(&mut self, index: usize) {
+        if let Some(position) = self.position_in_parent(index) {
+            let parent = self.get_node(index).parent.unwrap();
+            self.get_node_mut(parent).children.remove(position);
+            self.get_node_mut(index).parent = None;
+        }
+    }
+
+    // removes the node and its descendants, their slots are reused by later nodes
+    pub fn remove(&mut self, index: usize) -> T {
+        self.detach(index);
+        if self.root == Some(index) {
+            self.root = None;
+        }
+        let node = self.data[index].take().expect("the node was not removed");
+        self.free.push(index);
+        let mut stack = node.children;
+        while let Some(descendant) = stack.pop() {
+            let descendant_node = self.data[descendant]
+                .take()
+                .expect("the node was not removed");
+            self.free.push(descendant);
+            stack.extend(descendant_node.children);
+        }
+        node.data
+    }
+
+    // appends all the children of the node to the new parent, keeping their order
+    pub fn move_children(&mut self, from: usize, to: usize) {
+        let children = std::mem::take(&mut self.get_node_mut(from).children);
+        for child in &children {
+            self.get_node_mut(*child).parent = None;
+        }
+        self.add_children(to, children);
+    }
+
+    // the data of the node, e.g. a Node of the parsed tree
+    pub fn get(&self, index: usize) -> &T {
+        &self.get_node(index).data
+    }
+
+    // the data of the node to edit, e.g. the attributes of an element
+    pub fn get_mut(&mut self, index: usize) -> &mut T {
+        &mut self.get_node_mut(index).data
+    }
+
+    pub fn children(&self, index: usize) -> &[usize] {
+        &self.get_node(index).children
+    }
+
+    pub fn parent(&self, index: usize) -> Option<usize> {
+        self.get_node(index).parent
+    }
+
+    pub fn prev_sibling(&self, index: usize) -> Option<usize> {
+        let position = self.position_in_parent(index)?;
+        let parent = self.get_node(index).parent?;
+        position
+            .checked_sub(1)
+            .map(|position| self.get_node(parent).children[position])
+    }
+
+    pub fn next_sibling(&self, index: usize) -> Option<usize> {
+        let position = self.position_in_parent(index)?;
+        let parent = self.get_node(index).parent?;
+        self.get_node(parent).children.get(position + 1).copied()
+    }
+
+    // the node's parent, the parent's parent and so on
+    pub fn ancestors(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        std::iter::successors(self.parent(index), |node| self.parent(*node))
+    }
+
+    // the node's descendants in tree order (pre-order), with their depth below the node
+    pub fn descendants(&self, index: usize) -> Descendants<'_, T> {
+        let stack = self
+            .get_node(index)
+            .children
+            .iter()
+            .rev()
+            .map(|child| (*child, 1))
+            .collect();
+        Descendants { tree: self, stack }
+    }
+
+    fn position_in_parent(&self, index: usize) -> Option<usize> {
+        let parent = self.get_node(index).parent?;
+        self.get_node(parent)
+            .children
+            .iter()
+            .position(|child| *child == index)
+    }
+
+    pub(crate) fn get_node(&self, index: usize) -> &TreeNode<T> {
+        self.data[index].as_ref().expect("the node was removed")
+    }
+
+    pub(crate) fn get_node_mut(&mut self, index: usize) -> &mut TreeNode<T> {
+        self.data[index].as_mut().expect("the node was removed")
     }
 }
 
-pub fn debug_print_tree<T>(tree: &Tree<T>)
+pub(crate) fn debug_print_tree<T>(tree: &Tree<T>)
 where
     T: std::fmt::Debug,
 {
@@ -66,26 +203,40 @@ where
         stack.push((root, 0));
     }
 
-    loop {
-        if let Some((cur, depth)) = stack.pop() {
-            let node = tree.get_node(cur);
-            let indentation = if (depth == 0) {
-                "".to_owned()
-            } else {
-                let mut str = "|".to_owned();
-                str.push_str(&" ".repeat(depth * 2 - 1));
-                str
-            };
-            println!("{}{:?}", indentation, node.data);
-            for x in (&node.children).into_iter().rev() {
-                stack.push((*x, depth + 1))
-            }
+    while let Some((cur, depth)) = stack.pop() {
+        let node = tree.get_node(cur);
+        let indentation = if depth == 0 {
+            "".to_owned()
         } else {
-            break;
+            let mut str = "|".to_owned();
+            str.push_str(&" ".repeat(depth * 2 - 1));
+            str
+        };
+        println!("{}{:?}", indentation, node.data);
+        for x in node.children.iter().rev() {
+            stack.push((*x, depth + 1))
         }
     }
 }
 
+pub struct Descendants<'a, T> {
+    tree: &'a Tree<T>,
+    stack: Vec<(usize, usize)>,
+}
+
+impl<'a, T> Iterator for Descendants<'a, T> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (index, depth) = self.stack.pop()?;
+        let node = self.tree.get_node(index);
+        for child in node.children.iter().rev() {
+            self.stack.push((*child, depth + 1));
+        }
+        Some((index, depth))
+    }
+}
+
 pub struct IntoIter<'a, T> {
     tree: &'a Tree<T>,
     stack: Vec<(usize, usize)>,
@@ -93,15 +244,16 @@ pub struct IntoIter<'a, T> {
 }
 
 impl<'a, T> Tree<T> {
+    #[allow(clippy::wrong_self_convention)]
     pub fn into_iter(&'a self) -> IntoIter<'a, T> {
         let mut stack = Vec::new();
-        let mut current_depth = 0;
+        let current_depth = 0;
         if let Some(root) = self.root {
             stack.push((root, current_depth))
         }
 
         IntoIter {
-            tree: &self,
+            tree: self,
             stack,
             current_depth,
         }
@@ -121,10 +273,10 @@ impl<'a, T> Iterator for IntoIter<'a, T> {
         if let Some((cur, depth)) = self.stack.pop() {
             let node = self.tree.get_node(cur);
             self.current_depth = depth;
-            for x in (&node.children).into_iter().rev() {
+            for x in node.children.iter().rev() {
                 self.stack.push((*x, depth + 1));
             }
-            return Some(&node.data);
+            Some(&node.data)
         } else {
             self.current_depth = 0;
             None
@@ -132,11 +284,59 @@ impl<'a, T> Iterator for IntoIter<'a, T> {
     }
 }
 
-#[derive(Debug, PartialEq)]
-pub struct Document {}
+// https://infra.spec.whatwg.org/#namespaces
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Namespace {
+    Html,
+    MathMl,
+    Svg,
+    XLink,
+    Xml,
+    Xmlns,
+}
+
+impl Namespace {
+    pub fn url(&self) -> &'static str {
+        match self {
+            Self::Html => "http://www.w3.org/1999/xhtml",
+            Self::MathMl => "http://www.w3.org/1998/Math/MathML",
+            Self::Svg => "http://www.w3.org/2000/svg",
+            Self::XLink => "http://www.w3.org/1999/xlink",
+            Self::Xml => "http://www.w3.org/XML/1998/namespace",
+            Self::Xmlns => "http://www.w3.org/2000/xmlns/",
+        }
+    }
+}
+
+// https://dom.spec.whatwg.org/#concept-document-mode
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum QuirksMode {
+    #[default]
+    NoQuirks,
+    Quirks,
+    LimitedQuirks,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Document {
+    pub mode: QuirksMode,
+}
+// https://dom.spec.whatwg.org/#concept-doctype
+#[derive(Debug, Default, PartialEq)]
+pub struct DocumentType {
+    pub name: String,
+    pub public_id: String,
+    pub system_id: String,
+}
+
 #[derive(Debug, PartialEq)]
 pub struct Element {
-    tag_name: String,
+    pub namespace: Namespace,
+    pub local_name: Atom,
+    pub attributes: Vec<Attribute<'static>>,
+    // https://html.spec.whatwg.org/multipage/scripting.html#template-contents
+    // the DocumentFragment node of a template element, the sink creates it together with the element
+    pub template_contents: Option<usize>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -144,32 +344,106 @@ pub struct Text {
     pub data: String,
 }
 
+#[derive(Debug, PartialEq)]
+pub struct Comment {
+    pub data: String,
+}
+
 #[derive(PartialEq)]
 pub enum Node {
     Document(Document),
+    DocumentType(DocumentType),
+    DocumentFragment,
     Element(Element),
     Text(Text),
+    Comment(Comment),
 }
 
 impl fmt::Debug for Node {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::Document(document) => write!(f, "#document"),
-            Self::Element(element) => write!(f, "<{}>", element.tag_name),
+            Self::Document(_) => write!(f, "#document"),
+            // the identifiers are only written if the doctype has one of them, like in the html5lib tree format
+            Self::DocumentType(doctype)
+                if doctype.public_id.is_empty() && doctype.system_id.is_empty() =>
+            {
+                write!(f, "<!DOCTYPE {}>", doctype.name)
+            }
+            Self::DocumentType(doctype) => write!(
+                f,
+                "<!DOCTYPE {} \"{}\" \"{}\">",
+                doctype.name, doctype.public_id, doctype.system_id
+            ),
+            Self::DocumentFragment => write!(f, "#document-fragment"),
+            Self::Element(element) => match element.namespace {
+                Namespace::Svg => write!(f, "<svg {}>", element.local_name),
+                Namespace::MathMl => write!(f, "<math {}>", element.local_name),
+                _ => write!(f, "<{}>", element.local_name),
+            },
             Self::Text(text) => write!(f, "\"{}\"", text.data),
+            Self::Comment(comment) => write!(f, "<!-- {} -->", comment.data),
         }
     }
 }
 
 impl Document {
     pub fn new() -> Self {
-        Document {}
+        Document {
+            mode: QuirksMode::NoQuirks,
+        }
     }
 }
 
 impl Element {
-    pub fn new(tag_name: String) -> Self {
-        Element { tag_name }
+    pub fn new(local_name: impl Into<Atom>) -> Self {
+        Self::new_with_namespace(local_name, Namespace::Html)
+    }
+
+    pub fn new_with_namespace(local_name: impl Into<Atom>, namespace: Namespace) -> Self {
+        Element {
+            namespace,
+            local_name: local_name.into(),
+            attributes: Vec::new(),
+            template_contents: None,
+        }
+    }
+
+    pub fn is(&self, namespace: Namespace, local_name: Atom) -> bool {
+        self.namespace == namespace && self.local_name == local_name
+    }
+
+    pub fn is_html(&self, local_names: &[Atom]) -> bool {
+        self.namespace == Namespace::Html && local_names.contains(&self.local_name)
+    }
+
+    pub fn get_attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|attribute| attribute.name == name && attribute.namespace.is_none())
+            .map(|attribute| attribute.value.as_ref())
+    }
+
+    // https://dom.spec.whatwg.org/#concept-id
+    pub fn id(&self) -> Option<&str> {
+        self.get_attribute("id").filter(|id| !id.is_empty())
+    }
+
+    // https://dom.spec.whatwg.org/#concept-class
+    // the class attribute split on ASCII whitespace
+    pub fn class_list(&self) -> impl Iterator<Item = &str> {
+        self.get_attribute("class")
+            .unwrap_or_default()
+            .split_ascii_whitespace()
+    }
+}
+
+impl DocumentType {
+    pub fn new(name: String, public_id: String, system_id: String) -> Self {
+        DocumentType {
+            name,
+            public_id,
+            system_id,
+        }
     }
 }
 
@@ -179,10 +453,14 @@ impl Text {
     }
 }
 
+impl Comment {
+    pub fn new(data: String) -> Self {
+        Comment { data }
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use crate::html::tree_builder::tree::debug_print_tree;
-
     use super::{Document, Element, Node, Text, Tree};
 
     fn create_element_node(tag_name: &str) -> Node {
@@ -219,4 +497,153 @@ mod test {
         assert_eq!(iter.get_current_depth(), 3);
         assert_eq!(iter.next(), None);
     }
+
+    // every child points back to its parent, and removed nodes are neither reachable nor in use
+    fn assert_invariants<T>(tree: &Tree<T>) {
+        for (index, node) in tree.data.iter().enumerate() {
+            let Some(node) = node else {
+                assert!(tree.free.contains(&index));
+                continue;
+            };
+            assert!(!tree.free.contains(&index));
+            for child in &node.children {
+                assert_eq!(tree.parent(*child), Some(index));
+            }
+            if let Some(parent) = node.parent {
+                let count = tree
+                    .get_node(parent)
+                    .children
+                    .iter()
+                    .filter(|child| **child == index)
+                    .count();
+                assert_eq!(count, 1);
+            }
+        }
+    }
+
+    // document > html > (head, body > (a, b, c))
+    fn create_tree() -> (Tree<Node>, [usize; 7]) {
+        let mut tree = Tree::new();
+        let document = tree.create_node(Node::Document(Document::new()));
+        tree.set_root(document);
+        let html = tree.create_node(create_element_node("html"));
+        tree.add_child(document, html);
+        let head = tree.create_node(create_element_node("head"));
+        let body = tree.create_node(create_element_node("body"));
+        tree.add_children(html, vec![head, body]);
+        let a = tree.create_node(create_element_node("a"));
+        let b = tree.create_node(create_element_node("b"));
+        let c = tree.create_node(create_text_node("c"));
+        tree.add_children(body, vec![a, b, c]);
+        (tree, [document, html, head, body, a, b, c])
+    }
+
+    #[test]
+    fn parent_and_siblings() {
+        let (tree, [document, html, head, body, a, b, c]) = create_tree();
+        assert_invariants(&tree);
+        assert_eq!(tree.parent(document), None);
+        assert_eq!(tree.parent(html), Some(document));
+        assert_eq!(tree.parent(b), Some(body));
+        assert_eq!(tree.prev_sibling(a), None);
+        assert_eq!(tree.prev_sibling(b), Some(a));
+        assert_eq!(tree.next_sibling(b), Some(c));
+        assert_eq!(tree.next_sibling(c), None);
+        assert_eq!(tree.next_sibling(head), Some(body));
+        assert_eq!(tree.next_sibling(document), None);
+        assert_eq!(
+            tree.ancestors(c).collect::<Vec<_>>(),
+            [body, html, document]
+        );
+    }
+
+    #[test]
+    fn insert_before() {
+        let (mut tree, [_, _, head, body, a, b, c]) = create_tree();
+        let d = tree.create_node(create_element_node("d"));
+        tree.insert_before(body, d, Some(b));
+        assert_eq!(tree.get_node(body).children, [a, d, b, c]);
+        // moving a node within its parent
+        tree.insert_before(body, c, Some(a));
+        assert_eq!(tree.get_node(body).children, [c, a, d, b]);
+        // moving a node to another parent
+        tree.insert_before(head, a, None);
+        assert_eq!(tree.get_node(body).children, [c, d, b]);
+        assert_eq!(tree.get_node(head).children, [a]);
+        assert_eq!(tree.parent(a), Some(head));
+        assert_invariants(&tree);
+    }
+
+    #[test]
+    #[should_panic]
+    fn insert_into_descendant() {
+        let (mut tree, [_, html, _, _, a, _, _]) = create_tree();
+        tree.add_child(a, html);
+    }
+
+    #[test]
+    fn detach() {
+        let (mut tree, [document, html, head, body, a, b, c]) = create_tree();
+        tree.detach(body);
+        assert_eq!(tree.get_node(html).children, [head]);
+        assert_eq!(tree.parent(body), None);
+        // the detached node keeps its children and can be inserted again
+        assert_eq!(tree.get_node(body).children, [a, b, c]);
+        tree.add_child(html, body);
+        assert_eq!(tree.get_node(html).children, [head, body]);
+        // detaching a node without a parent does nothing
+        tree.detach(document);
+        assert_invariants(&tree);
+    }
+
+    #[test]
+    fn remove() {
+        let (mut tree, [_, html, head, body, a, b, c]) = create_tree();
+        let removed = tree.remove(body);
+        assert_eq!(removed, create_element_node("body"));
+        assert_eq!(tree.get_node(html).children, [head]);
+        assert_invariants(&tree);
+        let mut free = tree.free.clone();
+        free.sort();
+        assert_eq!(free, [body, a, b, c]);
+
+        // the slots of the removed nodes are reused
+        let mut created: Vec<usize> = (0..5)
+            .map(|_| tree.create_node(create_element_node("new")))
+            .collect();
+        created.sort();
+        assert_eq!(created, [body, a, b, c, c + 1]);
+        assert!(tree.get_node(a).children.is_empty());
+        assert_eq!(tree.parent(a), None);
+        assert_invariants(&tree);
+    }
+
+    #[test]
+    #[should_panic]
+    fn get_removed_node() {
+        let (mut tree, [_, _, _, _, _, b, _]) = create_tree();
+        tree.remove(b);
+        tree.get_node(b);
+    }
+
+    #[test]
+    fn move_children() {
+        let (mut tree, [_, _, head, body, a, b, c]) = create_tree();
+        let d = tree.create_node(create_element_node("d"));
+        tree.add_child(head, d);
+        tree.move_children(body, head);
+        assert!(tree.get_node(body).children.is_empty());
+        assert_eq!(tree.get_node(head).children, [d, a, b, c]);
+        assert_eq!(tree.parent(b), Some(head));
+        assert_invariants(&tree);
+    }
+
+    #[test]
+    fn get_mut() {
+        let (mut tree, [_, _, _, _, a, _, _]) = create_tree();
+        if let Node::Element(element) = tree.get_mut(a) {
+            element.local_name = "e".into();
+        }
+        assert!(matches!(tree.get(a), Node::Element(element) if element.local_name == "e"));
+    }
 }
@@ -0,0 +1,190 @@
+//! An HTML parser following the WHATWG HTML Standard, with CSS selectors to query the parsed tree.
+//!
+//! ```
+//! let document = fun_with_html::parse_document("<!DOCTYPE html><p id=greeting>Hello");
+//! let tree = document.tree();
+//! let p = tree.query_selector(document.root(), "#greeting").unwrap().unwrap();
+//! assert_eq!(tree.text_content(p).as_deref(), Some("Hello"));
+//! assert_eq!(tree.outer_html(p), r#"<p id="greeting">Hello</p>"#);
+//! ```
+// The tokenizer states and token names follow the spec.
+#![allow(clippy::enum_variant_names, clippy::upper_case_acronyms)]
+
+use std::sync::Arc;
+
+pub mod css;
+mod html;
+
+pub use crate::css::{SelectorError, compute_styles};
+pub use crate::html::atom::{self, Atom};
+pub use crate::html::formatter::{minify, pretty_print};
+pub use crate::html::tokenizer::{Attribute, DOCTYPE, Tag, Token, Tokenizer};
+pub use crate::html::tree_builder::observer::{ParseObserver, ParseTrace};
+pub use crate::html::tree_builder::sink::TreeSink;
+pub use crate::html::tree_builder::streaming::{Event, Parser};
+/// The reader and the tree format of the html5lib tree construction tests, for the `fun_with_html` binary.
+#[cfg(feature = "tester")]
+pub use crate::html::tree_builder::tester;
+pub use crate::html::tree_builder::tree::{
+    self, Comment, DocumentType, Element, Namespace, Node, QuirksMode, Text, Tree,
+};
+pub use crate::html::tree_builder::{ParseError, ParserOptions, TreeBuilder};
+
+/// A parsed document: the tree, whose root is the document node, and the parse errors.
+///
+/// The document is immutable, a clone shares the tree and can be sent to another thread.
+#[derive(Clone)]
+pub struct Document {
+    tree: Arc<Tree<Node>>,
+    parse_errors: Arc<[ParseError]>,
+}
+
+impl Document {
+    pub fn tree(&self) -> &Tree<Node> {
+        &self.tree
+    }
+
+    /// The document node.
+    pub fn root(&self) -> usize {
+        self.tree
+            .get_root()
+            .expect("a parsed tree has a document node")
+    }
+
+    pub fn parse_errors(&self) -> &[ParseError] {
+        &self.parse_errors
+    }
+
+    /// Serializes the document back to HTML.
+    pub fn to_html(&self) -> String {
+        self.tree.to_html()
+    }
+}
+
+/// The nodes parsed in the context of an element, see [`parse_fragment`].
+///
+/// Like a [`Document`], the fragment is immutable and cheap to clone.
+#[derive(Clone)]
+pub struct Fragment {
+    tree: Arc<Tree<Node>>,
+    nodes: Arc<[usize]>,
+    parse_errors: Arc<[ParseError]>,
+}
+
+impl Fragment {
+    pub fn tree(&self) -> &Tree<Node> {
+        &self.tree
+    }
+
+    /// The top-level nodes of the fragment, in tree order.
+    pub fn nodes(&self) -> &[usize] {
+        &self.nodes
+    }
+
+    pub fn parse_errors(&self) -> &[ParseError] {
+        &self.parse_errors
+    }
+
+    /// Serializes the nodes back to HTML.
+    pub fn to_html(&self) -> String {
+        self.nodes
+            .iter()
+            .map(|node| self.tree.outer_html(*node))
+            .collect()
+    }
+}
+
+/// Parses a whole document with scripting disabled.
+///
+/// # Panics
+///
+/// The table and select insertion modes are not implemented yet, the parser panics when it switches to one of them,
+/// e.g. for table rows or cells in a `<template>`. Elsewhere in the body, table elements are inserted like other elements.
+pub fn parse_document(input: &str) -> Document {
+    let mut tree_builder = TreeBuilder::new(ParserOptions::default());
+    tree_builder.build(Tokenizer::new(input));
+    let (tree, parse_errors) = tree_builder.into_parts();
+    Document {
+        tree: Arc::new(tree),
+        parse_errors: parse_errors.into(),
+    }
+}
+
+/// Parses the input like the `innerHTML` setter of the context element, e.g. `Element::new("td".to_string())`.
+///
+/// ```
+/// use fun_with_html::{Element, parse_fragment};
+///
+/// let fragment = parse_fragment(Element::new("div".to_string()), "<b>bold</i>");
+/// assert_eq!(fragment.to_html(), "<b>bold</b>");
+/// let errors: Vec<&str> = fragment.parse_errors().iter().map(|error| error.code.as_str()).collect();
+/// assert_eq!(errors, ["in-body-unexpected-end-tag", "in-body-eof-with-open-elements"]);
+/// ```
+///
+/// # Panics
+///
+/// Like [`parse_document`], e.g. when the context element is a table or select element.
+pub fn parse_fragment(context_element: Element, input: &str) -> Fragment {
+    let mut tree_builder = TreeBuilder::new(ParserOptions::default());
+    let nodes = tree_builder.parse_fragment(context_element, input);
+    let (tree, parse_errors) = tree_builder.into_parts();
+    Fragment {
+        tree: Arc::new(tree),
+        nodes: nodes.into(),
+        parse_errors: parse_errors.into(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::thread;
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn send_and_sync() {
+        assert_send_sync::<Document>();
+        assert_send_sync::<Fragment>();
+        assert_send_sync::<Tree<Node>>();
+        assert_send_sync::<Node>();
+        assert_send_sync::<ParseError>();
+    }
+
+    #[test]
+    fn share_a_document_across_threads() {
+        let document = parse_document("<ul><li>a<li>b<li>c</ul>");
+        let workers: Vec<_> = (1..=3)
+            .map(|index| {
+                let document = document.clone();
+                thread::spawn(move || {
+                    let tree = document.tree();
+                    let selector = format!("li:nth-child({index})");
+                    let li = tree.query_selector(document.root(), &selector).unwrap();
+                    tree.text_content(li.unwrap())
+                })
+            })
+            .collect();
+        let texts: Vec<_> = workers
+            .into_iter()
+            .map(|worker| worker.join().unwrap())
+            .collect();
+        assert_eq!(
+            texts,
+            [Some("a".into()), Some("b".into()), Some("c".into())]
+        );
+    }
+
+    #[test]
+    fn clones_share_the_tree() {
+        let document = parse_document("<p>a");
+        let clone = document.clone();
+        assert!(std::ptr::eq(document.tree(), clone.tree()));
+        assert!(std::ptr::eq(document.parse_errors(), clone.parse_errors()));
+
+        let fragment = parse_fragment(Element::new("div".to_string()), "<p>a");
+        let clone = fragment.clone();
+        assert!(std::ptr::eq(fragment.tree(), clone.tree()));
+        assert_eq!(clone.to_html(), "<p>a</p>");
+    }
+}
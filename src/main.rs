@@ -1,30 +1,224 @@
-#![allow(unused)]
-mod html;
-use crate::html::tree_builder::tester::TestReader;
-use crate::html::tree_builder::{self, TreeBuilder};
-
-use self::html::tokenizer::Tokenizer;
+use fun_with_html::tester::{TestCase, TestReader};
+use fun_with_html::{
+    Node, ParseObserver, ParseTrace, ParserOptions, Tokenizer, Tree, TreeBuilder, pretty_print,
+};
 
+use std::any::Any;
 use std::fs::File;
 use std::io::{self, Read};
+use std::panic::{self, AssertUnwindSafe};
+use std::process::ExitCode;
 
-fn main() -> io::Result<()> {
-    // let file_path = "src/index.html";
-    // let mut file = File::open(file_path)?;
-    // let mut contents = String::new();
-    // file.read_to_string(&mut contents)?;
+const USAGE: &str = "usage: fun_with_html <command> [arguments]
 
-    let file_path = "html5lib-tests/tree-construction/tests1.dat";
-    let test_reader = TestReader::<File>::new_from_file(file_path)?;
-    let test_case = test_reader.get();
-    println!("{:?}", test_case);
-    let contents = test_case.data.join("\n");
-    let tokenizer = Tokenizer::new(&contents);
-    let mut tree_builder = TreeBuilder::new();
-    tree_builder.build(tokenizer);
-    tree_builder.debug_print();
+commands:
+  tokenize [file]            prints the tokens of the input, one per line
+  parse [--trace] [file]     prints the tree in the html5lib test format,
+                             with --trace the parse steps are written to stderr
+  errors [file]              prints the parse errors with their line and column
+  serialize [file]           prints the tree serialized back to HTML
+  format [--minify] [file]   pretty-prints or minifies the document
+  query <selector> [file]    prints the elements matching the CSS selector
+  test <dat-file>            runs the html5lib tree construction tests of the file
+
+The input is read from stdin without a file.";
+
+// the input file, or stdin without a file
+fn read_input(file_path: Option<&str>) -> io::Result<String> {
+    let mut contents = String::new();
+    match file_path {
+        Some(file_path) => File::open(file_path)?.read_to_string(&mut contents)?,
+        None => io::stdin().read_to_string(&mut contents)?,
+    };
+    Ok(contents)
+}
+
+// runs f without the output of the default panic hook, the message of a panic is the error
+fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    panic::set_hook(default_hook);
+    result.map_err(|payload| panic_message(payload.as_ref()).to_string())
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<String>()
+        .map(String::as_str)
+        .or_else(|| payload.downcast_ref::<&str>().copied())
+        .unwrap_or("panicked")
+}
+
+fn parse(contents: &str) -> Result<TreeBuilder, String> {
+    parse_with_observer(contents, ())
+}
+
+// the parser panics in the parts that aren't implemented yet, e.g. the table insertion modes, this is reported as an error
+fn parse_with_observer<O: ParseObserver>(
+    contents: &str,
+    observer: O,
+) -> Result<TreeBuilder<Tree<Node>, O>, String> {
+    catch_panic(|| {
+        let mut tree_builder = TreeBuilder::with_observer(ParserOptions::default(), observer);
+        tree_builder.build(Tokenizer::new(contents));
+        tree_builder
+    })
+    .map_err(|message| format!("can't parse the input: {message}"))
+}
+
+fn tokenize(contents: &str) {
+    for token in Tokenizer::new(contents) {
+        println!("{token:?}");
+    }
+}
+
+fn parse_tree(contents: &str, trace: bool) -> Result<(), String> {
+    if trace {
+        print_tree(parse_with_observer(contents, ParseTrace::new(io::stderr()))?.get_tree());
+    } else {
+        print_tree(parse(contents)?.get_tree());
+    }
+    Ok(())
+}
+
+fn print_tree(tree: &Tree<Node>) {
+    let nodes = match tree.get_root() {
+        Some(root) => tree.children(root),
+        None => &[],
+    };
+    println!("#document");
+    for line in TestReader::<File>::format_tree(tree, nodes) {
+        println!("{line}");
+    }
+}
+
+fn errors(contents: &str) -> Result<(), String> {
+    for error in parse(contents)?.get_parse_errors() {
+        println!("({},{}): {}", error.line, error.column, error.code);
+    }
+    Ok(())
+}
+
+fn serialize(contents: &str) -> Result<(), String> {
+    println!("{}", parse(contents)?.get_tree().to_html());
+    Ok(())
+}
 
-    TestReader::<File>::assert_eq(&test_case, tree_builder.get_tree());
+fn format(contents: &str, minify: bool) -> Result<(), String> {
+    let tree_builder = parse(contents)?;
+    let tree = tree_builder.get_tree();
+    if minify {
+        println!("{}", fun_with_html::minify(tree));
+    } else {
+        print!("{}", pretty_print(tree));
+    }
+    Ok(())
+}
 
+fn query(selector: &str, contents: &str) -> Result<(), String> {
+    let tree_builder = parse(contents)?;
+    let tree = tree_builder.get_tree();
+    let Some(root) = tree.get_root() else {
+        return Ok(());
+    };
+    let elements = tree.query_selector_all(root, selector).map_err(|error| {
+        format!(
+            "invalid selector {selector:?}: {} at {}",
+            error.error, error.position
+        )
+    })?;
+    for element in elements {
+        println!("{}", tree.outer_html(element));
+    }
     Ok(())
 }
+
+// runs a test case with every scripting setting, a panic of an unimplemented part of the parser fails the test
+fn run_test_case(test_case: &TestCase) -> Result<(), String> {
+    let contents = test_case.data.join("\n");
+    for scripting in test_case.scripting_settings() {
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            let mut tree_builder = TreeBuilder::new(ParserOptions { scripting });
+            if let Some(context_element) = test_case.context_element() {
+                let nodes = tree_builder.parse_fragment(context_element, &contents);
+                TestReader::<File>::compare_nodes(test_case, tree_builder.get_tree(), &nodes)
+            } else {
+                tree_builder.build(Tokenizer::new(&contents));
+                let tree = tree_builder.get_tree();
+                let nodes = tree.children(tree.get_root().unwrap());
+                TestReader::<File>::compare_nodes(test_case, tree, nodes)
+            }
+        }));
+        match result {
+            Ok(Ok(())) => {}
+            Ok(Err(message)) => return Err(message),
+            Err(payload) => return Err(panic_message(payload.as_ref()).to_string()),
+        }
+    }
+    Ok(())
+}
+
+fn test(file_path: &str) -> Result<bool, String> {
+    let test_reader = TestReader::<File>::new_from_file(file_path)
+        .map_err(|error| format!("can't read {file_path}: {error}"))?;
+    // the failures are reported below, not by the default panic hook, reading the file may panic too
+    let (passed, failed) = catch_panic(|| {
+        let mut passed = 0;
+        let mut failed = 0;
+        for (index, test_case) in test_reader.enumerate() {
+            match run_test_case(&test_case) {
+                Ok(()) => passed += 1,
+                Err(message) => {
+                    failed += 1;
+                    println!("FAIL {index}: {:?}\n{message}\n", test_case.data.join("\n"));
+                }
+            }
+        }
+        (passed, failed)
+    })
+    .map_err(|message| format!("can't read the tests of {file_path}: {message}"))?;
+    println!("{passed} passed, {failed} failed");
+    Ok(failed == 0)
+}
+
+fn run(args: &[String]) -> Result<bool, String> {
+    let command = args.first().map(String::as_str);
+    let arguments: Vec<&str> = args.iter().skip(1).map(String::as_str).collect();
+    let input = |file_path: Option<&&str>| {
+        read_input(file_path.copied()).map_err(|error| format!("can't read the input: {error}"))
+    };
+    match (command, arguments.as_slice()) {
+        (Some("tokenize"), [] | [_]) => tokenize(&input(arguments.first())?),
+        (Some("parse"), ["--trace"]) => parse_tree(&input(None)?, true)?,
+        (Some("parse"), ["--trace", file_path]) => parse_tree(&input(Some(file_path))?, true)?,
+        (Some("parse"), [] | [_]) => parse_tree(&input(arguments.first())?, false)?,
+        (Some("errors"), [] | [_]) => errors(&input(arguments.first())?)?,
+        (Some("serialize"), [] | [_]) => serialize(&input(arguments.first())?)?,
+        (Some("format"), arguments) if arguments.len() <= 2 => {
+            let minify = arguments.contains(&"--minify");
+            let file_path = arguments
+                .iter()
+                .find(|argument| !argument.starts_with("--"));
+            format(&input(file_path)?, minify)?;
+        }
+        (Some("query"), [selector, rest @ ..]) if rest.len() <= 1 => {
+            query(selector, &input(rest.first())?)?
+        }
+        (Some("test"), [file_path]) => return test(file_path),
+        _ => return Err(USAGE.to_string()),
+    }
+    Ok(true)
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(message) => {
+            eprintln!("{message}");
+            ExitCode::from(2)
+        }
+    }
+}